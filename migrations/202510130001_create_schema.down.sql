@@ -0,0 +1,2 @@
+-- スキーマ削除
+DROP SCHEMA IF EXISTS rss CASCADE;
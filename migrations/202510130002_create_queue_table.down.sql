@@ -0,0 +1,3 @@
+-- queueテーブルとトリガー関数を削除
+DROP TABLE IF EXISTS rss.queue CASCADE;
+DROP FUNCTION IF EXISTS rss.update_updated_at_column();
@@ -0,0 +1,2 @@
+-- article_contentテーブルを削除
+DROP TABLE IF EXISTS rss.article_content CASCADE;
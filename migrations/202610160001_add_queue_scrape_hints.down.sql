@@ -0,0 +1,4 @@
+-- scraping API向けのリファラとAccept-Languageの列を削除
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS scrape_referer,
+    DROP COLUMN IF EXISTS scrape_accept_language;
@@ -0,0 +1,4 @@
+-- scraping APIへ透過送信するリファラとAccept-Languageをフィード設定から引き継ぐ
+ALTER TABLE rss.queue
+    ADD COLUMN scrape_referer TEXT,
+    ADD COLUMN scrape_accept_language TEXT;
@@ -0,0 +1,2 @@
+-- feed_metaテーブルを削除
+DROP TABLE IF EXISTS rss.feed_meta;
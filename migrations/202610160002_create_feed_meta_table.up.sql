@@ -0,0 +1,13 @@
+-- フィード単位のメタ情報（rss_links.ymlのgroup/name単位）
+CREATE TABLE rss.feed_meta (
+    "group" TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    url TEXT NOT NULL,
+    icon_url TEXT,
+    PRIMARY KEY ("group", name)
+);
+
+CREATE TRIGGER update_feed_meta_updated_at BEFORE UPDATE ON rss.feed_meta
+    FOR EACH ROW EXECUTE FUNCTION rss.update_updated_at_column();
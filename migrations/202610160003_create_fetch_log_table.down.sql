@@ -0,0 +1,2 @@
+-- fetch_logテーブルを削除
+DROP TABLE IF EXISTS rss.fetch_log;
@@ -0,0 +1,12 @@
+-- fetch実行ごとの処理結果の記録
+CREATE TABLE rss.fetch_log (
+    id UUID PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    command TEXT NOT NULL,
+    total_processed INTEGER NOT NULL,
+    saved INTEGER NOT NULL,
+    status_only INTEGER NOT NULL,
+    errors INTEGER NOT NULL
+);
+
+CREATE INDEX fetch_log_command_created_at_idx ON rss.fetch_log (command, created_at);
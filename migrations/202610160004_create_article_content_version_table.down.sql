@@ -0,0 +1,2 @@
+-- article_content_versionテーブルを削除
+DROP TABLE IF EXISTS rss.article_content_version;
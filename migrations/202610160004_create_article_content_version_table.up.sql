@@ -0,0 +1,9 @@
+-- 上書きされた過去の記事本文（最新版はarticle_contentに保持）
+CREATE TABLE rss.article_content_version (
+    queue_id UUID NOT NULL REFERENCES rss.queue(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    encoding TEXT NOT NULL CHECK (encoding IN ('full', 'delta')),
+    data BYTEA NOT NULL,
+    PRIMARY KEY (queue_id, version)
+);
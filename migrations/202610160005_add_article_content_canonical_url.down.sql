@@ -0,0 +1,3 @@
+-- article_contentのcanonical_url列を削除
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS canonical_url;
@@ -0,0 +1,3 @@
+-- 取得したHTMLの<link rel="canonical">を重複検出の材料として保持する
+ALTER TABLE rss.article_content
+    ADD COLUMN canonical_url TEXT;
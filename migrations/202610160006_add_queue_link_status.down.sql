@@ -0,0 +1,4 @@
+-- queueのリンク確認結果の列を削除
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS link_status,
+    DROP COLUMN IF EXISTS link_checked_at;
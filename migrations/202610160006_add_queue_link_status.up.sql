@@ -0,0 +1,4 @@
+-- check-linksで検出したリンク切れ（404/410）と最終確認日時を記録する
+ALTER TABLE rss.queue
+    ADD COLUMN link_status INTEGER,
+    ADD COLUMN link_checked_at TIMESTAMPTZ;
@@ -0,0 +1,4 @@
+-- 本文の圧縮方式を記録する（列追加前の既存データはbrotli）
+ALTER TABLE rss.article_content
+    ADD COLUMN compression TEXT NOT NULL DEFAULT 'brotli'
+        CHECK (compression IN ('brotli', 'gzip', 'zstd'));
@@ -0,0 +1,5 @@
+-- queueのクレーム情報の列を削除
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS claimed_at,
+    DROP COLUMN IF EXISTS claimed_by,
+    DROP COLUMN IF EXISTS acked_at;
@@ -0,0 +1,5 @@
+-- 記事の消費側が二重処理しないためのクレーム情報
+ALTER TABLE rss.queue
+    ADD COLUMN claimed_at TIMESTAMPTZ,
+    ADD COLUMN claimed_by TEXT,
+    ADD COLUMN acked_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+-- article_contentのcompression_quality列を削除
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS compression_quality;
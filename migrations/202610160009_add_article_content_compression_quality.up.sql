@@ -0,0 +1,3 @@
+-- 本文を保存したときの圧縮品質（brotliのquality、gzip/zstdのレベル）を記録する（列追加前の既存データはNULL）
+ALTER TABLE rss.article_content
+    ADD COLUMN compression_quality SMALLINT;
@@ -0,0 +1,2 @@
+-- feed_cacheテーブルを削除
+DROP TABLE IF EXISTS rss.feed_cache;
@@ -0,0 +1,13 @@
+-- フィード単位の前回処理時の状態（rss_links.ymlのgroup/name単位）。無変更のフィードの取り込みを省略するために使う
+CREATE TABLE rss.feed_cache (
+    "group" TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    url TEXT NOT NULL,
+    last_build_date TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY ("group", name)
+);
+
+CREATE TRIGGER update_feed_cache_updated_at BEFORE UPDATE ON rss.feed_cache
+    FOR EACH ROW EXECUTE FUNCTION rss.update_updated_at_column();
@@ -0,0 +1,3 @@
+-- queueのpub_date_tz_offset列を削除
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS pub_date_tz_offset;
@@ -0,0 +1,3 @@
+-- フィードに書かれていたpub_dateのタイムゾーンオフセット（UTCからの分数、+09:00なら540）。元の文字列から取れなかった場合はNULL
+ALTER TABLE rss.queue
+    ADD COLUMN pub_date_tz_offset INTEGER;
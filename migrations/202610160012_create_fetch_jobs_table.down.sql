@@ -0,0 +1,4 @@
+-- fetch_jobsテーブルとジョブ作成トリガーを削除
+DROP TRIGGER IF EXISTS create_queue_fetch_job ON rss.queue;
+DROP FUNCTION IF EXISTS rss.create_fetch_job();
+DROP TABLE IF EXISTS rss.fetch_jobs;
@@ -0,0 +1,43 @@
+-- fetch-content（本文取得）のジョブ状態。rss.queueはRSSから取り込んだ記事、こちらは本文取得の進行状況を持つ
+CREATE TABLE rss.fetch_jobs (
+    queue_id UUID PRIMARY KEY REFERENCES rss.queue(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'done', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT
+);
+
+CREATE INDEX fetch_jobs_status_next_run_at_idx ON rss.fetch_jobs (status, next_run_at);
+
+CREATE TRIGGER update_fetch_jobs_updated_at BEFORE UPDATE ON rss.fetch_jobs
+    FOR EACH ROW EXECUTE FUNCTION rss.update_updated_at_column();
+
+-- queueへ記事が追加されたら本文取得ジョブを作る（status_code付きで追加された場合はその状態から始める）
+CREATE OR REPLACE FUNCTION rss.create_fetch_job()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO rss.fetch_jobs (queue_id, status, attempts)
+    VALUES (
+        NEW.id,
+        CASE NEW.status_code WHEN 200 THEN 'done' WHEN 410 THEN 'failed' ELSE 'pending' END,
+        CASE WHEN NEW.status_code IS NULL THEN 0 ELSE 1 END
+    );
+    RETURN NEW;
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER create_queue_fetch_job AFTER INSERT ON rss.queue
+    FOR EACH ROW EXECUTE FUNCTION rss.create_fetch_job();
+
+-- 既存queueの状態を移行する（取得済み=done、諦め済み=failed、それ以外は従来どおりupdated_at順で再試行待ち）
+INSERT INTO rss.fetch_jobs (queue_id, created_at, status, attempts, next_run_at)
+SELECT
+    id,
+    created_at,
+    CASE status_code WHEN 200 THEN 'done' WHEN 410 THEN 'failed' ELSE 'pending' END,
+    CASE WHEN status_code IS NULL THEN 0 ELSE 1 END,
+    updated_at
+FROM rss.queue;
@@ -0,0 +1,3 @@
+-- 一覧表示用の本文要約（SUMMARY_API_URL設定時のみfetch-contentが保存する。未要約はNULL）
+ALTER TABLE rss.article_content
+    ADD COLUMN summary TEXT;
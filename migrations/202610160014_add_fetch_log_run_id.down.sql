@@ -0,0 +1,5 @@
+-- fetch_logからrun_id列を削除
+DROP INDEX IF EXISTS rss.fetch_log_run_id_idx;
+
+ALTER TABLE rss.fetch_log
+    DROP COLUMN IF EXISTS run_id;
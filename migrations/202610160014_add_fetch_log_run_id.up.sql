@@ -0,0 +1,5 @@
+-- fetch実行ごとの一意なID（Webhookペイロードの`run_id`と同じ値。追加前の記録はNULL）
+ALTER TABLE rss.fetch_log
+    ADD COLUMN run_id UUID;
+
+CREATE UNIQUE INDEX fetch_log_run_id_idx ON rss.fetch_log (run_id);
@@ -0,0 +1,5 @@
+-- article_contentからOGメタデータ列を削除
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS og_title,
+    DROP COLUMN IF EXISTS og_description,
+    DROP COLUMN IF EXISTS og_image;
@@ -0,0 +1,5 @@
+-- SNSシェア表示用のOGメタデータ（取得したHTMLにOGタグが無ければNULL）
+ALTER TABLE rss.article_content
+    ADD COLUMN og_title TEXT,
+    ADD COLUMN og_description TEXT,
+    ADD COLUMN og_image TEXT;
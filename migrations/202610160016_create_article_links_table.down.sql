@@ -0,0 +1,2 @@
+-- article_linksテーブルを削除
+DROP TABLE IF EXISTS rss.article_links;
@@ -0,0 +1,9 @@
+-- 記事本文から抽出したリンク（EXTRACT_LINKS=true時のみfetch-contentが保存する）
+CREATE TABLE rss.article_links (
+    queue_id UUID NOT NULL REFERENCES rss.queue(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    href TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (queue_id, position),
+    UNIQUE (queue_id, href)
+);
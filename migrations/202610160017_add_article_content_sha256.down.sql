@@ -0,0 +1,3 @@
+-- article_contentから本文のハッシュ列を削除
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS content_sha256;
@@ -0,0 +1,3 @@
+-- 本文の変更検出用に、圧縮前の本文のSHA-256（16進小文字）を保持する（列追加前に保存した本文はNULL）
+ALTER TABLE rss.article_content
+    ADD COLUMN content_sha256 TEXT;
@@ -0,0 +1,2 @@
+-- article_warcテーブルを削除
+DROP TABLE IF EXISTS rss.article_warc;
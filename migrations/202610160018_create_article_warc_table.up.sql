@@ -0,0 +1,8 @@
+-- 取得したHTMLのWARCアーカイブ（CONTENT_ARCHIVE=warc時のみfetch-contentが保存する）
+-- dataはrequest/responseレコードをそれぞれgzipメンバーにして連結した.warc.gz形式
+CREATE TABLE rss.article_warc (
+    queue_id UUID PRIMARY KEY REFERENCES rss.queue(id) ON DELETE CASCADE,
+    data BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
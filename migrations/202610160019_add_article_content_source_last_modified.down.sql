@@ -0,0 +1,3 @@
+-- article_contentから元ページのLast-Modified列を削除
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS source_last_modified;
@@ -0,0 +1,3 @@
+-- 記事の実際の更新日時の手がかりとして、scraping APIが返した元ページのLast-Modifiedを保持する（返されなかった・解釈できなかった場合はNULL）
+ALTER TABLE rss.article_content
+    ADD COLUMN source_last_modified TIMESTAMPTZ;
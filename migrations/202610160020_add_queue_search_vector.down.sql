@@ -0,0 +1,5 @@
+-- queueから全文検索用のtsvector列とインデックスを削除
+DROP INDEX IF EXISTS rss.queue_search_vector_idx;
+
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS search_vector;
@@ -0,0 +1,9 @@
+-- /api/articles?q=...の全文検索用に、title（重みA）とdescription（重みB）から生成するtsvector列とGINインデックスを追加する
+-- 言語依存の語幹処理を避けるためsimpleコンフィグを使う（日本語は空白・記号で区切られた語単位で一致する）
+ALTER TABLE rss.queue
+    ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', title), 'A')
+        || setweight(to_tsvector('simple', description), 'B')
+    ) STORED;
+
+CREATE INDEX queue_search_vector_idx ON rss.queue USING GIN (search_vector);
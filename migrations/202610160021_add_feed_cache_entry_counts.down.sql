@@ -0,0 +1,6 @@
+-- feed_cacheからentry数の履歴を削除し、last_build_dateを必須に戻す（履歴だけの行は削除する）
+DELETE FROM rss.feed_cache WHERE last_build_date IS NULL;
+
+ALTER TABLE rss.feed_cache
+    DROP COLUMN IF EXISTS entry_counts,
+    ALTER COLUMN last_build_date SET NOT NULL;
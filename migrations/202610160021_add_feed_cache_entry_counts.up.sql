@@ -0,0 +1,5 @@
+-- fetch-rssで取得したフィードのentry数の履歴（古い順、直近数回分）。件数の急減を検知するために使う
+-- last-build-dateを返さないフィードも履歴を残せるよう、last_build_dateをNULL許容にする
+ALTER TABLE rss.feed_cache
+    ALTER COLUMN last_build_date DROP NOT NULL,
+    ADD COLUMN entry_counts INTEGER[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+-- queueから取得優先度を削除する
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS priority;
@@ -0,0 +1,3 @@
+-- fetch-contentの取得優先度（大きいほど先に取得する）。rss_links.ymlのグループの`_priority`を引き継ぐ
+ALTER TABLE rss.queue
+    ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- queueからエラー詳細を削除する
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS last_error;
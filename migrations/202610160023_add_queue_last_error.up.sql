@@ -0,0 +1,3 @@
+-- fetch-contentでスクレイプ・保存に失敗したときのエラーメッセージ（次に200で取得できたらNULLへ戻す）
+ALTER TABLE rss.queue
+    ADD COLUMN last_error TEXT;
@@ -0,0 +1,3 @@
+-- article_contentからscraping APIの生レスポンスを削除する
+ALTER TABLE rss.article_content
+    DROP COLUMN IF EXISTS raw_response;
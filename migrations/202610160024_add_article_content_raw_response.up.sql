@@ -0,0 +1,3 @@
+-- CONTENT_RAW_RESPONSE=trueのとき、scraping APIのレスポンスJSON全体をそのまま保存する
+ALTER TABLE rss.article_content
+    ADD COLUMN raw_response JSONB;
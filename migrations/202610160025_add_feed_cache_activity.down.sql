@@ -0,0 +1,4 @@
+-- feed_cacheから休眠判定用の時刻を削除する
+ALTER TABLE rss.feed_cache
+    DROP COLUMN IF EXISTS last_entry_new_at,
+    DROP COLUMN IF EXISTS last_fetched_at;
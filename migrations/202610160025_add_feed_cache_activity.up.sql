@@ -0,0 +1,5 @@
+-- fetch-rssで最後に新規entryを追加した時刻と最後に取得した時刻。長く新規entryの無いフィード（休眠）の取得間隔を延ばすために使う
+-- 記録前の行はNULL（次に取得した時刻から休眠の判定を始める）
+ALTER TABLE rss.feed_cache
+    ADD COLUMN last_entry_new_at TIMESTAMPTZ,
+    ADD COLUMN last_fetched_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+-- queueからentryのcontentを削除する
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS content;
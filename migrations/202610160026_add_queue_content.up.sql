@@ -0,0 +1,3 @@
+-- フィードのentryのcontent（RSSの`content:encoded`・Atomの`content`）をsummaryとは別に保存する
+ALTER TABLE rss.queue
+    ADD COLUMN content TEXT;
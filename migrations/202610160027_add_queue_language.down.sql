@@ -0,0 +1,3 @@
+-- queueからentryの言語を削除する
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS language;
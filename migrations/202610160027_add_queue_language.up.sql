@@ -0,0 +1,3 @@
+-- entryの言語（Atomのcontentのxml:lang、無ければRSSのchannelのlanguage）を主言語タグで保存する
+ALTER TABLE rss.queue
+    ADD COLUMN language TEXT;
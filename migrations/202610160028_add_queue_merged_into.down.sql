@@ -0,0 +1,4 @@
+-- queueからマージ先の記録を削除する
+ALTER TABLE rss.queue
+    DROP COLUMN IF EXISTS merged_at,
+    DROP COLUMN IF EXISTS merged_into;
@@ -0,0 +1,5 @@
+-- 重複として別の記事へマージした記事を論理削除する（マージ先のID・マージ日時）
+-- マージ先の記事を削除したら、マージ済みの重複も一覧へ戻さず一緒に削除する
+ALTER TABLE rss.queue
+    ADD COLUMN merged_into UUID REFERENCES rss.queue(id) ON DELETE CASCADE,
+    ADD COLUMN merged_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+-- metrics_resetテーブルを削除
+DROP TABLE IF EXISTS rss.metrics_reset;
@@ -0,0 +1,5 @@
+-- 成功率メトリクスをリセットした時刻（コマンドごと）。これ以前のfetch_logは集計しない
+CREATE TABLE rss.metrics_reset (
+    command TEXT PRIMARY KEY,
+    reset_at TIMESTAMPTZ NOT NULL
+);
@@ -1,25 +1,82 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::Result;
-use axum::extract::{Query, State};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use axum::routing::{get, post};
+use axum::body::{to_bytes, Body};
+use axum::extract::ws::WebSocketUpgrade;
+use axum::extract::{ConnectInfo, Path, Query, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, patch, post};
 use axum::{Json, Router};
-use base64::{engine::general_purpose::STANDARD, Engine as _};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine as _,
+};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use sha2::Sha256;
 use sqlx::PgPool;
-use tracing::warn;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tower_http::limit::RequestBodyLimitLayer;
+use tracing::{info, warn};
 
-use crate::articles::{find_article_cursor, search_articles_window};
-use crate::fetch_content::{execute_fetch_content, FetchContentSummary};
-use crate::fetch_rss::{execute_fetch_rss, FetchRssSummary};
+use crate::article_export::{export_articles_tar_gz, ArticleExportFilter};
+use crate::article_filter_dsl::ArticleFilterDsl;
+use crate::article_links::search_article_links;
+use crate::articles::{
+    ack_articles, claim_articles, find_article_by_id, find_article_by_link, find_article_cursor,
+    find_articles_by_ids, merge_articles, reset_success_rate, search_articles_window,
+    search_success_rate, Article, ArticleCursor, ArticleCursorField, ArticleFilter,
+    ArticleGroupFilter, ArticleMerge, ArticleSort, ArticleTextSearch, MergeArticlesOutcome,
+    MetricsBucket, PageDirection, SuccessRatePoint,
+};
+use crate::compression::{decompress_content, to_brotli_blocking};
+use crate::events::{forward_to_websocket, EventHub, HubEventKind};
+use crate::feeds::{search_feed_meta, FeedMeta};
+use crate::fetch_content::{
+    execute_fetch_content_with_progress, notify_content_changes, set_queue_status,
+    FetchContentFilter, FetchContentOptions, FetchContentSummary,
+};
+use crate::fetch_rss::{
+    execute_fetch_rss, log_fetch_rss_webhook_failures, notify_entry_count_drops, FetchRssOptions,
+    FetchRssSummary,
+};
+use crate::models::Queue;
+use crate::response_cache::ResponseCache;
 use crate::webhook;
 
+/// プレビューページに付与するContent-Security-Policy。スクリプト・フォーム・外部フレームを一切許可しない。
+const PREVIEW_CONTENT_SECURITY_POLICY: &str = "default-src 'none'; img-src * data:; style-src 'unsafe-inline' *; font-src * data:; base-uri 'none'; form-action 'none'; frame-ancestors 'none'";
+
 const MAX_LIMIT: i64 = 500;
 const UNSPECIFIED_LIMIT: i64 = 500;
 pub(crate) const MAX_RESPONSE_BYTES: usize = 50 * 1024 * 1024;
+/// POSTエンドポイントで受け付けるリクエストボディの既定上限
+pub(crate) const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+/// アクセスログのtracingターゲット
+pub(crate) const ACCESS_LOG_TARGET: &str = "access_log";
+/// リクエストIDを受け渡すヘッダ（未指定ならサーバで採番してレスポンスに付ける）
+const REQUEST_ID_HEADER: &str = "x-request-id";
+/// RFC 7807のProblem Detailsのメディアタイプ
+const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+/// Problem Detailsの`type`の接頭辞（後ろにエラーコードを付ける）
+const PROBLEM_TYPE_PREFIX: &str = "urn:datadoggo-v3-rss:problem:";
+/// Problem Detailsへ詰め替えるために読み込むエラーレスポンスの上限バイト数
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+/// クレームの既定件数
+const DEFAULT_CLAIM_LIMIT: i64 = 10;
+/// クレームからこの秒数を過ぎてもackされない記事は再クレーム可能にする
+const CLAIM_LEASE_SECS: i64 = 600;
 
 /// APIサーバで共有する状態
 #[derive(Clone)]
@@ -28,6 +85,53 @@ pub struct ApiState {
     pub scraping_api_url: String,
     pub rss_links_path: String,
     pub webhook_url: Option<String>,
+    pub fetch_rss_options: FetchRssOptions,
+    pub fetch_content_options: FetchContentOptions,
+    pub json_case: JsonCase,
+    /// 記事一覧の既定の並び順（クエリの`sort`指定が優先）
+    pub default_sort: ArticleSort,
+    pub max_body_bytes: usize,
+    /// 管理系エンドポイントの認証に使うトークン（未設定なら管理系エンドポイントは使えない）
+    pub admin_token: Option<String>,
+    /// リクエストごとのアクセスログを出力する
+    pub access_log: bool,
+    /// `GET /api/ws`の購読者へfetch-content/fetch-rssのイベントを配信するハブ
+    pub events: EventHub,
+    /// 記事一覧のレスポンスキャッシュ（`API_CACHE_TTL_SECS`が0なら無効）
+    article_cache: Option<ResponseCache<ArticleListCacheKey>>,
+    /// 記事一覧のpage_token/prev_tokenの有効秒数（`API_TOKEN_TTL_SECS`、0なら無期限）
+    pub token_ttl_secs: u64,
+    /// 発行時刻付きのページングトークンに付けるHMACの鍵（`API_TOKEN_SECRET`、未設定なら起動ごとの乱数）
+    token_secret: Arc<[u8]>,
+    /// 終了シグナルでキャンセルされるトークン。fetch-contentは新規エントリの着手を止める
+    pub shutdown: CancellationToken,
+    /// レスポンス後も続くfetch-content（SSE）のタスク。終了時に完了を待つ
+    background_tasks: TaskTracker,
+}
+
+/// 記事取得APIのJSONキーの命名規則
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonCase {
+    /// snake_case（既定、後方互換）
+    #[default]
+    Snake,
+    /// camelCase
+    Camel,
+}
+
+impl std::str::FromStr for JsonCase {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "snake" => Ok(JsonCase::Snake),
+            "camel" => Ok(JsonCase::Camel),
+            other => Err(anyhow::anyhow!(
+                "無効なcase指定: {} (有効な値: snake, camel)",
+                other
+            )),
+        }
+    }
 }
 
 impl ApiState {
@@ -42,27 +146,289 @@ impl ApiState {
             scraping_api_url,
             rss_links_path,
             webhook_url,
+            fetch_rss_options: FetchRssOptions::default(),
+            fetch_content_options: FetchContentOptions::default(),
+            json_case: JsonCase::default(),
+            default_sort: ArticleSort::default(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            admin_token: None,
+            access_log: false,
+            events: EventHub::default(),
+            article_cache: None,
+            token_ttl_secs: 0,
+            token_secret: random_token_secret(),
+            shutdown: CancellationToken::new(),
+            background_tasks: TaskTracker::new(),
+        }
+    }
+
+    /// 終了シグナルでキャンセルされるトークンを設定する（キャンセルでサーバをgraceful shutdownする）
+    pub fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// 記事一覧のレスポンスを`ttl_secs`秒キャッシュする（0ならキャッシュしない）
+    pub fn with_article_cache_ttl(mut self, ttl_secs: u64) -> Self {
+        self.article_cache =
+            (ttl_secs > 0).then(|| ResponseCache::new(std::time::Duration::from_secs(ttl_secs)));
+        self
+    }
+
+    /// 記事一覧のpage_token/prev_tokenに発行時刻を埋め込み、`ttl_secs`秒を過ぎたものを拒否する（0なら無期限）
+    pub fn with_token_ttl(mut self, ttl_secs: u64) -> Self {
+        self.token_ttl_secs = ttl_secs;
+        self
+    }
+
+    /// ページングトークンの署名鍵を設定する（Noneなら起動ごとの乱数のまま）
+    pub fn with_token_secret(mut self, secret: Option<&str>) -> Self {
+        if let Some(secret) = secret {
+            self.token_secret = Arc::from(secret.as_bytes());
+        }
+        self
+    }
+
+    /// 記事の内容が変わる処理の後に、記事一覧のキャッシュを全件破棄する
+    fn clear_article_cache(&self) {
+        if let Some(cache) = &self.article_cache {
+            cache.clear();
         }
     }
+
+    /// アクセスログの出力有無を設定する
+    pub fn with_access_log(mut self, access_log: bool) -> Self {
+        self.access_log = access_log;
+        self
+    }
+
+    /// 管理系エンドポイントの認証トークンを設定する
+    pub fn with_admin_token(mut self, admin_token: Option<String>) -> Self {
+        self.admin_token = admin_token;
+        self
+    }
+
+    /// POSTエンドポイントのリクエストボディ上限を差し替える
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    /// 記事取得APIの既定のJSONキー命名規則を差し替える
+    pub fn with_json_case(mut self, json_case: JsonCase) -> Self {
+        self.json_case = json_case;
+        self
+    }
+
+    /// 記事一覧の既定の並び順を差し替える
+    pub fn with_default_sort(mut self, default_sort: ArticleSort) -> Self {
+        self.default_sort = default_sort;
+        self
+    }
+
+    /// fetch-rssの設定を差し替える
+    pub fn with_fetch_rss_options(mut self, options: FetchRssOptions) -> Self {
+        self.fetch_rss_options = options;
+        self
+    }
+
+    /// fetch-contentの設定を差し替える
+    pub fn with_fetch_content_options(mut self, options: FetchContentOptions) -> Self {
+        self.fetch_content_options = options;
+        self
+    }
 }
 
-/// APIサーバを起動する
+/// APIサーバを起動する。
+///
+/// `state.shutdown`がキャンセルされると新しい接続の受け付けをやめ、処理中のリクエストと
+/// バックグラウンドで続くfetch-contentが着手済みのエントリを終えるのを待ってから返す。
 pub async fn serve(state: ApiState, host: IpAddr, port: u16) -> Result<()> {
     let addr = SocketAddr::from((host, port));
     let listener = tokio::net::TcpListener::bind(addr).await?;
+    let shutdown = state.shutdown.clone();
+    let background_tasks = state.background_tasks.clone();
     let router = build_router(state);
-    axum::serve(listener, router).await?;
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown.cancelled_owned())
+    .await?;
+
+    background_tasks.close();
+    background_tasks.wait().await;
     Ok(())
 }
 
 /// ルータを構築する
 pub fn build_router(state: ApiState) -> Router {
-    Router::new()
+    // 巨大なボディでメモリを圧迫されないよう、POSTのみボディサイズを制限する（超過時は413）
+    let body_limit = RequestBodyLimitLayer::new(state.max_body_bytes);
+    let access_log = state.access_log;
+
+    let router = Router::new()
         .route("/health", get(health))
-        .route("/api/fetch-rss", post(fetch_rss_handler))
-        .route("/api/fetch-content", post(fetch_content_handler))
+        .route("/api/fetch-rss", post(fetch_rss_handler).layer(body_limit))
+        .route(
+            "/api/fetch-content",
+            post(fetch_content_handler).layer(body_limit),
+        )
+        .route(
+            "/api/fetch-content/stream",
+            get(fetch_content_stream_handler),
+        )
+        .route("/api/ws", get(ws_handler))
         .route("/api/articles", get(list_articles_handler))
+        .route("/api/articles/by-link", get(article_by_link_handler))
+        .route(
+            "/api/articles/batch",
+            post(batch_articles_handler).layer(body_limit),
+        )
+        .route("/api/articles/export.tar.gz", get(export_articles_handler))
+        .route(
+            "/api/articles/claim",
+            post(claim_articles_handler).layer(body_limit),
+        )
+        .route(
+            "/api/articles/ack",
+            post(ack_articles_handler).layer(body_limit),
+        )
+        .route(
+            "/api/articles/merge",
+            post(merge_articles_handler).layer(body_limit),
+        )
+        .route("/api/articles/:id/html", get(article_html_handler))
+        .route("/api/articles/:id/preview", get(article_preview_handler))
+        .route("/api/articles/:id/links", get(article_links_handler))
+        .route("/api/feed.xml", get(article_feed_handler))
+        .route("/api/feeds", get(list_feeds_handler))
+        .route("/api/metrics/success-rate", get(success_rate_handler))
+        .route("/api/admin/cache/clear", post(clear_cache_handler))
+        .route("/api/admin/metrics/reset", post(reset_metrics_handler))
+        .route(
+            "/api/queue/:id",
+            patch(update_queue_status_handler).layer(body_limit),
+        )
         .with_state(state)
+        .layer(middleware::from_fn(problem_details_middleware));
+
+    if access_log {
+        router.layer(middleware::from_fn(access_log_middleware))
+    } else {
+        router
+    }
+}
+
+/// 1リクエストごとにmethod/path/status/latency/request_id/client_ipを1件のログとして出力する。
+/// `LOG_FORMAT=json`では各項目がJSONのフィールドになる。
+async fn access_log_middleware(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    // クエリにはトークン等が含まれうるためパスのみ記録する
+    let path = request.uri().path().to_string();
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let client_ip = resolve_client_ip(&request);
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    info!(
+        target: ACCESS_LOG_TARGET,
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+        request_id = %request_id,
+        client_ip = client_ip.as_deref(),
+        "access"
+    );
+
+    response
+}
+
+/// `Accept: application/problem+json`のリクエストに限り、エラーレスポンス（`ErrorResponse`）を
+/// RFC 7807のProblem Details形式へ詰め替える。それ以外のリクエスト・レスポンスはそのまま返す。
+async fn problem_details_middleware(request: Request, next: Next) -> Response {
+    if !accepts_problem_json(request.headers()) {
+        return next.run(request).await;
+    }
+    // エラーの発生箇所としてパスのみ返す（クエリにはトークン等が含まれうる）
+    let instance = request.uri().path().to_string();
+
+    let response = next.run(request).await;
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(error = %e, "エラーレスポンスの読み込みに失敗しました");
+            return status.into_response();
+        }
+    };
+    let Ok(error) = serde_json::from_slice::<ErrorResponse>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let problem = ProblemDetails::new(status, error, instance);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(PROBLEM_JSON_CONTENT_TYPE),
+    );
+    let body = serde_json::to_vec(&problem).unwrap_or_default();
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Acceptヘッダに`application/problem+json`が含まれるか
+fn accepts_problem_json(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| {
+            media_type
+                .trim()
+                .eq_ignore_ascii_case(PROBLEM_JSON_CONTENT_TYPE)
+        })
+}
+
+/// クライアントIPを求める。プロキシ配下を想定して`X-Forwarded-For`の先頭を優先し、無ければ接続元アドレスを使う。
+fn resolve_client_ip(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+        })
 }
 
 async fn health() -> impl IntoResponse {
@@ -72,14 +438,65 @@ async fn health() -> impl IntoResponse {
 #[derive(Debug, Deserialize)]
 struct FetchContentRequest {
     limit: Option<i64>,
+    /// 取得対象のグループ（カンマ区切り）
+    group: Option<String>,
+    /// この日時（RFC3339、または`24h`のような相対期間）以降にqueueへ追加されたものに限る
+    created_after: Option<String>,
+    /// 取得対象のstatus_code（カンマ区切り、`unfetched`は未取得）
+    status: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+/// リクエストで指定された絞り込み条件を反映したfetch-contentの設定を作る
+fn fetch_content_options_with_filter(
+    state: &ApiState,
+    group: Option<&str>,
+    created_after: Option<&str>,
+    status: Option<&str>,
+) -> ApiResult<FetchContentOptions> {
+    let filter = FetchContentFilter::parse(group, created_after, status)
+        .map_err(|e| bad_request("invalid_filter", e))?;
+
+    Ok(FetchContentOptions {
+        filter,
+        ..state.fetch_content_options.clone()
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct ErrorResponse {
     code: String,
     message: String,
 }
 
+/// RFC 7807のProblem Details（`Accept: application/problem+json`のときのエラーレスポンス）
+#[derive(Debug, Serialize)]
+struct ProblemDetails {
+    /// エラーの種類を表すURI（`ErrorResponse.code`から組み立てる）
+    #[serde(rename = "type")]
+    problem_type: String,
+    /// HTTPステータスの説明句
+    title: String,
+    status: u16,
+    detail: String,
+    /// エラーになったリクエストのパス
+    instance: String,
+    /// 従来形式と同じエラーコード（拡張メンバー）
+    code: String,
+}
+
+impl ProblemDetails {
+    fn new(status: StatusCode, error: ErrorResponse, instance: String) -> Self {
+        Self {
+            problem_type: format!("{}{}", PROBLEM_TYPE_PREFIX, error.code),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: error.message,
+            instance,
+            code: error.code,
+        }
+    }
+}
+
 type ApiResult<T> = Result<T, (StatusCode, Json<ErrorResponse>)>;
 
 fn error_response(
@@ -107,18 +524,63 @@ fn bad_request(
     error_response(StatusCode::BAD_REQUEST, code, message)
 }
 
-async fn fetch_rss_handler(State(state): State<ApiState>) -> ApiResult<Json<FetchRssSummary>> {
-    let summary = execute_fetch_rss(&state.pool, &state.rss_links_path)
-        .await
-        .map_err(internal_error)?;
+/// 管理系エンドポイントの認証（`Authorization: Bearer <API_ADMIN_TOKEN>`）を行う
+fn require_admin(state: &ApiState, headers: &HeaderMap) -> ApiResult<()> {
+    let Some(expected) = state.admin_token.as_deref() else {
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "admin_api_disabled",
+            "API_ADMIN_TOKENが設定されていないため管理APIは使用できません",
+        ));
+    };
 
-    if let Err(e) = webhook::notify_fetch_rss(state.webhook_url.as_deref(), &summary, "api").await {
-        warn!(error = %e, "Webhook送信に失敗しました(fetch-rss)");
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            "unauthorized",
+            "認証トークンが不正です",
+        )),
     }
+}
+
+/// トークン比較で一致位置による応答時間の差が出ないよう、全バイトを比較する
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn fetch_rss_handler(State(state): State<ApiState>) -> ApiResult<Json<FetchRssSummary>> {
+    let mut summary =
+        execute_fetch_rss(&state.pool, &state.rss_links_path, &state.fetch_rss_options)
+            .await
+            .map_err(internal_error)?;
+    state.clear_article_cache();
+
+    webhook::notify_fetch_rss_by_group(state.webhook_url.as_deref(), &mut summary, "api").await;
+    log_fetch_rss_webhook_failures(&summary);
+    notify_entry_count_drops(state.webhook_url.as_deref(), &summary, "api").await;
+    state.events.publish(HubEventKind::RssSummary, &summary);
 
     Ok(Json(summary))
 }
 
+/// WebSocket接続を受け付け、以降にAPI経由で実行したfetch-content/fetch-rssのイベントをJSONでpushする
+async fn ws_handler(State(state): State<ApiState>, ws: WebSocketUpgrade) -> Response {
+    let receiver = state.events.subscribe();
+    ws.on_upgrade(move |socket| forward_to_websocket(socket, receiver))
+}
+
 async fn fetch_content_handler(
     State(state): State<ApiState>,
     Json(payload): Json<FetchContentRequest>,
@@ -130,647 +592,6598 @@ async fn fetch_content_handler(
             "limitは1以上で指定してください",
         ));
     }
+    let options = fetch_content_options_with_filter(
+        &state,
+        payload.group.as_deref(),
+        payload.created_after.as_deref(),
+        payload.status.as_deref(),
+    )?;
 
-    let summary = execute_fetch_content(&state.pool, limit, &state.scraping_api_url)
-        .await
-        .map_err(internal_error)?;
+    // 進捗はWebSocketの購読者へ配信する
+    let (progress_tx, forwarder) = state.events.forward_progress(None);
+    let result = execute_fetch_content_with_progress(
+        &state.pool,
+        limit,
+        &state.scraping_api_url,
+        &options,
+        Some(&progress_tx),
+        &state.shutdown,
+    )
+    .await;
+    // 進捗を配信し終えてからサマリを配信する
+    drop(progress_tx);
+    let _ = forwarder.await;
+    // 失敗時も途中までの取得結果は保存されているため、結果によらず破棄する
+    state.clear_article_cache();
+    let mut summary = match result {
+        Ok(summary) => summary,
+        Err(e) => {
+            state.events.publish(
+                HubEventKind::ContentError,
+                &json!({ "message": e.to_string() }),
+            );
+            return Err(internal_error(e));
+        }
+    };
 
-    if let Err(e) =
-        webhook::notify_fetch_content(state.webhook_url.as_deref(), &summary, "api").await
-    {
-        warn!(error = %e, "Webhook送信に失敗しました(fetch-content)");
+    summary.webhook = webhook::notify_fetch_content(
+        state.webhook_url.as_deref(),
+        &summary,
+        "api",
+        state.fetch_content_options.webhook_max_entries,
+    )
+    .await;
+    if let Some(result) = summary.webhook.as_ref().filter(|result| !result.sent) {
+        warn!(error = ?result.error, "Webhook送信に失敗しました(fetch-content)");
     }
+    notify_content_changes(&options, state.webhook_url.as_deref(), &summary, "api").await;
+    state.events.publish(HubEventKind::ContentSummary, &summary);
 
     Ok(Json(summary))
 }
 
 #[derive(Debug, Deserialize)]
-struct ArticleListQuery {
+struct FetchContentStreamQuery {
     limit: Option<i64>,
-    page_token: Option<uuid::Uuid>,
+    group: Option<String>,
+    created_after: Option<String>,
+    status: Option<String>,
+}
+
+/// fetch-contentを実行し、進捗をServer-Sent Eventsで配信する。
+/// エントリの結果が確定するたびに`progress`、最後に`summary`（失敗時は`error`）を送る。
+/// クライアントが切断しても処理は最後まで続ける。
+async fn fetch_content_stream_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<FetchContentStreamQuery>,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let limit = params.limit.unwrap_or(100);
+    if limit <= 0 {
+        return Err(bad_request(
+            "invalid_limit",
+            "limitは1以上で指定してください",
+        ));
+    }
+
+    let options = fetch_content_options_with_filter(
+        &state,
+        params.group.as_deref(),
+        params.created_after.as_deref(),
+        params.status.as_deref(),
+    )?;
+
+    let (progress_tx, progress_rx) = mpsc::unbounded_channel();
+    let (result_tx, result_rx) = oneshot::channel();
+
+    state.background_tasks.clone().spawn(async move {
+        // 進捗はWebSocketの購読者にも配信する
+        let (hub_tx, forwarder) = state.events.forward_progress(Some(progress_tx));
+        let result = execute_fetch_content_with_progress(
+            &state.pool,
+            limit,
+            &state.scraping_api_url,
+            &options,
+            Some(&hub_tx),
+            &state.shutdown,
+        )
+        .await;
+        // 進捗の送信を締め切ってからサマリを送る
+        drop(hub_tx);
+        let _ = forwarder.await;
+        state.clear_article_cache();
+
+        let event = match result {
+            Ok(mut summary) => {
+                summary.webhook = webhook::notify_fetch_content(
+                    state.webhook_url.as_deref(),
+                    &summary,
+                    "api",
+                    state.fetch_content_options.webhook_max_entries,
+                )
+                .await;
+                if let Some(result) = summary.webhook.as_ref().filter(|result| !result.sent) {
+                    warn!(error = ?result.error, "Webhook送信に失敗しました(fetch-content)");
+                }
+                notify_content_changes(&options, state.webhook_url.as_deref(), &summary, "api")
+                    .await;
+                state.events.publish(HubEventKind::ContentSummary, &summary);
+                sse_json_event("summary", &summary)
+            }
+            Err(e) => {
+                state.events.publish(
+                    HubEventKind::ContentError,
+                    &json!({ "message": e.to_string() }),
+                );
+                sse_json_event(
+                    "error",
+                    &ErrorResponse {
+                        code: "internal_error".to_string(),
+                        message: e.to_string(),
+                    },
+                )
+            }
+        };
+        let _ = result_tx.send(event);
+    });
+
+    let progress = stream::unfold(progress_rx, |mut rx| async move {
+        let progress = rx.recv().await?;
+        Some((sse_json_event("progress", &progress), rx))
+    });
+    let finished = stream::once(result_rx).filter_map(|event| async move { event.ok() });
+
+    Ok(Sse::new(progress.chain(finished).map(Ok)).keep_alive(KeepAlive::default()))
+}
+
+/// 値をJSONにしたSSEイベントを作る
+fn sse_json_event<T: Serialize>(name: &str, value: &T) -> Event {
+    let data = serde_json::to_string(value).unwrap_or_else(|e| {
+        json!({ "code": "internal_error", "message": e.to_string() }).to_string()
+    });
+    Event::default().event(name).data(data)
 }
 
 #[derive(Debug, Serialize)]
-struct ArticleItemResponse {
-    id: uuid::Uuid,
-    created_at: chrono::DateTime<chrono::Utc>,
-    updated_at: chrono::DateTime<chrono::Utc>,
-    link: String,
-    title: String,
-    pub_date: Option<chrono::DateTime<chrono::Utc>>,
-    description: String,
-    group: Option<String>,
-    content_brotli_base64: String,
+struct FeedListResponse {
+    items: Vec<FeedMeta>,
+}
+
+/// fetch-rssで記録したフィードのメタ情報（icon_urlなど）を返す
+async fn list_feeds_handler(State(state): State<ApiState>) -> ApiResult<Json<FeedListResponse>> {
+    let items = search_feed_meta(&state.pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(FeedListResponse { items }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SuccessRateQuery {
+    bucket: Option<String>,
+    since: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-struct ArticleListResponse {
-    items: Vec<ArticleItemResponse>,
-    next_token: Option<uuid::Uuid>,
+struct SuccessRateResponse {
+    bucket: String,
+    since: chrono::DateTime<chrono::Utc>,
+    points: Vec<SuccessRatePoint>,
 }
 
-async fn list_articles_handler(
+/// fetch_logから時間バケットごとの取得成功率（saved / total_processed）を返す
+async fn success_rate_handler(
     State(state): State<ApiState>,
-    Query(params): Query<ArticleListQuery>,
-) -> ApiResult<Json<ArticleListResponse>> {
-    let limit_param = match params.limit {
-        Some(value) if value <= 0 => {
-            return Err(bad_request(
-                "invalid_limit",
-                "limitは1以上で指定してください",
-            ));
-        }
-        Some(value) => value.min(MAX_LIMIT),
-        None => UNSPECIFIED_LIMIT,
-    };
+    Query(params): Query<SuccessRateQuery>,
+) -> ApiResult<Json<SuccessRateResponse>> {
+    let bucket_param = params.bucket.unwrap_or_else(|| "1h".to_string());
+    let bucket = parse_metrics_bucket(&bucket_param).ok_or_else(|| {
+        bad_request(
+            "invalid_bucket",
+            "bucketは1m/1h/1d/1wのいずれかで指定してください",
+        )
+    })?;
 
-    let cursor = if let Some(token) = params.page_token {
-        match find_article_cursor(&state.pool, token).await {
-            Ok(Some(cursor)) => Some(cursor),
-            Ok(None) => {
-                return Err(bad_request(
-                    "page_token_not_found",
-                    "page_token is not exist",
-                ))
-            }
-            Err(e) => return Err(internal_error(e)),
-        }
-    } else {
-        None
-    };
+    let since_param = params.since.unwrap_or_else(|| "24h".to_string());
+    let since = parse_relative_duration(&since_param)
+        .map(|duration| chrono::Utc::now() - duration)
+        .ok_or_else(|| {
+            bad_request(
+                "invalid_since",
+                "sinceは30m/24h/7d/4wのように数値と単位で指定してください",
+            )
+        })?;
+
+    let points = search_success_rate(&state.pool, bucket, since)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(SuccessRateResponse {
+        bucket: bucket_param,
+        since,
+        points,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct CacheClearResponse {
+    /// レスポンスキャッシュが有効（`API_CACHE_TTL_SECS`が1以上）だったか。無効なら何もしない
+    enabled: bool,
+}
+
+/// サーバ内のレスポンスキャッシュ（記事一覧）を全件破棄する。認証必須。
+async fn clear_cache_handler(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<CacheClearResponse>> {
+    require_admin(&state, &headers)?;
+    state.clear_article_cache();
+    Ok(Json(CacheClearResponse {
+        enabled: state.article_cache.is_some(),
+    }))
+}
 
-    let fetch_limit = limit_param.checked_add(1).unwrap_or_else(|| limit_param);
+#[derive(Debug, Serialize)]
+struct MetricsResetResponse {
+    /// リセットした時刻（これ以前のfetch_logは成功率に集計しない）
+    reset_at: chrono::DateTime<chrono::Utc>,
+}
 
-    let articles = search_articles_window(&state.pool, fetch_limit, cursor.as_ref())
+/// 成功率メトリクスをリセットしてカウンタを0に戻す。fetch_logは削除しない。認証必須。
+async fn reset_metrics_handler(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> ApiResult<Json<MetricsResetResponse>> {
+    require_admin(&state, &headers)?;
+    let reset_at = reset_success_rate(&state.pool)
         .await
         .map_err(internal_error)?;
+    info!(%reset_at, "成功率メトリクスをリセットしました");
+    Ok(Json(MetricsResetResponse { reset_at }))
+}
 
-    let mut has_more = false;
-    let mut trimmed_articles = articles;
-    if trimmed_articles.len() as i64 == fetch_limit {
-        has_more = true;
-        trimmed_articles.truncate(limit_param as usize);
+/// `1h`形式のバケット指定を解釈する（date_truncで扱える単位のみ受け付ける）
+fn parse_metrics_bucket(value: &str) -> Option<MetricsBucket> {
+    match value.trim() {
+        "1m" => Some(MetricsBucket::Minute),
+        "1h" => Some(MetricsBucket::Hour),
+        "1d" => Some(MetricsBucket::Day),
+        "1w" => Some(MetricsBucket::Week),
+        _ => None,
     }
+}
 
-    let mut total_base64_bytes = 0usize;
-    let mut response_items = Vec::new();
+/// `24h`形式の相対期間を解釈する（単位はm/h/d/w）
+pub(crate) fn parse_relative_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
 
-    for article in &trimmed_articles {
-        let encoded = STANDARD.encode(&article.data);
-        if encoded.len() > MAX_RESPONSE_BYTES {
-            return Err(error_response(
-                StatusCode::PAYLOAD_TOO_LARGE,
-                "article_too_large",
-                format!("記事ID {} の本文が応答許容量を超過しました", article.id),
-            ));
+    match unit {
+        'm' => chrono::Duration::try_minutes(amount),
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+struct ArticleListQuery {
+    limit: Option<i64>,
+    page_token: Option<PageToken>,
+    prev_token: Option<PageToken>,
+    description_max: Option<i64>,
+    case: Option<String>,
+    sort: Option<String>,
+    before: Option<String>,
+    before_id: Option<uuid::Uuid>,
+    /// カンマ区切りで複数指定するといずれかのgroupに属する記事を返す（`world,tech`）
+    group: Option<String>,
+    /// `group`を`/`区切りの階層とみなし、配下のグループの記事も返す
+    include_subgroups: Option<bool>,
+    /// `true`ならpub_dateのある記事だけ、`false`ならpub_dateが無い記事だけを返す
+    has_pubdate: Option<bool>,
+    /// title・descriptionの全文検索（`websearch_to_tsquery`の構文）
+    q: Option<String>,
+    /// `true`なら`q`の関連度（`ts_rank`）の高い順に返す
+    rank: Option<bool>,
+    /// ページングで比較する列（`created_at`（既定）/`updated_at`）
+    cursor_field: Option<String>,
+    /// 日時をこのタイムゾーン（IANA名、例: `Asia/Tokyo`）のオフセット付きRFC3339で返す
+    tz: Option<String>,
+    /// 複合条件の簡易DSL（`group:tech,after:2025-01-01,host:example.com`）。個別パラメータとはANDで組み合わせる
+    filter: Option<String>,
+    /// 既定では返さない項目をカンマ区切りで指定する（`feed_content`）
+    include: Option<String>,
+}
+
+/// 起動ごとに変わるページングトークンの署名鍵（`API_TOKEN_SECRET`未設定時）
+fn random_token_secret() -> Arc<[u8]> {
+    let mut secret = Vec::with_capacity(32);
+    secret.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+    secret.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+    Arc::from(secret)
+}
+
+/// 記事一覧のページングトークン。
+///
+/// 基準となる記事IDに、`API_TOKEN_TTL_SECS`が有効なときだけ発行時刻（UNIX秒）と
+/// そのHMAC-SHA256（Base64URL）を`<id>.<issued_at>.<mac>`の形で付ける。
+/// TTL無効時は従来どおり記事IDだけを返す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+struct PageToken {
+    id: uuid::Uuid,
+    issued_at: Option<i64>,
+    signature: Option<[u8; 32]>,
+}
+
+impl PageToken {
+    /// TTLが有効なら現在時刻を発行時刻として埋め込み、署名する
+    fn issue(id: uuid::Uuid, ttl_secs: u64, secret: &[u8]) -> Self {
+        if ttl_secs == 0 {
+            return PageToken {
+                id,
+                issued_at: None,
+                signature: None,
+            };
         }
+        Self::signed(id, chrono::Utc::now().timestamp(), secret)
+    }
 
-        if total_base64_bytes + encoded.len() > MAX_RESPONSE_BYTES {
-            has_more = true;
-            break;
+    fn signed(id: uuid::Uuid, issued_at: i64, secret: &[u8]) -> Self {
+        let signature = Self::mac(id, issued_at, secret)
+            .finalize()
+            .into_bytes()
+            .into();
+        PageToken {
+            id,
+            issued_at: Some(issued_at),
+            signature: Some(signature),
         }
+    }
 
-        total_base64_bytes += encoded.len();
-        response_items.push(ArticleItemResponse {
-            id: article.id,
-            created_at: article.created_at,
-            updated_at: article.updated_at,
-            link: article.link.clone(),
-            title: article.title.clone(),
-            pub_date: article.pub_date,
-            description: article.description.clone(),
-            group: article.group.clone(),
-            content_brotli_base64: encoded,
-        });
+    fn mac(id: uuid::Uuid, issued_at: i64, secret: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret).expect("HMACは任意の長さの鍵を受け付ける");
+        mac.update(format!("{}.{}", id, issued_at).as_bytes());
+        mac
     }
 
-    if response_items.len() < trimmed_articles.len() {
-        has_more = true;
+    /// 発行時刻がこのサーバの鍵で署名されたものか。発行時刻の無いトークンは署名不要とみなす
+    fn has_valid_signature(&self, secret: &[u8]) -> bool {
+        match (self.issued_at, self.signature) {
+            (None, _) => true,
+            (Some(issued_at), Some(signature)) => Self::mac(self.id, issued_at, secret)
+                .verify_slice(&signature)
+                .is_ok(),
+            (Some(_), None) => false,
+        }
     }
 
-    let next_token = if has_more {
-        response_items.last().map(|item| item.id)
-    } else {
-        None
-    };
+    /// 発行からTTLを過ぎたか。発行時刻の無いトークンはTTL有効時には期限切れとみなす
+    fn is_expired(&self, ttl_secs: u64, now: chrono::DateTime<chrono::Utc>) -> bool {
+        if ttl_secs == 0 {
+            return false;
+        }
+        match self.issued_at {
+            Some(issued_at) => now.timestamp().saturating_sub(issued_at) > ttl_secs as i64,
+            None => true,
+        }
+    }
+}
 
-    Ok(Json(ArticleListResponse {
-        items: response_items,
-        next_token,
-    }))
+impl std::str::FromStr for PageToken {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut parts = value.splitn(3, '.');
+        let id = parts.next().unwrap_or_default().parse()?;
+        let issued_at = parts.next().map(str::parse::<i64>).transpose()?;
+        let signature = parts
+            .next()
+            .map(|mac| -> Result<[u8; 32]> {
+                URL_SAFE_NO_PAD
+                    .decode(mac)?
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("署名の長さが不正です"))
+            })
+            .transpose()?;
+        Ok(PageToken {
+            id,
+            issued_at,
+            signature,
+        })
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    pub mod fetch_rss_endpoint {
-        use anyhow::Result;
-        use axum::body::{to_bytes, Body};
-        use axum::http::{Request, StatusCode};
-        use serde_json::Value;
-        use tower::ServiceExt;
-        use wiremock::matchers::{method, path};
-        use wiremock::{Mock, MockServer, ResponseTemplate};
+impl TryFrom<String> for PageToken {
+    type Error = anyhow::Error;
 
-        use crate::api::{build_router, ApiState};
-        use crate::test_support::{clear_rss_tables, create_temp_yaml, prepare_test_pool};
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
 
-        /// # 検証目的
-        /// API経由でRSS取得処理が実行され、結果がJSONで返ることを確認する。
-        #[tokio::test]
-        async fn rssを取得するエンドポイントが動作する() -> Result<()> {
-            let _lock = crate::test_support::acquire_db_lock().await;
-            let pool = prepare_test_pool().await?;
+impl From<PageToken> for String {
+    fn from(token: PageToken) -> Self {
+        match (token.issued_at, token.signature) {
+            (Some(issued_at), Some(signature)) => format!(
+                "{}.{}.{}",
+                token.id,
+                issued_at,
+                URL_SAFE_NO_PAD.encode(signature)
+            ),
+            (Some(issued_at), None) => format!("{}.{}", token.id, issued_at),
+            (None, _) => token.id.to_string(),
+        }
+    }
+}
 
-            sqlx::migrate!("./migrations").run(&pool).await?;
-            clear_rss_tables(&pool).await?;
+/// レスポンスの日時。既定はUTC（`Z`）のまま、`tz`指定時はそのタイムゾーンのオフセット付きで返す
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(untagged)]
+enum ResponseTimestamp {
+    Utc(chrono::DateTime<chrono::Utc>),
+    Local(chrono::DateTime<chrono::FixedOffset>),
+}
 
-            let server = MockServer::start().await;
-            let rss_body = r#"<?xml version="1.0" encoding="UTF-8"?>
-                <rss version="2.0">
-                  <channel>
-                    <title>Test</title>
+impl ResponseTimestamp {
+    fn to_timezone(self, tz: chrono_tz::Tz) -> Self {
+        match self {
+            Self::Utc(value) => Self::Local(value.with_timezone(&tz).fixed_offset()),
+            Self::Local(value) => Self::Local(value.with_timezone(&tz).fixed_offset()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ArticleItemResponse {
+    id: uuid::Uuid,
+    created_at: ResponseTimestamp,
+    updated_at: ResponseTimestamp,
+    link: String,
+    title: String,
+    pub_date: Option<ResponseTimestamp>,
+    /// フィードに書かれていたpub_dateのUTCからのオフセット（分）。現地時刻へ戻す際に使う
+    pub_date_tz_offset: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    group: Option<String>,
+    /// 本文取得時のスクレイプのstatus_code（queueの値をそのまま返す）
+    status_code: Option<i32>,
+    /// multipart/mixedで返す場合は本文を別パートに置くため省略する
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_brotli_base64: Option<String>,
+    /// 本文のBrotliバイト列（`content_brotli_base64`をBase64デコードしたもの）のバイト数。multipartでも返す
+    compressed_size: usize,
+    /// 保存時の圧縮品質（記録の無い既存記事はnull）
+    content_compression_quality: Option<u8>,
+    /// 要約APIで生成した本文の要約（未要約の記事はnull）
+    summary: Option<String>,
+    /// 記事HTMLのOGメタデータ（OGタグが無い記事はnull）
+    og_title: Option<String>,
+    og_description: Option<String>,
+    og_image: Option<String>,
+    /// フィードのentryのcontent（descriptionとは別に保存したもの）。`include=feed_content`指定時のみ返す
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_content: Option<String>,
+}
+
+impl ArticleItemResponse {
+    fn from_article(
+        article: &Article,
+        content_brotli_base64: Option<String>,
+        compressed_size: usize,
+    ) -> Self {
+        Self {
+            id: article.id,
+            created_at: ResponseTimestamp::Utc(article.created_at),
+            updated_at: ResponseTimestamp::Utc(article.updated_at),
+            link: article.link.clone(),
+            title: article.title.clone(),
+            pub_date: article.pub_date.map(ResponseTimestamp::Utc),
+            pub_date_tz_offset: article.pub_date_tz_offset,
+            description: Some(article.description.clone()),
+            group: article.group.clone(),
+            status_code: article.status_code,
+            content_brotli_base64,
+            compressed_size,
+            content_compression_quality: article
+                .compression_quality
+                .and_then(|quality| u8::try_from(quality).ok()),
+            summary: article.summary.clone(),
+            og_title: article.og_title.clone(),
+            og_description: article.og_description.clone(),
+            og_image: article.og_image.clone(),
+            feed_content: None,
+        }
+    }
+
+    /// `include=feed_content`指定時にフィードのentryのcontentを付ける
+    fn with_feed_content(mut self, feed_content: Option<String>) -> Self {
+        self.feed_content = feed_content;
+        self
+    }
+
+    /// pub_date・created_at・updated_atを`tz`の現地時刻（オフセット付き）へ変換する（Noneならそのまま）
+    fn with_timezone(mut self, tz: Option<chrono_tz::Tz>) -> Self {
+        if let Some(tz) = tz {
+            self.created_at = self.created_at.to_timezone(tz);
+            self.updated_at = self.updated_at.to_timezone(tz);
+            self.pub_date = self.pub_date.map(|pub_date| pub_date.to_timezone(tz));
+        }
+        self
+    }
+
+    /// descriptionを`max_chars`文字で切り詰める（0なら省略）
+    fn with_description_max(mut self, max_chars: Option<usize>) -> Self {
+        if let (Some(max_chars), Some(description)) = (max_chars, self.description.take()) {
+            self.description = truncate_description(&description, max_chars);
+        }
+        self
+    }
+}
+
+/// `include`の指定から`feed_content`を返すかを判定する。未知の項目は400にする
+fn resolve_include_feed_content(param: Option<&str>) -> ApiResult<bool> {
+    let mut feed_content = false;
+    for item in param
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        match item {
+            "feed_content" => feed_content = true,
+            other => {
+                return Err(bad_request(
+                    "invalid_include",
+                    format!("includeに指定できるのはfeed_contentのみです: {}", other),
+                ))
+            }
+        }
+    }
+    Ok(feed_content)
+}
+
+/// カンマ区切りのgroup指定を分解する。前後の空白を除き、空要素と重複は取り除く。
+pub(crate) fn parse_group_filter(value: &str) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for group in value
+        .split(',')
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+    {
+        if !groups.iter().any(|existing| existing == group) {
+            groups.push(group.to_string());
+        }
+    }
+    groups
+}
+
+/// 文字単位で切り詰め、切り詰めた場合は末尾に`…`を付ける。0文字指定時はNoneを返す。
+pub(crate) fn truncate_description(description: &str, max_chars: usize) -> Option<String> {
+    if max_chars == 0 {
+        return None;
+    }
+
+    match description.char_indices().nth(max_chars) {
+        Some((byte_index, _)) => Some(format!("{}…", &description[..byte_index])),
+        None => Some(description.to_string()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ArticleListResponse {
+    items: Vec<ArticleItemResponse>,
+    next_token: Option<PageToken>,
+    prev_token: Option<PageToken>,
+}
+
+/// 記事一覧のキャッシュキー。同じクエリでもレスポンス形式が違えば別に持つ
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ArticleListCacheKey {
+    query: ArticleListQuery,
+    multipart: bool,
+}
+
+/// 記事一覧を返す。`Accept: multipart/mixed`指定時は本文をBase64にせず生のBrotliパートで返す。
+/// キャッシュが有効なら同じクエリの2回目以降はDBを引かずにキャッシュから返す。
+async fn list_articles_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<ArticleListQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    // 期限切れのトークンがキャッシュ済みのページを引き続き受け取れないよう、キャッシュより先に確かめる
+    validate_page_tokens(&state, &params)?;
+
+    let multipart = accepts_multipart_mixed(&headers);
+    let Some(cache) = state.article_cache.clone() else {
+        return list_articles(&state, params, multipart).await;
+    };
+
+    let key = ArticleListCacheKey {
+        query: params.clone(),
+        multipart,
+    };
+    if let Some(response) = cache.get(&key).await {
+        return Ok(response);
+    }
+    let response = list_articles(&state, params, multipart).await?;
+    cache.insert(key, response).await.map_err(internal_error)
+}
+
+/// page_token・prev_tokenの署名と有効期限を確かめる
+fn validate_page_tokens(state: &ApiState, params: &ArticleListQuery) -> ApiResult<()> {
+    // 発行時刻を書き換えてTTLを回避できないよう、期限より先に署名を確かめる
+    if [params.page_token, params.prev_token]
+        .iter()
+        .flatten()
+        .any(|token| !token.has_valid_signature(&state.token_secret))
+    {
+        return Err(bad_request(
+            "invalid_token",
+            "ページングトークンが不正です。最初のページから取得し直してください",
+        ));
+    }
+    let now = chrono::Utc::now();
+    if [params.page_token, params.prev_token]
+        .iter()
+        .flatten()
+        .any(|token| token.is_expired(state.token_ttl_secs, now))
+    {
+        return Err(bad_request(
+            "token_expired",
+            "ページングトークンの有効期限が切れています。最初のページから取得し直してください",
+        ));
+    }
+
+    Ok(())
+}
+
+async fn list_articles(
+    state: &ApiState,
+    params: ArticleListQuery,
+    multipart: bool,
+) -> ApiResult<Response> {
+    let json_case = resolve_json_case(params.case.as_deref(), state.json_case)?;
+    let sort = match params.sort.as_deref() {
+        Some(value) => value
+            .parse()
+            .map_err(|e: anyhow::Error| bad_request("invalid_sort", e))?,
+        None => state.default_sort,
+    };
+    let cursor_field = match params.cursor_field.as_deref() {
+        Some(value) => value
+            .parse()
+            .map_err(|e: anyhow::Error| bad_request("invalid_cursor_field", e))?,
+        None => ArticleCursorField::default(),
+    };
+    if cursor_field == ArticleCursorField::UpdatedAt && params.sort.is_some() {
+        return Err(bad_request(
+            "conflicting_sort",
+            "cursor_field=updated_atはsortと同時に指定できません",
+        ));
+    }
+
+    let limit_param = match params.limit {
+        Some(value) if value <= 0 => {
+            return Err(bad_request(
+                "invalid_limit",
+                "limitは1以上で指定してください",
+            ));
+        }
+        Some(value) => value.min(MAX_LIMIT),
+        None => UNSPECIFIED_LIMIT,
+    };
+
+    let description_max = match params.description_max {
+        Some(value) if value < 0 => {
+            return Err(bad_request(
+                "invalid_description_max",
+                "description_maxは0以上で指定してください",
+            ));
+        }
+        Some(value) => Some(value as usize),
+        None => None,
+    };
+    let include_feed_content = resolve_include_feed_content(params.include.as_deref())?;
+
+    let tz = match params.tz.as_deref().map(str::trim) {
+        Some(name) => Some(name.parse::<chrono_tz::Tz>().map_err(|_| {
+            bad_request(
+                "invalid_tz",
+                format!(
+                    "tzはIANAのタイムゾーン名（例: Asia/Tokyo）で指定してください: {}",
+                    name
+                ),
+            )
+        })?),
+        None => None,
+    };
+
+    let groups = match params.group.as_deref() {
+        Some(value) => {
+            let groups = parse_group_filter(value);
+            if groups.is_empty() {
+                return Err(bad_request(
+                    "invalid_group",
+                    "groupは1つ以上のグループ名をカンマ区切りで指定してください",
+                ));
+            }
+            groups
+        }
+        None => Vec::new(),
+    };
+    let text_search = match params.q.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(bad_request(
+                "invalid_q",
+                "qは空でない文字列で指定してください",
+            ));
+        }
+        Some(query) => Some(ArticleTextSearch {
+            query: query.to_string(),
+            order_by_rank: params.rank.unwrap_or(false),
+        }),
+        None if params.rank.is_some() => {
+            return Err(bad_request(
+                "invalid_rank",
+                "rankはqと合わせて指定してください",
+            ));
+        }
+        None => None,
+    };
+    let order_by_rank = text_search
+        .as_ref()
+        .is_some_and(|search| search.order_by_rank);
+    // filterのDSLは個別パラメータとANDで組み合わせる。同じ条件を両方で指定した場合（group）は曖昧なので拒否する
+    let dsl = match params.filter.as_deref() {
+        Some(value) => value
+            .parse::<ArticleFilterDsl>()
+            .map_err(|e| bad_request("invalid_filter", e))?,
+        None => ArticleFilterDsl::default(),
+    };
+    if !dsl.groups.is_empty() && !groups.is_empty() {
+        return Err(bad_request(
+            "conflicting_filter",
+            "groupはfilterのgroup:と同時に指定できません",
+        ));
+    }
+    let groups = if dsl.groups.is_empty() {
+        groups
+    } else {
+        dsl.groups
+    };
+    let filter = ArticleFilter {
+        groups: ArticleGroupFilter {
+            groups,
+            include_subgroups: params.include_subgroups.unwrap_or(false),
+        },
+        has_pubdate: params.has_pubdate,
+        text_search,
+        pub_date_after: dsl.pub_date_after,
+        pub_date_before: dsl.pub_date_before,
+        hosts: dsl.hosts,
+    };
+
+    let cursor_params = [
+        params.page_token.is_some(),
+        params.prev_token.is_some(),
+        params.before.is_some() || params.before_id.is_some(),
+    ];
+    if cursor_params.iter().filter(|specified| **specified).count() > 1 {
+        return Err(bad_request(
+            "conflicting_cursor",
+            "page_token・prev_token・before/before_idは同時に指定できません",
+        ));
+    }
+    if order_by_rank && cursor_params.iter().any(|specified| *specified) {
+        return Err(bad_request(
+            "conflicting_cursor",
+            "rank=trueはpage_token・prev_token・before/before_idと同時に指定できません",
+        ));
+    }
+
+    let cursor = if let Some(before) = params.before.as_deref() {
+        let sort_key = chrono::DateTime::parse_from_rfc3339(before.trim())
+            .map_err(|_| {
+                bad_request(
+                    "invalid_before",
+                    "beforeはRFC3339形式の日時で指定してください",
+                )
+            })?
+            .with_timezone(&chrono::Utc);
+        // before_id未指定時はnil UUIDを使い、beforeと同時刻の記事を含めない
+        Some(ArticleCursor {
+            id: params.before_id.unwrap_or_else(uuid::Uuid::nil),
+            sort_key,
+            field: cursor_field,
+        })
+    } else if params.before_id.is_some() {
+        return Err(bad_request(
+            "invalid_before",
+            "before_idはbeforeと合わせて指定してください",
+        ));
+    } else if let Some(token) = params.page_token {
+        match find_article_cursor(&state.pool, token.id, sort, cursor_field).await {
+            Ok(Some(cursor)) => Some(cursor),
+            Ok(None) => {
+                return Err(bad_request(
+                    "page_token_not_found",
+                    "page_token is not exist",
+                ))
+            }
+            Err(e) => return Err(internal_error(e)),
+        }
+    } else if let Some(token) = params.prev_token {
+        match find_article_cursor(&state.pool, token.id, sort, cursor_field).await {
+            Ok(Some(cursor)) => Some(cursor),
+            Ok(None) => {
+                return Err(bad_request(
+                    "prev_token_not_found",
+                    "prev_token is not exist",
+                ))
+            }
+            Err(e) => return Err(internal_error(e)),
+        }
+    } else {
+        None
+    };
+
+    let direction = if params.prev_token.is_some() {
+        PageDirection::Newer
+    } else {
+        PageDirection::Older
+    };
+
+    let fetch_limit = limit_param.checked_add(1).unwrap_or(limit_param);
+
+    let articles = search_articles_window(
+        &state.pool,
+        fetch_limit,
+        cursor.as_ref(),
+        direction,
+        sort,
+        cursor_field,
+        &filter,
+    )
+    .await
+    .map_err(internal_error)?;
+
+    let mut trimmed_articles = articles;
+    let overflowed = trimmed_articles.len() as i64 == fetch_limit;
+    // has_moreは古い側、has_newerは新しい側に続きがあるか
+    let (mut has_more, has_newer) = match direction {
+        PageDirection::Older => {
+            if overflowed {
+                trimmed_articles.truncate(limit_param as usize);
+            }
+            (overflowed, cursor.is_some())
+        }
+        PageDirection::Newer => {
+            // 余剰分はカーソルから最も遠い（最も新しい）先頭側に来る
+            if overflowed {
+                trimmed_articles.remove(0);
+            }
+            (!trimmed_articles.is_empty(), overflowed)
+        }
+    };
+
+    let mut total_content_bytes = 0usize;
+    let mut response_items = Vec::new();
+    let mut content_parts = Vec::new();
+
+    for article in &trimmed_articles {
+        // content_brotli_base64の契約を保つため、Brotli以外で保存された本文は詰め替えて返す
+        let brotli = to_brotli_blocking(&article.data, article.compression)
+            .await
+            .map_err(internal_error)?;
+        // multipartでは生のBrotliバイト列、JSONではBase64後の長さで応答サイズを数える
+        let encoded = (!multipart).then(|| STANDARD.encode(&brotli));
+        let feed_content = include_feed_content
+            .then(|| article.feed_content.clone())
+            .flatten();
+        let content_bytes = encoded.as_ref().map_or(brotli.len(), String::len)
+            + feed_content.as_ref().map_or(0, String::len);
+        if content_bytes > MAX_RESPONSE_BYTES {
+            return Err(error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "article_too_large",
+                format!("記事ID {} の本文が応答許容量を超過しました", article.id),
+            ));
+        }
+
+        if total_content_bytes + content_bytes > MAX_RESPONSE_BYTES {
+            has_more = true;
+            break;
+        }
+
+        total_content_bytes += content_bytes;
+        response_items.push(
+            ArticleItemResponse::from_article(article, encoded, brotli.len())
+                .with_timezone(tz)
+                .with_description_max(description_max)
+                .with_feed_content(feed_content),
+        );
+        if multipart {
+            content_parts.push(brotli);
+        }
+    }
+
+    if response_items.len() < trimmed_articles.len() {
+        has_more = true;
+    }
+
+    // 関連度順はカーソルで続きを取れないため、先頭のlimit件だけを返す
+    let next_token = if has_more && !order_by_rank {
+        response_items
+            .last()
+            .map(|item| PageToken::issue(item.id, state.token_ttl_secs, &state.token_secret))
+    } else {
+        None
+    };
+    let prev_token = if has_newer {
+        response_items
+            .first()
+            .map(|item| PageToken::issue(item.id, state.token_ttl_secs, &state.token_secret))
+    } else {
+        None
+    };
+
+    let response = ArticleListResponse {
+        items: response_items,
+        next_token,
+        prev_token,
+    };
+    if multipart {
+        return render_multipart_articles(&response, json_case, content_parts);
+    }
+    Ok(render_json(&response, json_case)?.into_response())
+}
+
+/// Acceptヘッダに`multipart/mixed`が含まれるか
+pub(crate) fn accepts_multipart_mixed(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media_range| media_range.split(';').next())
+        .any(|media_type| media_type.trim().eq_ignore_ascii_case("multipart/mixed"))
+}
+
+/// 記事一覧をmultipart/mixedで組み立てる。
+///
+/// 先頭パートは`content_brotli_base64`を除いたメタ情報のJSON、以降は`items`と同じ順に各本文の生のBrotliバイト列を
+/// 1パートずつ置く（`Content-ID`は記事ID）。境界文字列はリクエストごとにランダムに生成する。
+fn render_multipart_articles(
+    response: &ArticleListResponse,
+    json_case: JsonCase,
+    content_parts: Vec<Vec<u8>>,
+) -> ApiResult<Response> {
+    let Json(meta) = render_json(response, json_case)?;
+    let meta = serde_json::to_vec(&meta).map_err(internal_error)?;
+    let boundary = format!("datadoggo-{}", uuid::Uuid::new_v4().simple());
+
+    let content_len: usize = content_parts.iter().map(Vec::len).sum();
+    let mut body = Vec::with_capacity(meta.len() + content_len + 256 * (content_parts.len() + 1));
+    body.extend_from_slice(
+        format!("--{}\r\nContent-Type: application/json\r\n\r\n", boundary).as_bytes(),
+    );
+    body.extend_from_slice(&meta);
+    for (item, content) in response.items.iter().zip(content_parts) {
+        body.extend_from_slice(
+            format!(
+                "\r\n--{}\r\nContent-Type: application/octet-stream\r\nContent-Encoding: br\r\nContent-ID: <{}>\r\n\r\n",
+                boundary, item.id
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(&content);
+    }
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            format!("multipart/mixed; boundary={}", boundary),
+        )],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaimArticlesQuery {
+    limit: Option<i64>,
+    consumer: Option<String>,
+    case: Option<String>,
+    /// 既定では返さない項目をカンマ区切りで指定する（`feed_content`）
+    include: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClaimArticlesResponse {
+    items: Vec<ArticleItemResponse>,
+}
+
+/// 未処理の記事をクレームして返す。同じ記事が複数のコンシューマへ同時に渡らないようにする。
+async fn claim_articles_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<ClaimArticlesQuery>,
+) -> ApiResult<Json<Value>> {
+    let json_case = resolve_json_case(params.case.as_deref(), state.json_case)?;
+    let consumer = require_consumer(params.consumer.as_deref())?;
+    let include_feed_content = resolve_include_feed_content(params.include.as_deref())?;
+
+    let limit = match params.limit {
+        Some(value) if value <= 0 => {
+            return Err(bad_request(
+                "invalid_limit",
+                "limitは1以上で指定してください",
+            ));
+        }
+        Some(value) => value.min(MAX_LIMIT),
+        None => DEFAULT_CLAIM_LIMIT,
+    };
+
+    let articles = claim_articles(&state.pool, limit, consumer, CLAIM_LEASE_SECS)
+        .await
+        .map_err(internal_error)?;
+
+    let mut items = Vec::with_capacity(articles.len());
+    for article in &articles {
+        let brotli = to_brotli_blocking(&article.data, article.compression)
+            .await
+            .map_err(internal_error)?;
+        items.push(
+            ArticleItemResponse::from_article(
+                article,
+                Some(STANDARD.encode(&brotli)),
+                brotli.len(),
+            )
+            .with_feed_content(
+                include_feed_content
+                    .then(|| article.feed_content.clone())
+                    .flatten(),
+            ),
+        );
+    }
+
+    render_json(&ClaimArticlesResponse { items }, json_case)
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchArticlesQuery {
+    case: Option<String>,
+    /// 既定では返さない項目をカンマ区切りで指定する（`feed_content`）
+    include: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchArticlesRequest {
+    ids: Vec<uuid::Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchArticlesResponse {
+    /// 指定した順に並べた記事（重複したIDは最初の位置に1件だけ）
+    items: Vec<ArticleItemResponse>,
+    /// 本文の無い・存在しない・別の記事へマージ済みのID
+    not_found: Vec<uuid::Uuid>,
+    /// 応答サイズの上限に達したため返さなかったID（指定順）。改めて指定すれば続きを取れる
+    remaining_ids: Vec<uuid::Uuid>,
+}
+
+/// 指定したIDの記事を指定順にまとめて返す。応答が上限を超える分は`remaining_ids`に回す。
+async fn batch_articles_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<BatchArticlesQuery>,
+    Json(payload): Json<BatchArticlesRequest>,
+) -> ApiResult<Json<Value>> {
+    let json_case = resolve_json_case(params.case.as_deref(), state.json_case)?;
+    let include_feed_content = resolve_include_feed_content(params.include.as_deref())?;
+
+    if payload.ids.is_empty() {
+        return Err(bad_request("invalid_ids", "idsを1件以上指定してください"));
+    }
+    // 重複除去より先に件数を確かめ、上限を超えるリクエストでは何もしない
+    if payload.ids.len() as i64 > MAX_LIMIT {
+        return Err(bad_request(
+            "too_many_ids",
+            format!("idsは{}件以下で指定してください", MAX_LIMIT),
+        ));
+    }
+    let mut seen = HashSet::with_capacity(payload.ids.len());
+    let ids: Vec<uuid::Uuid> = payload
+        .ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    let mut articles: HashMap<uuid::Uuid, Article> = find_articles_by_ids(&state.pool, &ids)
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .map(|article| (article.id, article))
+        .collect();
+
+    let mut response = BatchArticlesResponse {
+        items: Vec::new(),
+        not_found: Vec::new(),
+        remaining_ids: Vec::new(),
+    };
+    let mut total_content_bytes = 0usize;
+    for id in ids {
+        let Some(article) = articles.remove(&id) else {
+            response.not_found.push(id);
+            continue;
+        };
+        // 上限に達した後の記事は、収まるかどうかによらず指定順のまま続きへ回す
+        if !response.remaining_ids.is_empty() {
+            response.remaining_ids.push(id);
+            continue;
+        }
+
+        let brotli = to_brotli_blocking(&article.data, article.compression)
+            .await
+            .map_err(internal_error)?;
+        let encoded = STANDARD.encode(&brotli);
+        let feed_content = include_feed_content
+            .then(|| article.feed_content.clone())
+            .flatten();
+        let content_bytes = encoded.len() + feed_content.as_ref().map_or(0, String::len);
+        if content_bytes > MAX_RESPONSE_BYTES {
+            return Err(error_response(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "article_too_large",
+                format!("記事ID {} の本文が応答許容量を超過しました", article.id),
+            ));
+        }
+        if total_content_bytes + content_bytes > MAX_RESPONSE_BYTES {
+            response.remaining_ids.push(id);
+            continue;
+        }
+
+        total_content_bytes += content_bytes;
+        response.items.push(
+            ArticleItemResponse::from_article(&article, Some(encoded), brotli.len())
+                .with_feed_content(feed_content),
+        );
+    }
+
+    render_json(&response, json_case)
+}
+
+#[derive(Debug, Deserialize)]
+struct AckArticlesRequest {
+    consumer: String,
+    ids: Vec<uuid::Uuid>,
+}
+
+/// クレーム中の記事を処理済みにしてクレームを解除する。ackした件数を返す。
+async fn ack_articles_handler(
+    State(state): State<ApiState>,
+    Json(payload): Json<AckArticlesRequest>,
+) -> ApiResult<Json<Value>> {
+    let consumer = require_consumer(Some(&payload.consumer))?;
+
+    let acked = ack_articles(&state.pool, consumer, &payload.ids)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(json!({ "acked": acked })))
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeArticlesRequest {
+    keep: uuid::Uuid,
+    merge: Vec<uuid::Uuid>,
+}
+
+/// 重複した記事（`merge`）を`keep`へ統合し、`merge`側を論理削除する。認証必須。
+/// 存在しないIDやマージ済みのIDが1つでもあれば何も変更しない。
+async fn merge_articles_handler(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(payload): Json<MergeArticlesRequest>,
+) -> ApiResult<Json<ArticleMerge>> {
+    require_admin(&state, &headers)?;
+
+    if payload.merge.is_empty() {
+        return Err(bad_request(
+            "invalid_merge",
+            "mergeを1件以上指定してください",
+        ));
+    }
+    if payload.merge.len() > MAX_LIMIT as usize {
+        return Err(bad_request(
+            "invalid_merge",
+            format!("mergeは{}件以下で指定してください", MAX_LIMIT),
+        ));
+    }
+    if payload.merge.contains(&payload.keep) {
+        return Err(bad_request(
+            "invalid_merge",
+            "keepをmergeに含めることはできません",
+        ));
+    }
+
+    let merged = match merge_articles(&state.pool, payload.keep, &payload.merge)
+        .await
+        .map_err(internal_error)?
+    {
+        MergeArticlesOutcome::Merged(merged) => merged,
+        MergeArticlesOutcome::NotFound(ids) => {
+            return Err(error_response(
+                StatusCode::NOT_FOUND,
+                "article_not_found",
+                format!("記事が見つかりません: {}", join_ids(&ids)),
+            ));
+        }
+        MergeArticlesOutcome::AlreadyMerged(ids) => {
+            return Err(error_response(
+                StatusCode::CONFLICT,
+                "already_merged",
+                format!("既にマージ済みの記事です: {}", join_ids(&ids)),
+            ));
+        }
+    };
+
+    // マージした記事が一覧のキャッシュに残らないようにする
+    state.clear_article_cache();
+    info!(
+        keep = %merged.keep,
+        merged = merged.merged.len(),
+        content_moved_from = ?merged.content_moved_from,
+        "記事をマージしました"
+    );
+    Ok(Json(merged))
+}
+
+fn join_ids(ids: &[uuid::Uuid]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// コンシューマ名（空白のみは不可）を取り出す
+fn require_consumer(consumer: Option<&str>) -> ApiResult<&str> {
+    consumer
+        .map(str::trim)
+        .filter(|consumer| !consumer.is_empty())
+        .ok_or_else(|| bad_request("invalid_consumer", "consumerを指定してください"))
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateQueueStatusRequest {
+    status_code: i32,
+}
+
+/// queueのstatus_codeを手動で更新する（410で諦め済みにすると再取得対象から外れる）。認証必須。
+async fn update_queue_status_handler(
+    State(state): State<ApiState>,
+    Path(id): Path<uuid::Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateQueueStatusRequest>,
+) -> ApiResult<Json<Queue>> {
+    require_admin(&state, &headers)?;
+
+    // 200は本文保存と対になるため手動では設定させない
+    if !(100..=599).contains(&payload.status_code) || payload.status_code == 200 {
+        return Err(bad_request(
+            "invalid_status_code",
+            "status_codeは200以外の100〜599で指定してください",
+        ));
+    }
+
+    let entry = set_queue_status(&state.pool, id, payload.status_code)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "queue_not_found",
+                format!("queue ID {} が見つかりません", id),
+            )
+        })?;
+
+    // 一覧のキャッシュに更新前のstatus_codeが残らないようにする
+    state.clear_article_cache();
+
+    Ok(Json(entry))
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticleByLinkQuery {
+    url: String,
+    case: Option<String>,
+    /// 既定では返さない項目をカンマ区切りで指定する（`feed_content`）
+    include: Option<String>,
+}
+
+/// 正規化後のlinkが完全一致する記事を1件返す。表記揺れで複数該当した場合は最新の記事を返す。
+async fn article_by_link_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<ArticleByLinkQuery>,
+) -> ApiResult<Json<Value>> {
+    let json_case = resolve_json_case(params.case.as_deref(), state.json_case)?;
+    let include_feed_content = resolve_include_feed_content(params.include.as_deref())?;
+
+    if params.url.trim().is_empty() {
+        return Err(bad_request("invalid_url", "urlを指定してください"));
+    }
+
+    let article = find_article_by_link(&state.pool, &params.url)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "article_not_found",
+                "指定したurlの記事が見つかりません",
+            )
+        })?;
+
+    let brotli = to_brotli_blocking(&article.data, article.compression)
+        .await
+        .map_err(internal_error)?;
+    let encoded = STANDARD.encode(&brotli);
+    let feed_content = include_feed_content
+        .then(|| article.feed_content.clone())
+        .flatten();
+    if encoded.len() + feed_content.as_ref().map_or(0, String::len) > MAX_RESPONSE_BYTES {
+        return Err(error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "article_too_large",
+            format!("記事ID {} の本文が応答許容量を超過しました", article.id),
+        ));
+    }
+
+    render_json(
+        &ArticleItemResponse::from_article(&article, Some(encoded), brotli.len())
+            .with_feed_content(feed_content),
+        json_case,
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticleExportQuery {
+    /// カンマ区切りで複数指定するといずれかのgroupに属する記事を返す（`world,tech`）
+    group: Option<String>,
+    /// `group`を`/`区切りの階層とみなし、配下のグループの記事も含める
+    include_subgroups: Option<bool>,
+    /// この日時（RFC3339）以降にqueueへ追加された記事
+    created_after: Option<String>,
+    /// この日時（RFC3339）より前にqueueへ追加された記事
+    created_before: Option<String>,
+}
+
+/// クエリの日時（RFC3339）を解釈する。不正なら`code`の400を返す
+fn parse_rfc3339_param(
+    value: Option<&str>,
+    code: &str,
+    name: &str,
+) -> ApiResult<Option<chrono::DateTime<chrono::Utc>>> {
+    value
+        .map(|value| {
+            chrono::DateTime::parse_from_rfc3339(value.trim())
+                .map(|datetime| datetime.with_timezone(&chrono::Utc))
+                .map_err(|_| {
+                    bad_request(
+                        code,
+                        format!("{}はRFC3339形式の日時で指定してください", name),
+                    )
+                })
+        })
+        .transpose()
+}
+
+/// 条件に合う記事の本文を`<id>.html`として詰めたtar.gzをストリーミングで返す
+async fn export_articles_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<ArticleExportQuery>,
+) -> ApiResult<Response> {
+    let groups = match params.group.as_deref() {
+        Some(value) => {
+            let groups = parse_group_filter(value);
+            if groups.is_empty() {
+                return Err(bad_request(
+                    "invalid_group",
+                    "groupは1つ以上のグループ名をカンマ区切りで指定してください",
+                ));
+            }
+            groups
+        }
+        None => Vec::new(),
+    };
+    let filter = ArticleExportFilter {
+        groups: ArticleGroupFilter {
+            groups,
+            include_subgroups: params.include_subgroups.unwrap_or(false),
+        },
+        created_after: parse_rfc3339_param(
+            params.created_after.as_deref(),
+            "invalid_created_after",
+            "created_after",
+        )?,
+        created_before: parse_rfc3339_param(
+            params.created_before.as_deref(),
+            "invalid_created_before",
+            "created_before",
+        )?,
+    };
+
+    // 送信を始めた後はステータスを変えられないため、途中のエラーはログに残してボディを打ち切る
+    let stream = export_articles_tar_gz(state.pool.clone(), filter).inspect_err(|e| {
+        warn!(error = %e, "記事のtar.gzエクスポートを中断しました");
+    });
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/gzip"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"articles.tar.gz\"",
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+/// クエリの`case`指定を解釈する。未指定ならサーバ既定値を使う。
+fn resolve_json_case(param: Option<&str>, default: JsonCase) -> ApiResult<JsonCase> {
+    match param {
+        Some(value) => value
+            .parse()
+            .map_err(|e: anyhow::Error| bad_request("invalid_case", e)),
+        None => Ok(default),
+    }
+}
+
+/// レスポンスをJSONへ変換し、camelCase指定時はキーを一括変換する
+fn render_json<T: Serialize>(value: &T, json_case: JsonCase) -> ApiResult<Json<Value>> {
+    let value = serde_json::to_value(value).map_err(internal_error)?;
+    Ok(Json(match json_case {
+        JsonCase::Snake => value,
+        JsonCase::Camel => camelize_keys(value),
+    }))
+}
+
+/// オブジェクトのキーを再帰的にcamelCaseへ変換する（値は変更しない）
+pub(crate) fn camelize_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (snake_to_camel(&key), camelize_keys(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camelize_keys).collect()),
+        other => other,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper_next = false;
+
+    for ch in key.chars() {
+        if ch == '_' && !result.is_empty() {
+            upper_next = true;
+        } else if upper_next {
+            result.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
+/// Rangeヘッダの解釈結果
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ByteRange {
+    /// Range指定なし、または解釈できない指定（全体を返す）
+    Full,
+    /// 両端を含むバイト範囲
+    Partial { start: usize, end: usize },
+    /// 本文長に対して満たせない範囲
+    Unsatisfiable,
+}
+
+/// `Range: bytes=...`ヘッダを本文長に対して解釈する。単一範囲のみ対応し、複数範囲や不正な書式は全体扱いとする。
+pub(crate) fn resolve_byte_range(header: Option<&str>, len: usize) -> ByteRange {
+    let Some(spec) = header.and_then(|value| value.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return ByteRange::Full,
+        ("", suffix) => match suffix.parse::<usize>() {
+            Ok(0) => return ByteRange::Unsatisfiable,
+            Ok(suffix) => (len.saturating_sub(suffix), len.saturating_sub(1)),
+            Err(_) => return ByteRange::Full,
+        },
+        (start, "") => match start.parse::<usize>() {
+            Ok(start) => (start, len.saturating_sub(1)),
+            Err(_) => return ByteRange::Full,
+        },
+        (start, end) => match (start.parse::<usize>(), end.parse::<usize>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, end.min(len.saturating_sub(1))),
+            _ => return ByteRange::Full,
+        },
+    };
+
+    if len == 0 || start >= len {
+        ByteRange::Unsatisfiable
+    } else {
+        ByteRange::Partial { start, end }
+    }
+}
+
+/// 展開済みHTMLを返す。Rangeヘッダがあれば該当バイト範囲のみを206で返す。
+async fn article_html_handler(
+    State(state): State<ApiState>,
+    Path(id): Path<uuid::Uuid>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let article = find_article_by_id(&state.pool, id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "article_not_found",
+                format!("記事ID {} が見つかりません", id),
+            )
+        })?;
+
+    let html = decompress_content(&article.data, article.compression).map_err(internal_error)?;
+    let range_header = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+
+    let response = match resolve_byte_range(range_header, html.len()) {
+        ByteRange::Full => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+            ],
+            html,
+        )
+            .into_response(),
+        ByteRange::Partial { start, end } => (
+            StatusCode::PARTIAL_CONTENT,
+            [
+                (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+                (
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end, html.len()),
+                ),
+            ],
+            html[start..=end].to_vec(),
+        )
+            .into_response(),
+        ByteRange::Unsatisfiable => (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(header::CONTENT_RANGE, format!("bytes */{}", html.len()))],
+        )
+            .into_response(),
+    };
+
+    Ok(response)
+}
+
+#[derive(Debug, Serialize)]
+struct ArticleLinksResponse {
+    id: uuid::Uuid,
+    links: Vec<String>,
+}
+
+/// fetch-contentが`EXTRACT_LINKS=true`で保存した本文中のリンクを出現順に返す
+async fn article_links_handler(
+    State(state): State<ApiState>,
+    Path(id): Path<uuid::Uuid>,
+) -> ApiResult<Json<ArticleLinksResponse>> {
+    let links = search_article_links(&state.pool, id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "article_not_found",
+                format!("記事ID {} が見つかりません", id),
+            )
+        })?;
+
+    Ok(Json(ArticleLinksResponse { id, links }))
+}
+
+/// 集約フィード（`/api/feed.xml`）の既定の記事数
+const FEED_DEFAULT_LIMIT: i64 = 50;
+
+#[derive(Debug, Deserialize)]
+struct ArticleFeedQuery {
+    limit: Option<i64>,
+    /// カンマ区切りで複数指定するといずれかのgroupに属する記事を返す（`world,tech`）
+    group: Option<String>,
+    /// `group`を`/`区切りの階層とみなし、配下のグループの記事も含める
+    include_subgroups: Option<bool>,
+    /// `true`ならpub_dateのある記事だけ、`false`ならpub_dateが無い記事だけを含める
+    has_pubdate: Option<bool>,
+}
+
+/// 絞り込んだ記事をRSS 2.0のフィードとして返す（本文は含めず、descriptionを概要として載せる）
+async fn article_feed_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<ArticleFeedQuery>,
+    headers: HeaderMap,
+    uri: Uri,
+) -> ApiResult<Response> {
+    let limit = match params.limit {
+        Some(value) if value <= 0 => {
+            return Err(bad_request(
+                "invalid_limit",
+                "limitは1以上で指定してください",
+            ));
+        }
+        Some(value) => value.min(MAX_LIMIT),
+        None => FEED_DEFAULT_LIMIT,
+    };
+    let groups = match params.group.as_deref() {
+        Some(value) => {
+            let groups = parse_group_filter(value);
+            if groups.is_empty() {
+                return Err(bad_request(
+                    "invalid_group",
+                    "groupは1つ以上のグループ名をカンマ区切りで指定してください",
+                ));
+            }
+            groups
+        }
+        None => Vec::new(),
+    };
+    let filter = ArticleFilter {
+        groups: ArticleGroupFilter {
+            groups,
+            include_subgroups: params.include_subgroups.unwrap_or(false),
+        },
+        has_pubdate: params.has_pubdate,
+        ..ArticleFilter::default()
+    };
+
+    let articles = search_articles_window(
+        &state.pool,
+        limit,
+        None,
+        PageDirection::Older,
+        state.default_sort,
+        ArticleCursorField::default(),
+        &filter,
+    )
+    .await
+    .map_err(internal_error)?;
+
+    let channel_link = feed_self_url(&headers, &uri);
+    let xml = render_rss_feed(&articles, &filter.groups.groups, &channel_link);
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        xml,
+    )
+        .into_response())
+}
+
+/// リクエストされたフィード自身のURL（`X-Forwarded-Proto`・`Host`から組み立てる）
+fn feed_self_url(headers: &HeaderMap, uri: &Uri) -> String {
+    let header_value = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let path = uri
+        .path_and_query()
+        .map_or("/api/feed.xml", |path| path.as_str());
+    match header_value("host") {
+        Some(host) => {
+            let scheme = header_value("x-forwarded-proto").unwrap_or("http");
+            format!("{}://{}{}", scheme, host, path)
+        }
+        None => path.to_string(),
+    }
+}
+
+/// 記事をRSS 2.0のXMLへ変換する。各itemはtitle/link/description/pubDate（pub_dateが無い記事は省略）/guid（記事ID）を持つ。
+fn render_rss_feed(articles: &[Article], groups: &[String], channel_link: &str) -> String {
+    let channel_title = if groups.is_empty() {
+        "datadoggo-v3-rss".to_string()
+    } else {
+        format!("datadoggo-v3-rss ({})", groups.join(", "))
+    };
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n",
+    );
+    xml.push_str(&format!("<title>{}</title>\n", escape_xml(&channel_title)));
+    xml.push_str(&format!("<link>{}</link>\n", escape_xml(channel_link)));
+    xml.push_str("<description>datadoggo-v3-rssが収集した記事</description>\n");
+    if let Some(latest) = articles.iter().map(|article| article.created_at).max() {
+        xml.push_str(&format!(
+            "<lastBuildDate>{}</lastBuildDate>\n",
+            latest.to_rfc2822()
+        ));
+    }
+
+    for article in articles {
+        xml.push_str("<item>\n");
+        xml.push_str(&format!("<title>{}</title>\n", escape_xml(&article.title)));
+        xml.push_str(&format!("<link>{}</link>\n", escape_xml(&article.link)));
+        xml.push_str(&format!(
+            "<description>{}</description>\n",
+            escape_xml(&article.description)
+        ));
+        if let Some(pub_date) = article.pub_date {
+            xml.push_str(&format!("<pubDate>{}</pubDate>\n", pub_date.to_rfc2822()));
+        }
+        xml.push_str(&format!(
+            "<guid isPermaLink=\"false\">{}</guid>\n",
+            article.id
+        ));
+        xml.push_str("</item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+/// XMLのテキストとしてエスケープする（XML 1.0で使えない制御文字は取り除く）
+fn escape_xml(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    escape_html(&text)
+}
+
+/// HTMLの特殊文字をエスケープする
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 保存済みHTMLをサンドボックス化したiframe（srcdoc）で包んだ確認用ページを返す。
+/// 相対リンクや外部スクリプトは動作しない前提の簡易ビューア。
+async fn article_preview_handler(
+    State(state): State<ApiState>,
+    Path(id): Path<uuid::Uuid>,
+) -> ApiResult<Response> {
+    let article = find_article_by_id(&state.pool, id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            error_response(
+                StatusCode::NOT_FOUND,
+                "article_not_found",
+                format!("記事ID {} が見つかりません", id),
+            )
+        })?;
+
+    let html = decompress_content(&article.data, article.compression).map_err(internal_error)?;
+    let title = escape_html(&article.title);
+    let link = escape_html(&article.link);
+    let page = format!(
+        r#"<!DOCTYPE html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ margin: 0; font-family: sans-serif; }}
+header {{ padding: 8px 12px; border-bottom: 1px solid #ccc; }}
+iframe {{ border: 0; width: 100%; height: calc(100vh - 64px); }}
+</style>
+</head>
+<body>
+<header><strong>{title}</strong><br><a href="{link}" rel="noopener noreferrer">{link}</a></header>
+<iframe sandbox srcdoc="{content}"></iframe>
+</body>
+</html>
+"#,
+        title = title,
+        link = link,
+        content = escape_html(&String::from_utf8_lossy(&html)),
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8"),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                PREVIEW_CONTENT_SECURITY_POLICY,
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+            (header::REFERRER_POLICY, "no-referrer"),
+        ],
+        page,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod fetch_rss_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use serde_json::Value;
+        use tower::ServiceExt;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::api::{build_router, ApiState};
+        use crate::test_support::{clear_rss_tables, create_temp_yaml, prepare_test_pool};
+
+        /// # 検証目的
+        /// API経由でRSS取得処理が実行され、結果がJSONで返ることを確認する。
+        #[tokio::test]
+        async fn rssを取得するエンドポイントが動作する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let rss_body = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0">
+                  <channel>
+                    <title>Test</title>
+                    <item>
+                      <title>Item</title>
+                      <link>https://example.com/item</link>
+                      <description>desc</description>
+                      <pubDate>Mon, 13 Oct 2025 12:00:00 GMT</pubDate>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+
+            Mock::given(method("GET"))
+                .and(path("/feed"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(rss_body))
+                .mount(&server)
+                .await;
+
+            let temp_file =
+                create_temp_yaml(&format!("test:\n  sample: {url}/feed", url = server.uri()))?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                server.uri(),
+                temp_file.path().to_string_lossy().to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(Request::post("/api/fetch-rss").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let value: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(value["total_processed"].as_u64(), Some(1));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// Webhookの送信成否とステータスがfetch-rssのレスポンスに反映され、未設定時は含まれないことを確認する。
+        #[tokio::test]
+        async fn webhookの送信結果をレスポンスに含める() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/feed"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+                    <rss version="2.0"><channel><title>Test</title>
+                      <item><title>Item</title><link>https://example.com/webhook-item</link></item>
+                    </channel></rss>"#,
+                ))
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/hook-ok"))
+                .respond_with(ResponseTemplate::new(204))
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/hook-ng"))
+                .respond_with(ResponseTemplate::new(500))
+                .mount(&server)
+                .await;
+
+            let temp_file =
+                create_temp_yaml(&format!("test:\n  sample: {url}/feed", url = server.uri()))?;
+
+            let cases = [
+                (Some(format!("{}/hook-ok", server.uri())), Some((true, 204))),
+                (
+                    Some(format!("{}/hook-ng", server.uri())),
+                    Some((false, 500)),
+                ),
+                (None, None),
+            ];
+
+            for (webhook_url, expected) in cases {
+                let state = ApiState::new(
+                    pool.clone(),
+                    server.uri(),
+                    temp_file.path().to_string_lossy().to_string(),
+                    webhook_url,
+                );
+                let response = build_router(state)
+                    .oneshot(Request::post("/api/fetch-rss").body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let value: Value = serde_json::from_slice(&bytes)?;
+                match expected {
+                    Some((sent, status)) => {
+                        assert_eq!(value["webhook"]["sent"].as_bool(), Some(sent));
+                        assert_eq!(value["webhook"]["status"].as_u64(), Some(status));
+                        assert_eq!(value["webhook"]["error"].is_null(), sent);
+                    }
+                    None => assert!(value.get("webhook").is_none()),
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    pub mod fetch_content_endpoint {
+        use std::io::{Cursor, Read};
+
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use brotli::Decompressor;
+        use serde_json::json;
+        use tower::ServiceExt;
+        use uuid::Uuid;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::api::{build_router, ApiState};
+        use crate::models::ArticleContent;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// API経由でコンテンツ取得処理を実行し、本文保存とレスポンス内容を確認する。
+        #[tokio::test]
+        async fn コンテンツ取得apiが成功する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let html_body = "<html><body>api ok</body></html>";
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": html_body,
+                    "status_code": 200,
+                    "title": "API Title",
+                    "final_url": "https://example.com/api",
+                    "elapsed_ms": 10.0,
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                })))
+                .mount(&server)
+                .await;
+
+            let queue_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(queue_id)
+            .bind("https://example.com/api")
+            .bind("APIテスト")
+            .bind("API説明")
+            .execute(&pool)
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                server.uri(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::post("/api/fetch-content")
+                        .header("content-type", "application/json")
+                        .body(Body::from("{\"limit\":1}"))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let value: serde_json::Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(value["saved_count"].as_u64(), Some(1));
+            assert_eq!(value["status_only_count"].as_u64(), Some(0));
+
+            let stored: ArticleContent = sqlx::query_as(
+                "SELECT queue_id, created_at, updated_at, data FROM rss.article_content WHERE queue_id = $1",
+            )
+            .bind(queue_id)
+            .fetch_one(&pool)
+            .await?;
+
+            let mut decompressor = Decompressor::new(Cursor::new(stored.data), 4096);
+            let mut decompressed = String::new();
+            decompressor.read_to_string(&mut decompressed)?;
+            assert_eq!(decompressed, html_body);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// limitに0を指定したリクエストが400エラーと`invalid_limit`コードを返すことを確認する。
+        #[tokio::test]
+        async fn limitが0ならエラーを返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::post("/api/fetch-content")
+                        .header("content-type", "application/json")
+                        .body(Body::from("{\"limit\":0}"))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"].as_str(), Some("invalid_limit"));
+            assert_eq!(
+                body["message"].as_str(),
+                Some("limitは1以上で指定してください")
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// POSTボディが上限を超えた場合、Content-Lengthの有無にかかわらず413を返すことを確認する。
+        #[tokio::test]
+        async fn 上限超過ボディは413になる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_max_body_bytes(64);
+            let app = build_router(state);
+
+            let oversized = json!({ "limit": 1, "padding": "x".repeat(128) }).to_string();
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::post("/api/fetch-content")
+                        .header("content-type", "application/json")
+                        .header("content-length", oversized.len())
+                        .body(Body::from(oversized.clone()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+            let response = app
+                .oneshot(
+                    Request::post("/api/fetch-content")
+                        .header("content-type", "application/json")
+                        .body(Body::from(oversized))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+            Ok(())
+        }
+    }
+
+    pub mod fetch_content_stream_endpoint {
+        use std::time::Duration;
+
+        use anyhow::{Context, Result};
+        use axum::body::{Body, BodyDataStream};
+        use axum::http::{header, Request, StatusCode};
+        use futures::StreamExt;
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::api::{build_router, ApiState};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// SSEのイベントを1件読み、イベント名とJSONデータを返す
+        async fn next_event(
+            stream: &mut BodyDataStream,
+            buffer: &mut String,
+        ) -> Result<(String, Value)> {
+            loop {
+                if let Some(end) = buffer.find("\n\n") {
+                    let raw: String = buffer.drain(..end + 2).collect();
+                    let mut name = String::new();
+                    let mut data = String::new();
+                    for line in raw.lines() {
+                        if let Some(value) = line.strip_prefix("event:") {
+                            name = value.trim().to_string();
+                        } else if let Some(value) = line.strip_prefix("data:") {
+                            data.push_str(value.trim());
+                        }
+                    }
+                    // keep-aliveのコメント行は読み飛ばす
+                    if name.is_empty() {
+                        continue;
+                    }
+                    return Ok((name, serde_json::from_str(&data)?));
+                }
+                let chunk = stream
+                    .next()
+                    .await
+                    .context("イベントの途中でストリームが終了した")??;
+                buffer.push_str(std::str::from_utf8(&chunk)?);
+            }
+        }
+
+        /// # 検証目的
+        /// エントリが完了するたびにprogressイベントが順次届き、最後にsummaryイベントが届くことを確認する。
+        #[tokio::test]
+        async fn 進捗がsseで順次届く() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({
+                            "html": "<html><body>stream</body></html>",
+                            "status_code": 200,
+                            "title": "Stream",
+                            "final_url": "https://example.com/stream",
+                            "elapsed_ms": 10.0,
+                            "timestamp": chrono::Utc::now().to_rfc3339(),
+                        }))
+                        .set_delay(Duration::from_millis(500)),
+                )
+                .mount(&server)
+                .await;
+
+            for index in 0..2 {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(format!("https://example.com/stream/{}", index))
+                .bind(format!("記事{}", index))
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let state = ApiState::new(
+                pool.clone(),
+                server.uri(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/fetch-content/stream?limit=10")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "text/event-stream"
+            );
+
+            let mut stream = response.into_body().into_data_stream();
+            let mut buffer = String::new();
+
+            let (name, first) = next_event(&mut stream, &mut buffer).await?;
+            assert_eq!(name, "progress");
+            assert_eq!(first["completed"], json!(1));
+            assert_eq!(first["total"], json!(2));
+            assert_eq!(first["entry"]["result"]["type"], json!("saved"));
+
+            // 1件目の進捗を受け取った時点では2件目はまだ処理中
+            let saved: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.article_content")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!(saved, 1);
+
+            let (name, second) = next_event(&mut stream, &mut buffer).await?;
+            assert_eq!(name, "progress");
+            assert_eq!(second["completed"], json!(2));
+            assert_ne!(second["entry"]["queue_id"], first["entry"]["queue_id"]);
+
+            let (name, summary) = next_event(&mut stream, &mut buffer).await?;
+            assert_eq!(name, "summary");
+            assert_eq!(summary["saved_count"], json!(2));
+            assert_eq!(summary["entries"].as_array().map(Vec::len), Some(2));
+
+            assert!(
+                stream.next().await.is_none(),
+                "summaryの後にストリームが閉じる"
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// limitに0を指定するとストリームを開始せず400を返すことを確認する。
+        #[tokio::test]
+        async fn limitが0ならエラーを返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/fetch-content/stream?limit=0")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+    }
+
+    pub mod websocket_endpoint {
+        use std::time::Duration;
+
+        use anyhow::{Context, Result};
+        use axum::body::Body;
+        use axum::http::{header, Request, StatusCode};
+        use futures::{SinkExt, StreamExt};
+        use serde_json::{json, Value};
+        use tokio_tungstenite::tungstenite::Message;
+        use tower::ServiceExt;
+        use uuid::Uuid;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::api::{build_router, ApiState};
+        use crate::events::EventHub;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        type WsClient = tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >;
+
+        /// テキストメッセージを1件読み、JSONとして返す
+        async fn next_message(client: &mut WsClient) -> Result<Value> {
+            loop {
+                let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+                    .await
+                    .context("イベントが届かない")?
+                    .context("接続が閉じられた")??;
+                if let Message::Text(text) = message {
+                    return Ok(serde_json::from_str(&text)?);
+                }
+            }
+        }
+
+        /// 購読者数が`expected`になるまで待つ
+        async fn wait_for_receivers(hub: &EventHub, expected: usize) {
+            for _ in 0..100 {
+                if hub.receiver_count() == expected {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            panic!(
+                "購読者数が{}にならない（現在{}）",
+                expected,
+                hub.receiver_count()
+            );
+        }
+
+        /// # 検証目的
+        /// 複数のWebSocketクライアントがfetch-contentの進捗とサマリを受信でき、切断後は購読が解除されることを確認する。
+        #[tokio::test]
+        async fn websocketでイベントを受信できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html><body>ws</body></html>",
+                    "status_code": 200,
+                    "title": "WebSocket",
+                    "final_url": "https://example.com/ws",
+                    "elapsed_ms": 10.0,
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                })))
+                .mount(&server)
+                .await;
+
+            let queue_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(queue_id)
+            .bind("https://example.com/ws")
+            .bind("記事")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                server.uri(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let hub = state.events.clone();
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+            let addr = listener.local_addr()?;
+            let app = build_router(state.clone());
+            tokio::spawn(async move { axum::serve(listener, app).await });
+
+            let url = format!("ws://{}/api/ws", addr);
+            let (mut first, _) = tokio_tungstenite::connect_async(url.as_str()).await?;
+            let (mut second, _) = tokio_tungstenite::connect_async(url.as_str()).await?;
+            wait_for_receivers(&hub, 2).await;
+
+            let response = build_router(state)
+                .oneshot(
+                    Request::post("/api/fetch-content")
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(r#"{"limit": 10}"#))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            for client in [&mut first, &mut second] {
+                let progress = next_message(client).await?;
+                assert_eq!(progress["event"], json!("fetch_content_progress"));
+                assert_eq!(progress["data"]["completed"], json!(1));
+                assert_eq!(
+                    progress["data"]["entry"]["queue_id"],
+                    json!(queue_id.to_string())
+                );
+
+                let summary = next_message(client).await?;
+                assert_eq!(summary["event"], json!("fetch_content_summary"));
+                assert_eq!(summary["data"]["saved_count"], json!(1));
+            }
+
+            // Closeフレームでの切断と、フレームなしの接続断のどちらでも購読が解除される
+            first.send(Message::Close(None)).await?;
+            wait_for_receivers(&hub, 1).await;
+            drop(second);
+            wait_for_receivers(&hub, 0).await;
+
+            Ok(())
+        }
+    }
+
+    pub mod pipeline_flow {
+        use std::io::{Cursor, Read};
+
+        use anyhow::Result;
+        use brotli::Decompressor;
+        use serde_json::json;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::articles::{
+            search_articles_window, ArticleCursorField, ArticleFilter, ArticleSort, PageDirection,
+        };
+        use crate::fetch_content::{execute_fetch_content, FetchContentOptions};
+        use crate::fetch_rss::{execute_fetch_rss, FetchRssOptions};
+        use crate::test_support::{clear_rss_tables, create_temp_yaml, prepare_test_pool};
+
+        /// # 検証目的
+        /// RSS取り込みから本文保存、記事取得まで一連の流れが動作することを確認する。
+        #[tokio::test]
+        async fn rssから記事取得まで連携する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            let rss_body = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0">
+                  <channel>
+                    <title>Integration Feed</title>
                     <item>
-                      <title>Item</title>
+                      <title>Item Title</title>
                       <link>https://example.com/item</link>
-                      <description>desc</description>
+                      <description>本文サマリ</description>
                       <pubDate>Mon, 13 Oct 2025 12:00:00 GMT</pubDate>
                     </item>
                   </channel>
                 </rss>
             "#;
 
-            Mock::given(method("GET"))
-                .and(path("/feed"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(rss_body))
-                .mount(&server)
-                .await;
+            Mock::given(method("GET"))
+                .and(path("/feed"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(rss_body))
+                .mount(&server)
+                .await;
+
+            let html_body = "<html><body>integration ok</body></html>";
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": html_body,
+                    "status_code": 200,
+                    "title": "Integration",
+                    "final_url": "https://example.com/item",
+                    "elapsed_ms": 12.3,
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                })))
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "integration:\n  sample: {url}/feed",
+                url = server.uri()
+            ))?;
+
+            let rss_summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
+            assert_eq!(rss_summary.total_processed, 1);
+
+            let fetch_summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(fetch_summary.saved_count, 1);
+            assert_eq!(fetch_summary.status_only_count, 0);
+
+            let articles = search_articles_window(
+                &pool,
+                10,
+                None,
+                PageDirection::Older,
+                ArticleSort::CreatedDesc,
+                ArticleCursorField::CreatedAt,
+                &ArticleFilter::default(),
+            )
+            .await?;
+            assert_eq!(articles.len(), 1);
+            let article = &articles[0];
+            assert_eq!(article.link, "https://example.com/item");
+            assert_eq!(article.title, "Item Title");
+
+            let mut decompressor = Decompressor::new(Cursor::new(article.data.clone()), 4096);
+            let mut decompressed = String::new();
+            decompressor.read_to_string(&mut decompressed)?;
+            assert_eq!(decompressed, html_body);
+
+            let status: Option<i32> =
+                sqlx::query_scalar("SELECT status_code FROM rss.queue WHERE id = $1")
+                    .bind(article.id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(status, Some(200));
+
+            Ok(())
+        }
+    }
+
+    pub mod articles_endpoint {
+        use std::sync::atomic::Ordering;
+        use std::sync::Arc;
+
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use chrono::{Duration, Utc};
+        use serde_json::Value;
+        use sqlx::PgPool;
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, ApiState, JsonCase, PageToken};
+        use crate::articles::ArticleSort;
+        use crate::test_support::{
+            clear_rss_tables, fixed_datetime, prepare_counting_test_pool, prepare_test_pool,
+        };
+
+        const TOKEN_SECRET: &str = "test-token-secret";
+
+        async fn insert_article(
+            pool: &PgPool,
+            id: Uuid,
+            created_at: chrono::DateTime<chrono::Utc>,
+            link: &str,
+            title: &str,
+            description: &str,
+            data: &[u8],
+        ) -> Result<()> {
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description, pub_date)
+                VALUES ($1, $2, $3, $4, NOW())
+                "#,
+            )
+            .bind(id)
+            .bind(link)
+            .bind(title)
+            .bind(description)
+            .execute(pool)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE rss.queue
+                SET created_at = $2, updated_at = $2
+                WHERE id = $1
+                "#,
+            )
+            .bind(id)
+            .bind(created_at)
+            .execute(pool)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO rss.article_content (queue_id, data)
+                VALUES ($1, $2)
+                "#,
+            )
+            .bind(id)
+            .bind(data)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 記事一覧APIがページネーションおよびBase64エンコードを正しく行うことを確認する。
+        #[tokio::test]
+        async fn 記事一覧を取得できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+
+            insert_article(
+                &pool,
+                newer_id,
+                Utc::now(),
+                "https://example.com/new",
+                "新しい記事",
+                "新しい本文",
+                b"newer",
+            )
+            .await?;
+
+            insert_article(
+                &pool,
+                older_id,
+                Utc::now() - Duration::hours(1),
+                "https://example.com/old",
+                "古い記事",
+                "古い本文",
+                b"older",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get("/api/articles?limit=1")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 1);
+            let first = &items[0];
+            let first_id = first["id"].as_str().expect("idが文字列");
+            assert_eq!(first_id, newer_id.to_string());
+
+            let encoded = first["content_brotli_base64"]
+                .as_str()
+                .expect("content_brotli_base64が文字列");
+            let decoded = STANDARD.decode(encoded)?;
+            assert_eq!(decoded, b"newer");
+
+            let next_token = body["next_token"].as_str().expect("next_tokenが存在");
+
+            let response = app
+                .oneshot(
+                    Request::get(format!("/api/articles?page_token={}", next_token))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 1);
+            let first = &items[0];
+            let first_id = first["id"].as_str().expect("idが文字列");
+            assert_eq!(first_id, older_id.to_string());
+            let encoded = first["content_brotli_base64"]
+                .as_str()
+                .expect("content_brotli_base64が文字列");
+            let decoded = STANDARD.decode(encoded)?;
+            assert_eq!(decoded, b"older");
+            assert!(body["next_token"].is_null());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 一覧・link指定の取得とも、各itemの`compressed_size`が`content_brotli_base64`を
+        /// Base64デコードしたバイト数と一致することを確認する。
+        #[tokio::test]
+        async fn 本文の圧縮後サイズを返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            insert_article(
+                &pool,
+                Uuid::new_v4(),
+                Utc::now(),
+                "https://example.com/sized",
+                "サイズ確認",
+                "説明",
+                b"compressed-bytes",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            for uri in [
+                "/api/articles",
+                "/api/articles/by-link?url=https%3A%2F%2Fexample.com%2Fsized",
+            ] {
+                let response = app
+                    .clone()
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let item = if body["items"].is_array() {
+                    &body["items"][0]
+                } else {
+                    &body
+                };
+
+                let decoded = STANDARD.decode(item["content_brotli_base64"].as_str().unwrap())?;
+                assert_eq!(decoded.len(), b"compressed-bytes".len());
+                assert_eq!(item["compressed_size"], decoded.len(), "{}", uri);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// キャッシュ有効時は同じクエリの2回目がDBを1回も引かずに前回の結果を返し、破棄後は最新の内容を返すこと、
+        /// キャッシュに無いときのDBへの往復回数が記事の件数によらず一定であることを確認する。
+        #[tokio::test]
+        async fn 記事一覧をキャッシュから返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let (pool, acquires) = prepare_counting_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            insert_article(
+                &pool,
+                Uuid::new_v4(),
+                Utc::now() - Duration::hours(1),
+                "https://example.com/cached",
+                "キャッシュされる記事",
+                "本文",
+                b"cached",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_article_cache_ttl(60);
+            let app = build_router(state.clone());
+
+            // 記事一覧を取得し、件数とその間のDBへの往復回数を返す
+            let count_items = |app: axum::Router| {
+                let acquires = Arc::clone(&acquires);
+                async move {
+                    let before = acquires.load(Ordering::SeqCst);
+                    let response = app
+                        .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    assert_eq!(response.status(), StatusCode::OK);
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    let items = body["items"].as_array().expect("itemsが配列").len();
+                    (items, acquires.load(Ordering::SeqCst) - before)
+                }
+            };
+
+            let (items, single_item_queries) = count_items(app.clone()).await;
+            assert_eq!(items, 1);
+            assert!(single_item_queries > 0);
+
+            // DBを直接更新しても、キャッシュが生きている間はDBを引かずに前回の結果が返る
+            for index in 0..2 {
+                insert_article(
+                    &pool,
+                    Uuid::new_v4(),
+                    Utc::now() - Duration::minutes(index),
+                    &format!("https://example.com/added/{}", index),
+                    "後から追加した記事",
+                    "本文",
+                    b"added",
+                )
+                .await?;
+            }
+            assert_eq!(count_items(app.clone()).await, (1, 0));
+
+            // fetch後と同じくキャッシュを破棄すると最新の内容を引き直し、件数が増えても往復回数は変わらない
+            state.clear_article_cache();
+            assert_eq!(count_items(app).await, (3, single_item_queries));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `Accept: multipart/mixed`指定時、先頭のJSONパートにメタ情報、続くパートに各記事の生のBrotli本文が入ることを確認する。
+        #[tokio::test]
+        async fn multipartで本文をバイナリパートとして返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+            // 境界検出が本文中の改行やバイナリに影響されないことも確認する
+            let newer_data: &[u8] = b"newer\r\n\x00\xff";
+            insert_article(
+                &pool,
+                newer_id,
+                Utc::now(),
+                "https://example.com/new",
+                "新しい記事",
+                "新しい本文",
+                newer_data,
+            )
+            .await?;
+            insert_article(
+                &pool,
+                older_id,
+                Utc::now() - Duration::hours(1),
+                "https://example.com/old",
+                "古い記事",
+                "古い本文",
+                b"older",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles")
+                        .header("accept", "multipart/mixed, application/json;q=0.5")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let content_type = response
+                .headers()
+                .get("content-type")
+                .and_then(|value| value.to_str().ok())
+                .expect("content-typeが存在")
+                .to_string();
+            let boundary = content_type
+                .strip_prefix("multipart/mixed; boundary=")
+                .expect("multipart/mixedで返る");
+
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let parts = split_multipart(&bytes, boundary);
+            assert_eq!(parts.len(), 3);
+
+            let (meta_headers, meta_body) = &parts[0];
+            assert!(meta_headers.contains("Content-Type: application/json"));
+            let meta: Value = serde_json::from_slice(meta_body)?;
+            let items = meta["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 2);
+            assert!(items
+                .iter()
+                .all(|item| item.get("content_brotli_base64").is_none()));
+
+            for ((headers, body), (item, expected)) in parts[1..]
+                .iter()
+                .zip(items.iter().zip([newer_data, b"older".as_slice()]))
+            {
+                let id = item["id"].as_str().expect("idが文字列");
+                assert!(headers.contains("Content-Type: application/octet-stream"));
+                assert!(headers.contains(&format!("Content-ID: <{}>", id)));
+                assert_eq!(body.as_slice(), expected);
+            }
+            assert_eq!(items[0]["id"], newer_id.to_string());
+            assert_eq!(items[1]["id"], older_id.to_string());
+
+            Ok(())
+        }
+
+        /// multipart/mixedのボディを境界で分割し、パートごとのヘッダ文字列と本文を返す
+        fn split_multipart(body: &[u8], boundary: &str) -> Vec<(String, Vec<u8>)> {
+            let delimiter = format!("--{}", boundary);
+            let body = body
+                .strip_prefix(delimiter.as_bytes())
+                .expect("先頭が境界で始まる");
+            let delimiter = format!("\r\n--{}", boundary);
+            let mut parts = Vec::new();
+            let mut rest = body;
+            loop {
+                let rest_after_crlf = rest.strip_prefix(b"\r\n").expect("境界の後は改行");
+                let end = rest_after_crlf
+                    .windows(delimiter.len())
+                    .position(|window| window == delimiter.as_bytes())
+                    .expect("終端の境界が存在");
+                let part = &rest_after_crlf[..end];
+                let header_end = part
+                    .windows(4)
+                    .position(|window| window == b"\r\n\r\n")
+                    .expect("ヘッダの終端が存在");
+                parts.push((
+                    String::from_utf8_lossy(&part[..header_end]).to_string(),
+                    part[header_end + 4..].to_vec(),
+                ));
+                rest = &rest_after_crlf[end + delimiter.len()..];
+                if rest.starts_with(b"--") {
+                    return parts;
+                }
+            }
+        }
+
+        /// # 検証目的
+        /// prev_tokenで前のページへ戻れ、最初のページではprev_tokenがnullになることを確認する。
+        #[tokio::test]
+        async fn prev_tokenで前ページに戻れる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            // 新しい順にids[0]..ids[4]
+            let now = Utc::now();
+            let mut ids = Vec::new();
+            for index in 0..5 {
+                let id = Uuid::new_v4();
+                insert_article(
+                    &pool,
+                    id,
+                    now - Duration::hours(index),
+                    &format!("https://example.com/{}", index),
+                    "記事",
+                    "本文",
+                    b"body",
+                )
+                .await?;
+                ids.push(id.to_string());
+            }
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let get_page = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    assert_eq!(response.status(), StatusCode::OK);
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    let item_ids: Vec<String> = body["items"]
+                        .as_array()
+                        .expect("itemsが配列")
+                        .iter()
+                        .map(|item| item["id"].as_str().unwrap().to_string())
+                        .collect();
+                    (item_ids, body)
+                }
+            };
+
+            let (first_ids, first) = get_page("/api/articles?limit=2".to_string()).await;
+            assert_eq!(first_ids, ids[0..2]);
+            assert!(first["prev_token"].is_null());
+
+            let next = first["next_token"].as_str().expect("next_tokenが存在");
+            let (second_ids, second) =
+                get_page(format!("/api/articles?limit=2&page_token={}", next)).await;
+            assert_eq!(second_ids, ids[2..4]);
+            assert_eq!(second["prev_token"].as_str(), Some(ids[2].as_str()));
+
+            let next = second["next_token"].as_str().expect("next_tokenが存在");
+            let (third_ids, third) =
+                get_page(format!("/api/articles?limit=2&page_token={}", next)).await;
+            assert_eq!(third_ids, ids[4..5]);
+            assert!(third["next_token"].is_null());
+
+            // 最後のページから2ページ目へ戻る（さらに前があるのでprev_tokenが残る）
+            let prev = third["prev_token"].as_str().expect("prev_tokenが存在");
+            let (back_ids, back) =
+                get_page(format!("/api/articles?limit=2&prev_token={}", prev)).await;
+            assert_eq!(back_ids, ids[2..4]);
+            assert_eq!(back["prev_token"].as_str(), Some(ids[2].as_str()));
+            assert_eq!(back["next_token"].as_str(), Some(ids[3].as_str()));
+
+            // 2ページ目から最初のページへ戻るとprev_tokenはnull
+            let prev = back["prev_token"].as_str().expect("prev_tokenが存在");
+            let (front_ids, front) =
+                get_page(format!("/api/articles?limit=2&prev_token={}", prev)).await;
+            assert_eq!(front_ids, ids[0..2]);
+            assert!(front["prev_token"].is_null());
+            assert_eq!(front["next_token"].as_str(), Some(ids[1].as_str()));
+
+            let response = app
+                .oneshot(
+                    Request::get(format!(
+                        "/api/articles?page_token={}&prev_token={}",
+                        ids[0], ids[1]
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// API_DEFAULT_SORT相当の既定値でpub_date降順に切り替わり、クエリのsort指定がそれより優先されることを確認する。
+        #[tokio::test]
+        async fn 既定の並び順を設定で変更できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            // 取り込み順（created_at）と発行順（pub_date）が逆になる3件
+            let base = fixed_datetime(2025, 1, 10, 0, 0, 0);
+            let mut ids = Vec::new();
+            for index in 0..3 {
+                let id = Uuid::new_v4();
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index),
+                    &format!("https://example.com/sort/{}", index),
+                    "記事",
+                    "本文",
+                    b"body",
+                )
+                .await?;
+                sqlx::query("UPDATE rss.queue SET pub_date = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(base - Duration::days(index))
+                    .execute(&pool)
+                    .await?;
+                ids.push(id.to_string());
+            }
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+
+            let get_ids = |app: axum::Router, uri: String| async move {
+                let response = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes).unwrap();
+                body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            };
+
+            let created_order = vec![ids[2].clone(), ids[1].clone(), ids[0].clone()];
+            let default_app = build_router(state.clone());
+            assert_eq!(
+                get_ids(default_app, "/api/articles".to_string()).await,
+                created_order
+            );
+
+            let pubdate_app = build_router(state.with_default_sort(ArticleSort::PubDateDesc));
+            assert_eq!(
+                get_ids(pubdate_app.clone(), "/api/articles".to_string()).await,
+                ids
+            );
+            assert_eq!(
+                get_ids(
+                    pubdate_app.clone(),
+                    "/api/articles?sort=created_desc".to_string()
+                )
+                .await,
+                created_order
+            );
+
+            // ページングもpub_dateを基準に進む
+            let first = get_ids(pubdate_app.clone(), "/api/articles?limit=1".to_string()).await;
+            assert_eq!(first, ids[0..1]);
+            let uri = format!("/api/articles?limit=2&page_token={}", ids[0]);
+            assert_eq!(get_ids(pubdate_app.clone(), uri).await, ids[1..3]);
+
+            let response = pubdate_app
+                .oneshot(
+                    Request::get("/api/articles?sort=title")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// cursor_field=updated_atで更新日時の新しい順に並び、同時刻の記事を含めて重複・欠落なくページングできることを確認する。
+        #[tokio::test]
+        async fn 更新日時でカーソルページングできる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            // 取り込み順と更新順を逆にし、更新日時が同じ記事を2件含める
+            let base = fixed_datetime(2025, 1, 10, 0, 0, 0);
+            let updated_offsets = [0, 3, 3, 1, 2];
+            let mut rows = Vec::new();
+            for (index, offset) in updated_offsets.iter().enumerate() {
+                let id = Uuid::new_v4();
+                let updated_at = base + Duration::days(1) + Duration::hours(*offset);
+                // updated_atはUPDATE時にトリガーで上書きされるため、INSERTで直接指定する
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, created_at, updated_at)
+                    VALUES ($1, $2, '記事', '本文', $3, $4)
+                    "#,
+                )
+                .bind(id)
+                .bind(format!("https://example.com/updated/{}", index))
+                .bind(base - Duration::hours(index as i64))
+                .bind(updated_at)
+                .execute(&pool)
+                .await?;
+                sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                    .bind(id)
+                    .bind(b"body".as_slice())
+                    .execute(&pool)
+                    .await?;
+                rows.push((updated_at, id));
+            }
+            rows.sort_by(|left, right| right.cmp(left));
+            let expected: Vec<String> = rows.iter().map(|(_, id)| id.to_string()).collect();
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let get_page = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            let mut collected = Vec::new();
+            let mut uri = "/api/articles?limit=2&cursor_field=updated_at".to_string();
+            loop {
+                let (status, body) = get_page(uri.clone()).await;
+                assert_eq!(status, StatusCode::OK);
+                collected.extend(
+                    body["items"]
+                        .as_array()
+                        .expect("itemsが配列")
+                        .iter()
+                        .map(|item| item["id"].as_str().unwrap().to_string()),
+                );
+                match body["next_token"].as_str() {
+                    Some(token) => {
+                        uri = format!(
+                            "/api/articles?limit=2&cursor_field=updated_at&page_token={}",
+                            token
+                        )
+                    }
+                    None => break,
+                }
+            }
+            assert_eq!(collected, expected);
+
+            // prev_tokenでも同じ列を基準に戻れる
+            let uri = format!(
+                "/api/articles?limit=2&cursor_field=updated_at&prev_token={}",
+                expected[3]
+            );
+            let (status, body) = get_page(uri).await;
+            assert_eq!(status, StatusCode::OK);
+            let back: Vec<String> = body["items"]
+                .as_array()
+                .expect("itemsが配列")
+                .iter()
+                .map(|item| item["id"].as_str().unwrap().to_string())
+                .collect();
+            assert_eq!(back, expected[1..3]);
+
+            // 既定のcreated_at基準は従来どおり
+            let (_, body) = get_page("/api/articles".to_string()).await;
+            let created: Vec<String> = body["items"]
+                .as_array()
+                .expect("itemsが配列")
+                .iter()
+                .map(|item| item["id"].as_str().unwrap().to_string())
+                .collect();
+            assert_ne!(created, expected);
+
+            let (status, body) = get_page("/api/articles?cursor_field=title".to_string()).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("invalid_cursor_field"));
+
+            let (status, body) =
+                get_page("/api/articles?cursor_field=updated_at&sort=pubdate_desc".to_string())
+                    .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("conflicting_sort"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 存在しないトークンを指定した場合にエラーが返ることを確認する。
+        #[tokio::test]
+        async fn 無効なトークンでエラーを返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get(format!("/api/articles?page_token={}", Uuid::new_v4()))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"].as_str(), Some("page_token_not_found"));
+            assert_eq!(body["message"].as_str(), Some("page_token is not exist"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `API_TOKEN_TTL_SECS`が有効なとき、発行直後のトークンは使え、TTLを過ぎたトークンや
+        /// 発行時刻の無いトークンは400の`token_expired`で弾かれることを確認する。
+        #[tokio::test]
+        async fn 期限切れのトークンを拒否する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+            for (id, created_at, link) in [
+                (newer_id, Utc::now(), "https://example.com/new"),
+                (
+                    older_id,
+                    Utc::now() - Duration::hours(1),
+                    "https://example.com/old",
+                ),
+            ] {
+                insert_article(&pool, id, created_at, link, "記事", "本文", b"content").await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_token_ttl(60)
+            .with_token_secret(Some(TOKEN_SECRET));
+            let app = build_router(state);
+
+            let get = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            // 発行されたトークンには発行時刻が付き、期限内ならそのまま使える
+            let (status, first) = get("/api/articles?limit=1".to_string()).await;
+            assert_eq!(status, StatusCode::OK);
+            let next = first["next_token"].as_str().expect("next_tokenが存在");
+            assert!(next.starts_with(&format!("{}.", newer_id)));
+            let (status, second) = get(format!("/api/articles?limit=1&page_token={}", next)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                second["items"][0]["id"].as_str(),
+                Some(older_id.to_string().as_str())
+            );
+
+            // TTLを過ぎたトークンと発行時刻の無いトークンは拒否する
+            let issued_at = (Utc::now() - Duration::seconds(61)).timestamp();
+            for uri in [
+                format!(
+                    "/api/articles?page_token={}",
+                    String::from(PageToken::signed(
+                        newer_id,
+                        issued_at,
+                        TOKEN_SECRET.as_bytes()
+                    ))
+                ),
+                format!(
+                    "/api/articles?prev_token={}",
+                    String::from(PageToken::signed(
+                        older_id,
+                        issued_at,
+                        TOKEN_SECRET.as_bytes()
+                    ))
+                ),
+                format!("/api/articles?page_token={}", newer_id),
+            ] {
+                let (status, body) = get(uri).await;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body["code"].as_str(), Some("token_expired"));
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 一覧のキャッシュが有効でも、キャッシュ済みのページを引いたトークンは期限を過ぎた時点で
+        /// 400の`token_expired`になり、キャッシュから返され続けないことを確認する。
+        #[tokio::test]
+        async fn 期限切れのトークンはキャッシュからも返さない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+            for (id, created_at, link) in [
+                (newer_id, Utc::now(), "https://example.com/new"),
+                (
+                    older_id,
+                    Utc::now() - Duration::hours(1),
+                    "https://example.com/old",
+                ),
+            ] {
+                insert_article(&pool, id, created_at, link, "記事", "本文", b"content").await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_token_ttl(2)
+            .with_token_secret(Some(TOKEN_SECRET))
+            .with_article_cache_ttl(60);
+            let app = build_router(state);
+
+            let get = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            // 1秒前に発行した（TTL 2秒のうち少なくとも1秒は有効な）トークンでページをキャッシュさせる
+            let issued_at = Utc::now().timestamp() - 1;
+            let token = String::from(PageToken::signed(
+                newer_id,
+                issued_at,
+                TOKEN_SECRET.as_bytes(),
+            ));
+            let uri = format!("/api/articles?limit=1&page_token={}", token);
+            let (status, body) = get(uri.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                body["items"][0]["id"].as_str(),
+                Some(older_id.to_string().as_str())
+            );
+
+            while Utc::now().timestamp() - issued_at <= 2 {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+            let (status, body) = get(uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("token_expired"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 発行時刻を書き換えたトークン・署名の無いトークン・別の鍵で署名したトークンは、
+        /// 期限の判定より前に400の`invalid_token`で弾かれ、TTLを回避できないことを確認する。
+        #[tokio::test]
+        async fn 改ざんしたトークンを拒否する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+            for (id, created_at, link) in [
+                (newer_id, Utc::now(), "https://example.com/new"),
+                (
+                    older_id,
+                    Utc::now() - Duration::hours(1),
+                    "https://example.com/old",
+                ),
+            ] {
+                insert_article(&pool, id, created_at, link, "記事", "本文", b"content").await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_token_ttl(60)
+            .with_token_secret(Some(TOKEN_SECRET));
+            let app = build_router(state);
+
+            let get = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            // 期限切れのトークンの発行時刻だけを現在に書き換えても、署名が合わず拒否される
+            let expired_at = (Utc::now() - Duration::seconds(61)).timestamp();
+            let expired = String::from(PageToken::signed(
+                newer_id,
+                expired_at,
+                TOKEN_SECRET.as_bytes(),
+            ));
+            let (_, mac) = expired.rsplit_once('.').expect("署名付きトークン");
+            let now = Utc::now().timestamp();
+            let other_key = String::from(PageToken::signed(newer_id, now, b"other-secret"));
+            for uri in [
+                format!("/api/articles?page_token={}.{}.{}", newer_id, now, mac),
+                format!("/api/articles?prev_token={}.{}.{}", older_id, now, mac),
+                format!("/api/articles?page_token={}.{}", newer_id, now),
+                format!("/api/articles?page_token={}", other_key),
+            ] {
+                let (status, body) = get(uri).await;
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body["code"].as_str(), Some("invalid_token"));
+            }
+
+            // 同じ鍵で署名した期限内のトークンは使える
+            let valid = String::from(PageToken::signed(newer_id, now, TOKEN_SECRET.as_bytes()));
+            let (status, body) = get(format!("/api/articles?page_token={}", valid)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(
+                body["items"][0]["id"].as_str(),
+                Some(older_id.to_string().as_str())
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// limitに0を指定した場合に400エラーと`invalid_limit`コードが返ることを確認する。
+        #[tokio::test]
+        async fn 記事一覧のlimitが0ならエラー() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?limit=0")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"].as_str(), Some("invalid_limit"));
+            assert_eq!(
+                body["message"].as_str(),
+                Some("limitは1以上で指定してください")
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// Base64化後に50MB制限を超える記事が含まれる場合に413エラーが返ることを確認する。
+        #[tokio::test]
+        async fn 応答サイズ超過時にエラー() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let large_data = vec![0u8; crate::api::MAX_RESPONSE_BYTES];
+            let article_id = Uuid::new_v4();
+
+            insert_article(
+                &pool,
+                article_id,
+                Utc::now(),
+                "https://example.com/huge",
+                "巨大な記事",
+                "巨大本文",
+                &large_data,
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"].as_str(), Some("article_too_large"));
+            assert!(body["message"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("記事ID"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `feed_content`は`include=feed_content`指定時だけ返り、応答サイズの上限に数えられて
+        /// 収まらない記事は次のページへ回ること、未知のinclude指定は400になることを確認する。
+        #[tokio::test]
+        async fn feed_contentはinclude指定時だけ返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            // 2件合わせると応答サイズの上限を超えるfeed_content
+            let feed_content = "a".repeat(crate::api::MAX_RESPONSE_BYTES / 2 + 1);
+            let newer_id = Uuid::new_v4();
+            let older_id = Uuid::new_v4();
+            for (id, created_at, link) in [
+                (newer_id, Utc::now(), "https://example.com/content/new"),
+                (
+                    older_id,
+                    Utc::now() - Duration::hours(1),
+                    "https://example.com/content/old",
+                ),
+            ] {
+                insert_article(&pool, id, created_at, link, "記事", "説明", b"body").await?;
+                sqlx::query("UPDATE rss.queue SET content = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(&feed_content)
+                    .execute(&pool)
+                    .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let get = |uri: &str| {
+                let app = app.clone();
+                let request = Request::get(uri).body(Body::empty()).unwrap();
+                async move {
+                    let response = app.oneshot(request).await.unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            // 既定では返さないので、2件とも1ページに収まる
+            let (status, body) = get("/api/articles").await;
+            assert_eq!(status, StatusCode::OK);
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 2);
+            assert!(items.iter().all(|item| item.get("feed_content").is_none()));
+
+            // 指定すると返し、上限に収まらない2件目は次のページへ回す
+            let (status, body) = get("/api/articles?include=feed_content").await;
+            assert_eq!(status, StatusCode::OK);
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0]["id"].as_str(), Some(newer_id.to_string().as_str()));
+            assert_eq!(
+                items[0]["feed_content"].as_str().map(str::len),
+                Some(feed_content.len())
+            );
+            assert!(body["next_token"].is_string());
+
+            let (status, body) = get("/api/articles?include=raw").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("invalid_include"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 存在するlinkを指定すると該当記事が返り、存在しなければ404になることを確認する。
+        #[tokio::test]
+        async fn linkで記事を取得できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let article_id = Uuid::new_v4();
+            insert_article(
+                &pool,
+                article_id,
+                Utc::now(),
+                "https://example.com/by-link/",
+                "リンク記事",
+                "リンク本文",
+                b"by-link",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get("/api/articles/by-link?url=https://example.com/by-link")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["id"].as_str(), Some(article_id.to_string().as_str()));
+            let decoded = STANDARD.decode(body["content_brotli_base64"].as_str().unwrap())?;
+            assert_eq!(decoded, b"by-link");
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles/by-link?url=https://example.com/missing")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"].as_str(), Some("article_not_found"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// description_max指定でdescriptionが文字単位で切り詰められ、0指定で省略されることを確認する。
+        #[tokio::test]
+        async fn descriptionを切り詰めて返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            insert_article(
+                &pool,
+                Uuid::new_v4(),
+                Utc::now(),
+                "https://example.com/long",
+                "長い記事",
+                "日本語の長い説明文です",
+                b"long",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                ("description_max=5", Some("日本語の長…")),
+                ("description_max=100", Some("日本語の長い説明文です")),
+                ("description_max=0", None),
+            ];
+
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let item = &body["items"][0];
+                assert_eq!(item["description"].as_str(), expected, "query: {}", query);
+                if expected.is_none() {
+                    assert!(item.get("description").is_none());
+                }
+            }
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?description_max=-1")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// case=camel指定またはサーバ既定値でレスポンスキーがcamelCaseへ変換され、未指定時はsnake_caseのままであることを確認する。
+        #[tokio::test]
+        async fn camel指定でキーを変換する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            insert_article(
+                &pool,
+                Uuid::new_v4(),
+                Utc::now(),
+                "https://example.com/camel",
+                "キャメル",
+                "説明",
+                b"camel",
+            )
+            .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state.clone());
+
+            let fetch = |app: axum::Router, uri: &'static str| async move {
+                let response = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes).unwrap();
+                (status, body)
+            };
+
+            let (status, body) = fetch(app.clone(), "/api/articles").await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(body.get("next_token").is_some());
+            assert!(body["items"][0].get("content_brotli_base64").is_some());
+
+            let (status, body) = fetch(app.clone(), "/api/articles?case=camel").await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(body.get("nextToken").is_some());
+            assert!(body.get("next_token").is_none());
+            let item = &body["items"][0];
+            assert!(item.get("contentBrotliBase64").is_some());
+            assert!(item.get("createdAt").is_some());
+            assert!(item.get("pubDate").is_some());
+
+            let (status, body) = fetch(
+                app.clone(),
+                "/api/articles/by-link?url=https://example.com/camel&case=camel",
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(body.get("contentBrotliBase64").is_some());
+
+            let (status, body) = fetch(app, "/api/articles?case=kebab").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("invalid_case"));
+
+            let camel_app = build_router(state.with_json_case(JsonCase::Camel));
+            let (status, body) = fetch(camel_app.clone(), "/api/articles").await;
+            assert_eq!(status, StatusCode::OK);
+            assert!(body["items"][0].get("contentBrotliBase64").is_some());
+
+            let (_, body) = fetch(camel_app, "/api/articles?case=snake").await;
+            assert!(body["items"][0].get("content_brotli_base64").is_some());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// before/before_idで任意位置から再開でき、page_tokenとの同時指定は400になることを確認する。
+        #[tokio::test]
+        async fn beforeで任意位置から取得できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let newest = Uuid::new_v4();
+            let mut same_time = [Uuid::new_v4(), Uuid::new_v4()];
+            same_time.sort();
+            let oldest = Uuid::new_v4();
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+
+            for (id, created_at, link) in [
+                (
+                    newest,
+                    base + Duration::hours(1),
+                    "https://example.com/newest",
+                ),
+                (same_time[0], base, "https://example.com/same-0"),
+                (same_time[1], base, "https://example.com/same-1"),
+                (
+                    oldest,
+                    base - Duration::hours(1),
+                    "https://example.com/oldest",
+                ),
+            ] {
+                insert_article(&pool, id, created_at, link, "記事", "説明", b"body").await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let ids_of = |body: &Value| -> Vec<String> {
+                body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect()
+            };
+
+            let cases = [
+                (
+                    "before=2026-10-15T13:00:00Z".to_string(),
+                    vec![same_time[1], same_time[0], oldest],
+                ),
+                (
+                    format!("before=2026-10-15T12:00:00Z&before_id={}", same_time[1]),
+                    vec![same_time[0], oldest],
+                ),
+                ("before=2026-10-15T12:00:00Z".to_string(), vec![oldest]),
+            ];
+
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(ids_of(&body), expected, "query: {}", query);
+            }
+
+            for query in [
+                format!("page_token={}&before=2026-10-15T12:00:00Z", newest),
+                "before=not-a-date".to_string(),
+                format!("before_id={}", newest),
+            ] {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    response.status(),
+                    StatusCode::BAD_REQUEST,
+                    "query: {}",
+                    query
+                );
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `group`をカンマ区切りで複数指定すると、いずれかのgroupに属する記事が返ることを確認する。
+        #[tokio::test]
+        async fn 複数グループを指定できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let mut ids = Vec::new();
+            for (index, group) in ["world", "tech", "sports"].into_iter().enumerate() {
+                let id = Uuid::new_v4();
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    &format!("https://example.com/{}", group),
+                    group,
+                    "説明",
+                    b"body",
+                )
+                .await?;
+                sqlx::query("UPDATE rss.queue SET \"group\" = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(group)
+                    .execute(&pool)
+                    .await?;
+                ids.push(id);
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                ("group=world,tech", vec![ids[1], ids[0]]),
+                ("group=sports", vec![ids[2]]),
+                ("group=%20tech%20,unknown", vec![ids[1]]),
+            ];
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let actual: Vec<String> = body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect();
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(actual, expected, "query: {}", query);
+            }
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?group=,")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `include_subgroups=true`で階層グループの配下の記事も返り、名前が前方一致するだけの別グループは含まないことを確認する。
+        #[tokio::test]
+        async fn 階層グループの配下を含められる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let mut ids = Vec::new();
+            for (index, group) in ["world", "world/asia", "world/asia/japan", "worldwide"]
+                .into_iter()
+                .enumerate()
+            {
+                let id = Uuid::new_v4();
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    &format!("https://example.com/{}", group),
+                    group,
+                    "説明",
+                    b"body",
+                )
+                .await?;
+                sqlx::query("UPDATE rss.queue SET \"group\" = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(group)
+                    .execute(&pool)
+                    .await?;
+                ids.push(id);
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                ("group=world", vec![ids[0]]),
+                (
+                    "group=world&include_subgroups=true",
+                    vec![ids[2], ids[1], ids[0]],
+                ),
+                (
+                    "group=world/asia&include_subgroups=true",
+                    vec![ids[2], ids[1]],
+                ),
+                ("group=world/asia&include_subgroups=false", vec![ids[1]]),
+            ];
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let actual: Vec<String> = body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect();
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(actual, expected, "query: {}", query);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `has_pubdate=false`でpub_dateがNULLの記事だけ、`true`でpub_dateのある記事だけが返り、
+        /// 未指定なら両方返ることを確認する。
+        #[tokio::test]
+        async fn pub_dateの有無で絞り込める() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let with_pubdate = Uuid::new_v4();
+            let without_pubdate = Uuid::new_v4();
+            for (index, id) in [with_pubdate, without_pubdate].into_iter().enumerate() {
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    &format!("https://example.com/{}", index),
+                    "タイトル",
+                    "説明",
+                    b"body",
+                )
+                .await?;
+            }
+            sqlx::query("UPDATE rss.queue SET pub_date = NULL WHERE id = $1")
+                .bind(without_pubdate)
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                ("has_pubdate=true", vec![with_pubdate]),
+                ("has_pubdate=false", vec![without_pubdate]),
+                ("limit=10", vec![without_pubdate, with_pubdate]),
+            ];
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let actual: Vec<String> = body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect();
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(actual, expected, "query: {}", query);
+            }
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?has_pubdate=maybe")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `filter`のDSLでgroup・host・pub_dateの範囲を組み合わせて絞り込め、個別パラメータとはANDになり、
+        /// 未知のキーや個別の`group`との重複指定は400になることを確認する。
+        #[tokio::test]
+        async fn filterのdslで複合条件を指定できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let articles = [
+                (
+                    "tech",
+                    "https://example.com/a",
+                    fixed_datetime(2025, 3, 1, 0, 0, 0),
+                ),
+                (
+                    "tech",
+                    "https://news.example.org/b",
+                    fixed_datetime(2025, 3, 1, 0, 0, 0),
+                ),
+                (
+                    "world",
+                    "https://user@EXAMPLE.com:8080/c",
+                    fixed_datetime(2025, 3, 1, 0, 0, 0),
+                ),
+                (
+                    "tech",
+                    "https://example.com/d",
+                    fixed_datetime(2024, 12, 1, 0, 0, 0),
+                ),
+            ];
+            let mut ids = Vec::new();
+            for (index, (group, link, pub_date)) in articles.into_iter().enumerate() {
+                let id = Uuid::new_v4();
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    link,
+                    "タイトル",
+                    "説明",
+                    b"body",
+                )
+                .await?;
+                sqlx::query("UPDATE rss.queue SET \"group\" = $2, pub_date = $3 WHERE id = $1")
+                    .bind(id)
+                    .bind(group)
+                    .bind(pub_date)
+                    .execute(&pool)
+                    .await?;
+                ids.push(id);
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+            let get = |query: &str| {
+                let app = app.clone();
+                let uri = format!("/api/articles?{}", query);
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    let body: Value = serde_json::from_slice(&bytes).unwrap();
+                    (status, body)
+                }
+            };
+
+            let cases = [
+                (
+                    "filter=group:tech,after:2025-01-01,host:example.com",
+                    vec![ids[0]],
+                ),
+                ("filter=host:example.com", vec![ids[3], ids[2], ids[0]]),
+                (
+                    "filter=group:tech,group:world,before:2025-01-01",
+                    vec![ids[3]],
+                ),
+                ("filter=after:2025-01-01&group=world", vec![ids[2]]),
+            ];
+            for (query, expected) in cases {
+                let (status, body) = get(query).await;
+                assert_eq!(status, StatusCode::OK, "query: {}", query);
+                let actual: Vec<String> = body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect();
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(actual, expected, "query: {}", query);
+            }
+
+            let (status, body) = get("filter=lang:ja").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("invalid_filter"));
+
+            let (status, body) = get("filter=group:tech&group=world").await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("conflicting_filter"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `/api/feed.xml`がgroupで絞った記事をRSS 2.0として返し、特殊文字を含むtitleでも
+        /// RSSとしてパースでき、title/link/description/guidが記事と対応することを確認する。
+        #[tokio::test]
+        async fn 記事をrssフィードとして出力する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let tech = Uuid::new_v4();
+            let world = Uuid::new_v4();
+            for (index, (id, group, title)) in
+                [(tech, "tech", "Rust & <Tokio>"), (world, "world", "世界")]
+                    .into_iter()
+                    .enumerate()
+            {
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    &format!("https://example.com/feed/{}?a=1&b=2", index),
+                    title,
+                    "概要 \"quoted\"",
+                    b"body",
+                )
+                .await?;
+                sqlx::query("UPDATE rss.queue SET \"group\" = $2 WHERE id = $1")
+                    .bind(id)
+                    .bind(group)
+                    .execute(&pool)
+                    .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/feed.xml?group=tech")
+                        .header("host", "feeds.example.com")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()["content-type"],
+                "application/rss+xml; charset=utf-8"
+            );
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+            let feed = feed_rs::parser::parse(bytes.as_ref())?;
+            assert_eq!(feed.feed_type, feed_rs::model::FeedType::RSS2);
+            assert_eq!(
+                feed.links.first().map(|link| link.href.as_str()),
+                Some("http://feeds.example.com/api/feed.xml?group=tech")
+            );
+            assert_eq!(feed.entries.len(), 1);
+            let entry = &feed.entries[0];
+            assert_eq!(entry.id, tech.to_string());
+            assert_eq!(
+                entry.title.as_ref().map(|title| title.content.as_str()),
+                Some("Rust & <Tokio>")
+            );
+            assert_eq!(
+                entry.links.first().map(|link| link.href.as_str()),
+                Some("https://example.com/feed/0?a=1&b=2")
+            );
+            assert_eq!(
+                entry
+                    .summary
+                    .as_ref()
+                    .map(|summary| summary.content.as_str()),
+                Some("概要 \"quoted\"")
+            );
+            assert!(entry.published.is_some());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `q`でtitle・descriptionの全文検索（`websearch_to_tsquery`）がヒットした記事だけが返り、
+        /// `rank=true`では関連度の高い記事から順に返ることを確認する。
+        #[tokio::test]
+        async fn 全文検索で記事を絞り込める() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let base = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            let title_and_description = Uuid::new_v4();
+            let title_only = Uuid::new_v4();
+            let unrelated = Uuid::new_v4();
+            let articles = [
+                (
+                    title_and_description,
+                    "Rust 1.90 released",
+                    "Rust compiler update",
+                ),
+                (title_only, "Rust async runtime", "tokio 2.0"),
+                (unrelated, "Python news", "release notes"),
+            ];
+            for (index, (id, title, description)) in articles.into_iter().enumerate() {
+                insert_article(
+                    &pool,
+                    id,
+                    base + Duration::hours(index as i64),
+                    &format!("https://example.com/{}", index),
+                    title,
+                    description,
+                    b"body",
+                )
+                .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                // 既定の並び順（created_atの新しい順）のまま絞り込む
+                ("q=rust", vec![title_only, title_and_description]),
+                ("q=RUST%20-async", vec![title_and_description]),
+                ("q=tokio", vec![title_only]),
+                ("q=golang", vec![]),
+                // titleとdescriptionの両方に含む記事の方が関連度が高い
+                ("q=rust&rank=true", vec![title_and_description, title_only]),
+            ];
+            for (query, expected) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let actual: Vec<String> = body["items"]
+                    .as_array()
+                    .expect("itemsが配列")
+                    .iter()
+                    .map(|item| item["id"].as_str().unwrap().to_string())
+                    .collect();
+                let expected: Vec<String> = expected.iter().map(|id| id.to_string()).collect();
+                assert_eq!(actual, expected, "query: {}", query);
+            }
+
+            let ranked_with_cursor = format!("q=rust&rank=true&page_token={}", title_only);
+            for query in ["q=%20", "rank=true", ranked_with_cursor.as_str()] {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    response.status(),
+                    StatusCode::BAD_REQUEST,
+                    "query: {}",
+                    query
+                );
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `tz`を指定するとpub_date・created_at・updated_atがそのタイムゾーンのオフセット付きRFC3339で返り、
+        /// 未指定ならUTCのまま、不正なタイムゾーン名は400になることを確認する。
+        #[tokio::test]
+        async fn 指定したタイムゾーンで日時を返す() -> Result<()> {
+            use serde_json::json;
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = Uuid::new_v4();
+            let created_at = fixed_datetime(2026, 10, 15, 12, 0, 0);
+            insert_article(
+                &pool,
+                id,
+                created_at,
+                "https://example.com/tz",
+                "タイムゾーン",
+                "説明",
+                b"body",
+            )
+            .await?;
+            sqlx::query("UPDATE rss.queue SET pub_date = $2 WHERE id = $1")
+                .bind(id)
+                .bind(fixed_datetime(2026, 10, 15, 23, 30, 0))
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let cases = [
+                (
+                    "tz=Asia/Tokyo",
+                    "2026-10-16T08:30:00+09:00",
+                    "2026-10-15T21:00:00+09:00",
+                ),
+                (
+                    "tz=America/New_York",
+                    "2026-10-15T19:30:00-04:00",
+                    "2026-10-15T08:00:00-04:00",
+                ),
+                ("limit=1", "2026-10-15T23:30:00Z", "2026-10-15T12:00:00Z"),
+            ];
+            for (query, pub_date, created_at) in cases {
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles?{}", query))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK, "query: {}", query);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let item = &body["items"][0];
+                assert_eq!(item["pub_date"], json!(pub_date), "query: {}", query);
+                assert_eq!(item["created_at"], json!(created_at), "query: {}", query);
+                assert_eq!(item["updated_at"], json!(created_at), "query: {}", query);
+            }
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?tz=Mars/Olympus")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["code"], json!("invalid_tz"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// zstdで保存した本文も一覧ではBrotliへ詰め替えて`content_brotli_base64`で返し、
+        /// `compressed_size`が詰め替え後の長さになることを確認する（詰め替えはブロッキング用のスレッドで行う）。
+        #[tokio::test]
+        async fn zstdで保存した本文を一覧で返す() -> Result<()> {
+            use crate::compression::ContentCompression;
+            use crate::fetch_content::{compress_html, decompress_html};
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = Uuid::new_v4();
+            let html = "<html><body>zstdで保存した本文</body></html>";
+            insert_article(
+                &pool,
+                id,
+                Utc::now(),
+                "https://example.com/zstd",
+                "zstdの記事",
+                "説明",
+                &compress_html(html, ContentCompression::Zstd)?,
+            )
+            .await?;
+            sqlx::query("UPDATE rss.article_content SET compression = $2 WHERE queue_id = $1")
+                .bind(id)
+                .bind(ContentCompression::Zstd)
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0]["id"].as_str(), Some(id.to_string().as_str()));
+            let brotli = STANDARD.decode(
+                items[0]["content_brotli_base64"]
+                    .as_str()
+                    .expect("content_brotli_base64が文字列"),
+            )?;
+            assert_eq!(items[0]["compressed_size"], brotli.len());
+            assert_eq!(decompress_html(&brotli)?, html.as_bytes());
+
+            Ok(())
+        }
+    }
+
+    pub mod camelize_keys {
+        use serde_json::json;
+
+        use crate::api::camelize_keys;
+
+        /// # 検証目的
+        /// ネストしたオブジェクト・配列内のキーもcamelCaseへ変換され、値は変わらないことを確認する。
+        #[test]
+        fn キーを再帰的に変換する() {
+            let converted = camelize_keys(json!({
+                "next_token": "a_b",
+                "items": [{"content_brotli_base64": "x", "id": 1}],
+            }));
+
+            assert_eq!(
+                converted,
+                json!({
+                    "nextToken": "a_b",
+                    "items": [{"contentBrotliBase64": "x", "id": 1}],
+                })
+            );
+        }
+    }
+
+    pub mod truncate_description {
+        use crate::api::truncate_description;
+
+        /// # 検証目的
+        /// マルチバイト文字の途中で切らず、文字数で切り詰めることを確認する。
+        #[test]
+        fn 文字単位で切り詰める() {
+            assert_eq!(
+                truncate_description("あいうえお", 3).as_deref(),
+                Some("あいう…")
+            );
+            assert_eq!(
+                truncate_description("あいうえお", 5).as_deref(),
+                Some("あいうえお")
+            );
+            assert_eq!(
+                truncate_description("abc😀def", 4).as_deref(),
+                Some("abc😀…")
+            );
+            assert_eq!(truncate_description("", 3).as_deref(), Some(""));
+            assert_eq!(truncate_description("あいう", 0), None);
+        }
+    }
+
+    pub mod parse_group_filter {
+        use crate::api::parse_group_filter;
+
+        /// # 検証目的
+        /// カンマ区切りのgroup指定から空白・空要素・重複を除いて分解することを確認する。
+        #[test]
+        fn カンマ区切りのgroupを分解する() {
+            assert_eq!(parse_group_filter("world"), vec!["world"]);
+            assert_eq!(
+                parse_group_filter(" world , tech,,world "),
+                vec!["world", "tech"]
+            );
+            assert!(parse_group_filter(" , ").is_empty());
+        }
+    }
+
+    pub mod feeds_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use serde_json::Value;
+        use tower::ServiceExt;
+
+        use crate::api::{build_router, ApiState};
+        use crate::feeds::upsert_feed_meta;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// /api/feedsがfeed_metaに保存したicon_urlを含めて返すことを確認する。
+        #[tokio::test]
+        async fn フィード一覧にicon_urlを含める() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            upsert_feed_meta(
+                &pool,
+                "news",
+                "example",
+                "https://example.com/rss.xml",
+                Some("https://example.com/logo.png"),
+            )
+            .await?;
+            upsert_feed_meta(&pool, "news", "plain", "https://plain.example/rss", None).await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let response = build_router(state)
+                .oneshot(Request::get("/api/feeds").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let items = body["items"].as_array().expect("itemsが配列");
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[0]["name"].as_str(), Some("example"));
+            assert_eq!(
+                items[0]["icon_url"].as_str(),
+                Some("https://example.com/logo.png")
+            );
+            assert!(items[1]["icon_url"].is_null());
+
+            Ok(())
+        }
+    }
+
+    pub mod success_rate_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use chrono::{Duration, Utc};
+        use serde_json::Value;
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, parse_relative_duration, ApiState};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// 期間内のfetch_logからデータ点列が返り、不正なbucket指定は400になることを確認する。
+        #[tokio::test]
+        async fn 成功率のデータ点列を返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            for (created_at, total, saved) in [
+                (Utc::now() - Duration::minutes(5), 4, 3),
+                (Utc::now() - Duration::days(3), 10, 0),
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.fetch_log (id, created_at, command, total_processed, saved, status_only, errors)
+                    VALUES ($1, $2, 'fetch_content', $3, $4, 0, 0)
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(created_at)
+                .bind(total)
+                .bind(saved)
+                .execute(&pool)
+                .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get("/api/metrics/success-rate?bucket=1h&since=24h")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["bucket"].as_str(), Some("1h"));
+            let points = body["points"].as_array().expect("pointsが配列");
+            assert_eq!(points.len(), 1);
+            assert_eq!(points[0]["total_processed"].as_i64(), Some(4));
+            assert_eq!(points[0]["saved"].as_i64(), Some(3));
+            assert_eq!(points[0]["success_rate"].as_f64(), Some(0.75));
+
+            let response = app
+                .oneshot(
+                    Request::get("/api/metrics/success-rate?bucket=3h")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 相対期間の指定を単位ごとに解釈し、不正な値を拒否することを確認する。
+        #[test]
+        fn 相対期間を解釈する() {
+            assert_eq!(parse_relative_duration("24h"), Some(Duration::hours(24)));
+            assert_eq!(parse_relative_duration("30m"), Some(Duration::minutes(30)));
+            assert_eq!(parse_relative_duration("7d"), Some(Duration::days(7)));
+            assert_eq!(parse_relative_duration("2w"), Some(Duration::weeks(2)));
+            assert_eq!(parse_relative_duration("0h"), None);
+            assert_eq!(parse_relative_duration("h"), None);
+            assert_eq!(parse_relative_duration("10y"), None);
+            assert_eq!(parse_relative_duration(""), None);
+        }
+    }
+
+    pub mod article_html_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, resolve_byte_range, ApiState, ByteRange};
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::compress_html;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// Rangeヘッダの各書式（範囲・開始のみ・末尾指定・範囲外）が正しく解釈されることを確認する。
+        #[test]
+        fn rangeヘッダを解釈できる() {
+            assert_eq!(resolve_byte_range(None, 10), ByteRange::Full);
+            assert_eq!(
+                resolve_byte_range(Some("bytes=0-3"), 10),
+                ByteRange::Partial { start: 0, end: 3 }
+            );
+            assert_eq!(
+                resolve_byte_range(Some("bytes=5-"), 10),
+                ByteRange::Partial { start: 5, end: 9 }
+            );
+            assert_eq!(
+                resolve_byte_range(Some("bytes=-4"), 10),
+                ByteRange::Partial { start: 6, end: 9 }
+            );
+            assert_eq!(
+                resolve_byte_range(Some("bytes=8-100"), 10),
+                ByteRange::Partial { start: 8, end: 9 }
+            );
+            assert_eq!(
+                resolve_byte_range(Some("bytes=10-20"), 10),
+                ByteRange::Unsatisfiable
+            );
+            assert_eq!(
+                resolve_byte_range(Some("bytes=0-1,3-4"), 10),
+                ByteRange::Full
+            );
+            assert_eq!(resolve_byte_range(Some("items=0-1"), 10), ByteRange::Full);
+        }
+
+        /// # 検証目的
+        /// Range指定時は206と部分本文、指定なしは200と全体、範囲外は416が返ることを確認する。
+        #[tokio::test]
+        async fn range指定で部分取得できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let html = "<html><body>range body</body></html>";
+            let article_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(article_id)
+            .bind("https://example.com/range")
+            .bind("Range記事")
+            .bind("Range本文")
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(article_id)
+                .bind(compress_html(html, ContentCompression::Brotli)?)
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+            let uri = format!("/api/articles/{}/html", article_id);
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(&uri)
+                        .header(header::RANGE, "bytes=0-5")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+            assert_eq!(
+                response.headers()[header::CONTENT_RANGE],
+                format!("bytes 0-5/{}", html.len())
+            );
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(&bytes[..], b"<html>");
+
+            let response = app
+                .clone()
+                .oneshot(Request::get(&uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(&bytes[..], html.as_bytes());
+
+            let response = app
+                .oneshot(
+                    Request::get(&uri)
+                        .header(header::RANGE, "bytes=1000-")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+            assert_eq!(
+                response.headers()[header::CONTENT_RANGE],
+                format!("bytes */{}", html.len())
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// gzip/zstdで保存された本文も記録された方式で展開してHTMLを返し、一覧ではBrotliへ詰め替えて返すことを確認する。
+        #[tokio::test]
+        async fn 記録された圧縮方式で展開する() -> Result<()> {
+            use base64::{engine::general_purpose::STANDARD, Engine as _};
+            use serde_json::Value;
+
+            use crate::fetch_content::decompress_html;
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            for compression in [ContentCompression::Gzip, ContentCompression::Zstd] {
+                clear_rss_tables(&pool).await?;
+                let html = format!("<html><body>{:?}で保存した本文</body></html>", compression);
+                let article_id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(article_id)
+                .bind(format!("https://example.com/{:?}", compression))
+                .bind("圧縮方式")
+                .bind("本文")
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO rss.article_content (queue_id, data, compression) VALUES ($1, $2, $3)",
+                )
+                .bind(article_id)
+                .bind(compress_html(&html, compression)?)
+                .bind(compression)
+                .execute(&pool)
+                .await?;
+
+                let response = app
+                    .clone()
+                    .oneshot(
+                        Request::get(format!("/api/articles/{}/html", article_id))
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                assert_eq!(&bytes[..], html.as_bytes());
+
+                let response = app
+                    .clone()
+                    .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                let encoded = body["items"][0]["content_brotli_base64"]
+                    .as_str()
+                    .expect("content_brotli_base64が文字列");
+                assert_eq!(
+                    decompress_html(&STANDARD.decode(encoded)?)?,
+                    html.as_bytes()
+                );
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 記録された圧縮品質を`content_compression_quality`として返し、記録の無い既存記事はnullになることを確認する。
+        #[tokio::test]
+        async fn 保存時の圧縮品質を返す() -> Result<()> {
+            use serde_json::{json, Value};
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let recorded_id = Uuid::new_v4();
+            let legacy_id = Uuid::new_v4();
+            for (id, link) in [
+                (recorded_id, "https://example.com/quality/recorded"),
+                (legacy_id, "https://example.com/quality/legacy"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("圧縮品質")
+                .bind("本文")
+                .execute(&pool)
+                .await?;
+            }
+            sqlx::query(
+                "INSERT INTO rss.article_content (queue_id, data, compression, compression_quality) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(recorded_id)
+            .bind(compress_html("<p>zstd</p>", ContentCompression::Zstd)?)
+            .bind(ContentCompression::Zstd)
+            .bind(i16::from(ContentCompression::Zstd.quality()))
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(legacy_id)
+                .bind(compress_html("<p>legacy</p>", ContentCompression::Brotli)?)
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let quality_of = |id: Uuid| {
+                body["items"]
+                    .as_array()
+                    .and_then(|items| {
+                        items
+                            .iter()
+                            .find(|item| item["id"] == json!(id.to_string()))
+                    })
+                    .map(|item| item["content_compression_quality"].clone())
+            };
+            assert_eq!(
+                quality_of(recorded_id),
+                Some(json!(ContentCompression::Zstd.quality()))
+            );
+            assert_eq!(quality_of(legacy_id), Some(Value::Null));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// queueの`status_code`が記事ごとにそのまま`status_code`として返り、未記録の記事はnullになることを確認する。
+        #[tokio::test]
+        async fn スクレイプのstatus_codeを返す() -> Result<()> {
+            use serde_json::{json, Value};
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let fetched_id = Uuid::new_v4();
+            let unknown_id = Uuid::new_v4();
+            for (id, link, status_code) in [
+                (fetched_id, "https://example.com/status/fetched", Some(200)),
+                (unknown_id, "https://example.com/status/unknown", None),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description, status_code) VALUES ($1, $2, $3, $4, $5)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("ステータス")
+                .bind("本文")
+                .bind(status_code)
+                .execute(&pool)
+                .await?;
+                sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                    .bind(id)
+                    .bind(compress_html("<p>status</p>", ContentCompression::Brotli)?)
+                    .execute(&pool)
+                    .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let status_of = |id: Uuid| {
+                body["items"]
+                    .as_array()
+                    .and_then(|items| {
+                        items
+                            .iter()
+                            .find(|item| item["id"] == json!(id.to_string()))
+                    })
+                    .map(|item| item["status_code"].clone())
+            };
+            assert_eq!(status_of(fetched_id), Some(json!(200)));
+            assert_eq!(status_of(unknown_id), Some(Value::Null));
+
+            Ok(())
+        }
+    }
+
+    pub mod article_links_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{Request, StatusCode};
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, ApiState};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// 保存済みのリンクを出現順で返し、本文の無い記事は404になることを確認する。
+        #[tokio::test]
+        async fn 記事のリンク一覧を返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let article_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(article_id)
+            .bind("https://example.com/links")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(article_id)
+                .bind(b"data".as_slice())
+                .execute(&pool)
+                .await?;
+            for (position, href) in [
+                (1, "https://example.com/second"),
+                (0, "https://example.com/first"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.article_links (queue_id, position, href) VALUES ($1, $2, $3)",
+                )
+                .bind(article_id)
+                .bind(position)
+                .bind(href)
+                .execute(&pool)
+                .await?;
+            }
 
-            let temp_file =
-                create_temp_yaml(&format!("test:\n  sample: {url}/feed", url = server.uri()))?;
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(format!("/api/articles/{}/links", article_id))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(
+                body,
+                json!({
+                    "id": article_id.to_string(),
+                    "links": ["https://example.com/first", "https://example.com/second"],
+                })
+            );
+
+            let response = app
+                .oneshot(
+                    Request::get(format!("/api/articles/{}/links", Uuid::new_v4()))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            Ok(())
+        }
+    }
+
+    pub mod article_export_endpoint {
+        use std::io::Read;
+
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use chrono::{Duration, Utc};
+        use flate2::read::GzDecoder;
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, ApiState};
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::compress_html;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// tar.gzを展開し、エントリ名と本文を格納順に返す
+        fn read_tar_gz(bytes: &[u8]) -> Result<Vec<(String, String)>> {
+            let mut entries = Vec::new();
+            let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().into_owned();
+                let mut html = String::new();
+                entry.read_to_string(&mut html)?;
+                entries.push((name, html));
+            }
+            Ok(entries)
+        }
+
+        /// # 検証目的
+        /// 生成したtar.gzを展開すると本文のある記事数と一致する`<id>.html`が展開済みで入っており、
+        /// group・created_after/created_beforeで絞り込め、不正な日時は400になることを確認する。
+        #[tokio::test]
+        async fn 記事本文をtar_gzでエクスポートする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let now = Utc::now();
+            let articles = [
+                (Uuid::new_v4(), "tech", now - Duration::days(3)),
+                (Uuid::new_v4(), "tech", now - Duration::hours(1)),
+                (Uuid::new_v4(), "world", now - Duration::hours(2)),
+            ];
+            for (id, group, created_at) in articles {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, "group", created_at)
+                    VALUES ($1, $2, 'タイトル', '説明', $3, $4)
+                    "#,
+                )
+                .bind(id)
+                .bind(format!("https://example.com/{}", id))
+                .bind(group)
+                .bind(created_at)
+                .execute(&pool)
+                .await?;
+                sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                    .bind(id)
+                    .bind(compress_html(
+                        &format!("<p>{}</p>", id),
+                        ContentCompression::Brotli,
+                    )?)
+                    .execute(&pool)
+                    .await?;
+            }
+            // 本文の無いエントリはエクスポートしない
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, 'タイトル', '説明')",
+            )
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/no-content")
+            .execute(&pool)
+            .await?;
 
             let state = ApiState::new(
-                pool.clone(),
-                server.uri(),
-                temp_file.path().to_string_lossy().to_string(),
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let export = |uri: String| {
+                let app = app.clone();
+                async move {
+                    let response = app
+                        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let content_type = response.headers().get(header::CONTENT_TYPE).cloned();
+                    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                    (status, content_type, bytes)
+                }
+            };
+
+            let (status, content_type, bytes) =
+                export("/api/articles/export.tar.gz".to_string()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(content_type.unwrap(), "application/gzip");
+            let entries = read_tar_gz(&bytes)?;
+            // created_atの古い順に並ぶ
+            let expected: Vec<(String, String)> = [articles[0].0, articles[2].0, articles[1].0]
+                .iter()
+                .map(|id| (format!("{}.html", id), format!("<p>{}</p>", id)))
+                .collect();
+            assert_eq!(entries, expected);
+
+            let (status, _, bytes) = export(format!(
+                "/api/articles/export.tar.gz?group=tech&created_after={}",
+                (now - Duration::days(1)).format("%Y-%m-%dT%H:%M:%SZ")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let names: Vec<String> = read_tar_gz(&bytes)?
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            assert_eq!(names, vec![format!("{}.html", articles[1].0)]);
+
+            let (status, _, bytes) = export(format!(
+                "/api/articles/export.tar.gz?created_before={}",
+                (now - Duration::days(1)).format("%Y-%m-%dT%H:%M:%SZ")
+            ))
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(read_tar_gz(&bytes)?.len(), 1);
+
+            let (status, _, _) =
+                export("/api/articles/export.tar.gz?created_after=yesterday".to_string()).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+
+            Ok(())
+        }
+    }
+
+    pub mod article_preview_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, escape_html, ApiState};
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::compress_html;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// HTMLの特殊文字がエスケープされることを確認する。
+        #[test]
+        fn html特殊文字をエスケープする() {
+            assert_eq!(
+                escape_html(r#"<a href="x">'&'</a>"#),
+                "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+            );
+        }
+
+        /// # 検証目的
+        /// プレビューが200でHTMLを返し、CSPヘッダとサンドボックス化された本文を含むことを確認する。
+        #[tokio::test]
+        async fn プレビューがhtmlを返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let article_id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(article_id)
+            .bind("https://example.com/preview")
+            .bind("プレビュー<記事>")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(article_id)
+                .bind(compress_html(
+                    "<html><body><p>preview</p><script>alert(1)</script></body></html>",
+                    ContentCompression::Brotli,
+                )?)
+                .execute(&pool)
+                .await?;
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
                 None,
             );
             let app = build_router(state);
 
             let response = app
-                .oneshot(Request::post("/api/fetch-rss").body(Body::empty()).unwrap())
+                .oneshot(
+                    Request::get(format!("/api/articles/{}/preview", article_id))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
                 .await
                 .unwrap();
 
             assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "text/html; charset=utf-8"
+            );
+            let csp = response.headers()[header::CONTENT_SECURITY_POLICY]
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert!(csp.contains("default-src 'none'"));
 
             let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let value: Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(value["total_processed"].as_u64(), Some(1));
+            let page = String::from_utf8(bytes.to_vec())?;
+            assert!(page.contains("<iframe sandbox srcdoc=\"&lt;html&gt;"));
+            assert!(page.contains("&lt;p&gt;preview&lt;/p&gt;"));
+            assert!(page.contains("プレビュー&lt;記事&gt;"));
+            assert!(!page.contains("<script>"));
 
             Ok(())
         }
     }
 
-    pub mod fetch_content_endpoint {
-        use std::io::{Cursor, Read};
+    pub mod article_batch_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use axum::Router;
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, ApiState};
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::{compress_html, decompress_html};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        async fn batch(app: &Router, ids: Value) -> (StatusCode, Value) {
+            let request = Request::post("/api/articles/batch")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(json!({ "ids": ids }).to_string()))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice(&bytes).unwrap())
+        }
+
+        /// # 検証目的
+        /// 指定したIDの記事が指定順に本文付きで返り、重複は1件にまとめ、存在しないIDは`not_found`に回り、
+        /// 空の指定は400になることを確認する。
+        #[tokio::test]
+        async fn 指定したidの記事を指定順に返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+            for (index, id) in ids.iter().enumerate() {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(format!("https://example.com/batch/{}", index))
+                .bind(format!("記事{}", index))
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO rss.article_content (queue_id, data, compression) VALUES ($1, $2, $3)",
+                )
+                .bind(id)
+                .bind(compress_html(&format!("<p>{}</p>", index), ContentCompression::Gzip)?)
+                .bind(ContentCompression::Gzip)
+                .execute(&pool)
+                .await?;
+            }
+
+            let state = ApiState::new(
+                pool,
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let missing = Uuid::new_v4();
+            let (status, body) = batch(&app, json!([ids[2], missing, ids[0], ids[2]])).await;
+            assert_eq!(status, StatusCode::OK);
+            let items = body["items"].as_array().expect("itemsが配列");
+            let returned: Vec<&str> = items
+                .iter()
+                .map(|item| item["id"].as_str().unwrap())
+                .collect();
+            assert_eq!(
+                returned,
+                vec![ids[2].to_string().as_str(), ids[0].to_string().as_str()]
+            );
+            // Brotli以外で保存した本文もBrotliに詰め替えて返す
+            let encoded = items[0]["content_brotli_base64"].as_str().unwrap();
+            assert_eq!(decompress_html(&STANDARD.decode(encoded)?)?, b"<p>2</p>");
+            assert_eq!(body["not_found"], json!([missing]));
+            assert_eq!(body["remaining_ids"], json!([]));
+
+            let (status, body) = batch(&app, json!([])).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("invalid_ids"));
+
+            // 件数は重複を除く前に数える
+            let oversized = vec![ids[0]; crate::api::MAX_LIMIT as usize + 1];
+            let (status, body) = batch(&app, json!(oversized)).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"].as_str(), Some("too_many_ids"));
+
+            Ok(())
+        }
+    }
+
+    pub mod article_claim_endpoint {
+        use std::collections::HashSet;
 
         use anyhow::Result;
         use axum::body::{to_bytes, Body};
-        use axum::http::{Request, StatusCode};
-        use brotli::Decompressor;
-        use serde_json::json;
+        use axum::http::{header, Request, StatusCode};
+        use axum::Router;
+        use serde_json::{json, Value};
         use tower::ServiceExt;
         use uuid::Uuid;
-        use wiremock::matchers::{method, path};
-        use wiremock::{Mock, MockServer, ResponseTemplate};
 
         use crate::api::{build_router, ApiState};
-        use crate::models::ArticleContent;
+        use crate::articles::claim_articles;
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::compress_html;
         use crate::test_support::{clear_rss_tables, prepare_test_pool};
 
+        async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Value) {
+            let response = app.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice(&bytes).unwrap())
+        }
+
+        fn claim(limit: i64, consumer: &str) -> Request<Body> {
+            Request::post(format!(
+                "/api/articles/claim?limit={}&consumer={}",
+                limit, consumer
+            ))
+            .body(Body::empty())
+            .unwrap()
+        }
+
+        fn ack(consumer: &str, ids: &[String]) -> Request<Body> {
+            Request::post("/api/articles/ack")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({ "consumer": consumer, "ids": ids }).to_string(),
+                ))
+                .unwrap()
+        }
+
+        fn item_ids(body: &Value) -> Vec<String> {
+            body["items"]
+                .as_array()
+                .expect("itemsが配列")
+                .iter()
+                .map(|item| item["id"].as_str().unwrap().to_string())
+                .collect()
+        }
+
         /// # 検証目的
-        /// API経由でコンテンツ取得処理を実行し、本文保存とレスポンス内容を確認する。
+        /// 同時にクレームしても同じ記事が二重に渡らず、ackした記事は再クレームされないことを確認する。
         #[tokio::test]
-        async fn コンテンツ取得apiが成功する() -> Result<()> {
+        async fn 同じ記事を二重にクレームしない() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
-            let server = MockServer::start().await;
-            let html_body = "<html><body>api ok</body></html>";
+            for index in 0..5 {
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(format!("https://example.com/claim/{}", index))
+                .bind("記事")
+                .bind("本文")
+                .execute(&pool)
+                .await?;
+                sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                    .bind(id)
+                    .bind(compress_html("<p>claim</p>", ContentCompression::Brotli)?)
+                    .execute(&pool)
+                    .await?;
+            }
 
-            Mock::given(method("POST"))
-                .and(path("/fetch"))
-                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-                    "html": html_body,
-                    "status_code": 200,
-                    "title": "API Title",
-                    "final_url": "https://example.com/api",
-                    "elapsed_ms": 10.0,
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                })))
-                .mount(&server)
-                .await;
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(state);
+
+            let ((status_a, body_a), (status_b, body_b)) = tokio::join!(
+                send(&app, claim(3, "worker-a")),
+                send(&app, claim(3, "worker-b"))
+            );
+            assert_eq!((status_a, status_b), (StatusCode::OK, StatusCode::OK));
+            let ids_a = item_ids(&body_a);
+            let ids_b = item_ids(&body_b);
+            assert_eq!(ids_a.len() + ids_b.len(), 5);
+            let unique: HashSet<&String> = ids_a.iter().chain(ids_b.iter()).collect();
+            assert_eq!(
+                unique.len(),
+                5,
+                "二重にクレームされた: {:?} {:?}",
+                ids_a,
+                ids_b
+            );
+
+            let (_, body) = send(&app, claim(10, "worker-c")).await;
+            assert!(item_ids(&body).is_empty());
+
+            // 他のコンシューマのクレームはackできない
+            let (_, body) = send(&app, ack("worker-b", &ids_a)).await;
+            assert_eq!(body["acked"], json!(0));
+            let (status, body) = send(&app, ack("worker-a", &ids_a)).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["acked"], json!(ids_a.len()));
+
+            // リース切れ扱いでもack済みの記事は再クレームされない
+            let reclaimed = claim_articles(&pool, 10, "worker-c", 0).await?;
+            let reclaimed_ids: Vec<String> = reclaimed
+                .iter()
+                .map(|article| article.id.to_string())
+                .collect();
+            let mut expected = ids_b.clone();
+            expected.sort();
+            let mut actual = reclaimed_ids.clone();
+            actual.sort();
+            assert_eq!(actual, expected);
+
+            let (status, body) = send(&app, claim(1, "%20")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body["code"], json!("invalid_consumer"));
+
+            Ok(())
+        }
+    }
+
+    pub mod queue_status_endpoint {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use axum::Router;
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{build_router, ApiState};
+        use crate::fetch_content::{execute_fetch_content, FetchContentOptions};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        const ADMIN_TOKEN: &str = "secret-token";
+
+        /// 一覧の先頭の記事のstatus_codeを返す
+        async fn list_status_code(app: &Router) -> Value {
+            let response = app
+                .clone()
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes).unwrap();
+            body["items"][0]["status_code"].clone()
+        }
 
-            let queue_id = Uuid::new_v4();
+        fn patch_request(id: Uuid, token: Option<&str>, body: Value) -> Request<Body> {
+            let mut builder = Request::patch(format!("/api/queue/{}", id))
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            builder.body(Body::from(body.to_string())).unwrap()
+        }
+
+        /// # 検証目的
+        /// 認証付きでstatus_codeを410へ更新でき、更新後はfetch-contentの再試行対象から外れることを確認する。
+        #[tokio::test]
+        async fn 手動更新後は再試行対象外になる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = Uuid::new_v4();
             sqlx::query(
                 r#"
-                INSERT INTO rss.queue (id, link, title, description)
-                VALUES ($1, $2, $3, $4)
+                INSERT INTO rss.queue (id, link, title, description, status_code)
+                VALUES ($1, $2, $3, $4, 404)
                 "#,
             )
-            .bind(queue_id)
-            .bind("https://example.com/api")
-            .bind("APIテスト")
-            .bind("API説明")
+            .bind(id)
+            .bind("https://example.com/not-found")
+            .bind("タイトル")
+            .bind("説明")
             .execute(&pool)
             .await?;
 
             let state = ApiState::new(
                 pool.clone(),
-                server.uri(),
+                "http://localhost:8000".to_string(),
                 "rss_links.yml".to_string(),
                 None,
-            );
+            )
+            .with_admin_token(Some(ADMIN_TOKEN.to_string()));
             let app = build_router(state);
 
             let response = app
-                .oneshot(
-                    Request::post("/api/fetch-content")
-                        .header("content-type", "application/json")
-                        .body(Body::from("{\"limit\":1}"))
-                        .unwrap(),
-                )
+                .clone()
+                .oneshot(patch_request(id, None, json!({ "status_code": 410 })))
                 .await
                 .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 
-            assert_eq!(response.status(), StatusCode::OK);
+            let response = app
+                .clone()
+                .oneshot(patch_request(
+                    id,
+                    Some("wrong-token"),
+                    json!({ "status_code": 410 }),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 
+            let response = app
+                .clone()
+                .oneshot(patch_request(
+                    id,
+                    Some(ADMIN_TOKEN),
+                    json!({ "status_code": 410 }),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
             let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let value: serde_json::Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(value["saved_count"].as_u64(), Some(1));
-            assert_eq!(value["status_only_count"].as_u64(), Some(0));
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["id"], id.to_string());
+            assert_eq!(body["status_code"], 410);
 
-            let stored: ArticleContent = sqlx::query_as(
-                "SELECT queue_id, created_at, updated_at, data FROM rss.article_content WHERE queue_id = $1",
+            // 再試行対象が無いのでscraping APIは呼ばれない
+            let summary = execute_fetch_content(
+                &pool,
+                10,
+                "http://127.0.0.1:9",
+                &FetchContentOptions::default(),
             )
-            .bind(queue_id)
-            .fetch_one(&pool)
             .await?;
-
-            let mut decompressor = Decompressor::new(Cursor::new(stored.data), 4096);
-            let mut decompressed = String::new();
-            decompressor.read_to_string(&mut decompressed)?;
-            assert_eq!(decompressed, html_body);
+            assert!(summary.entries.is_empty());
 
             Ok(())
         }
 
         /// # 検証目的
-        /// limitに0を指定したリクエストが400エラーと`invalid_limit`コードを返すことを確認する。
+        /// 一覧のキャッシュが有効でも、status_codeの手動更新後は更新後の値が一覧に返ることを確認する。
         #[tokio::test]
-        async fn limitが0ならエラーを返す() -> Result<()> {
+        async fn 手動更新で一覧のキャッシュを破棄する() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
+            let id = Uuid::new_v4();
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description, status_code)
+                VALUES ($1, $2, $3, $4, 404)
+                "#,
+            )
+            .bind(id)
+            .bind("https://example.com/cached")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(id)
+                .bind(b"old".as_slice())
+                .execute(&pool)
+                .await?;
+
             let state = ApiState::new(
-                pool,
+                pool.clone(),
                 "http://localhost:8000".to_string(),
                 "rss_links.yml".to_string(),
                 None,
-            );
+            )
+            .with_admin_token(Some(ADMIN_TOKEN.to_string()))
+            .with_article_cache_ttl(60);
             let app = build_router(state);
 
+            assert_eq!(list_status_code(&app).await, 404);
+
             let response = app
-                .oneshot(
-                    Request::post("/api/fetch-content")
-                        .header("content-type", "application/json")
-                        .body(Body::from("{\"limit\":0}"))
-                        .unwrap(),
-                )
+                .clone()
+                .oneshot(patch_request(
+                    id,
+                    Some(ADMIN_TOKEN),
+                    json!({ "status_code": 410 }),
+                ))
                 .await
                 .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
 
-            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let body: serde_json::Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(body["code"].as_str(), Some("invalid_limit"));
-            assert_eq!(
-                body["message"].as_str(),
-                Some("limitは1以上で指定してください")
-            );
+            assert_eq!(list_status_code(&app).await, 410);
 
             Ok(())
         }
-    }
-
-    pub mod pipeline_flow {
-        use std::io::{Cursor, Read};
-
-        use anyhow::Result;
-        use brotli::Decompressor;
-        use serde_json::json;
-        use wiremock::matchers::{method, path};
-        use wiremock::{Mock, MockServer, ResponseTemplate};
-
-        use crate::articles::search_articles_window;
-        use crate::fetch_content::execute_fetch_content;
-        use crate::fetch_rss::execute_fetch_rss;
-        use crate::test_support::{clear_rss_tables, create_temp_yaml, prepare_test_pool};
 
         /// # 検証目的
-        /// RSS取り込みから本文保存、記事取得まで一連の流れが動作することを確認する。
+        /// 不正なstatus_code・存在しないID・トークン未設定時にそれぞれエラーを返すことを確認する。
         #[tokio::test]
-        async fn rssから記事取得まで連携する() -> Result<()> {
+        async fn 不正な更新はエラーを返す() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
-            let server = MockServer::start().await;
-
-            let rss_body = r#"<?xml version="1.0" encoding="UTF-8"?>
-                <rss version="2.0">
-                  <channel>
-                    <title>Integration Feed</title>
-                    <item>
-                      <title>Item Title</title>
-                      <link>https://example.com/item</link>
-                      <description>本文サマリ</description>
-                      <pubDate>Mon, 13 Oct 2025 12:00:00 GMT</pubDate>
-                    </item>
-                  </channel>
-                </rss>
-            "#;
-
-            Mock::given(method("GET"))
-                .and(path("/feed"))
-                .respond_with(ResponseTemplate::new(200).set_body_string(rss_body))
-                .mount(&server)
-                .await;
-
-            let html_body = "<html><body>integration ok</body></html>";
-
-            Mock::given(method("POST"))
-                .and(path("/fetch"))
-                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-                    "html": html_body,
-                    "status_code": 200,
-                    "title": "Integration",
-                    "final_url": "https://example.com/item",
-                    "elapsed_ms": 12.3,
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                })))
-                .mount(&server)
-                .await;
-
-            let temp_file = create_temp_yaml(&format!(
-                "integration:\n  sample: {url}/feed",
-                url = server.uri()
-            ))?;
-
-            let rss_summary =
-                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref()).await?;
-            assert_eq!(rss_summary.total_processed, 1);
-
-            let fetch_summary = execute_fetch_content(&pool, 10, &server.uri()).await?;
-            assert_eq!(fetch_summary.saved_count, 1);
-            assert_eq!(fetch_summary.status_only_count, 0);
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let app = build_router(
+                state
+                    .clone()
+                    .with_admin_token(Some(ADMIN_TOKEN.to_string())),
+            );
 
-            let articles = search_articles_window(&pool, 10, None).await?;
-            assert_eq!(articles.len(), 1);
-            let article = &articles[0];
-            assert_eq!(article.link, "https://example.com/item");
-            assert_eq!(article.title, "Item Title");
+            for status_code in [200, 99, 600] {
+                let response = app
+                    .clone()
+                    .oneshot(patch_request(
+                        Uuid::new_v4(),
+                        Some(ADMIN_TOKEN),
+                        json!({ "status_code": status_code }),
+                    ))
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            }
 
-            let mut decompressor = Decompressor::new(Cursor::new(article.data.clone()), 4096);
-            let mut decompressed = String::new();
-            decompressor.read_to_string(&mut decompressed)?;
-            assert_eq!(decompressed, html_body);
+            let response = app
+                .oneshot(patch_request(
+                    Uuid::new_v4(),
+                    Some(ADMIN_TOKEN),
+                    json!({ "status_code": 410 }),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
-            let status: Option<i32> =
-                sqlx::query_scalar("SELECT status_code FROM rss.queue WHERE id = $1")
-                    .bind(article.id)
-                    .fetch_one(&pool)
-                    .await?;
-            assert_eq!(status, Some(200));
+            let response = build_router(state)
+                .oneshot(patch_request(
+                    Uuid::new_v4(),
+                    Some(ADMIN_TOKEN),
+                    json!({ "status_code": 410 }),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
 
             Ok(())
         }
     }
 
-    pub mod articles_endpoint {
+    pub mod admin_endpoint {
         use anyhow::Result;
         use axum::body::{to_bytes, Body};
-        use axum::http::{Request, StatusCode};
-        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use axum::http::{header, Request, StatusCode};
+        use axum::Router;
         use chrono::{Duration, Utc};
         use serde_json::Value;
-        use sqlx::PgPool;
         use tower::ServiceExt;
         use uuid::Uuid;
 
         use crate::api::{build_router, ApiState};
         use crate::test_support::{clear_rss_tables, prepare_test_pool};
 
-        async fn insert_article(
-            pool: &PgPool,
-            id: Uuid,
-            created_at: chrono::DateTime<chrono::Utc>,
-            link: &str,
-            title: &str,
-            description: &str,
-            data: &[u8],
-        ) -> Result<()> {
+        const ADMIN_TOKEN: &str = "secret-token";
+
+        fn post_request(path: &str, token: Option<&str>) -> Request<Body> {
+            let mut builder = Request::post(path);
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            builder.body(Body::empty()).unwrap()
+        }
+
+        async fn insert_article(pool: &sqlx::PgPool, link: &str) -> Result<()> {
+            let id = Uuid::new_v4();
             sqlx::query(
-                r#"
-                INSERT INTO rss.queue (id, link, title, description, pub_date)
-                VALUES ($1, $2, $3, $4, NOW())
-                "#,
+                "INSERT INTO rss.queue (id, link, title, description, created_at) VALUES ($1, $2, 'タイトル', '説明', $3)",
             )
             .bind(id)
             .bind(link)
-            .bind(title)
-            .bind(description)
+            .bind(Utc::now() - Duration::minutes(1))
             .execute(pool)
             .await?;
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(id)
+                .bind(b"body".as_slice())
+                .execute(pool)
+                .await?;
+            Ok(())
+        }
+
+        async fn success_rate_points(app: &Router) -> Result<Vec<Value>> {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get("/api/metrics/success-rate?bucket=1h&since=24h")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            Ok(body["points"].as_array().cloned().unwrap_or_default())
+        }
+
+        /// # 検証目的
+        /// キャッシュクリア後の記事一覧がキャッシュではなくDBから引き直され、
+        /// 認証の無いクリア要求は401で拒否されてキャッシュが残ることを確認する。
+        #[tokio::test]
+        async fn キャッシュクリア後はdbから引き直す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+            insert_article(&pool, "https://example.com/cached").await?;
+
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            )
+            .with_article_cache_ttl(60)
+            .with_admin_token(Some(ADMIN_TOKEN.to_string()));
+            let app = build_router(state);
+
+            async fn count_items(app: &axum::Router) -> Result<usize> {
+                let response = app
+                    .clone()
+                    .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let body: Value = serde_json::from_slice(&bytes)?;
+                Ok(body["items"].as_array().expect("itemsが配列").len())
+            }
+
+            assert_eq!(count_items(&app).await?, 1);
+            insert_article(&pool, "https://example.com/added").await?;
+
+            let response = app
+                .clone()
+                .oneshot(post_request("/api/admin/cache/clear", None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(count_items(&app).await?, 1);
+
+            let response = app
+                .clone()
+                .oneshot(post_request("/api/admin/cache/clear", Some(ADMIN_TOKEN)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["enabled"], Value::Bool(true));
+            assert_eq!(count_items(&app).await?, 2);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// メトリクスのリセットで成功率のデータ点が空になってもfetch_logの行（run_id）は残り、
+        /// リセット後の実行は再び集計されること、トークン未設定のサーバでは403になることを確認する。
+        #[tokio::test]
+        async fn メトリクスをリセットする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
 
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
             sqlx::query(
                 r#"
-                UPDATE rss.queue
-                SET created_at = $2, updated_at = $2
-                WHERE id = $1
+                INSERT INTO rss.fetch_log (id, created_at, command, total_processed, saved, status_only, errors)
+                VALUES ($1, $2, 'fetch_content', 4, 3, 0, 0)
                 "#,
             )
-            .bind(id)
-            .bind(created_at)
-            .execute(pool)
+            .bind(Uuid::new_v4())
+            .bind(Utc::now() - Duration::minutes(5))
+            .execute(&pool)
             .await?;
 
+            let state = ApiState::new(
+                pool.clone(),
+                "http://localhost:8000".to_string(),
+                "rss_links.yml".to_string(),
+                None,
+            );
+            let response = build_router(state.clone())
+                .oneshot(post_request("/api/admin/metrics/reset", Some(ADMIN_TOKEN)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+            let app = build_router(state.with_admin_token(Some(ADMIN_TOKEN.to_string())));
+            let response = app
+                .clone()
+                .oneshot(post_request("/api/admin/metrics/reset", Some(ADMIN_TOKEN)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert!(body["reset_at"].is_string());
+
+            assert!(success_rate_points(&app).await?.is_empty());
+
+            let (remaining,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM rss.fetch_log")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!(remaining, 1);
+
             sqlx::query(
                 r#"
-                INSERT INTO rss.article_content (queue_id, data)
-                VALUES ($1, $2)
+                INSERT INTO rss.fetch_log (id, created_at, command, total_processed, saved, status_only, errors)
+                VALUES ($1, NOW() + INTERVAL '1 second', 'fetch_content', 2, 1, 0, 1)
                 "#,
             )
-            .bind(id)
-            .bind(data)
-            .execute(pool)
+            .bind(Uuid::new_v4())
+            .execute(&pool)
             .await?;
+            let points = success_rate_points(&app).await?;
+            assert_eq!(points.len(), 1);
+            assert_eq!(points[0]["total_processed"].as_i64(), Some(2));
+            assert_eq!(points[0]["saved"].as_i64(), Some(1));
 
             Ok(())
         }
 
         /// # 検証目的
-        /// 記事一覧APIがページネーションおよびBase64エンコードを正しく行うことを確認する。
+        /// マージでmerge対象が記事一覧・html/preview・batchから消えて論理削除（merged_into）され、
+        /// 本文の無いkeepへはmerge対象の本文が移ること、未知のIDを含むリクエストは404で何も変更しないことを確認する。
         #[tokio::test]
-        async fn 記事一覧を取得できる() -> Result<()> {
+        async fn マージした記事は一覧から消える() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
-            let newer_id = Uuid::new_v4();
-            let older_id = Uuid::new_v4();
-
-            insert_article(
-                &pool,
-                newer_id,
-                Utc::now(),
-                "https://example.com/new",
-                "新しい記事",
-                "新しい本文",
-                b"newer",
-            )
-            .await?;
-
-            insert_article(
-                &pool,
-                older_id,
-                Utc::now() - Duration::hours(1),
-                "https://example.com/old",
-                "古い記事",
-                "古い本文",
-                b"older",
-            )
-            .await?;
+            let keep = Uuid::new_v4();
+            let merged = [Uuid::new_v4(), Uuid::new_v4()];
+            for (index, id) in std::iter::once(keep).chain(merged).enumerate() {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, 'タイトル', '説明')",
+                )
+                .bind(id)
+                .bind(format!("https://example.com/dup/{}", index))
+                .execute(&pool)
+                .await?;
+            }
+            // keepには本文が無く、merge対象の1件目にだけ本文がある
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(merged[0])
+                .bind(b"body".as_slice())
+                .execute(&pool)
+                .await?;
 
             let state = ApiState::new(
                 pool.clone(),
                 "http://localhost:8000".to_string(),
                 "rss_links.yml".to_string(),
                 None,
-            );
+            )
+            .with_admin_token(Some(ADMIN_TOKEN.to_string()));
             let app = build_router(state);
 
+            let merge_request = |payload: Value, token: Option<&str>| {
+                let mut builder = Request::post("/api/articles/merge")
+                    .header(header::CONTENT_TYPE, "application/json");
+                if let Some(token) = token {
+                    builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+                }
+                builder.body(Body::from(payload.to_string())).unwrap()
+            };
+            let payload = serde_json::json!({ "keep": keep, "merge": merged });
+
             let response = app
                 .clone()
-                .oneshot(
-                    Request::get("/api/articles?limit=1")
-                        .body(Body::empty())
-                        .unwrap(),
-                )
+                .oneshot(merge_request(payload.clone(), None))
                 .await
                 .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
 
-            assert_eq!(response.status(), StatusCode::OK);
+            let unknown = Uuid::new_v4();
+            let response = app
+                .clone()
+                .oneshot(merge_request(
+                    serde_json::json!({ "keep": keep, "merge": [merged[0], unknown] }),
+                    Some(ADMIN_TOKEN),
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let merged_count: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM rss.queue WHERE merged_into IS NOT NULL")
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(merged_count, 0, "失敗したマージで論理削除された");
 
+            let response = app
+                .clone()
+                .oneshot(merge_request(payload, Some(ADMIN_TOKEN)))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
             let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
             let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["keep"], keep.to_string());
+            assert_eq!(body["merged"].as_array().map(Vec::len), Some(2));
+            assert_eq!(body["content_moved_from"], merged[0].to_string());
 
-            let items = body["items"].as_array().expect("itemsが配列");
-            assert_eq!(items.len(), 1);
-            let first = &items[0];
-            let first_id = first["id"].as_str().expect("idが文字列");
-            assert_eq!(first_id, newer_id.to_string());
-
-            let encoded = first["content_brotli_base64"]
-                .as_str()
-                .expect("content_brotli_base64が文字列");
-            let decoded = STANDARD.decode(encoded)?;
-            assert_eq!(decoded, b"newer");
-
-            let next_token = body["next_token"].as_str().expect("next_tokenが存在");
+            let response = app
+                .clone()
+                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            let ids: Vec<&str> = body["items"]
+                .as_array()
+                .expect("itemsが配列")
+                .iter()
+                .map(|item| item["id"].as_str().unwrap())
+                .collect();
+            assert_eq!(ids, vec![keep.to_string()]);
 
+            for uri in [
+                format!("/api/articles/{}/html", merged[0]),
+                format!("/api/articles/{}/preview", merged[0]),
+            ] {
+                let response = app
+                    .clone()
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            }
             let response = app
+                .clone()
                 .oneshot(
-                    Request::get(format!("/api/articles?page_token={}", next_token))
-                        .body(Body::empty())
+                    Request::post("/api/articles/batch")
+                        .header(header::CONTENT_TYPE, "application/json")
+                        .body(Body::from(
+                            serde_json::json!({ "ids": [keep, merged[0]] }).to_string(),
+                        ))
                         .unwrap(),
                 )
                 .await
                 .unwrap();
-
             assert_eq!(response.status(), StatusCode::OK);
             let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
             let body: Value = serde_json::from_slice(&bytes)?;
-            let items = body["items"].as_array().expect("itemsが配列");
-            assert_eq!(items.len(), 1);
-            let first = &items[0];
-            let first_id = first["id"].as_str().expect("idが文字列");
-            assert_eq!(first_id, older_id.to_string());
-            let encoded = first["content_brotli_base64"]
-                .as_str()
-                .expect("content_brotli_base64が文字列");
-            let decoded = STANDARD.decode(encoded)?;
-            assert_eq!(decoded, b"older");
-            assert!(body["next_token"].is_null());
+            assert_eq!(body["items"].as_array().map(Vec::len), Some(1));
+            assert_eq!(body["items"][0]["id"], keep.to_string());
+            assert_eq!(body["not_found"], serde_json::json!([merged[0]]));
+
+            let merged_into: Vec<Option<Uuid>> =
+                sqlx::query_scalar("SELECT merged_into FROM rss.queue WHERE id = ANY($1)")
+                    .bind(merged.as_slice())
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(merged_into, vec![Some(keep), Some(keep)]);
 
             Ok(())
         }
+    }
+
+    pub mod access_log {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        use anyhow::Result;
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use tracing_subscriber::fmt::MakeWriter;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        use crate::api::{build_router, ApiState, ACCESS_LOG_TARGET};
+        use crate::telemetry::json_fmt_layer;
+        use crate::test_support::prepare_test_pool;
+
+        /// ログ出力をメモリに溜めるライタ
+        #[derive(Clone, Default)]
+        struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for SharedBuffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        impl<'a> MakeWriter<'a> for SharedBuffer {
+            type Writer = SharedBuffer;
+
+            fn make_writer(&'a self) -> Self::Writer {
+                self.clone()
+            }
+        }
+
+        impl SharedBuffer {
+            /// 出力された行のうちアクセスログをJSONとして取り出す
+            fn access_logs(&self) -> Vec<Value> {
+                let bytes = self.0.lock().unwrap().clone();
+                String::from_utf8(bytes)
+                    .unwrap()
+                    .lines()
+                    .map(|line| serde_json::from_str::<Value>(line).expect("1行が1つのJSON"))
+                    .filter(|log| log["target"] == json!(ACCESS_LOG_TARGET))
+                    .collect()
+            }
+        }
 
         /// # 検証目的
-        /// 存在しないトークンを指定した場合にエラーが返ることを確認する。
+        /// アクセスログ有効時、リクエストごとにmethod/path/status/latency/request_id/client_ipを持つJSONが1行出力されることを確認する。
         #[tokio::test]
-        async fn 無効なトークンでエラーを返す() -> Result<()> {
-            let _lock = crate::test_support::acquire_db_lock().await;
+        async fn アクセスログをjsonで出力する() -> Result<()> {
             let pool = prepare_test_pool().await?;
-
-            sqlx::migrate!("./migrations").run(&pool).await?;
-            clear_rss_tables(&pool).await?;
+            let buffer = SharedBuffer::default();
+            let subscriber = tracing_subscriber::registry().with(json_fmt_layer(buffer.clone()));
+            let _guard = tracing::subscriber::set_default(subscriber);
 
             let state = ApiState::new(
                 pool,
                 "http://localhost:8000".to_string(),
                 "rss_links.yml".to_string(),
                 None,
-            );
+            )
+            .with_access_log(true);
             let app = build_router(state);
 
             let response = app
+                .clone()
                 .oneshot(
-                    Request::get(format!("/api/articles?page_token={}", Uuid::new_v4()))
+                    Request::get("/health?token=secret")
+                        .header("x-request-id", "req-123")
+                        .header("x-forwarded-for", "203.0.113.5, 10.0.0.1")
                         .body(Body::empty())
                         .unwrap(),
                 )
                 .await
                 .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["x-request-id"], "req-123");
 
-            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let body: Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(body["code"].as_str(), Some("page_token_not_found"));
-            assert_eq!(body["message"].as_str(), Some("page_token is not exist"));
+            let response = app
+                .oneshot(Request::get("/missing").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let generated_id = response.headers()["x-request-id"].to_str()?.to_string();
+
+            let logs = buffer.access_logs();
+            assert_eq!(logs.len(), 2, "{:?}", logs);
+
+            let health = &logs[0];
+            assert_eq!(health["method"], json!("GET"));
+            assert_eq!(health["path"], json!("/health"));
+            assert_eq!(health["status"], json!(200));
+            assert!(health["latency_ms"].is_u64());
+            assert_eq!(health["request_id"], json!("req-123"));
+            assert_eq!(health["client_ip"], json!("203.0.113.5"));
+
+            let missing = &logs[1];
+            assert_eq!(missing["status"], json!(404));
+            assert_eq!(missing["request_id"], json!(generated_id));
+            assert!(missing.get("client_ip").is_none());
 
             Ok(())
         }
 
         /// # 検証目的
-        /// limitに0を指定した場合に400エラーと`invalid_limit`コードが返ることを確認する。
+        /// アクセスログ無効（既定）ではアクセスログを出力しないことを確認する。
         #[tokio::test]
-        async fn 記事一覧のlimitが0ならエラー() -> Result<()> {
-            let _lock = crate::test_support::acquire_db_lock().await;
+        async fn 既定ではアクセスログを出力しない() -> Result<()> {
             let pool = prepare_test_pool().await?;
-
-            sqlx::migrate!("./migrations").run(&pool).await?;
-            clear_rss_tables(&pool).await?;
+            let buffer = SharedBuffer::default();
+            let subscriber = tracing_subscriber::registry().with(json_fmt_layer(buffer.clone()));
+            let _guard = tracing::subscriber::set_default(subscriber);
 
             let state = ApiState::new(
                 pool,
@@ -778,53 +7191,40 @@ mod tests {
                 "rss_links.yml".to_string(),
                 None,
             );
-            let app = build_router(state);
-
-            let response = app
-                .oneshot(
-                    Request::get("/api/articles?limit=0")
-                        .body(Body::empty())
-                        .unwrap(),
-                )
+            let response = build_router(state)
+                .oneshot(Request::get("/health").body(Body::empty()).unwrap())
                 .await
                 .unwrap();
-
-            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
-            let body: Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(body["code"].as_str(), Some("invalid_limit"));
-            assert_eq!(
-                body["message"].as_str(),
-                Some("limitは1以上で指定してください")
-            );
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get("x-request-id").is_none());
+            assert!(buffer.access_logs().is_empty());
 
             Ok(())
         }
+    }
+
+    pub mod problem_details {
+        use anyhow::Result;
+        use axum::body::{to_bytes, Body};
+        use axum::http::{header, Request, StatusCode};
+        use serde_json::{json, Value};
+        use tower::ServiceExt;
+        use uuid::Uuid;
+
+        use crate::api::{accepts_problem_json, build_router, ApiState};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
 
         /// # 検証目的
-        /// Base64化後に50MB制限を超える記事が含まれる場合に413エラーが返ることを確認する。
+        /// `Accept: application/problem+json`を付けたリクエストのエラーがRFC 7807形式（`application/problem+json`）で返り、
+        /// 付けないリクエストは従来の`{code, message}`形式のままであることを確認する。
         #[tokio::test]
-        async fn 応答サイズ超過時にエラー() -> Result<()> {
+        async fn problem_json指定時はrfc7807形式で返す() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
-            let large_data = vec![0u8; crate::api::MAX_RESPONSE_BYTES];
-            let article_id = Uuid::new_v4();
-
-            insert_article(
-                &pool,
-                article_id,
-                Utc::now(),
-                "https://example.com/huge",
-                "巨大な記事",
-                "巨大本文",
-                &large_data,
-            )
-            .await?;
-
             let state = ApiState::new(
                 pool,
                 "http://localhost:8000".to_string(),
@@ -834,20 +7234,94 @@ mod tests {
             let app = build_router(state);
 
             let response = app
-                .oneshot(Request::get("/api/articles").body(Body::empty()).unwrap())
+                .clone()
+                .oneshot(
+                    Request::get("/api/articles?limit=0")
+                        .header(
+                            header::ACCEPT,
+                            "application/problem+json, application/json;q=0.5",
+                        )
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(
+                response.headers()[header::CONTENT_TYPE],
+                "application/problem+json"
+            );
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(
+                body,
+                json!({
+                    "type": "urn:datadoggo-v3-rss:problem:invalid_limit",
+                    "title": "Bad Request",
+                    "status": 400,
+                    "detail": "limitは1以上で指定してください",
+                    "instance": "/api/articles",
+                    "code": "invalid_limit",
+                })
+            );
+
+            let missing_path = format!("/api/articles/{}/links", Uuid::new_v4());
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::get(&missing_path)
+                        .header(header::ACCEPT, "application/problem+json")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
                 .await
                 .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: Value = serde_json::from_slice(&bytes)?;
+            assert_eq!(body["status"], json!(404));
+            assert_eq!(body["title"], json!("Not Found"));
+            assert_eq!(body["instance"], json!(missing_path));
 
-            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            let response = app
+                .oneshot(
+                    Request::get("/api/articles?limit=0")
+                        .header(header::ACCEPT, "application/json")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
             let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
             let body: Value = serde_json::from_slice(&bytes)?;
-            assert_eq!(body["code"].as_str(), Some("article_too_large"));
-            assert!(body["message"]
-                .as_str()
-                .unwrap_or_default()
-                .contains("記事ID"));
+            assert_eq!(
+                body,
+                json!({
+                    "code": "invalid_limit",
+                    "message": "limitは1以上で指定してください",
+                })
+            );
 
             Ok(())
         }
+
+        /// # 検証目的
+        /// Acceptヘッダのメディアタイプ一覧からproblem+jsonを大文字小文字・パラメータによらず見つけることを確認する。
+        #[test]
+        fn acceptからproblem_jsonを判定する() {
+            let headers = |value: &str| {
+                let mut headers = axum::http::HeaderMap::new();
+                headers.insert(header::ACCEPT, value.parse().unwrap());
+                headers
+            };
+            assert!(accepts_problem_json(&headers("application/problem+json")));
+            assert!(accepts_problem_json(&headers(
+                "text/html, Application/Problem+JSON; q=0.9"
+            )));
+            assert!(!accepts_problem_json(&headers("application/json")));
+            assert!(!accepts_problem_json(&axum::http::HeaderMap::new()));
+        }
     }
 }
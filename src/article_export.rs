@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use futures::stream::{self, Stream};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::articles::ArticleGroupFilter;
+use crate::compression::{decompress_content, ContentCompression};
+
+/// 1回のクエリで読み込む記事数（同時にメモリへ載せる本文はこの件数まで）
+const EXPORT_PAGE_SIZE: i64 = 100;
+
+/// tar.gzエクスポートの絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleExportFilter {
+    pub groups: ArticleGroupFilter,
+    /// この日時以降にqueueへ追加された記事
+    pub created_after: Option<DateTime<Utc>>,
+    /// この日時より前にqueueへ追加された記事
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// エクスポートする記事の本文（圧縮されたまま）
+#[derive(Debug, FromRow)]
+struct ExportedArticle {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    data: Vec<u8>,
+    compression: ContentCompression,
+}
+
+/// `after`（created_at, id）より後の記事本文を古い順にlimit件取得する
+async fn search_export_page(
+    pool: &PgPool,
+    filter: &ArticleExportFilter,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    limit: i64,
+) -> Result<Vec<ExportedArticle>> {
+    let articles = sqlx::query_as::<_, ExportedArticle>(
+        r#"
+        SELECT
+            q.id,
+            q.created_at,
+            ac.updated_at,
+            ac.data,
+            ac.compression
+        FROM rss.queue AS q
+        INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+        WHERE q.merged_into IS NULL
+        AND (
+            $2::timestamptz IS NULL
+            OR (q.created_at, q.id) > ($2, $3::uuid)
+        )
+        AND (
+            cardinality($4::text[]) = 0
+            OR q."group" = ANY($4)
+            OR q."group" LIKE ANY($5::text[])
+        )
+        AND ($6::timestamptz IS NULL OR q.created_at >= $6)
+        AND ($7::timestamptz IS NULL OR q.created_at < $7)
+        ORDER BY q.created_at ASC, q.id ASC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .bind(after.map(|(created_at, _)| created_at))
+    .bind(after.map(|(_, id)| id))
+    .bind(&filter.groups.groups)
+    .bind(filter.groups.subgroup_patterns())
+    .bind(filter.created_after)
+    .bind(filter.created_before)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(articles)
+}
+
+/// 展開した本文を`<id>.html`としてtarへ追加する
+fn append_article(
+    builder: &mut tar::Builder<GzEncoder<Vec<u8>>>,
+    article: &ExportedArticle,
+) -> Result<()> {
+    let html = decompress_content(&article.data, article.compression)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(html.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(article.updated_at.timestamp().max(0) as u64);
+    builder.append_data(&mut header, format!("{}.html", article.id), html.as_slice())?;
+    Ok(())
+}
+
+/// エクスポートの途中状態。記事を1ページずつ読み込み、1件追加するごとに圧縮済みのバイト列を取り出す
+struct ExportState {
+    pool: PgPool,
+    filter: ArticleExportFilter,
+    page_size: i64,
+    /// 終端まで書き出したらNone
+    builder: Option<tar::Builder<GzEncoder<Vec<u8>>>>,
+    pending: VecDeque<ExportedArticle>,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    exhausted: bool,
+}
+
+impl ExportState {
+    /// 次に送るtar.gzの断片を返す。すべて書き出したらNone
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            let Some(mut builder) = self.builder.take() else {
+                return Ok(None);
+            };
+
+            if let Some(article) = self.pending.pop_front() {
+                // 展開とgzip圧縮はCPUを使うため、builderごとブロッキング用のスレッドへ渡す
+                let (builder, chunk) = tokio::task::spawn_blocking(move || -> Result<_> {
+                    append_article(&mut builder, &article)?;
+                    let chunk = std::mem::take(builder.get_mut().get_mut());
+                    Ok((builder, chunk))
+                })
+                .await
+                .context("エクスポートタスクの実行に失敗")??;
+                self.builder = Some(builder);
+                // gzipが内部にバッファしている間は出力が空なので、次の記事へ進む
+                if !chunk.is_empty() {
+                    return Ok(Some(chunk));
+                }
+                continue;
+            }
+
+            if self.exhausted {
+                // tarの終端ブロックとgzipのトレーラを書き出して終える
+                let chunk = tokio::task::spawn_blocking(move || -> Result<_> {
+                    Ok(builder.into_inner()?.finish()?)
+                })
+                .await
+                .context("エクスポートタスクの実行に失敗")??;
+                return Ok(Some(chunk));
+            }
+
+            self.builder = Some(builder);
+
+            let page =
+                search_export_page(&self.pool, &self.filter, self.after, self.page_size).await?;
+            self.exhausted = (page.len() as i64) < self.page_size;
+            self.after = page.last().map(|article| (article.created_at, article.id));
+            self.pending.extend(page);
+        }
+    }
+}
+
+/// 条件に合う記事の本文を`<id>.html`（展開済み）として詰めたtar.gzを、古い順にストリームで生成する。
+///
+/// 記事はページ単位で読み込むため、全件をメモリに載せずに返せる。途中でDBエラーなどが起きた場合は
+/// その時点でErrを返して終わる（受け取り側には途中までのtar.gzが届く）。
+pub fn export_articles_tar_gz(
+    pool: PgPool,
+    filter: ArticleExportFilter,
+) -> impl Stream<Item = Result<Vec<u8>>> {
+    export_articles_tar_gz_with_page_size(pool, filter, EXPORT_PAGE_SIZE)
+}
+
+fn export_articles_tar_gz_with_page_size(
+    pool: PgPool,
+    filter: ArticleExportFilter,
+    page_size: i64,
+) -> impl Stream<Item = Result<Vec<u8>>> {
+    let state = ExportState {
+        pool,
+        filter,
+        page_size,
+        builder: Some(tar::Builder::new(GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ))),
+        pending: VecDeque::new(),
+        after: None,
+        exhausted: false,
+    };
+
+    stream::try_unfold(state, |mut state| async move {
+        Ok(state.next_chunk().await?.map(|chunk| (chunk, state)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod export_articles_tar_gz {
+        use std::io::Read;
+
+        use anyhow::Result;
+        use chrono::{Duration, Utc};
+        use flate2::read::GzDecoder;
+        use futures::TryStreamExt;
+        use uuid::Uuid;
+
+        use crate::article_export::{export_articles_tar_gz_with_page_size, ArticleExportFilter};
+        use crate::compression::ContentCompression;
+        use crate::fetch_content::compress_html;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// ページサイズを跨いで全記事が古い順に`<id>.html`として展開済みの本文で格納され、
+        /// 本文の無いエントリは含まれないことを確認する。
+        #[tokio::test]
+        async fn ページを跨いで全記事を格納する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            // created_atが同じ記事を含め、カーソルの同時刻比較も通す
+            let base = Utc::now() - Duration::hours(1);
+            let mut expected = Vec::new();
+            for (index, offset) in [0, 0, 1, 2, 3].into_iter().enumerate() {
+                let id = Uuid::new_v4();
+                let html = format!("<p>記事{}</p>", index);
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, created_at)
+                    VALUES ($1, $2, 'タイトル', '説明', $3)
+                    "#,
+                )
+                .bind(id)
+                .bind(format!("https://example.com/{}", index))
+                .bind(base + Duration::minutes(offset))
+                .execute(&pool)
+                .await?;
+                sqlx::query(
+                    "INSERT INTO rss.article_content (queue_id, data, compression) VALUES ($1, $2, $3)",
+                )
+                .bind(id)
+                .bind(compress_html(&html, ContentCompression::Zstd)?)
+                .bind(ContentCompression::Zstd)
+                .execute(&pool)
+                .await?;
+                expected.push((base + Duration::minutes(offset), id, html));
+            }
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, 'タイトル', '説明')",
+            )
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/no-content")
+            .execute(&pool)
+            .await?;
+            expected.sort_by_key(|(created_at, id, _)| (*created_at, *id));
+
+            let chunks: Vec<Vec<u8>> =
+                export_articles_tar_gz_with_page_size(pool, ArticleExportFilter::default(), 2)
+                    .try_collect()
+                    .await?;
+            let archive = chunks.concat();
+
+            let mut entries = Vec::new();
+            let mut tar = tar::Archive::new(GzDecoder::new(archive.as_slice()));
+            for entry in tar.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().into_owned();
+                let mut html = String::new();
+                entry.read_to_string(&mut html)?;
+                entries.push((name, html));
+            }
+
+            let expected: Vec<(String, String)> = expected
+                .into_iter()
+                .map(|(_, id, html)| (format!("{}.html", id), html))
+                .collect();
+            assert_eq!(entries, expected);
+
+            Ok(())
+        }
+    }
+}
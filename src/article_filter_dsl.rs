@@ -0,0 +1,138 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// `/api/articles`の`filter`パラメータ（`group:tech,after:2025-01-01,host:example.com`）を解釈した条件。
+///
+/// `,`区切りの`キー:値`を並べ、異なるキーの条件はAND、同じキーを複数書いた`group`・`host`は
+/// いずれかに一致すればよい（OR）として扱う。`after`・`before`はpub_dateと比較し、1回までしか書けない。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleFilterDsl {
+    /// いずれかのgroupに属する記事（`group:<name>`）
+    pub groups: Vec<String>,
+    /// リンクのホスト名がいずれかに一致する記事（`host:<name>`、大文字小文字は区別しない）
+    pub hosts: Vec<String>,
+    /// pub_dateがこの日時以降の記事（`after:<日付またはRFC3339>`）
+    pub pub_date_after: Option<DateTime<Utc>>,
+    /// pub_dateがこの日時より前の記事（`before:<日付またはRFC3339>`）
+    pub pub_date_before: Option<DateTime<Utc>>,
+}
+
+impl FromStr for ArticleFilterDsl {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut filter = ArticleFilterDsl::default();
+        for term in value.split(',').map(str::trim) {
+            if term.is_empty() {
+                continue;
+            }
+            let (key, value) = term
+                .split_once(':')
+                .ok_or_else(|| anyhow!("`キー:値`の形式で指定してください: {}", term))?;
+            let (key, value) = (key.trim(), value.trim());
+            if value.is_empty() {
+                bail!("{}の値が空です", key);
+            }
+            match key {
+                "group" => {
+                    if !filter.groups.iter().any(|group| group == value) {
+                        filter.groups.push(value.to_string());
+                    }
+                }
+                "host" => {
+                    let host = value.to_ascii_lowercase();
+                    if !filter.hosts.contains(&host) {
+                        filter.hosts.push(host);
+                    }
+                }
+                "after" => {
+                    if filter.pub_date_after.is_some() {
+                        bail!("afterは1回だけ指定できます");
+                    }
+                    filter.pub_date_after = Some(parse_datetime(key, value)?);
+                }
+                "before" => {
+                    if filter.pub_date_before.is_some() {
+                        bail!("beforeは1回だけ指定できます");
+                    }
+                    filter.pub_date_before = Some(parse_datetime(key, value)?);
+                }
+                _ => bail!(
+                    "未知のキーです（group / host / after / beforeのいずれか）: {}",
+                    key
+                ),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// `YYYY-MM-DD`（UTCの0時）またはRFC3339の日時を解釈する
+fn parse_datetime(key: &str, value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).expect("0時は常に有効").and_utc());
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|_| {
+            anyhow!(
+                "{}はYYYY-MM-DDまたはRFC3339形式の日時で指定してください: {}",
+                key,
+                value
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod parse {
+        use crate::article_filter_dsl::ArticleFilterDsl;
+        use crate::test_support::fixed_datetime;
+
+        /// # 検証目的
+        /// 各キーが対応する条件に変換され、同じキーのgroup・hostは重複を除いて並び順どおりに集まり、
+        /// 日付のみの指定はUTCの0時、RFC3339はUTCへ変換されることを確認する。
+        #[test]
+        fn キーごとの条件に変換する() {
+            let filter: ArticleFilterDsl =
+                "group:tech, host:Example.com,group:world,group:tech,after:2025-01-01,before:2025-02-01T09:00:00+09:00,"
+                    .parse()
+                    .expect("パースできる");
+
+            assert_eq!(
+                filter,
+                ArticleFilterDsl {
+                    groups: vec!["tech".to_string(), "world".to_string()],
+                    hosts: vec!["example.com".to_string()],
+                    pub_date_after: Some(fixed_datetime(2025, 1, 1, 0, 0, 0)),
+                    pub_date_before: Some(fixed_datetime(2025, 2, 1, 0, 0, 0)),
+                }
+            );
+            assert_eq!(
+                "".parse::<ArticleFilterDsl>().ok(),
+                Some(ArticleFilterDsl::default())
+            );
+        }
+
+        /// # 検証目的
+        /// 未知のキー・区切りの無い項目・空の値・解釈できない日付・afterの重複指定をエラーにすることを確認する。
+        #[test]
+        fn 不正な指定を拒否する() {
+            for value in [
+                "lang:ja",
+                "tech",
+                "group:",
+                "after:2025/01/01",
+                "after:2025-01-01,after:2025-02-01",
+            ] {
+                assert!(
+                    value.parse::<ArticleFilterDsl>().is_err(),
+                    "エラーにならなかった: {}",
+                    value
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use scraper::{Html, Selector};
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+static ANCHOR_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("a[href]").expect("リンク用セレクタが不正"));
+
+/// HTMLの`<a href>`を文書順に取り出し、`base`を基準に絶対URLへ解決する。
+///
+/// http(s)以外（`mailto:`・`javascript:`など）と解決できないhrefは除き、フラグメントだけが異なるリンクは1つにまとめる。
+/// `base`が解釈できない場合は絶対URLのhrefだけを返す。
+pub(crate) fn extract_article_links(html: &str, base: &str) -> Vec<String> {
+    let base = reqwest::Url::parse(base.trim()).ok();
+    let document = Html::parse_document(html);
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for element in document.select(&ANCHOR_SELECTOR) {
+        let Some(href) = element.value().attr("href").map(str::trim) else {
+            continue;
+        };
+        // ページ内リンクは記事の外へのリンクではないため数えない
+        if href.is_empty() || href.starts_with('#') {
+            continue;
+        }
+        let resolved = match &base {
+            Some(base) => base.join(href),
+            None => reqwest::Url::parse(href),
+        };
+        let Ok(mut url) = resolved else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            continue;
+        }
+        url.set_fragment(None);
+        let url = String::from(url);
+        if seen.insert(url.clone()) {
+            links.push(url);
+        }
+    }
+    links
+}
+
+/// 記事のリンク一覧を置き換える（前回取得時のリンクは削除する）
+pub(crate) async fn save_article_links(
+    tx: &mut Transaction<'_, Postgres>,
+    queue_id: Uuid,
+    links: &[String],
+) -> Result<()> {
+    sqlx::query("DELETE FROM rss.article_links WHERE queue_id = $1")
+        .bind(queue_id)
+        .execute(&mut **tx)
+        .await?;
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    let positions: Vec<i32> = (0..links.len() as i32).collect();
+    sqlx::query(
+        r#"
+        INSERT INTO rss.article_links (queue_id, position, href)
+        SELECT $1, position, href
+        FROM UNNEST($2::int[], $3::text[]) AS t(position, href)
+        "#,
+    )
+    .bind(queue_id)
+    .bind(&positions)
+    .bind(links)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// 記事のリンク一覧を本文中の出現順で返す。本文が保存されていない記事ならNoneを返す
+pub async fn search_article_links(pool: &PgPool, queue_id: Uuid) -> Result<Option<Vec<String>>> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM rss.article_content WHERE queue_id = $1)")
+            .bind(queue_id)
+            .fetch_one(pool)
+            .await?;
+    if !exists {
+        return Ok(None);
+    }
+
+    let links = sqlx::query_scalar(
+        r#"
+        SELECT href
+        FROM rss.article_links
+        WHERE queue_id = $1
+        ORDER BY position
+        "#,
+    )
+    .bind(queue_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(links))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod extract_article_links {
+        use crate::article_links::extract_article_links;
+
+        /// # 検証目的
+        /// 既知のHTMLから`<a href>`を文書順に取り出し、相対URLの解決・http(s)以外とページ内リンクの除外・重複の除去を行うことを確認する。
+        #[test]
+        fn リンクを抽出する() {
+            let html = r##"<html><body>
+<a href="https://other.example.org/a">外部</a>
+<a href="../b?x=1">相対</a>
+<a href="#section">ページ内</a>
+<a href="mailto:info@example.com">メール</a>
+<a href="javascript:void(0)">スクリプト</a>
+<a href="https://other.example.org/a#top">重複</a>
+<a>hrefなし</a>
+</body></html>"##;
+            assert_eq!(
+                extract_article_links(html, "https://example.com/news/1"),
+                vec![
+                    "https://other.example.org/a".to_string(),
+                    "https://example.com/b?x=1".to_string(),
+                ]
+            );
+        }
+
+        /// # 検証目的
+        /// 基準URLが解釈できない場合は絶対URLのリンクだけを返すことを確認する。
+        #[test]
+        fn 基準urlが無ければ絶対urlだけ返す() {
+            let html = r#"<a href="/relative">相対</a><a href="https://example.com/abs">絶対</a>"#;
+            assert_eq!(
+                extract_article_links(html, "not a url"),
+                vec!["https://example.com/abs".to_string()]
+            );
+        }
+    }
+}
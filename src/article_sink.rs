@@ -0,0 +1,173 @@
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use redis::aio::MultiplexedConnection;
+use serde::Serialize;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+/// Redis Streamの既定のキー（`REDIS_STREAM_KEY`）
+pub const DEFAULT_REDIS_STREAM_KEY: &str = "rss:articles";
+
+/// fetch-rssで新しく取り込んだ記事の流し先（`OUTPUT_SINK`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputSink {
+    /// queueテーブルへの保存だけ行う（既定）
+    #[default]
+    Queue,
+    /// queueへの保存に加え、新規記事をRedis Streamへpublishする
+    RedisStream,
+}
+
+impl std::str::FromStr for OutputSink {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "queue" => Ok(OutputSink::Queue),
+            "redis" => Ok(OutputSink::RedisStream),
+            other => Err(anyhow::anyhow!(
+                "無効な出力先: {} (有効な値: queue, redis)",
+                other
+            )),
+        }
+    }
+}
+
+/// sinkへpublishする記事（queueへINSERTされた行）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PublishedArticle {
+    pub id: Uuid,
+    pub link: String,
+    pub title: String,
+    pub pub_date: Option<DateTime<Utc>>,
+    pub description: String,
+    pub group: Option<String>,
+    pub language: Option<String>,
+}
+
+/// fetch-rssで新しく取り込んだ記事を外部のメッセージキューへ流す
+///
+/// 1フィード分の新規記事をまとめて受け取る。失敗してもqueueへの保存は取り消さない。
+pub trait ArticleSink: Send + Sync + fmt::Debug {
+    fn publish<'a>(&'a self, articles: &'a [PublishedArticle]) -> BoxFuture<'a, Result<()>>;
+}
+
+/// 記事1件を1エントリとしてRedis Streamへ`XADD`する
+///
+/// エントリのフィールドは`article`（`PublishedArticle`のJSON）の1つだけ。
+/// 接続は最初のpublish時に張り、以降は多重化した同じ接続を使い回す。
+pub struct RedisStreamSink {
+    client: redis::Client,
+    stream_key: String,
+    connection: OnceCell<MultiplexedConnection>,
+}
+
+impl fmt::Debug for RedisStreamSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 接続URLには認証情報が含まれうるため出力しない
+        f.debug_struct("RedisStreamSink")
+            .field("stream_key", &self.stream_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RedisStreamSink {
+    pub fn new(url: &str, stream_key: &str) -> Result<Self> {
+        let client = redis::Client::open(url).context("REDIS_URLを解釈できません")?;
+        Ok(Self {
+            client,
+            stream_key: stream_key.to_string(),
+            connection: OnceCell::new(),
+        })
+    }
+
+    async fn connection(&self) -> Result<MultiplexedConnection> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| self.client.get_multiplexed_async_connection())
+            .await
+            .context("Redisへ接続できません")?;
+        Ok(connection.clone())
+    }
+}
+
+impl ArticleSink for RedisStreamSink {
+    fn publish<'a>(&'a self, articles: &'a [PublishedArticle]) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if articles.is_empty() {
+                return Ok(());
+            }
+            let mut pipe = redis::pipe();
+            for article in articles {
+                pipe.cmd("XADD")
+                    .arg(&self.stream_key)
+                    .arg("*")
+                    .arg("article")
+                    .arg(serde_json::to_string(article)?)
+                    .ignore();
+            }
+            let mut connection = self.connection().await?;
+            let _: () = pipe
+                .query_async(&mut connection)
+                .await
+                .with_context(|| format!("Redis Streamへpublishできません: {}", self.stream_key))?;
+            Ok(())
+        })
+    }
+}
+
+/// `OUTPUT_SINK`に応じたsinkを組み立てる（`queue`ならNone）
+pub fn build_article_sink(
+    output_sink: OutputSink,
+    redis_url: Option<&str>,
+    stream_key: &str,
+) -> Result<Option<Arc<dyn ArticleSink>>> {
+    match output_sink {
+        OutputSink::Queue => Ok(None),
+        OutputSink::RedisStream => {
+            let Some(url) = redis_url else {
+                bail!("OUTPUT_SINK=redisの場合はREDIS_URLを設定してください");
+            };
+            Ok(Some(Arc::new(RedisStreamSink::new(url, stream_key)?)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod build_article_sink {
+        use crate::article_sink::{build_article_sink, OutputSink, DEFAULT_REDIS_STREAM_KEY};
+
+        /// # 検証目的
+        /// `queue`ではsinkを作らず、`redis`ではREDIS_URLが必須になり、接続せずにsinkを組み立てられることを確認する。
+        #[test]
+        fn 出力先に応じてsinkを組み立てる() {
+            assert!(
+                build_article_sink(OutputSink::Queue, None, DEFAULT_REDIS_STREAM_KEY)
+                    .unwrap()
+                    .is_none()
+            );
+            assert!(
+                build_article_sink(OutputSink::RedisStream, None, DEFAULT_REDIS_STREAM_KEY)
+                    .is_err()
+            );
+
+            let sink = build_article_sink(
+                OutputSink::RedisStream,
+                Some("redis://:password@localhost:6379/0"),
+                DEFAULT_REDIS_STREAM_KEY,
+            )
+            .unwrap()
+            .expect("redisならsinkを作る");
+            let debug = format!("{:?}", sink);
+            assert!(debug.contains(DEFAULT_REDIS_STREAM_KEY));
+            assert!(!debug.contains("password"));
+
+            assert!("nats".parse::<OutputSink>().is_err());
+        }
+    }
+}
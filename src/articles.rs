@@ -1,8 +1,12 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use sqlx::{FromRow, PgPool};
 use uuid::Uuid;
 
+use crate::compression::ContentCompression;
+use crate::fetch_content::FETCH_CONTENT_LOG_COMMAND;
+
 /// queueとarticle_contentを結合した記事データ
 #[allow(dead_code)] // 将来のAPI向けに用意しており現時点では内部から参照されない
 #[derive(Debug, Clone, FromRow)]
@@ -13,9 +17,27 @@ pub struct Article {
     pub link: String,
     pub title: String,
     pub pub_date: Option<DateTime<Utc>>,
+    /// フィードに書かれていたpub_dateのUTCからのオフセット（分）
+    pub pub_date_tz_offset: Option<i32>,
     pub description: String,
+    /// スクレイプ時のHTTPステータス（queueの`status_code`）
+    pub status_code: Option<i32>,
     pub data: Vec<u8>,
+    /// `data`の圧縮方式
+    pub compression: ContentCompression,
+    /// 保存時の圧縮品質（列追加前に保存した記事はNULL）
+    pub compression_quality: Option<i16>,
+    /// 要約APIで生成した本文の要約（未設定・失敗時はNULL）
+    pub summary: Option<String>,
+    /// 取得したHTMLの`og:title`（OGタグが無ければNULL）
+    pub og_title: Option<String>,
+    /// 取得したHTMLの`og:description`
+    pub og_description: Option<String>,
+    /// 取得したHTMLの`og:image`（絶対URLに解決済み）
+    pub og_image: Option<String>,
     pub group: Option<String>,
+    /// フィードのentryのcontent（queueの`content`。フィードに無ければNULL）
+    pub feed_content: Option<String>,
 }
 
 /// 最新の記事を取得する。limit件数分のみ返す。
@@ -30,11 +52,21 @@ pub async fn search_articles(pool: &PgPool, limit: i64) -> Result<Vec<Article>>
             q.link,
             q.title,
             q.pub_date,
+            q.pub_date_tz_offset,
             q.description,
+            q.status_code,
             ac.data,
-            q."group"
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
         FROM rss.queue AS q
         INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+        WHERE q.merged_into IS NULL
         ORDER BY q.created_at DESC
         LIMIT $1
         "#,
@@ -46,39 +78,229 @@ pub async fn search_articles(pool: &PgPool, limit: i64) -> Result<Vec<Article>>
     Ok(articles)
 }
 
+/// 記事一覧の並び順
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArticleSort {
+    /// 取り込み日時（created_at）の新しい順（既定）
+    #[default]
+    CreatedDesc,
+    /// 発行日時（pub_date、未提供ならcreated_at）の新しい順
+    PubDateDesc,
+}
+
+impl ArticleSort {
+    /// ソートキーとなるSQL式
+    fn sort_key_expr(self) -> &'static str {
+        match self {
+            ArticleSort::CreatedDesc => "q.created_at",
+            ArticleSort::PubDateDesc => "COALESCE(q.pub_date, q.created_at)",
+        }
+    }
+}
+
+impl std::str::FromStr for ArticleSort {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "created_desc" => Ok(ArticleSort::CreatedDesc),
+            "pubdate_desc" => Ok(ArticleSort::PubDateDesc),
+            other => Err(anyhow::anyhow!(
+                "無効なsort指定: {} (有効な値: created_desc, pubdate_desc)",
+                other
+            )),
+        }
+    }
+}
+
+/// カーソルページングで比較する列
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArticleCursorField {
+    /// `sort`に従う（created_atまたはpub_date、既定）
+    #[default]
+    CreatedAt,
+    /// 更新日時（updated_at）の新しい順。`sort`より優先する
+    UpdatedAt,
+}
+
+impl ArticleCursorField {
+    /// `sort`と組み合わせたソートキーのSQL式
+    fn sort_key_expr(self, sort: ArticleSort) -> &'static str {
+        match self {
+            ArticleCursorField::CreatedAt => sort.sort_key_expr(),
+            ArticleCursorField::UpdatedAt => "q.updated_at",
+        }
+    }
+}
+
+impl std::str::FromStr for ArticleCursorField {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "created_at" => Ok(ArticleCursorField::CreatedAt),
+            "updated_at" => Ok(ArticleCursorField::UpdatedAt),
+            other => Err(anyhow::anyhow!(
+                "無効なcursor_field指定: {} (有効な値: created_at, updated_at)",
+                other
+            )),
+        }
+    }
+}
+
 /// ページネーション用カーソル
 #[derive(Debug, Clone)]
 pub struct ArticleCursor {
     pub id: Uuid,
-    pub created_at: DateTime<Utc>,
+    /// 並び順に応じたソートキーの値（created_at・pub_date・updated_atのいずれか）
+    pub sort_key: DateTime<Utc>,
+    /// `sort_key`をどの列から取ったか
+    pub field: ArticleCursorField,
 }
 
-/// 指定したIDのカーソル情報を取得する
-pub async fn find_article_cursor(pool: &PgPool, id: Uuid) -> Result<Option<ArticleCursor>> {
-    let row = sqlx::query_as::<_, (DateTime<Utc>,)>(
+/// 指定したIDのカーソル情報を並び順・比較列に応じて取得する
+pub async fn find_article_cursor(
+    pool: &PgPool,
+    id: Uuid,
+    sort: ArticleSort,
+    field: ArticleCursorField,
+) -> Result<Option<ArticleCursor>> {
+    let query = format!(
         r#"
-        SELECT created_at
-        FROM rss.queue
-        WHERE id = $1
+        SELECT {sort_key}
+        FROM rss.queue AS q
+        WHERE q.id = $1
         "#,
-    )
-    .bind(id)
-    .fetch_optional(pool)
-    .await?;
+        sort_key = field.sort_key_expr(sort)
+    );
+    let row = sqlx::query_as::<_, (DateTime<Utc>,)>(&query)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
 
     Ok(row.map(|record| ArticleCursor {
         id,
-        created_at: record.0,
+        sort_key: record.0,
+        field,
     }))
 }
 
+/// 記事一覧のgroup絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleGroupFilter {
+    /// いずれかのgroupに属する記事に絞る（空なら絞り込まない）
+    pub groups: Vec<String>,
+    /// groupを`/`区切りの階層とみなし、配下のgroup（`world`に対する`world/asia/japan`）も含める
+    pub include_subgroups: bool,
+}
+
+impl ArticleGroupFilter {
+    /// 配下のgroupに一致させるLIKEパターン（`world/%`）。`include_subgroups`が無効なら空
+    pub(crate) fn subgroup_patterns(&self) -> Vec<String> {
+        if !self.include_subgroups {
+            return Vec::new();
+        }
+        self.groups
+            .iter()
+            .map(|group| format!("{}/%", escape_like(group.trim_end_matches('/'))))
+            .collect()
+    }
+}
+
+/// 記事一覧の絞り込み条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleFilter {
+    pub groups: ArticleGroupFilter,
+    /// `Some(true)`ならpub_dateのある記事だけ、`Some(false)`ならpub_dateがNULLの記事だけに絞る
+    pub has_pubdate: Option<bool>,
+    /// title・descriptionの全文検索（`rss.queue.search_vector`）
+    pub text_search: Option<ArticleTextSearch>,
+    /// pub_dateがこの日時以降の記事だけに絞る（pub_dateがNULLの記事は含まない）
+    pub pub_date_after: Option<DateTime<Utc>>,
+    /// pub_dateがこの日時より前の記事だけに絞る（pub_dateがNULLの記事は含まない）
+    pub pub_date_before: Option<DateTime<Utc>>,
+    /// リンクのホスト名（小文字）がいずれかに一致する記事に絞る（空なら絞り込まない）
+    pub hosts: Vec<String>,
+}
+
+/// 記事一覧の全文検索条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArticleTextSearch {
+    /// `websearch_to_tsquery`の構文（`"完全一致" -除外 OR`）で解釈する検索語
+    pub query: String,
+    /// `ts_rank`の高い順に並べる（`sort`より優先し、カーソルとは併用できない）
+    pub order_by_rank: bool,
+}
+
+/// LIKEのワイルドカード（`%`・`_`）とエスケープ文字をリテラルとして扱えるようエスケープする
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// ページングの方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageDirection {
+    /// カーソルより古い記事（次ページ）
+    #[default]
+    Older,
+    /// カーソルより新しい記事（前ページ）
+    Newer,
+}
+
 /// ページネーション条件に従い記事を検索する。limitに+αした件数を取得し、呼び出し側で件数調整する想定。
+///
+/// 結果は方向によらず`sort`の新しい順で返す。`Newer`はカーソルに近い記事から取るため昇順で取得して反転する
+/// （そのため+α分の余剰はリストの先頭側に来る）。`filter.groups`の指定があれば、いずれかのgroup（`include_subgroups`なら配下も）に属する記事に絞り、
+/// `filter.has_pubdate`の指定があればpub_dateの有無で絞る。`filter.pub_date_after`/`pub_date_before`でpub_dateの範囲、
+/// `filter.hosts`でリンクのホスト名（ユーザー情報・ポートを除いて小文字化したもの）を絞り込む。
+/// `cursor_field`が`UpdatedAt`なら`sort`によらずupdated_atで並べて比較する（カーソルも同じ列で取得しておくこと）。
+/// `filter.text_search`の指定があれば`search_vector`に一致する記事に絞り、`order_by_rank`なら関連度の高い順
+/// （同点は`sort`の新しい順）に先頭から返す。
 pub async fn search_articles_window(
     pool: &PgPool,
     limit: i64,
     cursor: Option<&ArticleCursor>,
+    direction: PageDirection,
+    sort: ArticleSort,
+    cursor_field: ArticleCursorField,
+    filter: &ArticleFilter,
 ) -> Result<Vec<Article>> {
-    let articles = sqlx::query_as::<_, Article>(
+    if let Some(cursor) = cursor {
+        anyhow::ensure!(
+            cursor.field == cursor_field,
+            "カーソルの比較列({:?})とcursor_field({:?})が一致しません",
+            cursor.field,
+            cursor_field
+        );
+    }
+    let order_by_rank = filter
+        .text_search
+        .as_ref()
+        .is_some_and(|search| search.order_by_rank);
+    anyhow::ensure!(
+        !order_by_rank || (cursor.is_none() && direction == PageDirection::Older),
+        "関連度順の検索はカーソルと併用できません"
+    );
+    let (comparison, order) = match direction {
+        PageDirection::Older => ("<", "DESC"),
+        PageDirection::Newer => (">", "ASC"),
+    };
+    let sort_key = cursor_field.sort_key_expr(sort);
+    let order_by = if order_by_rank {
+        format!(
+            "ts_rank(q.search_vector, websearch_to_tsquery('simple', $7)) DESC, {sort_key} DESC, q.id DESC"
+        )
+    } else {
+        format!("{sort_key} {order}, q.id {order}")
+    };
+    let query = format!(
         r#"
         SELECT
             q.id,
@@ -87,29 +309,507 @@ pub async fn search_articles_window(
             q.link,
             q.title,
             q.pub_date,
+            q.pub_date_tz_offset,
             q.description,
+            q.status_code,
             ac.data,
-            q."group"
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
         FROM rss.queue AS q
         INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
-        WHERE (
+        WHERE q.merged_into IS NULL
+        AND (
             $2::timestamptz IS NULL
-            OR q.created_at < $2
-            OR (q.created_at = $2 AND q.id < $3)
+            OR {sort_key} {comparison} $2
+            OR ({sort_key} = $2 AND q.id {comparison} $3)
         )
-        ORDER BY q.created_at DESC, q.id DESC
+        AND (
+            cardinality($4::text[]) = 0
+            OR q."group" = ANY($4)
+            OR q."group" LIKE ANY($5::text[])
+        )
+        AND ($6::boolean IS NULL OR (q.pub_date IS NOT NULL) = $6)
+        AND ($7::text IS NULL OR q.search_vector @@ websearch_to_tsquery('simple', $7))
+        AND ($8::timestamptz IS NULL OR q.pub_date >= $8)
+        AND ($9::timestamptz IS NULL OR q.pub_date < $9)
+        AND (
+            cardinality($10::text[]) = 0
+            OR lower(substring(q.link FROM '^[^:/?#]+://(?:[^/?#@]*@)?([^/?#:]+)')) = ANY($10)
+        )
+        ORDER BY {order_by}
         LIMIT $1
         "#,
+    );
+
+    let mut articles = sqlx::query_as::<_, Article>(&query)
+        .bind(limit)
+        .bind(cursor.map(|c| c.sort_key))
+        .bind(cursor.map(|c| c.id))
+        .bind(&filter.groups.groups)
+        .bind(filter.groups.subgroup_patterns())
+        .bind(filter.has_pubdate)
+        .bind(
+            filter
+                .text_search
+                .as_ref()
+                .map(|search| search.query.as_str()),
+        )
+        .bind(filter.pub_date_after)
+        .bind(filter.pub_date_before)
+        .bind(&filter.hosts)
+        .fetch_all(pool)
+        .await?;
+
+    if direction == PageDirection::Newer {
+        articles.reverse();
+    }
+    Ok(articles)
+}
+
+/// 指定したIDの記事を取得する（別の記事へマージ済みの記事は含まない）
+pub async fn find_article_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Article>> {
+    let article = sqlx::query_as::<_, Article>(
+        r#"
+        SELECT
+            q.id,
+            q.created_at,
+            q.updated_at,
+            q.link,
+            q.title,
+            q.pub_date,
+            q.pub_date_tz_offset,
+            q.description,
+            q.status_code,
+            ac.data,
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
+        FROM rss.queue AS q
+        INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+        WHERE q.id = $1 AND q.merged_into IS NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(article)
+}
+
+/// 指定したIDの記事をまとめて取得する（順不同。本文の無い・存在しない・マージ済みのIDは含まない）
+pub async fn find_articles_by_ids(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<Article>> {
+    let articles = sqlx::query_as::<_, Article>(
+        r#"
+        SELECT
+            q.id,
+            q.created_at,
+            q.updated_at,
+            q.link,
+            q.title,
+            q.pub_date,
+            q.pub_date_tz_offset,
+            q.description,
+            q.status_code,
+            ac.data,
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
+        FROM rss.queue AS q
+        INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+        WHERE q.id = ANY($1) AND q.merged_into IS NULL
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(articles)
+}
+
+/// 比較用にlinkを正規化する。前後の空白・フラグメント・末尾スラッシュを取り除く。
+pub fn normalize_link(link: &str) -> String {
+    let trimmed = link.trim();
+    let without_fragment = trimmed.split('#').next().unwrap_or(trimmed);
+    without_fragment.trim_end_matches('/').to_string()
+}
+
+/// 正規化後のlinkが一致する記事を1件取得する。表記揺れで複数該当した場合は最新の記事を返す。
+pub async fn find_article_by_link(pool: &PgPool, link: &str) -> Result<Option<Article>> {
+    let article = sqlx::query_as::<_, Article>(
+        r#"
+        SELECT
+            q.id,
+            q.created_at,
+            q.updated_at,
+            q.link,
+            q.title,
+            q.pub_date,
+            q.pub_date_tz_offset,
+            q.description,
+            q.status_code,
+            ac.data,
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
+        FROM rss.queue AS q
+        INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+        WHERE rtrim(split_part(btrim(q.link), '#', 1), '/') = $1
+          AND q.merged_into IS NULL
+        ORDER BY q.created_at DESC, q.id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(normalize_link(link))
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(article)
+}
+
+/// 未処理（未ack）かつ未クレームの記事を古い順に最大`limit`件クレームし、`consumer`の担当として返す。
+///
+/// `FOR UPDATE SKIP LOCKED`で行を確保するため、複数のコンシューマが同時に呼んでも同じ記事は返らない。
+/// クレームから`lease_secs`秒経ってもackされない記事は、コンシューマが異常終了したとみなして再度クレームできる。
+pub async fn claim_articles(
+    pool: &PgPool,
+    limit: i64,
+    consumer: &str,
+    lease_secs: i64,
+) -> Result<Vec<Article>> {
+    let mut articles = sqlx::query_as::<_, Article>(
+        r#"
+        WITH target AS (
+            SELECT q.id
+            FROM rss.queue AS q
+            INNER JOIN rss.article_content AS ac ON ac.queue_id = q.id
+            WHERE q.acked_at IS NULL
+              AND q.merged_into IS NULL
+              AND (q.claimed_at IS NULL OR q.claimed_at < NOW() - make_interval(secs => $3))
+            ORDER BY q.created_at ASC, q.id ASC
+            LIMIT $1
+            FOR UPDATE OF q SKIP LOCKED
+        )
+        UPDATE rss.queue AS q
+        SET claimed_at = NOW(), claimed_by = $2
+        FROM target, rss.article_content AS ac
+        WHERE q.id = target.id AND ac.queue_id = q.id
+        RETURNING
+            q.id,
+            q.created_at,
+            q.updated_at,
+            q.link,
+            q.title,
+            q.pub_date,
+            q.pub_date_tz_offset,
+            q.description,
+            q.status_code,
+            ac.data,
+            ac.compression,
+            ac.compression_quality,
+            ac.summary,
+            ac.og_title,
+            ac.og_description,
+            ac.og_image,
+            q."group",
+            q.content AS feed_content
+        "#,
     )
     .bind(limit)
-    .bind(cursor.map(|c| c.created_at))
-    .bind(cursor.map(|c| c.id))
+    .bind(consumer)
+    .bind(lease_secs as f64)
     .fetch_all(pool)
     .await?;
 
+    articles.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
     Ok(articles)
 }
 
+/// `consumer`がクレーム中の記事を処理済み（ack）にしてクレームを解除し、ackした件数を返す。
+/// 他のコンシューマのクレームや処理済みの記事は対象外。
+pub async fn ack_articles(pool: &PgPool, consumer: &str, ids: &[Uuid]) -> Result<u64> {
+    let result = sqlx::query(
+        r#"
+        UPDATE rss.queue
+        SET acked_at = NOW(), claimed_at = NULL
+        WHERE id = ANY($1) AND claimed_by = $2 AND claimed_at IS NOT NULL AND acked_at IS NULL
+        "#,
+    )
+    .bind(ids)
+    .bind(consumer)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// `merge_articles`でマージした結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArticleMerge {
+    pub keep: Uuid,
+    /// 論理削除した記事（リクエストの重複を除いた順）
+    pub merged: Vec<Uuid>,
+    /// keepに本文が無かったため本文を移した記事（移さなかった場合はNone）
+    pub content_moved_from: Option<Uuid>,
+}
+
+/// `merge_articles`の結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeArticlesOutcome {
+    Merged(ArticleMerge),
+    /// 存在しないID（何も変更していない）
+    NotFound(Vec<Uuid>),
+    /// 既に他の記事へマージ済みのID（何も変更していない）
+    AlreadyMerged(Vec<Uuid>),
+}
+
+/// 重複した記事（`merge`）を`keep`へ統合する。`merge`側は`merged_into`にkeepを記録して論理削除し、
+/// 記事一覧・link検索・クレーム・エクスポート・fetch-contentの対象から外す。
+///
+/// keepに本文（article_content）が無く`merge`側にあれば、最も新しく更新された本文をstatus_codeごとkeepへ移す。
+/// 本文に対応する過去の版・WARC・抽出リンクも同じ記事からkeepへ移す。
+/// keepに本文があればkeepの本文を優先し、`merge`側の本文はそのまま残す。
+/// 全IDの行をロックしてから検証・更新するため、一部だけマージされた状態にはならない。
+pub async fn merge_articles(
+    pool: &PgPool,
+    keep: Uuid,
+    merge: &[Uuid],
+) -> Result<MergeArticlesOutcome> {
+    let mut merge_ids: Vec<Uuid> = Vec::with_capacity(merge.len());
+    for id in merge {
+        if !merge_ids.contains(id) {
+            merge_ids.push(*id);
+        }
+    }
+    anyhow::ensure!(!merge_ids.is_empty(), "mergeが空です");
+    anyhow::ensure!(
+        !merge_ids.contains(&keep),
+        "keepをmergeに含めることはできません"
+    );
+
+    let all_ids: Vec<Uuid> = std::iter::once(keep)
+        .chain(merge_ids.iter().copied())
+        .collect();
+    let mut tx = pool.begin().await?;
+
+    let rows: Vec<(Uuid, bool)> = sqlx::query_as(
+        r#"
+        SELECT id, merged_into IS NOT NULL
+        FROM rss.queue
+        WHERE id = ANY($1)
+        ORDER BY id
+        FOR UPDATE
+        "#,
+    )
+    .bind(&all_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let not_found: Vec<Uuid> = all_ids
+        .iter()
+        .filter(|id| !rows.iter().any(|(row_id, _)| row_id == *id))
+        .copied()
+        .collect();
+    if !not_found.is_empty() {
+        return Ok(MergeArticlesOutcome::NotFound(not_found));
+    }
+    let already_merged: Vec<Uuid> = all_ids
+        .iter()
+        .filter(|id| rows.iter().any(|(row_id, merged)| row_id == *id && *merged))
+        .copied()
+        .collect();
+    if !already_merged.is_empty() {
+        return Ok(MergeArticlesOutcome::AlreadyMerged(already_merged));
+    }
+
+    let keep_has_content: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM rss.article_content WHERE queue_id = $1)")
+            .bind(keep)
+            .fetch_one(&mut *tx)
+            .await?;
+    let content_moved_from = if keep_has_content {
+        None
+    } else {
+        let source: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT queue_id
+            FROM rss.article_content
+            WHERE queue_id = ANY($1)
+            ORDER BY updated_at DESC, queue_id
+            LIMIT 1
+            "#,
+        )
+        .bind(&merge_ids)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if let Some(source) = source {
+            // keepに残る版・WARC・リンクは本文と対応しないため、移す本文のものに置き換える
+            for table in [
+                "rss.article_content",
+                "rss.article_content_version",
+                "rss.article_warc",
+                "rss.article_links",
+            ] {
+                sqlx::query(&format!("DELETE FROM {} WHERE queue_id = $1", table))
+                    .bind(keep)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(&format!(
+                    "UPDATE {} SET queue_id = $1 WHERE queue_id = $2",
+                    table
+                ))
+                .bind(keep)
+                .bind(source)
+                .execute(&mut *tx)
+                .await?;
+            }
+            sqlx::query(
+                r#"
+                UPDATE rss.queue AS keep
+                SET status_code = source.status_code
+                FROM rss.queue AS source
+                WHERE keep.id = $1 AND source.id = $2
+                "#,
+            )
+            .bind(keep)
+            .bind(source)
+            .execute(&mut *tx)
+            .await?;
+        }
+        source
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE rss.queue
+        SET merged_into = $1, merged_at = NOW()
+        WHERE id = ANY($2)
+        "#,
+    )
+    .bind(keep)
+    .bind(&merge_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(MergeArticlesOutcome::Merged(ArticleMerge {
+        keep,
+        merged: merge_ids,
+        content_moved_from,
+    }))
+}
+
+/// 成功率を集計する時間バケットの粒度（`date_trunc`の単位）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsBucket {
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+impl MetricsBucket {
+    fn date_trunc_unit(self) -> &'static str {
+        match self {
+            MetricsBucket::Minute => "minute",
+            MetricsBucket::Hour => "hour",
+            MetricsBucket::Day => "day",
+            MetricsBucket::Week => "week",
+        }
+    }
+}
+
+/// 時間バケットごとの取得成功率
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SuccessRatePoint {
+    pub bucket_start: DateTime<Utc>,
+    pub total_processed: i64,
+    pub saved: i64,
+    /// saved / total_processed（処理件数0のバケットはnull）
+    pub success_rate: Option<f64>,
+}
+
+/// fetch_logのfetch-content実行結果を時間バケットごとに集計し、古い順に返す。記録の無いバケットは含めない。
+///
+/// `reset_success_rate`でリセットした時刻以前の記録は集計しない。
+pub async fn search_success_rate(
+    pool: &PgPool,
+    bucket: MetricsBucket,
+    since: DateTime<Utc>,
+) -> Result<Vec<SuccessRatePoint>> {
+    let rows = sqlx::query_as::<_, (DateTime<Utc>, i64, i64)>(
+        r#"
+        SELECT
+            date_trunc($1, created_at, 'UTC') AS bucket_start,
+            SUM(total_processed)::BIGINT AS total_processed,
+            SUM(saved)::BIGINT AS saved
+        FROM rss.fetch_log l
+        WHERE l.command = $2
+          AND l.created_at >= $3
+          AND NOT EXISTS (
+              SELECT 1 FROM rss.metrics_reset r
+              WHERE r.command = l.command AND l.created_at <= r.reset_at
+          )
+        GROUP BY bucket_start
+        ORDER BY bucket_start
+        "#,
+    )
+    .bind(bucket.date_trunc_unit())
+    .bind(FETCH_CONTENT_LOG_COMMAND)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(bucket_start, total_processed, saved)| SuccessRatePoint {
+            bucket_start,
+            total_processed,
+            saved,
+            success_rate: (total_processed > 0).then(|| saved as f64 / total_processed as f64),
+        })
+        .collect())
+}
+
+/// 成功率のリセット時刻を記録し、その時刻を返す
+///
+/// fetch_log自体は実行履歴（`run_id`）として残し、リセット以前の記録を集計から外すだけにする。
+pub async fn reset_success_rate(pool: &PgPool) -> Result<DateTime<Utc>> {
+    let reset_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+        r#"
+        INSERT INTO rss.metrics_reset (command, reset_at)
+        VALUES ($1, NOW())
+        ON CONFLICT (command) DO UPDATE SET reset_at = EXCLUDED.reset_at
+        RETURNING reset_at
+        "#,
+    )
+    .bind(FETCH_CONTENT_LOG_COMMAND)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(reset_at)
+}
+
 #[cfg(test)]
 mod tests {
     pub mod search_articles {
@@ -209,4 +909,316 @@ mod tests {
             Ok(())
         }
     }
+
+    pub mod find_article_by_link {
+        use anyhow::Result;
+        use chrono::{Duration, Utc};
+        use uuid::Uuid;
+
+        use crate::articles::{find_article_by_link, normalize_link};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool, set_queue_timestamp};
+
+        /// # 検証目的
+        /// 前後の空白・フラグメント・末尾スラッシュが正規化で除去されることを確認する。
+        #[test]
+        fn linkを正規化できる() {
+            assert_eq!(
+                normalize_link("  https://example.com/a/#top "),
+                "https://example.com/a"
+            );
+            assert_eq!(
+                normalize_link("https://example.com/a"),
+                "https://example.com/a"
+            );
+        }
+
+        /// # 検証目的
+        /// 表記揺れのあるlinkが複数存在する場合に最新の記事が返ることを確認する。
+        #[tokio::test]
+        async fn 表記揺れがあれば最新の記事を返す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let older_id = Uuid::new_v4();
+            let newer_id = Uuid::new_v4();
+
+            for (id, link) in [
+                (older_id, "https://example.com/dup"),
+                (newer_id, "https://example.com/dup/"),
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(id)
+                .bind(link)
+                .bind("重複記事")
+                .bind("本文")
+                .execute(&pool)
+                .await?;
+
+                sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                    .bind(id)
+                    .bind(b"data".to_vec())
+                    .execute(&pool)
+                    .await?;
+            }
+
+            set_queue_timestamp(&pool, older_id, Utc::now() - Duration::hours(1)).await?;
+
+            let found = find_article_by_link(&pool, "https://example.com/dup#section").await?;
+            assert_eq!(found.map(|a| a.id), Some(newer_id));
+
+            let missing = find_article_by_link(&pool, "https://example.com/none").await?;
+            assert!(missing.is_none());
+
+            Ok(())
+        }
+    }
+
+    pub mod merge_articles {
+        use anyhow::Result;
+        use uuid::Uuid;
+
+        use crate::articles::{merge_articles, MergeArticlesOutcome};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        async fn count_rows(pool: &sqlx::PgPool, table: &str, id: Uuid) -> Result<i64> {
+            Ok(sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM {} WHERE queue_id = $1",
+                table
+            ))
+            .bind(id)
+            .fetch_one(pool)
+            .await?)
+        }
+
+        /// # 検証目的
+        /// 本文の無いkeepへ本文を移すとき、過去の版・WARC・抽出リンクも同じ記事からkeepへ移り、
+        /// keepを削除するとマージ済みの重複も一緒に削除されて一覧へ戻らないことを確認する。
+        #[tokio::test]
+        async fn 本文と一緒に版とwarcとリンクを移す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let keep = Uuid::new_v4();
+            let source = Uuid::new_v4();
+            for (id, link) in [
+                (keep, "https://example.com/keep"),
+                (source, "https://example.com/source"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, 'タイトル', '説明')",
+                )
+                .bind(id)
+                .bind(link)
+                .execute(&pool)
+                .await?;
+            }
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(source)
+                .bind(b"body".as_slice())
+                .execute(&pool)
+                .await?;
+            sqlx::query(
+                "INSERT INTO rss.article_content_version (queue_id, version, encoding, data) VALUES ($1, 1, 'full', $2)",
+            )
+            .bind(source)
+            .bind(b"old body".as_slice())
+            .execute(&pool)
+            .await?;
+            sqlx::query("INSERT INTO rss.article_warc (queue_id, data) VALUES ($1, $2)")
+                .bind(source)
+                .bind(b"warc".as_slice())
+                .execute(&pool)
+                .await?;
+            sqlx::query(
+                "INSERT INTO rss.article_links (queue_id, position, href) VALUES ($1, 0, 'https://example.com/linked')",
+            )
+            .bind(source)
+            .execute(&pool)
+            .await?;
+
+            let outcome = merge_articles(&pool, keep, &[source]).await?;
+            let MergeArticlesOutcome::Merged(merge) = outcome else {
+                panic!("マージできる想定: {:?}", outcome);
+            };
+            assert_eq!(merge.content_moved_from, Some(source));
+
+            for table in [
+                "rss.article_content",
+                "rss.article_content_version",
+                "rss.article_warc",
+                "rss.article_links",
+            ] {
+                assert_eq!(count_rows(&pool, table, keep).await?, 1, "{}", table);
+                assert_eq!(count_rows(&pool, table, source).await?, 0, "{}", table);
+            }
+
+            sqlx::query("DELETE FROM rss.queue WHERE id = $1")
+                .bind(keep)
+                .execute(&pool)
+                .await?;
+            let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.queue")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!(remaining, 0, "マージ済みの重複が一覧へ戻った");
+
+            Ok(())
+        }
+    }
+
+    pub mod search_success_rate {
+        use anyhow::Result;
+        use chrono::{DateTime, Utc};
+        use sqlx::PgPool;
+        use uuid::Uuid;
+
+        use crate::articles::{search_success_rate, MetricsBucket};
+        use crate::test_support::{clear_rss_tables, fixed_datetime, prepare_test_pool};
+
+        async fn insert_log(
+            pool: &PgPool,
+            command: &str,
+            created_at: DateTime<Utc>,
+            total_processed: i32,
+            saved: i32,
+        ) -> Result<()> {
+            sqlx::query(
+                r#"
+                INSERT INTO rss.fetch_log (id, created_at, command, total_processed, saved, status_only, errors)
+                VALUES ($1, $2, $3, $4, $5, $6, 0)
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(created_at)
+            .bind(command)
+            .bind(total_processed)
+            .bind(saved)
+            .bind(total_processed - saved)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 同一バケット内の実行は合算され、バケットごとの成功率が算出されることを確認する。
+        /// 期間外・他コマンドの記録は含めず、処理件数0のバケットは成功率をnullにする。
+        #[tokio::test]
+        async fn バケットごとの成功率を集計する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            insert_log(
+                &pool,
+                "fetch_content",
+                fixed_datetime(2026, 10, 15, 8, 59, 0),
+                10,
+                10,
+            )
+            .await?;
+            insert_log(
+                &pool,
+                "fetch_content",
+                fixed_datetime(2026, 10, 15, 10, 5, 0),
+                4,
+                3,
+            )
+            .await?;
+            insert_log(
+                &pool,
+                "fetch_content",
+                fixed_datetime(2026, 10, 15, 10, 45, 0),
+                6,
+                2,
+            )
+            .await?;
+            insert_log(
+                &pool,
+                "fetch_content",
+                fixed_datetime(2026, 10, 15, 11, 30, 0),
+                0,
+                0,
+            )
+            .await?;
+            insert_log(
+                &pool,
+                "fetch_rss",
+                fixed_datetime(2026, 10, 15, 10, 10, 0),
+                100,
+                0,
+            )
+            .await?;
+
+            let points = search_success_rate(
+                &pool,
+                MetricsBucket::Hour,
+                fixed_datetime(2026, 10, 15, 9, 0, 0),
+            )
+            .await?;
+
+            assert_eq!(points.len(), 2);
+            assert_eq!(
+                points[0].bucket_start,
+                fixed_datetime(2026, 10, 15, 10, 0, 0)
+            );
+            assert_eq!(points[0].total_processed, 10);
+            assert_eq!(points[0].saved, 5);
+            assert_eq!(points[0].success_rate, Some(0.5));
+            assert_eq!(
+                points[1].bucket_start,
+                fixed_datetime(2026, 10, 15, 11, 0, 0)
+            );
+            assert_eq!(points[1].success_rate, None);
+
+            let daily = search_success_rate(
+                &pool,
+                MetricsBucket::Day,
+                fixed_datetime(2026, 10, 15, 0, 0, 0),
+            )
+            .await?;
+            assert_eq!(daily.len(), 1);
+            assert_eq!(daily[0].total_processed, 20);
+            assert_eq!(daily[0].saved, 15);
+            assert_eq!(daily[0].success_rate, Some(0.75));
+
+            Ok(())
+        }
+    }
+
+    pub mod article_group_filter {
+        use crate::articles::ArticleGroupFilter;
+
+        /// # 検証目的
+        /// 配下のgroupを含める場合のみLIKEパターンを作り、ワイルドカード文字と末尾の`/`を扱えることを確認する。
+        #[test]
+        fn 配下groupのパターンを作る() {
+            let filter = ArticleGroupFilter {
+                groups: vec!["world/".to_string(), "100%_news".to_string()],
+                include_subgroups: true,
+            };
+            assert_eq!(
+                filter.subgroup_patterns(),
+                ["world/%".to_string(), "100\\%\\_news/%".to_string()]
+            );
+
+            let filter = ArticleGroupFilter {
+                include_subgroups: false,
+                ..filter
+            };
+            assert!(filter.subgroup_patterns().is_empty());
+        }
+    }
 }
@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use futures::{stream, StreamExt};
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::proxy::apply_proxy;
+use crate::rate_limit::RateLimiter;
+
+/// リンク確認1件あたりのタイムアウト秒数
+const CHECK_LINKS_TIMEOUT_SECS: u64 = 10;
+/// 同時に確認するリンク数の既定値
+pub(crate) const DEFAULT_CHECK_LINKS_CONCURRENCY: usize = 8;
+/// リクエストを送り出す最小間隔の既定値（全体で1秒あたり最大5件）
+pub(crate) const DEFAULT_CHECK_LINKS_INTERVAL_MS: u64 = 200;
+/// リンク切れとして記録するステータス
+const BROKEN_LINK_STATUS_CODES: [u16; 2] = [404, 410];
+
+/// check-linksの挙動を調整する設定
+#[derive(Debug, Clone)]
+pub struct CheckLinksOptions {
+    /// 同時に確認するリンク数
+    pub concurrency: usize,
+    /// リクエストを送り出す最小間隔（同一ホストへの連打を避けるため全体で間隔を空ける）
+    pub min_interval: Duration,
+    /// リンク確認に使うプロキシURL（未指定なら直接接続）
+    pub proxy_url: Option<String>,
+}
+
+impl Default for CheckLinksOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CHECK_LINKS_CONCURRENCY,
+            min_interval: Duration::from_millis(DEFAULT_CHECK_LINKS_INTERVAL_MS),
+            proxy_url: None,
+        }
+    }
+}
+
+/// リンク1件の確認結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LinkCheckOutcome {
+    /// 到達できた（404/410以外の応答）
+    Reachable { status_code: u16 },
+    /// リンク切れ（404/410）
+    Broken { status_code: u16 },
+    /// 接続失敗・タイムアウトなど
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkCheckReport {
+    pub queue_id: Uuid,
+    pub link: String,
+    pub result: LinkCheckOutcome,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckLinksSummary {
+    pub reachable_count: usize,
+    pub broken_count: usize,
+    pub error_count: usize,
+    pub entries: Vec<LinkCheckReport>,
+}
+
+#[derive(Debug, FromRow)]
+struct LinkTarget {
+    id: Uuid,
+    link: String,
+}
+
+/// check-linksコマンドのメイン処理
+pub async fn run(pool: PgPool, limit: i64, options: &CheckLinksOptions) -> Result<()> {
+    let summary = execute_check_links(&pool, limit, options).await?;
+
+    if summary.entries.is_empty() {
+        info!("確認対象のリンクがありません");
+        return Ok(());
+    }
+
+    for entry in &summary.entries {
+        match &entry.result {
+            LinkCheckOutcome::Broken { status_code } => {
+                warn!(queue_id = %entry.queue_id, link = %entry.link, status_code, "リンク切れを検出");
+            }
+            LinkCheckOutcome::Error { message } => {
+                warn!(queue_id = %entry.queue_id, link = %entry.link, error = %message, "リンクを確認できませんでした");
+            }
+            LinkCheckOutcome::Reachable { .. } => {}
+        }
+    }
+
+    info!(
+        reachable = summary.reachable_count,
+        broken = summary.broken_count,
+        errors = summary.error_count,
+        "リンク確認が完了"
+    );
+
+    Ok(())
+}
+
+/// 確認日時の古い順に最大`limit`件のlinkへHEADリクエストを送り、結果をqueueへ記録する。
+///
+/// 404/410なら`link_status`にステータスを記録し、それ以外の応答なら`link_status`をNULLへ戻す（復旧扱い）。
+/// 接続失敗時は`link_status`を変えず`link_checked_at`だけ更新し、次回は他のリンクを優先する。
+pub async fn execute_check_links(
+    pool: &PgPool,
+    limit: i64,
+    options: &CheckLinksOptions,
+) -> Result<CheckLinksSummary> {
+    let targets = sqlx::query_as::<_, LinkTarget>(
+        r#"
+        SELECT id, link
+        FROM rss.queue
+        ORDER BY link_checked_at ASC NULLS FIRST, created_at DESC
+        LIMIT $1
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    let client = apply_proxy(
+        Client::builder().timeout(Duration::from_secs(CHECK_LINKS_TIMEOUT_SECS)),
+        options.proxy_url.as_deref(),
+    )?
+    .build()?;
+    let limiter = RateLimiter::from_interval(options.min_interval);
+
+    let entries: Vec<LinkCheckReport> = stream::iter(targets)
+        .map(|target| {
+            let client = &client;
+            let limiter = &limiter;
+            async move {
+                limiter.acquire().await;
+                let result = check_link(client, &target.link).await;
+                LinkCheckReport {
+                    queue_id: target.id,
+                    link: target.link,
+                    result,
+                }
+            }
+        })
+        .buffer_unordered(options.concurrency.max(1))
+        .collect()
+        .await;
+
+    let mut summary = CheckLinksSummary {
+        reachable_count: 0,
+        broken_count: 0,
+        error_count: 0,
+        entries: Vec::with_capacity(entries.len()),
+    };
+    for entry in entries {
+        record_link_status(pool, &entry).await?;
+        match entry.result {
+            LinkCheckOutcome::Reachable { .. } => summary.reachable_count += 1,
+            LinkCheckOutcome::Broken { .. } => summary.broken_count += 1,
+            LinkCheckOutcome::Error { .. } => summary.error_count += 1,
+        }
+        summary.entries.push(entry);
+    }
+
+    Ok(summary)
+}
+
+/// HEADでリンクの到達性を確認する。HEADを受け付けないサーバ（405/501）にはGETで確認し直す
+async fn check_link(client: &Client, link: &str) -> LinkCheckOutcome {
+    let mut response = client.head(link).send().await;
+    if let Ok(head) = &response {
+        if matches!(
+            head.status(),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+        ) {
+            response = client.get(link).send().await;
+        }
+    }
+
+    match response {
+        Ok(response) => {
+            let status_code = response.status().as_u16();
+            if BROKEN_LINK_STATUS_CODES.contains(&status_code) {
+                LinkCheckOutcome::Broken { status_code }
+            } else {
+                LinkCheckOutcome::Reachable { status_code }
+            }
+        }
+        Err(e) => LinkCheckOutcome::Error {
+            message: e.to_string(),
+        },
+    }
+}
+
+async fn record_link_status(pool: &PgPool, entry: &LinkCheckReport) -> Result<()> {
+    let query = match entry.result {
+        LinkCheckOutcome::Broken { status_code } => sqlx::query(
+            "UPDATE rss.queue SET link_status = $2, link_checked_at = NOW() WHERE id = $1",
+        )
+        .bind(entry.queue_id)
+        .bind(i32::from(status_code)),
+        LinkCheckOutcome::Reachable { .. } => sqlx::query(
+            "UPDATE rss.queue SET link_status = NULL, link_checked_at = NOW() WHERE id = $1",
+        )
+        .bind(entry.queue_id),
+        LinkCheckOutcome::Error { .. } => {
+            sqlx::query("UPDATE rss.queue SET link_checked_at = NOW() WHERE id = $1")
+                .bind(entry.queue_id)
+        }
+    };
+    query.execute(pool).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod execute_check_links_tests {
+        use std::time::Duration;
+
+        use anyhow::Result;
+        use uuid::Uuid;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::check_links::{execute_check_links, CheckLinksOptions};
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        /// # 検証目的
+        /// HEADの応答が404/410のリンクはlink_statusに記録され、到達できるリンク（HEAD非対応でGETが200のものを含む）はNULLのままになることを確認する。
+        #[tokio::test]
+        async fn リンク切れを記録する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            for (route, status) in [("/ok", 200), ("/missing", 404), ("/gone", 410)] {
+                Mock::given(method("HEAD"))
+                    .and(path(route))
+                    .respond_with(ResponseTemplate::new(status))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+            }
+            Mock::given(method("HEAD"))
+                .and(path("/no-head"))
+                .respond_with(ResponseTemplate::new(405))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/no-head"))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let mut ids = Vec::new();
+            for route in ["/ok", "/missing", "/gone", "/no-head"] {
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(format!("{}{}", server.uri(), route))
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+                ids.push(id);
+            }
+
+            let options = CheckLinksOptions {
+                min_interval: Duration::ZERO,
+                ..CheckLinksOptions::default()
+            };
+            let summary = execute_check_links(&pool, 100, &options).await?;
+            assert_eq!(
+                (
+                    summary.reachable_count,
+                    summary.broken_count,
+                    summary.error_count
+                ),
+                (2, 2, 0)
+            );
+
+            let mut statuses = Vec::new();
+            for id in &ids {
+                let (status, checked): (Option<i32>, bool) = sqlx::query_as(
+                    "SELECT link_status, link_checked_at IS NOT NULL FROM rss.queue WHERE id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await?;
+                assert!(checked);
+                statuses.push(status);
+            }
+            assert_eq!(statuses, vec![None, Some(404), Some(410), None]);
+
+            Ok(())
+        }
+    }
+}
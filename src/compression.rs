@@ -0,0 +1,163 @@
+use std::io::{Read, Write};
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::fetch_content::{compress_bytes, decompress_html};
+
+/// Brotliのquality（圧縮率と速度のバランスを取る）
+pub(crate) const BROTLI_QUALITY: i32 = 6;
+/// gzipの圧縮レベル（Brotliのquality 6と同程度の速度を狙う）
+const GZIP_LEVEL: u32 = 6;
+/// zstdの圧縮レベル（展開速度重視のため既定値を使う）
+const ZSTD_LEVEL: i32 = 3;
+
+/// article_contentの本文の圧縮方式（`rss.article_content.compression`に記録する）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum ContentCompression {
+    /// 既定。列追加前の既存データもbrotliとして扱う
+    #[default]
+    Brotli,
+    /// 互換性重視
+    Gzip,
+    /// 展開速度重視
+    Zstd,
+}
+
+impl std::str::FromStr for ContentCompression {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "brotli" => Ok(ContentCompression::Brotli),
+            "gzip" => Ok(ContentCompression::Gzip),
+            "zstd" => Ok(ContentCompression::Zstd),
+            other => Err(anyhow::anyhow!(
+                "無効な圧縮方式: {} (有効な値: brotli, gzip, zstd)",
+                other
+            )),
+        }
+    }
+}
+
+impl ContentCompression {
+    /// この方式で圧縮するときの品質（brotliのquality、gzip/zstdのレベル）。`rss.article_content.compression_quality`に記録する
+    pub fn quality(self) -> u8 {
+        match self {
+            ContentCompression::Brotli => BROTLI_QUALITY as u8,
+            ContentCompression::Gzip => GZIP_LEVEL as u8,
+            ContentCompression::Zstd => ZSTD_LEVEL as u8,
+        }
+    }
+}
+
+/// 指定した方式でバイト列を圧縮する
+pub(crate) fn compress_content(data: &[u8], compression: ContentCompression) -> Result<Vec<u8>> {
+    match compression {
+        ContentCompression::Brotli => compress_bytes(data),
+        ContentCompression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::new(GZIP_LEVEL));
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?)
+        }
+        ContentCompression::Zstd => Ok(zstd::encode_all(data, ZSTD_LEVEL)?),
+    }
+}
+
+/// 記録された方式で本文を展開する
+pub(crate) fn decompress_content(data: &[u8], compression: ContentCompression) -> Result<Vec<u8>> {
+    match compression {
+        ContentCompression::Brotli => decompress_html(data),
+        ContentCompression::Gzip => {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(data)
+                .read_to_end(&mut decompressed)
+                .context("gzip本文の展開に失敗")?;
+            Ok(decompressed)
+        }
+        ContentCompression::Zstd => zstd::decode_all(data).context("zstd本文の展開に失敗"),
+    }
+}
+
+/// Brotli以外で保存された本文をBrotliへ詰め替える（Brotliならそのまま返す）
+pub(crate) fn to_brotli(data: &[u8], compression: ContentCompression) -> Result<Vec<u8>> {
+    match compression {
+        ContentCompression::Brotli => Ok(data.to_vec()),
+        other => compress_bytes(&decompress_content(data, other)?),
+    }
+}
+
+/// `to_brotli`の詰め替えをブロッキング用のスレッドで行う（展開と再圧縮でasyncのワーカーを塞がないため）。
+/// Brotliで保存されていればスレッドへ渡さずにそのまま返す
+pub(crate) async fn to_brotli_blocking(
+    data: &[u8],
+    compression: ContentCompression,
+) -> Result<Vec<u8>> {
+    if matches!(compression, ContentCompression::Brotli) {
+        return Ok(data.to_vec());
+    }
+    let data = data.to_vec();
+    tokio::task::spawn_blocking(move || to_brotli(&data, compression))
+        .await
+        .context("Brotliへの詰め替えタスクの実行に失敗")?
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod roundtrip {
+        use anyhow::Result;
+
+        use crate::compression::{
+            compress_content, decompress_content, to_brotli, ContentCompression,
+        };
+        use crate::fetch_content::decompress_html;
+
+        const ALL: [ContentCompression; 3] = [
+            ContentCompression::Brotli,
+            ContentCompression::Gzip,
+            ContentCompression::Zstd,
+        ];
+
+        /// # 検証目的
+        /// 各圧縮方式で圧縮したHTMLが同じ方式で元に戻り、Brotliへの詰め替えも元のHTMLになることを確認する。
+        #[test]
+        fn 各方式で往復できる() -> Result<()> {
+            let html = "<html><body>".to_string()
+                + &"<p>圧縮方式ごとの往復テスト用の本文です。</p>".repeat(200)
+                + "</body></html>";
+
+            for compression in ALL {
+                let compressed = compress_content(html.as_bytes(), compression)?;
+                assert!(compressed.len() < html.len(), "{:?}", compression);
+                assert_eq!(
+                    decompress_content(&compressed, compression)?,
+                    html.as_bytes(),
+                    "{:?}",
+                    compression
+                );
+                assert_eq!(
+                    decompress_html(&to_brotli(&compressed, compression)?)?,
+                    html.as_bytes()
+                );
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 設定値を圧縮方式として解釈でき、未知の方式がエラーになることを確認する。
+        #[test]
+        fn 圧縮方式を解釈できる() {
+            for (value, compression) in ["brotli", "gzip", "zstd"].into_iter().zip(ALL) {
+                assert_eq!(value.parse::<ContentCompression>().unwrap(), compression);
+            }
+            assert_eq!(
+                " ZSTD ".parse::<ContentCompression>().unwrap(),
+                ContentCompression::Zstd
+            );
+            assert!("lz4".parse::<ContentCompression>().is_err());
+        }
+    }
+}
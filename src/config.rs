@@ -1,32 +1,564 @@
 use anyhow::Result;
 use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::{JsonCase, DEFAULT_MAX_BODY_BYTES};
+use crate::article_sink::{build_article_sink, ArticleSink, OutputSink, DEFAULT_REDIS_STREAM_KEY};
+use crate::articles::ArticleSort;
+use crate::compression::ContentCompression;
+use crate::fetch_content::{FetchContentFilter, FetchContentOptions, DEFAULT_BATCH_SIZE};
+use crate::fetch_rss::{
+    FeedDormancy, FeedRetryPolicy, FetchRssOptions, PubDatePreference,
+    DEFAULT_DESCRIPTION_MAX_CHARS, DEFAULT_DORMANT_INTERVAL_HOURS, DEFAULT_FEED_TIMEOUT_SECS,
+    DEFAULT_RETRY_BACKOFF_MS, DEFAULT_RETRY_MAX, DEFAULT_TITLE_MAX_CHARS,
+};
+use crate::rate_limit::RateLimiter;
+use crate::secrets::{resolve_secret, AwsSecretsManager, SecretResolver};
+use crate::telemetry::LogFormat;
+use crate::warc::ContentArchive;
+use crate::webhook::{WebhookBatchOptions, DEFAULT_WEBHOOK_MAX_ENTRIES};
+
+/// 環境変数より優先するCLIのグローバルフラグ（指定したものだけ上書きする）
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ConfigOverrides {
+    /// 接続先DBのURL（`DATABASE_URL`・`ENVIRONMENT`による選択より優先）
+    #[arg(long, global = true)]
+    pub database_url: Option<String>,
+
+    /// scraping APIのURL（`SCRAPING_API_URL`より優先）
+    #[arg(long, global = true)]
+    pub scraping_api_url: Option<String>,
+
+    /// 実行結果の通知先URL（`WEBHOOK_URL`より優先）
+    #[arg(long, global = true)]
+    pub webhook_url: Option<String>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
     pub scraping_api_url: String,
     pub webhook_url: Option<String>,
+    pub scraping_accept_language: Option<String>,
+    pub fetch_content_batch_size: usize,
+    pub fetch_content_deny_hosts: Vec<String>,
+    pub proxy_url: Option<String>,
+    pub content_delta_encoding: bool,
+    pub content_compression: ContentCompression,
+    pub content_archive: ContentArchive,
+    pub rss_dns_cache: bool,
+    pub rss_user_agents: Vec<String>,
+    pub fetch_rss_feed_timeout_secs: u64,
+    pub fetch_rss_retry_statuses: Vec<u16>,
+    pub fetch_rss_retry_max: u32,
+    pub fetch_rss_retry_backoff_ms: u64,
+    pub fetch_rss_dormant_after_days: u64,
+    pub fetch_rss_dormant_interval_hours: u64,
+    pub extract_main_content: bool,
+    pub precheck_url: bool,
+    pub extract_links: bool,
+    pub content_raw_response: bool,
+    pub notify_on_update: bool,
+    pub webhook_max_entries: usize,
+    pub webhook_batch_size: usize,
+    pub webhook_batch_interval_secs: u64,
+    pub api_json_case: JsonCase,
+    pub api_default_sort: ArticleSort,
+    pub api_max_body_bytes: usize,
+    pub api_cache_ttl_secs: u64,
+    pub api_token_ttl_secs: u64,
+    pub api_token_secret: Option<String>,
+    pub api_admin_token: Option<String>,
+    pub log_format: LogFormat,
+    pub summary_api_url: Option<String>,
+    pub fetch_debug_dir: Option<PathBuf>,
+    pub queue_title_max_chars: usize,
+    pub queue_description_max_chars: usize,
+    pub pub_date_preference: PubDatePreference,
+    /// `OUTPUT_SINK`・`REDIS_URL`・`REDIS_STREAM_KEY`から組み立てたfetch-rssの新規記事の流し先
+    pub article_sink: Option<Arc<dyn ArticleSink>>,
+    /// `SCRAPING_API_QPS`から組み立てたscraping API全体のQPS制限（fetch-contentの実行間で共有する）
+    pub scrape_rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl Config {
-    /// 環境変数から設定を読み込む
-    pub fn from_env() -> Result<Self> {
+    /// 環境変数から設定を読み込み、CLIで指定された項目を上書きする。
+    ///
+    /// 優先順位はCLIフラグ > 環境変数 > デフォルト。
+    /// 接続情報などの値が`secret://<key>`形式ならAWS Secrets Managerから実値を取得する。
+    pub fn from_env_with_overrides(overrides: &ConfigOverrides) -> Result<Self> {
+        Self::from_env_with_resolver(overrides, &AwsSecretsManager)
+    }
+
+    /// `from_env_with_overrides`と同じ手順で、`secret://<key>`の解決に`resolver`を使う
+    ///
+    /// 対象はDB URL・`WEBHOOK_URL`・プロキシURL・`API_ADMIN_TOKEN`・`API_TOKEN_SECRET`（CLIフラグで指定した値も含む）。
+    fn from_env_with_resolver(
+        overrides: &ConfigOverrides,
+        resolver: &dyn SecretResolver,
+    ) -> Result<Self> {
         dotenv::dotenv().ok();
 
-        let database_url = Self::get_database_url()?;
+        // CLIで指定されていればENVIRONMENTの検証（PROD_CONFIRMEDなど）も行わない
+        let database_url = match &overrides.database_url {
+            Some(url) => url.clone(),
+            None => Self::get_database_url()?,
+        };
+        let database_url = resolve_secret(&database_url, resolver)?;
+
+        let scraping_api_url = overrides.scraping_api_url.clone().unwrap_or_else(|| {
+            env::var("SCRAPING_API_URL").unwrap_or_else(|_| "http://localhost:8000".to_string())
+        });
+
+        let webhook_url = overrides
+            .webhook_url
+            .clone()
+            .or_else(|| env::var("WEBHOOK_URL").ok())
+            .map(|url| resolve_secret(&url, resolver))
+            .transpose()?;
+
+        let scraping_accept_language = env::var("SCRAPING_ACCEPT_LANGUAGE")
+            .ok()
+            .filter(|value| !value.trim().is_empty());
+
+        let fetch_content_batch_size = match env::var("FETCH_CONTENT_BATCH_SIZE") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<usize>() {
+                Ok(size) if size > 0 => size,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "FETCH_CONTENT_BATCH_SIZEは1以上の整数で指定してください: {}",
+                        value
+                    ))
+                }
+            },
+            _ => DEFAULT_BATCH_SIZE,
+        };
+
+        let fetch_content_deny_hosts = env::var("FETCH_CONTENT_DENY_HOSTS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|host| host.trim().to_string())
+                    .filter(|host| !host.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let webhook_max_entries = match env::var("WEBHOOK_MAX_ENTRIES") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "WEBHOOK_MAX_ENTRIESは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => DEFAULT_WEBHOOK_MAX_ENTRIES,
+        };
+
+        let webhook_batch_size = match env::var("WEBHOOK_BATCH_SIZE") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "WEBHOOK_BATCH_SIZEは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => 0,
+        };
+
+        let webhook_batch_interval_secs = match env::var("WEBHOOK_BATCH_INTERVAL_SECS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "WEBHOOK_BATCH_INTERVAL_SECSは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => 0,
+        };
+
+        let proxy_url = Self::get_proxy_url()
+            .map(|url| resolve_secret(&url, resolver))
+            .transpose()?;
+
+        let content_delta_encoding = Self::get_bool_env("CONTENT_DELTA_ENCODING")?;
+
+        let content_compression = match env::var("CONTENT_COMPRESSION") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => ContentCompression::default(),
+        };
+
+        let content_archive = match env::var("CONTENT_ARCHIVE") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => ContentArchive::default(),
+        };
+
+        let rss_dns_cache = Self::get_bool_env("RSS_DNS_CACHE")?;
+
+        let rss_user_agents = env::var("RSS_USER_AGENTS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|user_agent| user_agent.trim().to_string())
+                    .filter(|user_agent| !user_agent.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let fetch_rss_feed_timeout_secs = match env::var("FETCH_RSS_FEED_TIMEOUT_SECS") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(secs) if secs > 0 => secs,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "FETCH_RSS_FEED_TIMEOUT_SECSは1以上の整数で指定してください: {}",
+                        value
+                    ))
+                }
+            },
+            _ => DEFAULT_FEED_TIMEOUT_SECS,
+        };
+
+        let fetch_rss_retry_statuses = match env::var("FETCH_RSS_RETRY_STATUSES") {
+            Ok(value) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|status| !status.is_empty())
+                .map(|status| match status.parse::<u16>() {
+                    Ok(code) if (100..=599).contains(&code) => Ok(code),
+                    _ => Err(anyhow::anyhow!(
+                        "FETCH_RSS_RETRY_STATUSESはHTTPステータス（100〜599）のカンマ区切りで指定してください: {}",
+                        value
+                    )),
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Err(_) => Vec::new(),
+        };
+
+        let fetch_rss_retry_max = match env::var("FETCH_RSS_RETRY_MAX") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "FETCH_RSS_RETRY_MAXは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => DEFAULT_RETRY_MAX,
+        };
+
+        let fetch_rss_retry_backoff_ms = match env::var("FETCH_RSS_RETRY_BACKOFF_MS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "FETCH_RSS_RETRY_BACKOFF_MSは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => DEFAULT_RETRY_BACKOFF_MS,
+        };
+
+        let fetch_rss_dormant_after_days = match env::var("FETCH_RSS_DORMANT_AFTER_DAYS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "FETCH_RSS_DORMANT_AFTER_DAYSは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => 0,
+        };
+
+        let fetch_rss_dormant_interval_hours = match env::var("FETCH_RSS_DORMANT_INTERVAL_HOURS") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(hours) if hours > 0 => hours,
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "FETCH_RSS_DORMANT_INTERVAL_HOURSは1以上の整数で指定してください: {}",
+                        value
+                    ))
+                }
+            },
+            _ => DEFAULT_DORMANT_INTERVAL_HOURS,
+        };
 
-        let scraping_api_url =
-            env::var("SCRAPING_API_URL").unwrap_or_else(|_| "http://localhost:8000".to_string());
+        let extract_main_content = Self::get_bool_env("EXTRACT_MAIN_CONTENT")?;
 
-        let webhook_url = env::var("WEBHOOK_URL").ok();
+        let precheck_url = Self::get_bool_env("PRECHECK_URL")?;
+
+        let extract_links = Self::get_bool_env("EXTRACT_LINKS")?;
+
+        let content_raw_response = Self::get_bool_env("CONTENT_RAW_RESPONSE")?;
+
+        let notify_on_update = Self::get_bool_env("NOTIFY_ON_UPDATE")?;
+
+        let api_json_case = match env::var("API_JSON_CASE") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => JsonCase::default(),
+        };
+
+        let api_default_sort = match env::var("API_DEFAULT_SORT") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => ArticleSort::default(),
+        };
+
+        let api_max_body_bytes = match env::var("API_MAX_BODY_BYTES") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "API_MAX_BODY_BYTESは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => DEFAULT_MAX_BODY_BYTES,
+        };
+
+        let api_cache_ttl_secs = match env::var("API_CACHE_TTL_SECS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "API_CACHE_TTL_SECSは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => 0,
+        };
+
+        let api_token_ttl_secs = match env::var("API_TOKEN_TTL_SECS") {
+            Ok(value) if !value.trim().is_empty() => value.trim().parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "API_TOKEN_TTL_SECSは0以上の整数で指定してください: {}",
+                    value
+                )
+            })?,
+            _ => 0,
+        };
+
+        let api_token_secret = env::var("API_TOKEN_SECRET")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(|secret| resolve_secret(&secret, resolver))
+            .transpose()?;
+
+        let api_admin_token = env::var("API_ADMIN_TOKEN")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(|token| resolve_secret(&token, resolver))
+            .transpose()?;
+
+        let log_format = match env::var("LOG_FORMAT") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => LogFormat::default(),
+        };
+
+        let summary_api_url = env::var("SUMMARY_API_URL")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        let fetch_debug_dir = env::var("FETCH_DEBUG_DIR")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+
+        let queue_title_max_chars =
+            Self::get_max_chars_env("QUEUE_TITLE_MAX_CHARS", DEFAULT_TITLE_MAX_CHARS)?;
+        let queue_description_max_chars =
+            Self::get_max_chars_env("QUEUE_DESCRIPTION_MAX_CHARS", DEFAULT_DESCRIPTION_MAX_CHARS)?;
+
+        let pub_date_preference = match env::var("PUBDATE_PREFERENCE") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => PubDatePreference::default(),
+        };
+
+        let output_sink = match env::var("OUTPUT_SINK") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => OutputSink::default(),
+        };
+        let redis_url = env::var("REDIS_URL")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(|url| resolve_secret(&url, resolver))
+            .transpose()?;
+        let redis_stream_key = env::var("REDIS_STREAM_KEY")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| DEFAULT_REDIS_STREAM_KEY.to_string());
+        let article_sink =
+            build_article_sink(output_sink, redis_url.as_deref(), &redis_stream_key)?;
+
+        let scrape_rate_limiter = match env::var("SCRAPING_API_QPS") {
+            Ok(value) if !value.trim().is_empty() => {
+                let invalid = || {
+                    anyhow::anyhow!(
+                        "SCRAPING_API_QPSは0より大きい数値で指定してください: {}",
+                        value
+                    )
+                };
+                let qps: f64 = value.trim().parse().map_err(|_| invalid())?;
+                // 0以下や、間隔がDurationに収まらないほど小さい値も同じエラーにする
+                Some(Arc::new(RateLimiter::new(qps).map_err(|_| invalid())?))
+            }
+            _ => None,
+        };
 
         Ok(Config {
             database_url,
             scraping_api_url,
             webhook_url,
+            scraping_accept_language,
+            fetch_content_batch_size,
+            fetch_content_deny_hosts,
+            proxy_url,
+            content_delta_encoding,
+            content_compression,
+            content_archive,
+            rss_dns_cache,
+            rss_user_agents,
+            fetch_rss_feed_timeout_secs,
+            fetch_rss_retry_statuses,
+            fetch_rss_retry_max,
+            fetch_rss_retry_backoff_ms,
+            fetch_rss_dormant_after_days,
+            fetch_rss_dormant_interval_hours,
+            extract_main_content,
+            precheck_url,
+            extract_links,
+            content_raw_response,
+            notify_on_update,
+            webhook_max_entries,
+            webhook_batch_size,
+            webhook_batch_interval_secs,
+            api_json_case,
+            api_default_sort,
+            api_max_body_bytes,
+            api_cache_ttl_secs,
+            api_token_ttl_secs,
+            api_token_secret,
+            api_admin_token,
+            log_format,
+            summary_api_url,
+            fetch_debug_dir,
+            queue_title_max_chars,
+            queue_description_max_chars,
+            pub_date_preference,
+            article_sink,
+            scrape_rate_limiter,
         })
     }
 
+    /// fetch-rss向けの設定を組み立てる（実行ごとのフラグは既定値）
+    pub fn fetch_rss_options(&self) -> FetchRssOptions {
+        FetchRssOptions {
+            proxy_url: self.proxy_url.clone(),
+            dns_cache: self.rss_dns_cache,
+            user_agents: self.rss_user_agents.clone(),
+            title_max_chars: self.queue_title_max_chars,
+            description_max_chars: self.queue_description_max_chars,
+            feed_timeout: Duration::from_secs(self.fetch_rss_feed_timeout_secs),
+            retry_policy: FeedRetryPolicy {
+                statuses: self.fetch_rss_retry_statuses.clone(),
+                max_retries: self.fetch_rss_retry_max,
+                backoff: Duration::from_millis(self.fetch_rss_retry_backoff_ms),
+            },
+            dormancy: (self.fetch_rss_dormant_after_days > 0).then(|| FeedDormancy {
+                after: Duration::from_secs(
+                    self.fetch_rss_dormant_after_days.saturating_mul(86_400),
+                ),
+                interval: Duration::from_secs(
+                    self.fetch_rss_dormant_interval_hours.saturating_mul(3_600),
+                ),
+            }),
+            sink: self.article_sink.clone(),
+            pub_date_preference: self.pub_date_preference,
+            ..FetchRssOptions::default()
+        }
+    }
+
+    /// fetch-content向けの設定を組み立てる
+    pub fn fetch_content_options(&self) -> FetchContentOptions {
+        FetchContentOptions {
+            accept_language: self.scraping_accept_language.clone(),
+            batch_size: self.fetch_content_batch_size,
+            deny_hosts: self.fetch_content_deny_hosts.clone(),
+            proxy_url: self.proxy_url.clone(),
+            delta_encoding: self.content_delta_encoding,
+            extract_main_content: self.extract_main_content,
+            webhook_max_entries: self.webhook_max_entries,
+            compression: self.content_compression,
+            archive: self.content_archive,
+            summary_api_url: self.summary_api_url.clone(),
+            debug_dir: self.fetch_debug_dir.clone(),
+            filter: FetchContentFilter::default(),
+            precheck_url: self.precheck_url,
+            extract_links: self.extract_links,
+            raw_response: self.content_raw_response,
+            notify_on_update: self.notify_on_update,
+            webhook_batch: self.webhook_batch_options(),
+            scrape_rate_limiter: self.scrape_rate_limiter.clone(),
+        }
+    }
+
+    /// `WEBHOOK_BATCH_SIZE`が1以上で`WEBHOOK_URL`があればバッチ通知の設定を組み立てる
+    fn webhook_batch_options(&self) -> Option<WebhookBatchOptions> {
+        if self.webhook_batch_size == 0 {
+            return None;
+        }
+        Some(WebhookBatchOptions {
+            url: self.webhook_url.clone()?,
+            max_entries: self.webhook_batch_size,
+            interval: (self.webhook_batch_interval_secs > 0)
+                .then(|| Duration::from_secs(self.webhook_batch_interval_secs)),
+        })
+    }
+
+    /// 真偽値の環境変数を読み込む（`true`/`false`/`1`/`0`、未設定・空ならfalse）
+    fn get_bool_env(key: &str) -> Result<bool> {
+        match env::var(key) {
+            Ok(value) if !value.trim().is_empty() => {
+                match value.trim().to_ascii_lowercase().as_str() {
+                    "true" | "1" => Ok(true),
+                    "false" | "0" => Ok(false),
+                    _ => Err(anyhow::anyhow!(
+                        "{}はtrueまたはfalseで指定してください: {}",
+                        key,
+                        value
+                    )),
+                }
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// 文字数上限の環境変数を読み込む（1以上の整数、未設定・空なら`default`）
+    fn get_max_chars_env(key: &str, default: usize) -> Result<usize> {
+        match env::var(key) {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<usize>() {
+                Ok(max_chars) if max_chars > 0 => Ok(max_chars),
+                _ => Err(anyhow::anyhow!(
+                    "{}は1以上の整数で指定してください: {}",
+                    key,
+                    value
+                )),
+            },
+            _ => Ok(default),
+        }
+    }
+
+    /// 外部への接続に使うプロキシURLを取得する
+    ///
+    /// `RSS_PROXY_URL`を優先し、未設定なら`HTTPS_PROXY`→`HTTP_PROXY`（小文字の変数名も可）を使う。
+    fn get_proxy_url() -> Option<String> {
+        [
+            "RSS_PROXY_URL",
+            "HTTPS_PROXY",
+            "https_proxy",
+            "HTTP_PROXY",
+            "http_proxy",
+        ]
+        .iter()
+        .filter_map(|key| env::var(key).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+    }
+
     /// データベースURLを取得する
     ///
     /// 優先順位:
@@ -71,3 +603,88 @@ impl Config {
         Ok(url)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    pub mod from_env_with_overrides {
+        use crate::config::{Config, ConfigOverrides};
+        use crate::test_support::EnvGuard;
+
+        /// # 検証目的
+        /// CLIフラグで指定した項目は環境変数より優先され、指定しなかった項目は環境変数の値が使われることを確認する。
+        #[test]
+        fn cliフラグが環境変数を上書きする() {
+            let mut env = EnvGuard::lock();
+            env.set("SCRAPING_API_URL", "http://env-scraper:8000");
+            env.set("WEBHOOK_URL", "http://env-webhook/notify");
+
+            let overrides = ConfigOverrides {
+                database_url: Some("postgres://cli/db".to_string()),
+                scraping_api_url: Some("http://cli-scraper:8000".to_string()),
+                webhook_url: None,
+            };
+            let config = Config::from_env_with_overrides(&overrides).expect("設定を読み込める");
+
+            assert_eq!(config.database_url, "postgres://cli/db");
+            assert_eq!(config.scraping_api_url, "http://cli-scraper:8000");
+            assert_eq!(
+                config.webhook_url.as_deref(),
+                Some("http://env-webhook/notify")
+            );
+
+            let overrides = ConfigOverrides {
+                webhook_url: Some("http://cli-webhook/notify".to_string()),
+                ..overrides
+            };
+            let config = Config::from_env_with_overrides(&overrides).expect("設定を読み込める");
+            assert_eq!(
+                config.webhook_url.as_deref(),
+                Some("http://cli-webhook/notify")
+            );
+        }
+    }
+
+    pub mod from_env_with_resolver {
+        use crate::config::{Config, ConfigOverrides};
+        use crate::test_support::{EnvGuard, StaticSecrets};
+
+        /// # 検証目的
+        /// `secret://<key>`形式の値は解決器から取得した実値に展開され、プレフィックスの無い値は平文のまま使われることを確認する。
+        #[test]
+        fn secret形式の値を解決器で展開する() {
+            // 他のテストが書き換えた環境変数を読まないよう直列化する
+            let _env = EnvGuard::lock();
+            let resolver = StaticSecrets::new(&[
+                (
+                    "datadoggo/database-url",
+                    "postgres://secret-user:pw@db/prod",
+                ),
+                (
+                    "datadoggo/webhook-url",
+                    "http://hooks.internal/notify?key=k",
+                ),
+            ]);
+            let overrides = ConfigOverrides {
+                database_url: Some("secret://datadoggo/database-url".to_string()),
+                scraping_api_url: Some("http://plain-scraper:8000".to_string()),
+                webhook_url: Some("secret://datadoggo/webhook-url".to_string()),
+            };
+            let config =
+                Config::from_env_with_resolver(&overrides, &resolver).expect("設定を読み込める");
+
+            assert_eq!(config.database_url, "postgres://secret-user:pw@db/prod");
+            assert_eq!(
+                config.webhook_url.as_deref(),
+                Some("http://hooks.internal/notify?key=k")
+            );
+            assert_eq!(config.scraping_api_url, "http://plain-scraper:8000");
+
+            // 解決できないキーは平文扱いにせず設定の読み込みをエラーにする
+            let overrides = ConfigOverrides {
+                database_url: Some("secret://datadoggo/missing".to_string()),
+                ..overrides
+            };
+            assert!(Config::from_env_with_resolver(&overrides, &resolver).is_err());
+        }
+    }
+}
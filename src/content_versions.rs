@@ -0,0 +1,291 @@
+use anyhow::{Context, Result};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::compression::{decompress_content, to_brotli, ContentCompression};
+use crate::delta::{apply_delta, encode_delta};
+use crate::fetch_content::{compress_bytes, decompress_html};
+
+/// 連続して保存するdeltaの上限。超える場合はフル保存を挟み、復元時のパッチ適用回数を抑える
+pub(crate) const MAX_DELTA_CHAIN: usize = 10;
+
+const ENCODING_FULL: &str = "full";
+const ENCODING_DELTA: &str = "delta";
+
+#[allow(dead_code)] // load_content_version専用
+#[derive(Debug, FromRow)]
+struct ContentVersionRow {
+    version: i32,
+    encoding: String,
+    data: Vec<u8>,
+}
+
+/// article_contentを上書きする前に、現在の本文を過去バージョンとして退避する
+///
+/// `delta_encoding`が有効なら、退避する本文を上書き後の本文からのdelta（逆方向差分）として保存する。
+/// 最新版は常にarticle_contentにフルで残るため、過去バージョンは新しい側から順にパッチを当てて復元できる。
+/// 本文が未保存・変化なしの場合は何もしない。過去バージョンは本文の圧縮方式によらずBrotliで保存する。
+pub(crate) async fn archive_content_version(
+    tx: &mut Transaction<'_, Postgres>,
+    queue_id: Uuid,
+    new_data: &[u8],
+    new_compression: ContentCompression,
+    delta_encoding: bool,
+) -> Result<()> {
+    let current: Option<(Vec<u8>, ContentCompression)> = sqlx::query_as(
+        "SELECT data, compression FROM rss.article_content WHERE queue_id = $1 FOR UPDATE",
+    )
+    .bind(queue_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let Some((current, current_compression)) = current else {
+        return Ok(());
+    };
+    let unchanged = if current_compression == new_compression {
+        current == new_data
+    } else {
+        decompress_content(&current, current_compression)?
+            == decompress_content(new_data, new_compression)?
+    };
+    if unchanged {
+        return Ok(());
+    }
+
+    let recent: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT encoding
+        FROM rss.article_content_version
+        WHERE queue_id = $1
+        ORDER BY version DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(queue_id)
+    .bind(MAX_DELTA_CHAIN as i64)
+    .fetch_all(&mut **tx)
+    .await?;
+    let chain_length = recent
+        .iter()
+        .take_while(|encoding| encoding.as_str() == ENCODING_DELTA)
+        .count();
+
+    let (encoding, data) = if delta_encoding && chain_length + 1 < MAX_DELTA_CHAIN {
+        let delta = encode_delta(
+            &decompress_content(new_data, new_compression)?,
+            &decompress_content(&current, current_compression)?,
+        );
+        (ENCODING_DELTA, compress_bytes(&delta)?)
+    } else {
+        (ENCODING_FULL, to_brotli(&current, current_compression)?)
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO rss.article_content_version (queue_id, version, encoding, data)
+        SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3
+        FROM rss.article_content_version
+        WHERE queue_id = $1
+        "#,
+    )
+    .bind(queue_id)
+    .bind(encoding)
+    .bind(data)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// 過去バージョンの本文（展開済みHTML）を復元する。存在しないバージョンならNoneを返す
+#[allow(dead_code)] // 過去バージョンの参照APIは未提供のため現時点では内部から参照されない
+pub async fn load_content_version(
+    pool: &PgPool,
+    queue_id: Uuid,
+    version: i32,
+) -> Result<Option<Vec<u8>>> {
+    let rows = sqlx::query_as::<_, ContentVersionRow>(
+        r#"
+        SELECT version, encoding, data
+        FROM rss.article_content_version
+        WHERE queue_id = $1 AND version >= $2
+        ORDER BY version ASC
+        "#,
+    )
+    .bind(queue_id)
+    .bind(version)
+    .fetch_all(pool)
+    .await?;
+
+    if rows.first().map(|row| row.version) != Some(version) {
+        return Ok(None);
+    }
+
+    // 対象より新しい最初のフル保存（無ければ最新版）を起点に、新しい側からdeltaを当てていく
+    let full_index = rows.iter().position(|row| row.encoding == ENCODING_FULL);
+    let mut content = match full_index {
+        Some(index) => decompress_html(&rows[index].data)?,
+        None => {
+            let (latest, compression): (Vec<u8>, ContentCompression) = sqlx::query_as(
+                "SELECT data, compression FROM rss.article_content WHERE queue_id = $1",
+            )
+            .bind(queue_id)
+            .fetch_optional(pool)
+            .await?
+            .context("最新の本文が見つからないため過去バージョンを復元できません")?;
+            decompress_content(&latest, compression)?
+        }
+    };
+
+    let end = full_index.unwrap_or(rows.len());
+    for row in rows[..end].iter().rev() {
+        let delta = decompress_html(&row.data)?;
+        content = apply_delta(&content, &delta)
+            .with_context(|| format!("version {}のdelta適用に失敗", row.version))?;
+    }
+
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod content_versions {
+        use anyhow::Result;
+        use uuid::Uuid;
+
+        use crate::compression::ContentCompression;
+        use crate::content_versions::{
+            archive_content_version, load_content_version, MAX_DELTA_CHAIN,
+        };
+        use crate::fetch_content::compress_html;
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        fn version_html(index: usize) -> String {
+            let body = "<p>変わらない段落が続く本文です。</p>".repeat(100);
+            format!(
+                "<html><body>{}<p>版{}の更新箇所</p>{}</body></html>",
+                body, index, body
+            )
+        }
+
+        /// 本文を上書き保存する（fetch-contentの保存処理と同じ順序で退避してから上書き）
+        async fn save_version(
+            pool: &sqlx::PgPool,
+            queue_id: Uuid,
+            html: &str,
+            delta_encoding: bool,
+        ) -> Result<()> {
+            let data = compress_html(html, ContentCompression::Brotli)?;
+            let mut tx = pool.begin().await?;
+            archive_content_version(
+                &mut tx,
+                queue_id,
+                &data,
+                ContentCompression::Brotli,
+                delta_encoding,
+            )
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)
+                ON CONFLICT (queue_id) DO UPDATE SET data = EXCLUDED.data
+                "#,
+            )
+            .bind(queue_id)
+            .bind(&data)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        }
+
+        async fn insert_queue(pool: &sqlx::PgPool) -> Result<Uuid> {
+            let queue_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(queue_id)
+            .bind(format!("https://example.com/{}", queue_id))
+            .bind("タイトル")
+            .bind("説明")
+            .execute(pool)
+            .await?;
+            Ok(queue_id)
+        }
+
+        /// # 検証目的
+        /// delta chainを有効にした場合、上限を超えてフル保存を挟む長さの履歴でも全バージョンを元どおり復元できることを確認する。
+        #[tokio::test]
+        async fn delta_chainから過去バージョンを復元できる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let queue_id = insert_queue(&pool).await?;
+            let total = MAX_DELTA_CHAIN + 5;
+            for index in 0..total {
+                save_version(&pool, queue_id, &version_html(index), true).await?;
+            }
+
+            let encodings: Vec<String> = sqlx::query_scalar(
+                "SELECT encoding FROM rss.article_content_version WHERE queue_id = $1 ORDER BY version",
+            )
+            .bind(queue_id)
+            .fetch_all(&pool)
+            .await?;
+            assert_eq!(encodings.len(), total - 1);
+            assert!(encodings.iter().any(|encoding| encoding == "delta"));
+            assert!(encodings.iter().any(|encoding| encoding == "full"));
+
+            for index in 0..total - 1 {
+                let restored = load_content_version(&pool, queue_id, index as i32 + 1)
+                    .await?
+                    .expect("バージョンが見つからない");
+                assert_eq!(
+                    restored,
+                    version_html(index).into_bytes(),
+                    "version {}",
+                    index + 1
+                );
+            }
+            assert!(load_content_version(&pool, queue_id, total as i32)
+                .await?
+                .is_none());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// delta無効時は過去バージョンをフル保存し、同じ本文での上書きでは履歴を増やさないことを確認する。
+        #[tokio::test]
+        async fn 無効時はフル保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let queue_id = insert_queue(&pool).await?;
+            save_version(&pool, queue_id, &version_html(0), false).await?;
+            save_version(&pool, queue_id, &version_html(0), false).await?;
+            save_version(&pool, queue_id, &version_html(1), false).await?;
+
+            let encodings: Vec<String> = sqlx::query_scalar(
+                "SELECT encoding FROM rss.article_content_version WHERE queue_id = $1 ORDER BY version",
+            )
+            .bind(queue_id)
+            .fetch_all(&pool)
+            .await?;
+            assert_eq!(encodings, vec!["full".to_string()]);
+
+            let restored = load_content_version(&pool, queue_id, 1)
+                .await?
+                .expect("バージョンが見つからない");
+            assert_eq!(restored, version_html(0).into_bytes());
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::fetch_rss::ParsedFeed;
+use crate::models::NewQueue;
+
+/// 汎用JSON形式（`custom_json`）の1記事分
+#[derive(Debug, Deserialize)]
+struct CustomJsonItem {
+    url: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    published: Option<String>,
+}
+
+/// `feed_rs`で扱えないJSON配列形式（`[{"url", "title", "published"}]`）のフィードを解析する。
+///
+/// `url`が無い・空の要素や型の合わない要素は`malformed_entries`として数えて読み飛ばす。
+/// `published`はRFC3339またはRFC2822で解釈し、書かれていたオフセットも保持する。
+/// トップレベルが配列でない場合はエラーとする。
+pub(crate) fn parse_custom_json_feed(content: &[u8], group: Option<&str>) -> Result<ParsedFeed> {
+    let items: Vec<Value> =
+        serde_json::from_slice(content).context("custom_jsonのフィードがJSON配列ではありません")?;
+
+    let mut entries = Vec::new();
+    let mut malformed_entries = 0;
+
+    for item in items {
+        let Ok(item) = serde_json::from_value::<CustomJsonItem>(item) else {
+            malformed_entries += 1;
+            continue;
+        };
+        let link = item.url.trim();
+        if link.is_empty() {
+            malformed_entries += 1;
+            continue;
+        }
+
+        let published = item.published.as_deref().and_then(parse_published);
+
+        entries.push(NewQueue {
+            link: link.to_string(),
+            title: item
+                .title
+                .filter(|title| !title.trim().is_empty())
+                .unwrap_or_else(|| "No title".to_string()),
+            pub_date: published.map(|published| published.with_timezone(&Utc)),
+            pub_date_tz_offset: published
+                .map(|published| published.offset().local_minus_utc() / 60),
+            description: String::new(),
+            content_body: None,
+            language: None,
+            group: group.map(|g| g.to_string()),
+            scrape_referer: None,
+            scrape_accept_language: None,
+            priority: 0,
+        });
+    }
+
+    Ok(ParsedFeed {
+        entries,
+        malformed_entries,
+        icon_url: None,
+        elapsed_ms: 0,
+        last_build_date: None,
+    })
+}
+
+fn parse_published(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_rfc2822(value))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod parse_custom_json_feed {
+        use anyhow::Result;
+
+        use crate::custom_feed::parse_custom_json_feed;
+        use crate::test_support::fixed_datetime;
+
+        /// # 検証目的
+        /// 汎用JSON形式の配列をqueue用のentryへ変換し、urlの無い要素は読み飛ばすことを確認する。
+        #[test]
+        fn json配列をentryに変換する() -> Result<()> {
+            let json = r#"[
+                {"url": "https://example.com/a", "title": "記事A", "published": "2025-10-12T18:00:00+09:00"},
+                {"url": "https://example.com/b"},
+                {"title": "urlなし"},
+                "not an object",
+                {"url": "  ", "title": "空のurl"}
+            ]"#;
+
+            let parsed = parse_custom_json_feed(json.as_bytes(), Some("api"))?;
+            assert_eq!(parsed.entries.len(), 2);
+            assert_eq!(parsed.malformed_entries, 3);
+
+            let first = &parsed.entries[0];
+            assert_eq!(first.link, "https://example.com/a");
+            assert_eq!(first.title, "記事A");
+            assert_eq!(first.pub_date, Some(fixed_datetime(2025, 10, 12, 9, 0, 0)));
+            assert_eq!(first.pub_date_tz_offset, Some(540));
+            assert_eq!(first.group.as_deref(), Some("api"));
+
+            let second = &parsed.entries[1];
+            assert_eq!(second.title, "No title");
+            assert!(second.pub_date.is_none());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// トップレベルが配列でないJSONはエラーになることを確認する。
+        #[test]
+        fn 配列でなければエラー() {
+            assert!(parse_custom_json_feed(br#"{"url": "https://example.com/a"}"#, None).is_err());
+            assert!(parse_custom_json_feed(b"<rss></rss>", None).is_err());
+        }
+    }
+}
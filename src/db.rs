@@ -1,5 +1,9 @@
 use anyhow::Result;
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use sqlx::{migrate::Migrator, postgres::PgPoolOptions, PgPool};
+use tracing::info;
+
+/// `migrations`ディレクトリのマイグレーション（`.up.sql`/`.down.sql`の可逆形式）
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
 
 /// データベース接続プールを作成
 pub async fn create_pool(database_url: &str) -> Result<PgPool> {
@@ -11,6 +15,34 @@ pub async fn create_pool(database_url: &str) -> Result<PgPool> {
     Ok(pool)
 }
 
+/// 未適用のマイグレーションをすべて適用する
+pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    MIGRATOR.run(pool).await?;
+    info!("マイグレーションを適用しました");
+    Ok(())
+}
+
+/// 適用済みマイグレーションを新しい順に`steps`件ロールバックする。ロールバックしたバージョンを返す。
+pub async fn rollback_migrations(pool: &PgPool, steps: usize) -> Result<Vec<i64>> {
+    if steps == 0 {
+        return Ok(Vec::new());
+    }
+
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations ORDER BY version DESC")
+            .fetch_all(pool)
+            .await?;
+
+    let reverted: Vec<i64> = applied.iter().take(steps).copied().collect();
+    // undoは指定したバージョンより新しいものをすべて戻すため、残す中で最新のバージョンを渡す
+    let target = applied.get(steps).copied().unwrap_or(0);
+
+    MIGRATOR.undo(pool, target).await?;
+    info!(?reverted, "マイグレーションをロールバックしました");
+
+    Ok(reverted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +61,49 @@ mod tests {
         let result = create_pool(&db_url).await;
         assert!(result.is_ok(), "テスト用データベースへの接続に失敗");
     }
+
+    /// rssスキーマの列定義を一覧化する
+    async fn snapshot_rss_columns(pool: &PgPool) -> Result<Vec<(String, String, String)>> {
+        let columns = sqlx::query_as::<_, (String, String, String)>(
+            r#"
+            SELECT table_name::text, column_name::text, data_type::text
+            FROM information_schema.columns
+            WHERE table_schema = 'rss'
+            ORDER BY table_name, ordinal_position
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(columns)
+    }
+
+    /// # 検証目的
+    /// 直前のマイグレーションをロールバックして再適用すると、スキーマが元の状態と一致することを確認する。
+    #[tokio::test]
+    async fn ロールバック後の再適用で状態が一致する() -> Result<()> {
+        let _lock = crate::test_support::acquire_db_lock().await;
+        let pool = crate::test_support::prepare_test_pool().await?;
+
+        run_migrations(&pool).await?;
+        let before = snapshot_rss_columns(&pool).await?;
+        let latest = MIGRATOR
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .expect("マイグレーションが存在する");
+
+        let reverted = rollback_migrations(&pool, 1).await?;
+        assert_eq!(reverted, vec![latest]);
+
+        let remaining: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations")
+            .fetch_all(&pool)
+            .await?;
+        assert!(!remaining.contains(&latest));
+        assert_ne!(snapshot_rss_columns(&pool).await?, before);
+
+        run_migrations(&pool).await?;
+        assert_eq!(snapshot_rss_columns(&pool).await?, before);
+
+        Ok(())
+    }
 }
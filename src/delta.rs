@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+
+/// 一致判定に使うブロック長。これより短い一致はリテラルとして扱う
+const BLOCK_SIZE: usize = 32;
+
+const OP_COPY: u8 = 0;
+const OP_INSERT: u8 = 1;
+
+/// `base`から`target`を復元するためのdeltaを計算する
+///
+/// `base`を固定長ブロックで索引化し、`target`を走査して一致区間を`base`からのコピー、
+/// それ以外をリテラル挿入として記録する（bsdiff/xdeltaと同系統の単純な方式）。
+/// 形式は先頭に`target`の長さ（u32 LE）、続いて命令列
+/// （コピー: `0, offset: u32, len: u32` / 挿入: `1, len: u32, bytes`）。
+pub fn encode_delta(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut index: HashMap<&[u8], usize> = HashMap::new();
+    for offset in (0..base.len().saturating_sub(BLOCK_SIZE - 1)).step_by(BLOCK_SIZE) {
+        index
+            .entry(&base[offset..offset + BLOCK_SIZE])
+            .or_insert(offset);
+    }
+
+    let mut delta = Vec::new();
+    delta.extend_from_slice(&(target.len() as u32).to_le_bytes());
+
+    let mut literal: Vec<u8> = Vec::new();
+    let mut position = 0;
+    while position < target.len() {
+        let matched = target
+            .get(position..position + BLOCK_SIZE)
+            .and_then(|block| index.get(block).copied());
+
+        let Some(mut base_start) = matched else {
+            literal.push(target[position]);
+            position += 1;
+            continue;
+        };
+
+        // 直前のリテラルのうち`base`と一致する末尾はコピーに含める
+        let mut target_start = position;
+        while base_start > 0 && literal.last() == Some(&base[base_start - 1]) {
+            literal.pop();
+            base_start -= 1;
+            target_start -= 1;
+        }
+
+        let mut length = position - target_start + BLOCK_SIZE;
+        while base_start + length < base.len()
+            && target_start + length < target.len()
+            && base[base_start + length] == target[target_start + length]
+        {
+            length += 1;
+        }
+
+        push_insert(&mut delta, &mut literal);
+        delta.push(OP_COPY);
+        delta.extend_from_slice(&(base_start as u32).to_le_bytes());
+        delta.extend_from_slice(&(length as u32).to_le_bytes());
+        position = target_start + length;
+    }
+    push_insert(&mut delta, &mut literal);
+
+    delta
+}
+
+/// `encode_delta`で計算したdeltaを`base`に適用して元のデータを復元する
+#[allow(dead_code)] // 復元はload_content_versionからのみ使う
+pub fn apply_delta(base: &[u8], delta: &[u8]) -> Result<Vec<u8>> {
+    let mut reader = delta;
+    let expected_len = read_u32(&mut reader)? as usize;
+    let mut restored = Vec::with_capacity(expected_len);
+
+    while let Some((&op, rest)) = reader.split_first() {
+        reader = rest;
+        match op {
+            OP_COPY => {
+                let offset = read_u32(&mut reader)? as usize;
+                let length = read_u32(&mut reader)? as usize;
+                let chunk = base
+                    .get(offset..offset + length)
+                    .context("deltaのコピー範囲が元データの範囲外です")?;
+                restored.extend_from_slice(chunk);
+            }
+            OP_INSERT => {
+                let length = read_u32(&mut reader)? as usize;
+                if reader.len() < length {
+                    bail!("deltaの挿入データが途中で途切れています");
+                }
+                let (bytes, rest) = reader.split_at(length);
+                restored.extend_from_slice(bytes);
+                reader = rest;
+            }
+            other => bail!("deltaに不明な命令が含まれています: {}", other),
+        }
+    }
+
+    if restored.len() != expected_len {
+        bail!(
+            "deltaの復元結果の長さが一致しません(期待値{}、実際{})",
+            expected_len,
+            restored.len()
+        );
+    }
+
+    Ok(restored)
+}
+
+fn push_insert(delta: &mut Vec<u8>, literal: &mut Vec<u8>) {
+    if literal.is_empty() {
+        return;
+    }
+    delta.push(OP_INSERT);
+    delta.extend_from_slice(&(literal.len() as u32).to_le_bytes());
+    delta.append(literal);
+}
+
+#[allow(dead_code)] // apply_delta専用
+fn read_u32(reader: &mut &[u8]) -> Result<u32> {
+    if reader.len() < 4 {
+        bail!("deltaが途中で途切れています");
+    }
+    let (bytes, rest) = reader.split_at(4);
+    *reader = rest;
+    Ok(u32::from_le_bytes(bytes.try_into()?))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod delta_roundtrip {
+        use anyhow::Result;
+
+        use crate::delta::{apply_delta, encode_delta};
+
+        fn sample_html(paragraphs: usize, marker: &str) -> Vec<u8> {
+            let mut html = String::from("<html><head><title>記事</title></head><body>");
+            for index in 0..paragraphs {
+                html.push_str(&format!(
+                    "<p class=\"body\">段落{}: 同じ定型文が続く記事本文のテキストです。</p>",
+                    index
+                ));
+                if index == paragraphs / 2 {
+                    html.push_str(marker);
+                }
+            }
+            html.push_str("</body></html>");
+            html.into_bytes()
+        }
+
+        /// # 検証目的
+        /// 前バージョンとの差分から復元した本文が元と一致し、deltaがフル保存より小さいことを確認する。
+        #[test]
+        fn deltaから元の本文を復元できる() -> Result<()> {
+            let base = sample_html(200, "<p>更新前の一文</p>");
+            let target = sample_html(200, "<aside>追記された広告枠</aside><p>更新後の一文</p>");
+
+            let delta = encode_delta(&base, &target);
+            assert_eq!(apply_delta(&base, &delta)?, target);
+            assert!(delta.len() < target.len() / 10, "delta長: {}", delta.len());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 空データや共通部分の無いデータ同士でも往復で一致することを確認する。
+        #[test]
+        fn 境界ケースでも往復で一致する() -> Result<()> {
+            let cases: Vec<(Vec<u8>, Vec<u8>)> = vec![
+                (Vec::new(), Vec::new()),
+                (Vec::new(), b"new".to_vec()),
+                (b"old content".to_vec(), Vec::new()),
+                (vec![0u8; 1000], vec![1u8; 999]),
+                (sample_html(50, ""), sample_html(50, "")),
+                (sample_html(50, ""), sample_html(80, "<p>x</p>")),
+            ];
+
+            for (base, target) in cases {
+                let delta = encode_delta(&base, &target);
+                assert_eq!(apply_delta(&base, &delta)?, target);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 途切れたdeltaや別の元データへの適用はエラーになることを確認する。
+        #[test]
+        fn 壊れたdeltaはエラーになる() {
+            let base = sample_html(50, "");
+            let target = sample_html(50, "<p>追記</p>");
+            let delta = encode_delta(&base, &target);
+
+            assert!(apply_delta(&base, &delta[..delta.len() - 1]).is_err());
+            assert!(apply_delta(b"short", &delta).is_err());
+            assert!(apply_delta(&base, &[0, 0]).is_err());
+        }
+    }
+}
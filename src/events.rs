@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::fetch_content::FetchContentProgress;
+
+/// ハブが保持する未配信イベントの上限。受信が追いつかないクライアントは超過分を取りこぼし、`lagged`通知を受け取る
+pub(crate) const EVENT_HUB_CAPACITY: usize = 256;
+
+/// ハブが配信するイベントの種類（JSONの`event`フィールド）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HubEventKind {
+    /// fetch-contentのエントリの結果が確定した（`FetchContentProgress`）
+    ContentProgress,
+    /// fetch-contentが完了した（`FetchContentSummary`）
+    ContentSummary,
+    /// fetch-contentの実行自体が失敗した
+    ContentError,
+    /// fetch-rssが完了した（`FetchRssSummary`）
+    RssSummary,
+}
+
+impl HubEventKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            HubEventKind::ContentProgress => "fetch_content_progress",
+            HubEventKind::ContentSummary => "fetch_content_summary",
+            HubEventKind::ContentError => "fetch_content_error",
+            HubEventKind::RssSummary => "fetch_rss_summary",
+        }
+    }
+}
+
+/// API経由で実行したfetch-content/fetch-rssのイベントを購読者へファンアウトするハブ。
+/// イベントは`{"event": "...", "data": {...}}`のJSON文字列として1度だけ直列化し、全購読者で共有する。
+#[derive(Debug, Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<Arc<str>>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_HUB_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl EventHub {
+    /// イベントを配信する。購読者がいなければ何もしない
+    pub fn publish<T: Serialize>(&self, kind: HubEventKind, data: &T) {
+        if self.receiver_count() == 0 {
+            return;
+        }
+        match serde_json::to_string(&json!({ "event": kind.as_str(), "data": data })) {
+            Ok(text) => {
+                let _ = self.sender.send(Arc::from(text));
+            }
+            Err(e) => warn!(error = %e, event = kind.as_str(), "イベントの直列化に失敗しました"),
+        }
+    }
+
+    /// 以降に配信されるイベントを受け取る
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<str>> {
+        self.sender.subscribe()
+    }
+
+    /// 現在の購読者数
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// fetch-contentの進捗を受け取り、ハブへ配信する送信口を作る。
+    /// `also`を指定すると同じ進捗をそちらへも転送する（SSEと併用する場合）。
+    /// 送信口をすべてdropすると転送タスクは残りを配信し終えて終了する（完了を待つには戻り値のJoinHandleをawaitする）。
+    pub fn forward_progress(
+        &self,
+        also: Option<UnboundedSender<FetchContentProgress>>,
+    ) -> (UnboundedSender<FetchContentProgress>, JoinHandle<()>) {
+        let (tx, mut rx) = mpsc::unbounded_channel::<FetchContentProgress>();
+        let hub = self.clone();
+        let handle = tokio::spawn(async move {
+            while let Some(progress) = rx.recv().await {
+                hub.publish(HubEventKind::ContentProgress, &progress);
+                if let Some(also) = &also {
+                    let _ = also.send(progress);
+                }
+            }
+        });
+        (tx, handle)
+    }
+}
+
+/// 購読したイベントをWebSocketクライアントへ送り続ける。
+/// クライアントの切断（Close・接続断）や送信失敗を検知したら抜け、購読を解除する。
+/// 取りこぼしが発生した場合は`{"event": "lagged", "data": {"skipped": n}}`を送って続ける。
+pub(crate) async fn forward_to_websocket(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<Arc<str>>,
+) {
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let text = match event {
+                    Ok(text) => text.to_string(),
+                    Err(RecvError::Lagged(skipped)) => {
+                        json!({ "event": "lagged", "data": { "skipped": skipped } }).to_string()
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if socket.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                // クライアントからのメッセージは使わない（Pingへの応答はaxumが行う）
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
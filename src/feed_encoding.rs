@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+use tracing::warn;
+
+/// XML宣言の`encoding`属性（宣言はフィード先頭にあるため、BOMを除いた先頭だけを見る）
+static XML_ENCODING_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*<\?xml[^>]*?\sencoding\s*=\s*["']([A-Za-z0-9._:-]+)["']"#)
+        .expect("valid xml encoding regex")
+});
+
+/// XML宣言を探す範囲（バイト数）
+const XML_DECLARATION_SCAN_BYTES: usize = 1024;
+
+/// XML宣言に書かれたエンコードを返す（宣言が無い・未知のラベルならNone）
+pub(crate) fn declared_feed_encoding(content: &[u8]) -> Option<&'static Encoding> {
+    let content = strip_utf8_bom(content);
+    let head = &content[..content.len().min(XML_DECLARATION_SCAN_BYTES)];
+    let label = XML_ENCODING_PATTERN.captures(head)?.get(1)?;
+    Encoding::for_label(label.as_bytes())
+}
+
+/// フィードの実際のエンコードを推定する。
+///
+/// BOMがあればBOMに従う。ASCIIのみなら宣言と矛盾しないため宣言（ASCII非互換の宣言や未宣言ならUTF-8）を使い、
+/// UTF-8として妥当ならUTF-8とする。いずれでもなければ`chardetng`でバイトパターンから推定する。
+pub(crate) fn detect_feed_encoding(
+    content: &[u8],
+    declared: Option<&'static Encoding>,
+) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(content) {
+        return encoding;
+    }
+    if content.is_ascii() {
+        return declared
+            .filter(|encoding| encoding.is_ascii_compatible())
+            .unwrap_or(UTF_8);
+    }
+    if std::str::from_utf8(content).is_ok() {
+        return UTF_8;
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(content, true);
+    detector.guess(None, false)
+}
+
+/// フィードを実際のエンコードで読み、XML宣言の`encoding`をUTF-8へ書き換えたUTF-8のバイト列を返す。
+///
+/// 宣言と推定結果が食い違う場合は推定結果を優先し、警告ログを出す。UTF-8のまま読めるフィードはコピーしない。
+pub(crate) fn normalize_feed_encoding<'a>(content: &'a [u8], group: Option<&str>) -> Cow<'a, [u8]> {
+    let declared = declared_feed_encoding(content);
+    let detected = detect_feed_encoding(content, declared);
+
+    if let Some(declared) = declared.filter(|declared| *declared != detected) {
+        warn!(
+            group = group.unwrap_or_default(),
+            declared = declared.name(),
+            detected = detected.name(),
+            "フィードの宣言と実際のエンコードが異なるため推定結果で読み込みます"
+        );
+    }
+
+    let has_bom = Encoding::for_bom(content).is_some();
+    if detected == UTF_8 && declared.is_none_or(|declared| declared == UTF_8) && !has_bom {
+        return Cow::Borrowed(content);
+    }
+
+    let (text, _) = detected.decode_with_bom_removal(content);
+    let text = text.as_bytes();
+    let rewritten = match XML_ENCODING_PATTERN
+        .captures(text)
+        .and_then(|caps| caps.get(1))
+    {
+        Some(label) => [
+            &text[..label.start()],
+            b"UTF-8".as_slice(),
+            &text[label.end()..],
+        ]
+        .concat(),
+        None => text.to_vec(),
+    };
+    Cow::Owned(rewritten)
+}
+
+fn strip_utf8_bom(content: &[u8]) -> &[u8] {
+    content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(content)
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod detect_feed_encoding {
+        use encoding_rs::{SHIFT_JIS, UTF_16LE, UTF_8};
+
+        use crate::feed_encoding::{declared_feed_encoding, detect_feed_encoding};
+
+        /// # 検証目的
+        /// BOM・ASCIIのみ・UTF-8・それ以外のバイト列で、それぞれ想定どおりのエンコードを推定することを確認する。
+        #[test]
+        fn バイト列から実際のエンコードを推定する() {
+            let declared = Some(SHIFT_JIS);
+            assert_eq!(detect_feed_encoding(b"\xFF\xFE<\x00", declared), UTF_16LE);
+            assert_eq!(detect_feed_encoding(b"<rss></rss>", declared), SHIFT_JIS);
+            assert_eq!(detect_feed_encoding(b"<rss></rss>", None), UTF_8);
+            assert_eq!(
+                detect_feed_encoding("<title>日本語</title>".as_bytes(), declared),
+                UTF_8
+            );
+
+            let (sjis, _, _) =
+                SHIFT_JIS.encode("<title>東京都で新しい交通システムの運用が始まりました。</title>");
+            assert_eq!(detect_feed_encoding(&sjis, Some(UTF_8)), SHIFT_JIS);
+        }
+
+        /// # 検証目的
+        /// XML宣言の`encoding`を大文字小文字・引用符・BOMの有無によらず読み取れることを確認する。
+        #[test]
+        fn xml宣言のエンコードを読み取る() {
+            assert_eq!(
+                declared_feed_encoding(br#"<?xml version="1.0" encoding="UTF-8"?><rss/>"#),
+                Some(UTF_8)
+            );
+            assert_eq!(
+                declared_feed_encoding(b"\xEF\xBB\xBF<?xml version='1.0' encoding='shift_jis'?>"),
+                Some(SHIFT_JIS)
+            );
+            assert_eq!(declared_feed_encoding(br#"<?xml version="1.0"?>"#), None);
+            assert_eq!(declared_feed_encoding(b"<rss></rss>"), None);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+use tokio::sync::Mutex;
+
+/// 水平スケールした複数インスタンスが同じフィードを同時に取得しないためのフィード単位のadvisory lock。
+///
+/// `pg_try_advisory_lock(hashtext(feed_url))`のセッションロックを1本の専用接続にまとめて保持する。
+/// フィードごとに接続を握ると同時取得数だけ接続を占有し、プールを使い切ってupsertが詰まるため。
+pub(crate) struct FeedLocks {
+    conn: Mutex<Option<PoolConnection<Postgres>>>,
+}
+
+impl FeedLocks {
+    /// ロック用の接続をプールから1本確保する
+    pub(crate) async fn acquire(pool: &PgPool) -> Result<Self> {
+        let conn = pool.acquire().await?;
+        Ok(Self {
+            conn: Mutex::new(Some(conn)),
+        })
+    }
+
+    /// フィードのロックを試みる。取れればtrue、他のインスタンスが保持中ならfalseを返す
+    pub(crate) async fn try_lock(&self, feed_url: &str) -> Result<bool> {
+        let mut guard = self.conn.lock().await;
+        let conn = guard
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("ロック用の接続は解放済みです"))?;
+        let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext($1))")
+            .bind(feed_url)
+            .fetch_one(&mut **conn)
+            .await?;
+        Ok(locked)
+    }
+
+    /// `try_lock`で取ったロックを解放する
+    pub(crate) async fn unlock(&self, feed_url: &str) -> Result<()> {
+        let mut guard = self.conn.lock().await;
+        let conn = guard
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("ロック用の接続は解放済みです"))?;
+        sqlx::query("SELECT pg_advisory_unlock(hashtext($1))")
+            .bind(feed_url)
+            .execute(&mut **conn)
+            .await?;
+        Ok(())
+    }
+
+    /// 残っているロックをすべて解放し、接続をプールへ返す
+    pub(crate) async fn release(self) -> Result<()> {
+        let Some(mut conn) = self.conn.lock().await.take() else {
+            return Ok(());
+        };
+        if let Err(e) = sqlx::query("SELECT pg_advisory_unlock_all()")
+            .execute(&mut *conn)
+            .await
+        {
+            // ロックが残ったままプールへ戻さないよう、接続ごと閉じる
+            drop(conn.detach());
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FeedLocks {
+    fn drop(&mut self) {
+        // releaseせずに破棄された（処理が中断された）場合は、セッションを閉じてロックを手放す
+        if let Some(conn) = self.conn.get_mut().take() {
+            drop(conn.detach());
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+
+/// rss.feed_metaのモデル（フィード単位のメタ情報）
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct FeedMeta {
+    pub group: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub url: String,
+    pub icon_url: Option<String>,
+}
+
+/// フィードのメタ情報を保存する。icon_urlが取得できなかった場合は既存値を残す。
+pub async fn upsert_feed_meta(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+    icon_url: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rss.feed_meta ("group", name, url, icon_url)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT ("group", name)
+        DO UPDATE SET
+            url = EXCLUDED.url,
+            icon_url = COALESCE(EXCLUDED.icon_url, rss.feed_meta.icon_url),
+            updated_at = NOW()
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .bind(icon_url)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// 前回処理時に記録したフィードのlast-build-dateを取得する。URLが変わったフィードは未記録として扱う。
+pub async fn find_feed_last_build_date(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+) -> Result<Option<DateTime<Utc>>> {
+    // entry数の履歴だけを記録した行はlast_build_dateがNULL
+    let last_build_date: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+        r#"
+        SELECT last_build_date
+        FROM rss.feed_cache
+        WHERE "group" = $1 AND name = $2 AND url = $3
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(last_build_date.flatten())
+}
+
+/// フィードのlast-build-dateを記録する（entryの取り込みに成功した後に呼ぶ）
+pub async fn upsert_feed_last_build_date(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+    last_build_date: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rss.feed_cache ("group", name, url, last_build_date)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT ("group", name)
+        DO UPDATE SET
+            url = EXCLUDED.url,
+            last_build_date = EXCLUDED.last_build_date,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .bind(last_build_date)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// フィードの取得状況（休眠の判定に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRow)]
+pub struct FeedActivity {
+    /// 最後に新規entryを追加した時刻（記録前はNULL）
+    pub last_entry_new_at: Option<DateTime<Utc>>,
+    /// 最後にフィードを取得した時刻（記録前はNULL）
+    pub last_fetched_at: Option<DateTime<Utc>>,
+}
+
+/// 記録済みのフィードの取得状況を取得する。URLが変わったフィードは未記録として扱う。
+pub async fn find_feed_activity(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+) -> Result<Option<FeedActivity>> {
+    let activity = sqlx::query_as::<_, FeedActivity>(
+        r#"
+        SELECT last_entry_new_at, last_fetched_at
+        FROM rss.feed_cache
+        WHERE "group" = $1 AND name = $2 AND url = $3
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(activity)
+}
+
+/// フィードを取得した時刻を記録する。
+///
+/// `has_new_entries`なら新規entryの追加時刻も今回にする。追加時刻が未記録のフィードは今回を起点にし、
+/// そこから新規entryの無い期間を数える。
+pub async fn record_feed_activity(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+    has_new_entries: bool,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rss.feed_cache ("group", name, url, last_entry_new_at, last_fetched_at)
+        VALUES ($1, $2, $3, NOW(), NOW())
+        ON CONFLICT ("group", name)
+        DO UPDATE SET
+            url = EXCLUDED.url,
+            last_entry_new_at = CASE
+                WHEN $4 THEN NOW()
+                ELSE COALESCE(rss.feed_cache.last_entry_new_at, NOW())
+            END,
+            last_fetched_at = NOW(),
+            updated_at = NOW()
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .bind(has_new_entries)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// 今回取得したフィードのentry数を履歴へ追加し、追加前の履歴（古い順）を返す。
+///
+/// 履歴は直近`history_size`回分だけ残す。URLが変わったフィードは別物として履歴（と取得状況）を捨て、空の履歴を返す。
+pub async fn record_feed_entry_count(
+    pool: &PgPool,
+    group: &str,
+    name: &str,
+    url: &str,
+    entry_count: i32,
+    history_size: usize,
+) -> Result<Vec<i32>> {
+    let mut tx = pool.begin().await?;
+
+    let previous: Option<Vec<i32>> = sqlx::query_scalar(
+        r#"
+        SELECT entry_counts
+        FROM rss.feed_cache
+        WHERE "group" = $1 AND name = $2 AND url = $3
+        FOR UPDATE
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let previous = previous.unwrap_or_default();
+
+    let mut entry_counts = previous.clone();
+    entry_counts.push(entry_count);
+    let overflow = entry_counts.len().saturating_sub(history_size.max(1));
+    entry_counts.drain(..overflow);
+
+    sqlx::query(
+        r#"
+        INSERT INTO rss.feed_cache ("group", name, url, entry_counts)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT ("group", name)
+        DO UPDATE SET
+            url = EXCLUDED.url,
+            last_build_date = CASE
+                WHEN rss.feed_cache.url = EXCLUDED.url THEN rss.feed_cache.last_build_date
+            END,
+            entry_counts = EXCLUDED.entry_counts,
+            last_entry_new_at = CASE
+                WHEN rss.feed_cache.url = EXCLUDED.url THEN rss.feed_cache.last_entry_new_at
+            END,
+            last_fetched_at = CASE
+                WHEN rss.feed_cache.url = EXCLUDED.url THEN rss.feed_cache.last_fetched_at
+            END,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(group)
+    .bind(name)
+    .bind(url)
+    .bind(&entry_counts)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(previous)
+}
+
+/// 保存済みのフィードメタ情報をgroup・name順で取得する
+pub async fn search_feed_meta(pool: &PgPool) -> Result<Vec<FeedMeta>> {
+    let feeds = sqlx::query_as::<_, FeedMeta>(
+        r#"
+        SELECT "group", name, created_at, updated_at, url, icon_url
+        FROM rss.feed_meta
+        ORDER BY "group", name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(feeds)
+}
+
+/// フィードURLのホストから`/favicon.ico`の推定URLを組み立てる
+pub(crate) fn guess_favicon_url(feed_url: &str) -> Option<String> {
+    let mut url = Url::parse(feed_url).ok()?;
+    url.host_str()?;
+    url.set_path("/favicon.ico");
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod guess_favicon_url {
+        use crate::feeds::guess_favicon_url;
+
+        /// # 検証目的
+        /// フィードURLのスキーム・ホスト・ポートを保ったまま/favicon.icoを推定することを確認する。
+        #[test]
+        fn フィードホストのfaviconを推定する() {
+            assert_eq!(
+                guess_favicon_url("https://example.com/news/rss.xml?lang=ja").as_deref(),
+                Some("https://example.com/favicon.ico")
+            );
+            assert_eq!(
+                guess_favicon_url("http://127.0.0.1:8080/feed").as_deref(),
+                Some("http://127.0.0.1:8080/favicon.ico")
+            );
+            assert_eq!(guess_favicon_url("not a url"), None);
+        }
+    }
+}
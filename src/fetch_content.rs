@@ -1,57 +1,362 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::api::{parse_group_filter, parse_relative_duration};
+use crate::article_links::{extract_article_links, save_article_links};
+use crate::compression::{compress_content, ContentCompression, BROTLI_QUALITY};
+use crate::content_versions::archive_content_version;
+use crate::fetch_jobs::{
+    defer_denied_fetch_jobs, finish_fetch_job, release_fetch_jobs, reset_fetch_job,
+    schedule_fetch_job_retry, FetchJobStatus, FETCH_JOB_STALE_SECS,
+};
 use crate::models::{Queue, ScrapeRequest, ScrapeResponse};
+use crate::proxy::apply_proxy;
+use crate::rate_limit::RateLimiter;
+use crate::summarize::{html_to_text, request_summary, save_article_summary};
+use crate::warc::{build_warc_gz_blocking, save_article_warc, ContentArchive, WarcCapture};
+use crate::webhook::{
+    ArticleBatchNotifier, WebhookBatchOptions, WebhookResult, DEFAULT_WEBHOOK_MAX_ENTRIES,
+};
 use anyhow::{Context, Result};
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Postgres, Transaction};
-use tracing::{error, info, warn};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 15;
+/// 事前チェックのHEADリクエストのタイムアウト（本番のスクレイプより短くする）
+const PRECHECK_TIMEOUT_SECS: u64 = 5;
+/// スクレイピングAPIレスポンスとして読み込む最大バイト数
+pub(crate) const SCRAPING_MAX_RESPONSE_BYTES: usize = 20 * 1024 * 1024;
+/// fetch_logに記録するfetch-contentのコマンド名
+pub(crate) const FETCH_CONTENT_LOG_COMMAND: &str = "fetch_content";
+/// 再取得を諦めたことを表すstatus_code（手動更新APIで設定する想定）
+pub(crate) const GIVE_UP_STATUS_CODE: i32 = 410;
+/// 既定では1件ごとにコミットする
+pub(crate) const DEFAULT_BATCH_SIZE: usize = 1;
+
+/// fetch-contentの挙動を調整する設定（環境変数から組み立てる）
+#[derive(Debug, Clone)]
+pub struct FetchContentOptions {
+    /// フィード側で未指定のときにscraping APIへ渡すAccept-Language
+    pub accept_language: Option<String>,
+    /// 何件分の取得結果を1トランザクションでコミットするか（1なら1件ごと）。
+    /// 大きくするとDB往復が減る一方、コミット前に異常終了するとバッチ内の結果がまとめて失われ、
+    /// 保存に失敗した場合もバッチ内の全件が保存エラー扱いになる（いずれも次回の再取得対象に残る）。
+    pub batch_size: usize,
+    /// 本文取得をスキップするホスト（`*.example.com`でサブドメインに一致）
+    pub deny_hosts: Vec<String>,
+    /// scraping APIへの接続に使うプロキシURL（未指定なら直接接続）
+    pub proxy_url: Option<String>,
+    /// 本文を上書きする際、過去バージョンを次のバージョンとのdeltaで保存する（無効ならフル保存）
+    pub delta_encoding: bool,
+    /// 生HTMLではなくreadabilityで抽出した本文エリアを保存する（抽出失敗時は生HTML）
+    pub extract_main_content: bool,
+    /// Webhook通知に含める`entries`の上限件数（超過分は件数のみ通知）
+    pub webhook_max_entries: usize,
+    /// 本文の圧縮方式
+    pub compression: ContentCompression,
+    /// 取得したHTMLのアーカイブ方式（WARCなら`rss.article_warc`へ保存する）
+    pub archive: ContentArchive,
+    /// 本文を保存した記事の要約を生成する要約APIのURL（未指定なら要約しない）
+    pub summary_api_url: Option<String>,
+    /// scraping APIから受け取ったHTMLを`<dir>/<queue_id>.html`へ書き出すデバッグ用ディレクトリ（本番では未指定）
+    pub debug_dir: Option<PathBuf>,
+    /// 取得対象の絞り込み条件（実行ごとにCLI引数・APIリクエストから指定する）
+    pub filter: FetchContentFilter,
+    /// scraping APIを呼ぶ前に記事URLへHEADを送り、4xx（405を除く）ならスクレイプせずstatus_codeのみ記録する
+    pub precheck_url: bool,
+    /// 本文中の`<a href>`を抽出して`rss.article_links`へ保存する
+    pub extract_links: bool,
+    /// scraping APIのレスポンスJSON全体を`rss.article_content.raw_response`へそのまま保存する
+    pub raw_response: bool,
+    /// 本文を初めて保存した記事・本文が変わった記事ごとに`article_created`/`article_updated`のWebhookを送る
+    pub notify_on_update: bool,
+    /// 保存に成功したエントリを実行中にまとめて`articles_batch`で通知する（未指定なら送らない）
+    pub webhook_batch: Option<WebhookBatchOptions>,
+    /// scraping API全体のQPS制限（未指定なら制限しない）
+    pub scrape_rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl Default for FetchContentOptions {
+    fn default() -> Self {
+        Self {
+            accept_language: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            deny_hosts: Vec::new(),
+            proxy_url: None,
+            delta_encoding: false,
+            extract_main_content: false,
+            webhook_max_entries: DEFAULT_WEBHOOK_MAX_ENTRIES,
+            compression: ContentCompression::default(),
+            archive: ContentArchive::default(),
+            summary_api_url: None,
+            debug_dir: None,
+            filter: FetchContentFilter::default(),
+            precheck_url: false,
+            extract_links: false,
+            raw_response: false,
+            notify_on_update: false,
+            webhook_batch: None,
+            scrape_rate_limiter: None,
+        }
+    }
+}
+
+/// fetch-contentの取得対象を絞り込む条件。指定した項目はすべてAND条件になり、未指定の項目では絞り込まない。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchContentFilter {
+    /// いずれかのgroupに属するエントリ
+    pub groups: Vec<String>,
+    /// この日時以降にqueueへ追加されたエントリ
+    pub created_after: Option<DateTime<Utc>>,
+    /// queueの`status_code`がいずれかに一致するエントリ
+    pub statuses: Vec<QueueStatusFilter>,
+}
+
+impl FetchContentFilter {
+    /// カンマ区切りの文字列表現から組み立てる（CLI引数・APIリクエストで共通）
+    ///
+    /// - `groups`: `world,tech`
+    /// - `created_after`: RFC3339の日時、または`24h`のような現在からの相対期間
+    /// - `statuses`: `unfetched,503`（`unfetched`は`status_code`がNULL）
+    pub fn parse(
+        groups: Option<&str>,
+        created_after: Option<&str>,
+        statuses: Option<&str>,
+    ) -> Result<Self> {
+        let groups = groups.map(parse_group_filter).unwrap_or_default();
+
+        let created_after = match created_after.map(str::trim) {
+            Some(value) => Some(match DateTime::parse_from_rfc3339(value) {
+                Ok(datetime) => datetime.with_timezone(&Utc),
+                Err(_) => {
+                    let duration = parse_relative_duration(value).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "created_afterはRFC3339の日時か24hのような相対期間で指定してください: {}",
+                            value
+                        )
+                    })?;
+                    Utc::now() - duration
+                }
+            }),
+            None => None,
+        };
+
+        let statuses = match statuses {
+            Some(value) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|status| !status.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<QueueStatusFilter>>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            groups,
+            created_after,
+            statuses,
+        })
+    }
+}
+
+/// 取得対象とするqueueの`status_code`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueStatusFilter {
+    /// 未取得（`status_code`がNULL）
+    Unfetched,
+    /// 指定したstatus_codeで記録済み
+    Code(i32),
+}
+
+impl std::str::FromStr for QueueStatusFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("unfetched") {
+            return Ok(QueueStatusFilter::Unfetched);
+        }
+        match value.parse::<i32>() {
+            Ok(code) if (100..=599).contains(&code) => Ok(QueueStatusFilter::Code(code)),
+            _ => Err(anyhow::anyhow!(
+                "無効なstatus指定: {} (有効な値: unfetched, 100〜599のstatus_code)",
+                value
+            )),
+        }
+    }
+}
 
 enum ScrapeResult {
-    Success(ScrapeResponse),
-    HttpError { status_code: i32 },
+    Success {
+        response: ScrapeResponse,
+        /// デコード前のレスポンスボディ（`raw_response`有効時にそのまま保存する）
+        raw_body: Vec<u8>,
+        /// HTTPステータスとボディの`status_code`が食い違っていた
+        status_mismatch: bool,
+    },
+    HttpError {
+        status_code: i32,
+    },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FetchContentEntryReport {
     pub queue_id: Uuid,
     pub title: String,
+    pub group: Option<String>,
     pub result: FetchContentEntryOutcome,
+    /// scraping APIのHTTPステータスとボディの`status_code`が食い違っていた（判定はボディ側を信頼）
+    #[serde(default)]
+    pub status_mismatch: bool,
+    /// 本文を保存した場合の、保存済み本文からの変化（`content_sha256`で判定）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_change: Option<ContentChange>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 本文の保存による変化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentChange {
+    /// 初めて本文を保存した
+    Created,
+    /// 保存済みの本文と内容が変わった
+    Updated,
+    /// 保存済みの本文と同じ内容だった
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FetchContentEntryOutcome {
-    Saved { status_code: i32 },
-    StatusOnly { status_code: i32 },
-    ApiError { message: String },
-    PersistError { message: String },
+    Saved {
+        status_code: i32,
+    },
+    StatusOnly {
+        status_code: i32,
+    },
+    ApiError {
+        message: String,
+    },
+    PersistError {
+        message: String,
+    },
+    /// 前回の取得から未更新（scraping APIが304を返した）のため本文を書き換えなかった
+    NotModified,
+    /// 除外ホストのためscraping APIを呼ばなかった
+    Skipped {
+        host: String,
+    },
+}
+
+/// エントリの結果が確定するたびに送る進捗（バッチコミット時は保存後にまとめて届く）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchContentProgress {
+    /// 結果が確定したエントリ数（このエントリを含む）
+    pub completed: usize,
+    /// 処理対象のエントリ数
+    pub total: usize,
+    pub entry: FetchContentEntryReport,
+}
+
+/// グループ単位の取得結果の集計
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchContentGroupSummary {
+    pub group: Option<String>,
+    pub saved: usize,
+    pub status_only: usize,
+    pub error: usize,
+    pub skipped: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FetchContentSummary {
+    /// 実行ごとに一意なID（Webhook受信側での重複排除、fetch_logとの突き合わせに使う）
+    #[serde(default)]
+    pub run_id: Uuid,
     pub saved_count: usize,
     pub status_only_count: usize,
     pub error_count: usize,
+    pub skipped_count: usize,
+    /// キャンセル（終了シグナル）により着手しなかったエントリ数。ジョブはpendingへ戻して次回に回す
+    #[serde(default)]
+    pub cancelled_count: usize,
+    pub by_group: Vec<FetchContentGroupSummary>,
     pub entries: Vec<FetchContentEntryReport>,
+    /// Webhookの送信結果（API経由で実行し、Webhookが設定されている場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookResult>,
 }
 
 impl FetchContentSummary {
-    fn new() -> Self {
+    fn new(run_id: Uuid) -> Self {
         Self {
+            run_id,
             saved_count: 0,
             status_only_count: 0,
             error_count: 0,
+            skipped_count: 0,
+            cancelled_count: 0,
+            by_group: Vec::new(),
             entries: Vec::new(),
+            webhook: None,
+        }
+    }
+}
+
+/// エントリの処理結果をqueueのgroupごとに集計する（groupなしはNULLとして1グループにまとめる）
+pub(crate) fn summarize_by_group(
+    entries: &[FetchContentEntryReport],
+) -> Vec<FetchContentGroupSummary> {
+    let mut grouped: BTreeMap<Option<&str>, FetchContentGroupSummary> = BTreeMap::new();
+
+    for entry in entries {
+        let group =
+            grouped
+                .entry(entry.group.as_deref())
+                .or_insert_with(|| FetchContentGroupSummary {
+                    group: entry.group.clone(),
+                    saved: 0,
+                    status_only: 0,
+                    error: 0,
+                    skipped: 0,
+                });
+
+        match entry.result {
+            FetchContentEntryOutcome::Saved { .. } => group.saved += 1,
+            FetchContentEntryOutcome::StatusOnly { .. } | FetchContentEntryOutcome::NotModified => {
+                group.status_only += 1
+            }
+            FetchContentEntryOutcome::ApiError { .. }
+            | FetchContentEntryOutcome::PersistError { .. } => group.error += 1,
+            FetchContentEntryOutcome::Skipped { .. } => group.skipped += 1,
         }
     }
+
+    grouped.into_values().collect()
 }
 
 /// fetch-contentコマンドのメイン処理
-pub async fn run(pool: PgPool, limit: i64, api_url: &str, webhook_url: Option<&str>) -> Result<()> {
+pub async fn run(
+    pool: PgPool,
+    limit: i64,
+    api_url: &str,
+    options: &FetchContentOptions,
+    webhook_url: Option<&str>,
+    cancel: &CancellationToken,
+) -> Result<()> {
     info!("status_code=NULLまたは非200のエントリを取得中...");
-    let summary = execute_fetch_content(&pool, limit, api_url).await?;
+    let summary =
+        execute_fetch_content_with_progress(&pool, limit, api_url, options, None, cancel).await?;
 
     if summary.entries.is_empty() {
         info!("処理対象のエントリがありません");
@@ -61,13 +366,40 @@ pub async fn run(pool: PgPool, limit: i64, api_url: &str, webhook_url: Option<&s
     info!("{}件のエントリを処理します", summary.entries.len());
     log_fetch_content_summary(&summary);
 
-    if let Err(e) = crate::webhook::notify_fetch_content(webhook_url, &summary, "cli").await {
-        warn!(error = %e, "Webhook送信に失敗しました(fetch-content)");
+    if let Some(result) = crate::webhook::notify_fetch_content(
+        webhook_url,
+        &summary,
+        "cli",
+        options.webhook_max_entries,
+    )
+    .await
+    {
+        if !result.sent {
+            warn!(error = ?result.error, "Webhook送信に失敗しました(fetch-content)");
+        }
     }
+    notify_content_changes(options, webhook_url, &summary, "cli").await;
 
     Ok(())
 }
 
+/// `notify_on_update`が有効なら、本文を新規保存・更新した記事ごとにWebhookを送る（失敗は警告ログのみ）
+pub(crate) async fn notify_content_changes(
+    options: &FetchContentOptions,
+    webhook_url: Option<&str>,
+    summary: &FetchContentSummary,
+    source: &str,
+) {
+    if !options.notify_on_update {
+        return;
+    }
+
+    let results = crate::webhook::notify_article_changes(webhook_url, summary, source).await;
+    for result in results.iter().filter(|result| !result.sent) {
+        warn!(error = ?result.error, "Webhook送信に失敗しました(記事の更新通知)");
+    }
+}
+
 pub(crate) fn log_fetch_content_summary(summary: &FetchContentSummary) {
     for entry in &summary.entries {
         match &entry.result {
@@ -87,6 +419,13 @@ pub(crate) fn log_fetch_content_summary(summary: &FetchContentSummary) {
                     "status_codeのみ記録"
                 );
             }
+            FetchContentEntryOutcome::NotModified => {
+                info!(
+                    queue_id = %entry.queue_id,
+                    title = %entry.title,
+                    "前回の取得から未更新のため本文の保存をスキップ"
+                );
+            }
             FetchContentEntryOutcome::ApiError { message } => {
                 error!(
                     queue_id = %entry.queue_id,
@@ -103,127 +442,675 @@ pub(crate) fn log_fetch_content_summary(summary: &FetchContentSummary) {
                     "保存エラー"
                 );
             }
+            FetchContentEntryOutcome::Skipped { host } => {
+                info!(
+                    queue_id = %entry.queue_id,
+                    title = %entry.title,
+                    %host,
+                    "除外ホストのためスキップ"
+                );
+            }
         }
     }
 
+    for group in &summary.by_group {
+        info!(
+            group = group.group.as_deref().unwrap_or("(none)"),
+            saved = group.saved,
+            status_only = group.status_only,
+            errors = group.error,
+            skipped = group.skipped,
+            "グループ別の処理結果"
+        );
+    }
+
     info!(
         saved = summary.saved_count,
         status_only = summary.status_only_count,
         errors = summary.error_count,
+        skipped = summary.skipped_count,
+        cancelled = summary.cancelled_count,
         "処理完了"
     );
 }
 
-/// fetch-contentのメインロジックを実行し、結果を返す
+/// テスト用に、進捗を送らずキャンセルもせずにfetch-contentを最後まで実行して結果を返す
+/// （CLI・APIはキャンセル可能な`execute_fetch_content_with_progress`を使う）
+#[cfg(test)]
 pub async fn execute_fetch_content(
     pool: &PgPool,
     limit: i64,
     api_url: &str,
+    options: &FetchContentOptions,
+) -> Result<FetchContentSummary> {
+    execute_fetch_content_with_progress(
+        pool,
+        limit,
+        api_url,
+        options,
+        None,
+        &CancellationToken::new(),
+    )
+    .await
+}
+
+/// fetch-contentを実行し、エントリの結果が確定するたびに`progress`へ進捗を送る。
+/// 受信側が切断していても処理は最後まで続ける。
+///
+/// `cancel`がキャンセルされると新しいエントリには着手せず、着手済みのエントリを保存まで終えてから返す。
+/// 保存はエントリ（バッチ）単位のトランザクションのため、途中で止めても中途半端な状態は残らない。
+/// 着手しなかったエントリのジョブはpendingへ戻す。
+#[instrument(
+    name = "execute_fetch_content",
+    skip(pool, api_url, options, progress, cancel),
+    fields(run_id)
+)]
+pub async fn execute_fetch_content_with_progress(
+    pool: &PgPool,
+    limit: i64,
+    api_url: &str,
+    options: &FetchContentOptions,
+    progress: Option<&UnboundedSender<FetchContentProgress>>,
+    cancel: &CancellationToken,
 ) -> Result<FetchContentSummary> {
-    let entries = search_queue_entries_for_fetch(pool, limit).await?;
+    let run_id = Uuid::new_v4();
+    tracing::Span::current().record("run_id", tracing::field::display(run_id));
+
+    let entries = search_queue_entries_for_fetch(pool, limit, &options.filter).await?;
 
     if entries.is_empty() {
-        return Ok(FetchContentSummary::new());
+        return Ok(FetchContentSummary::new(run_id));
     }
 
-    let client = Client::new();
-    let mut summary = FetchContentSummary::new();
+    let total = entries.len();
+    let mut completed = 0;
+    let mut report_progress = |report: &FetchContentEntryReport| {
+        completed += 1;
+        if let Some(sender) = progress {
+            // 受信側が切断済みなら送れないだけなので無視する
+            let _ = sender.send(FetchContentProgress {
+                completed,
+                total,
+                entry: report.clone(),
+            });
+        }
+    };
 
-    for entry in entries {
-        let request = ScrapeRequest {
-            url: entry.link.clone(),
-            wait_for_selector: None,
-            timeout: Some(DEFAULT_TIMEOUT_SECS),
-        };
+    let client = apply_proxy(Client::builder(), options.proxy_url.as_deref())?.build()?;
+    let mut summary = FetchContentSummary::new(run_id);
+    let batch_size = options.batch_size.max(1);
+    let mut pending: Vec<PendingWrite> = Vec::with_capacity(batch_size);
+
+    // 除外ホスト・キャンセルで取得しなかったエントリ（ジョブをpendingへ戻す）
+    let mut released_ids = Vec::new();
+    let mut denied_ids = Vec::new();
+    // 要約APIが設定されている場合に、本文を保存した記事を後でまとめて要約するための情報
+    let mut summary_targets: Vec<SummaryTarget> = Vec::new();
+    let mut batch_notifier = options
+        .webhook_batch
+        .clone()
+        .map(|batch| ArticleBatchNotifier::new(batch, run_id));
+
+    let mut entries = entries.into_iter();
+    while let Some(entry) = entries.next() {
+        if cancel.is_cancelled() {
+            let remaining: Vec<Uuid> = std::iter::once(entry.id)
+                .chain(entries.by_ref().map(|entry| entry.id))
+                .collect();
+            info!(
+                remaining = remaining.len(),
+                "キャンセルされたため残りのエントリには着手せずに終了します"
+            );
+            summary.cancelled_count = remaining.len();
+            released_ids.extend(remaining);
+            break;
+        }
+
+        if let Some(host) = find_denied_host(&entry.link, &options.deny_hosts) {
+            denied_ids.push(entry.id);
+            summary.entries.push(FetchContentEntryReport {
+                queue_id: entry.id,
+                title: entry.title.clone(),
+                group: entry.group.clone(),
+                result: FetchContentEntryOutcome::Skipped { host },
+                status_mismatch: false,
+                content_change: None,
+            });
+            report_progress(&summary.entries[summary.entries.len() - 1]);
+            continue;
+        }
+
+        let previous_last_modified = find_source_last_modified(pool, entry.id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(queue_id = %entry.id, error = %e, "保存済み本文のLast-Modifiedを取得できません");
+                None
+            });
+        let request = build_scrape_request(&entry, options, previous_last_modified);
 
         let mut report = FetchContentEntryReport {
             queue_id: entry.id,
             title: entry.title.clone(),
+            group: entry.group.clone(),
             result: FetchContentEntryOutcome::ApiError {
                 message: "未処理".to_string(),
             },
+            status_mismatch: false,
+            content_change: None,
         };
 
-        match call_scrape_api(&client, api_url, &request).await {
-            Ok(ScrapeResult::Success(response)) => {
-                if response.status_code == 200 {
-                    match persist_success(pool, entry.id, &response.html, response.status_code)
-                        .await
-                    {
-                        Ok(_) => {
-                            summary.saved_count += 1;
-                            report.result = FetchContentEntryOutcome::Saved {
-                                status_code: response.status_code,
-                            };
-                        }
-                        Err(e) => {
-                            summary.error_count += 1;
-                            report.result = FetchContentEntryOutcome::PersistError {
-                                message: e.to_string(),
-                            };
-                        }
-                    }
-                } else {
-                    match persist_status_only(pool, entry.id, response.status_code).await {
-                        Ok(_) => {
-                            summary.status_only_count += 1;
-                            report.result = FetchContentEntryOutcome::StatusOnly {
-                                status_code: response.status_code,
-                            };
-                        }
-                        Err(e) => {
-                            summary.error_count += 1;
-                            report.result = FetchContentEntryOutcome::PersistError {
-                                message: e.to_string(),
-                            };
-                        }
-                    }
+        let precheck_status = if options.precheck_url {
+            precheck_url(&client, &entry.link).await
+        } else {
+            None
+        };
+        let scrape_result = match precheck_status {
+            Some(status_code) => {
+                info!(
+                    queue_id = %entry.id,
+                    status_code,
+                    "事前チェックで記事URLが存在しないため取得をスキップします"
+                );
+                Ok(ScrapeResult::HttpError { status_code })
+            }
+            None => {
+                if let Some(limiter) = &options.scrape_rate_limiter {
+                    limiter.acquire().await;
                 }
+                call_scrape_api(&client, api_url, &request, SCRAPING_MAX_RESPONSE_BYTES).await
             }
-            Ok(ScrapeResult::HttpError { status_code }) => {
-                match persist_status_only(pool, entry.id, status_code).await {
-                    Ok(_) => {
-                        summary.status_only_count += 1;
-                        report.result = FetchContentEntryOutcome::StatusOnly { status_code };
+        };
+        if let Ok(ScrapeResult::Success {
+            status_mismatch: true,
+            ..
+        }) = &scrape_result
+        {
+            report.status_mismatch = true;
+        }
+
+        if let (Some(dir), Ok(ScrapeResult::Success { response, .. })) =
+            (&options.debug_dir, &scrape_result)
+        {
+            write_debug_html(dir, entry.id, &response.html).await;
+        }
+
+        // 条件付き取得で304が返ったら、保存済みの本文をそのまま残してstatusだけ200で確定させる
+        let not_modified = request.if_modified_since.is_some()
+            && match &scrape_result {
+                Ok(ScrapeResult::Success { response, .. }) => response.status_code == 304,
+                Ok(ScrapeResult::HttpError { status_code }) => *status_code == 304,
+                Err(_) => false,
+            };
+
+        let write = match scrape_result {
+            _ if not_modified => Some(PendingWrite {
+                report_index: summary.entries.len(),
+                queue_id: entry.id,
+                status_code: 200,
+                content: None,
+                content_sha256: None,
+                warc: None,
+                raw_response: None,
+                canonical_url: None,
+                og_metadata: OgMetadata::default(),
+                source_last_modified: None,
+                links: None,
+                not_modified: true,
+            }),
+            Ok(ScrapeResult::Success {
+                response, raw_body, ..
+            }) if response.status_code == 200 => {
+                // 本文抽出で<head>が落ちるため、canonicalは取得したHTML全体から探す
+                let canonical_url = extract_canonical_url(&response.html, &entry.link);
+                let og_metadata = extract_og_metadata(&response.html, &entry.link);
+                let source_last_modified = response.last_modified.as_deref().and_then(|value| {
+                    let parsed = parse_last_modified(value);
+                    if parsed.is_none() {
+                        warn!(
+                            queue_id = %entry.id,
+                            last_modified = value,
+                            "Last-Modifiedを日時として解釈できないため保存しません"
+                        );
                     }
+                    parsed
+                });
+                // アーカイブは本文抽出前の取得結果そのものを残す
+                let warc = match options.archive {
+                    ContentArchive::Warc => build_warc_gz_blocking(WarcCapture {
+                        target_uri: response
+                            .final_url
+                            .clone()
+                            .unwrap_or_else(|| entry.link.clone()),
+                        fetched_at: Utc::now(),
+                        status_code: response.status_code,
+                        html: response.html.clone(),
+                        referer: request.referer.clone(),
+                        accept_language: request.accept_language.clone(),
+                    })
+                    .await
+                    .inspect_err(|e| {
+                        warn!(queue_id = %entry.id, error = %e, "WARCレコードの生成に失敗しました");
+                    })
+                    .ok(),
+                    ContentArchive::None => None,
+                };
+                // JSONとしてデコード済みのボディなので、UTF-8として読めない場合だけ保存を諦める
+                let raw_response = options
+                    .raw_response
+                    .then_some(raw_body)
+                    .and_then(|body| String::from_utf8(body).ok());
+                let html = if options.extract_main_content {
+                    extract_main_content(&response.html, &entry.link).unwrap_or_else(|| {
+                        warn!(
+                            queue_id = %entry.id,
+                            "本文の抽出に失敗したため生HTMLを保存します"
+                        );
+                        response.html
+                    })
+                } else {
+                    response.html
+                };
+                // 相対リンクはcanonical→最終URL→記事linkの順で基準を選んで解決する
+                let links = options.extract_links.then(|| {
+                    let base = canonical_url
+                        .as_deref()
+                        .or(response.final_url.as_deref())
+                        .unwrap_or(&entry.link);
+                    extract_article_links(&html, base)
+                });
+                if options.summary_api_url.is_some() {
+                    summary_targets.push(SummaryTarget {
+                        report_index: summary.entries.len(),
+                        queue_id: entry.id,
+                        link: entry.link.clone(),
+                        title: entry.title.clone(),
+                        text: html_to_text(&html),
+                    });
+                }
+                let sha256 = content_sha256(&html);
+                match compress_html_blocking(html, options.compression).await {
+                    Ok(compressed) => Some(PendingWrite {
+                        report_index: summary.entries.len(),
+                        queue_id: entry.id,
+                        status_code: response.status_code,
+                        content: Some((compressed, options.compression)),
+                        content_sha256: Some(sha256),
+                        warc,
+                        raw_response,
+                        canonical_url,
+                        og_metadata,
+                        source_last_modified,
+                        links,
+                        not_modified: false,
+                    }),
                     Err(e) => {
-                        summary.error_count += 1;
                         report.result = FetchContentEntryOutcome::PersistError {
                             message: e.to_string(),
                         };
+                        None
                     }
                 }
             }
+            Ok(ScrapeResult::Success { response, .. }) => Some(PendingWrite {
+                report_index: summary.entries.len(),
+                queue_id: entry.id,
+                status_code: response.status_code,
+                content: None,
+                content_sha256: None,
+                warc: None,
+                raw_response: None,
+                canonical_url: None,
+                og_metadata: OgMetadata::default(),
+                source_last_modified: None,
+                links: None,
+                not_modified: false,
+            }),
+            Ok(ScrapeResult::HttpError { status_code }) => Some(PendingWrite {
+                report_index: summary.entries.len(),
+                queue_id: entry.id,
+                status_code,
+                content: None,
+                content_sha256: None,
+                warc: None,
+                raw_response: None,
+                canonical_url: None,
+                og_metadata: OgMetadata::default(),
+                source_last_modified: None,
+                links: None,
+                not_modified: false,
+            }),
             Err(e) => {
-                summary.error_count += 1;
                 report.result = FetchContentEntryOutcome::ApiError {
                     message: e.to_string(),
                 };
+                None
             }
-        }
+        };
 
         summary.entries.push(report);
+
+        match write {
+            Some(write) => {
+                pending.push(write);
+                if pending.len() >= batch_size {
+                    let flushed = flush_pending_writes(
+                        pool,
+                        &mut pending,
+                        &mut summary.entries,
+                        options.delta_encoding,
+                    )
+                    .await;
+                    for index in flushed {
+                        report_progress(&summary.entries[index]);
+                        if let Some(notifier) = batch_notifier.as_mut() {
+                            notifier.push(&summary.entries[index]).await;
+                        }
+                    }
+                }
+            }
+            None => report_progress(&summary.entries[summary.entries.len() - 1]),
+        }
+
+        if let Some(notifier) = batch_notifier.as_mut() {
+            notifier.flush_if_due().await;
+        }
+    }
+
+    let flushed = flush_pending_writes(
+        pool,
+        &mut pending,
+        &mut summary.entries,
+        options.delta_encoding,
+    )
+    .await;
+    for index in flushed {
+        report_progress(&summary.entries[index]);
+        if let Some(notifier) = batch_notifier.as_mut() {
+            notifier.push(&summary.entries[index]).await;
+        }
+    }
+    if let Some(notifier) = batch_notifier.as_mut() {
+        notifier.flush().await;
+    }
+
+    if let Some(api_url) = options.summary_api_url.as_deref() {
+        summarize_saved_entries(pool, api_url, summary_targets, &summary.entries).await;
+    }
+
+    if let Err(e) = release_fetch_jobs(pool, &released_ids).await {
+        warn!(error = %e, "着手しなかったエントリのジョブ更新に失敗しました");
+    }
+    if let Err(e) = defer_denied_fetch_jobs(pool, &denied_ids).await {
+        warn!(error = %e, "除外ホストのエントリのジョブ更新に失敗しました");
+    }
+    for entry in &summary.entries {
+        let message = match &entry.result {
+            FetchContentEntryOutcome::ApiError { message }
+            | FetchContentEntryOutcome::PersistError { message } => message,
+            _ => continue,
+        };
+        if let Err(e) = schedule_fetch_job_retry(pool, entry.queue_id, message).await {
+            warn!(queue_id = %entry.queue_id, error = %e, "再試行の登録に失敗しました");
+        }
+        if let Err(e) = record_queue_error(pool, entry.queue_id, message).await {
+            warn!(queue_id = %entry.queue_id, error = %e, "エラー詳細の記録に失敗しました");
+        }
+    }
+
+    for entry in &summary.entries {
+        match entry.result {
+            FetchContentEntryOutcome::Saved { .. } => summary.saved_count += 1,
+            FetchContentEntryOutcome::StatusOnly { .. } | FetchContentEntryOutcome::NotModified => {
+                summary.status_only_count += 1
+            }
+            FetchContentEntryOutcome::ApiError { .. }
+            | FetchContentEntryOutcome::PersistError { .. } => summary.error_count += 1,
+            FetchContentEntryOutcome::Skipped { .. } => summary.skipped_count += 1,
+        }
+    }
+    summary.by_group = summarize_by_group(&summary.entries);
+
+    if let Err(e) = record_fetch_log(pool, &summary).await {
+        warn!(error = %e, "fetch_logの記録に失敗しました");
     }
 
     Ok(summary)
 }
 
-/// スクレイピングAPIを呼び出す
+/// 要約対象の記事（本文保存前に取り出したテキストを持つ）
+struct SummaryTarget {
+    report_index: usize,
+    queue_id: Uuid,
+    link: String,
+    title: String,
+    text: String,
+}
+
+/// 本文の保存に成功した記事を要約APIで要約し、article_contentへ記録する。
+/// 要約はベストエフォートで、失敗しても本文の保存結果には影響させない（警告ログのみ）。
+async fn summarize_saved_entries(
+    pool: &PgPool,
+    api_url: &str,
+    targets: Vec<SummaryTarget>,
+    reports: &[FetchContentEntryReport],
+) {
+    for target in targets {
+        if !matches!(
+            reports[target.report_index].result,
+            FetchContentEntryOutcome::Saved { .. }
+        ) || target.text.is_empty()
+        {
+            continue;
+        }
+
+        let result = match request_summary(api_url, &target.link, &target.title, &target.text).await
+        {
+            Ok(text) => save_article_summary(pool, target.queue_id, &text).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            warn!(queue_id = %target.queue_id, error = %e, "記事の要約に失敗しました");
+        }
+    }
+}
+
+/// 実行結果をfetch_logへ記録する（成功率の時系列集計に使う）
+async fn record_fetch_log(pool: &PgPool, summary: &FetchContentSummary) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rss.fetch_log (id, command, total_processed, saved, status_only, errors, run_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(FETCH_CONTENT_LOG_COMMAND)
+    .bind((summary.entries.len() - summary.skipped_count) as i32)
+    .bind(summary.saved_count as i32)
+    .bind(summary.status_only_count as i32)
+    .bind(summary.error_count as i32)
+    .bind(summary.run_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// linkのホストが除外リストに一致する場合、そのホスト名を返す
+pub(crate) fn find_denied_host(link: &str, deny_hosts: &[String]) -> Option<String> {
+    if deny_hosts.is_empty() {
+        return None;
+    }
+
+    let url = reqwest::Url::parse(link.trim()).ok()?;
+    let host = url.host_str()?.trim_end_matches('.').to_ascii_lowercase();
+
+    let denied = deny_hosts.iter().any(|pattern| {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host
+                .strip_suffix(domain)
+                .is_some_and(|prefix| prefix.ends_with('.')),
+            None => host == pattern,
+        }
+    });
+
+    denied.then_some(host)
+}
+
+/// DBへの書き込み待ちの取得結果（本文はBrotli圧縮済み）
+struct PendingWrite {
+    report_index: usize,
+    queue_id: Uuid,
+    status_code: i32,
+    /// status_code=200のときのみ本文（圧縮済み）とその圧縮方式を持つ
+    content: Option<(Vec<u8>, ContentCompression)>,
+    /// 圧縮前の本文のSHA-256（`content`があるときのみ）
+    content_sha256: Option<String>,
+    /// 取得結果のWARCアーカイブ（`.warc.gz`形式、`CONTENT_ARCHIVE=warc`かつstatus_code=200のときのみ）
+    warc: Option<Vec<u8>>,
+    /// scraping APIのレスポンスJSON全体（`raw_response`有効時かつstatus_code=200のときのみ）
+    raw_response: Option<String>,
+    /// HTMLの`<link rel="canonical">`（絶対URLに解決済み）
+    canonical_url: Option<String>,
+    /// HTMLのOGメタデータ（status_code=200以外は空）
+    og_metadata: OgMetadata,
+    /// scraping APIが返した元ページの`Last-Modified`（UTCへ変換済み）
+    source_last_modified: Option<DateTime<Utc>>,
+    /// 本文から抽出したリンク（`extract_links`無効時・status_code=200以外はNone）
+    links: Option<Vec<String>>,
+    /// 条件付き取得で未更新（304）だった。本文は書き換えず、statusだけ200で確定させる
+    not_modified: bool,
+}
+
+/// 溜めた取得結果を1トランザクションで保存し、各エントリの結果を確定させる。
+/// 失敗時はバッチ内の全エントリを保存エラーとして扱う（ロールバックされるため再試行対象に残る）。
+/// 結果を確定させたエントリの`reports`上の位置を返す。
+async fn flush_pending_writes(
+    pool: &PgPool,
+    pending: &mut Vec<PendingWrite>,
+    reports: &mut [FetchContentEntryReport],
+    delta_encoding: bool,
+) -> Vec<usize> {
+    if pending.is_empty() {
+        return Vec::new();
+    }
+
+    let writes = std::mem::take(pending);
+    match persist_writes(pool, &writes, delta_encoding).await {
+        Ok(changes) => {
+            for (write, change) in writes.iter().zip(changes) {
+                reports[write.report_index].content_change = change;
+                reports[write.report_index].result = if write.not_modified {
+                    FetchContentEntryOutcome::NotModified
+                } else if write.content.is_some() {
+                    FetchContentEntryOutcome::Saved {
+                        status_code: write.status_code,
+                    }
+                } else {
+                    FetchContentEntryOutcome::StatusOnly {
+                        status_code: write.status_code,
+                    }
+                };
+            }
+        }
+        Err(e) => {
+            let message = e.to_string();
+            for write in &writes {
+                reports[write.report_index].result = FetchContentEntryOutcome::PersistError {
+                    message: message.clone(),
+                };
+            }
+        }
+    }
+
+    writes.iter().map(|write| write.report_index).collect()
+}
+
+/// queueエントリからscraping APIへのリクエストを組み立てる（フィード設定の値を環境変数より優先）。
+/// 保存済み本文の`source_last_modified`があれば`if_modified_since`として渡し、条件付き取得を依頼する。
+fn build_scrape_request(
+    entry: &Queue,
+    options: &FetchContentOptions,
+    previous_last_modified: Option<DateTime<Utc>>,
+) -> ScrapeRequest {
+    ScrapeRequest {
+        url: entry.link.clone(),
+        wait_for_selector: None,
+        timeout: Some(DEFAULT_TIMEOUT_SECS),
+        referer: entry.scrape_referer.clone(),
+        accept_language: entry
+            .scrape_accept_language
+            .clone()
+            .or_else(|| options.accept_language.clone()),
+        if_modified_since: previous_last_modified
+            .map(|value| value.format("%a, %d %b %Y %H:%M:%S GMT").to_string()),
+    }
+}
+
+/// 保存済み本文の`source_last_modified`を返す（本文が未保存・記録なしならNone）
+async fn find_source_last_modified(pool: &PgPool, queue_id: Uuid) -> Result<Option<DateTime<Utc>>> {
+    let value: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+        "SELECT source_last_modified FROM rss.article_content WHERE queue_id = $1",
+    )
+    .bind(queue_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(value.flatten())
+}
+
+/// 記事URLへHEADリクエストを送り、スクレイプしても無駄な4xxならそのstatus_codeを返す。
+///
+/// HEAD非対応（405）、4xx以外、接続エラー・タイムアウトの場合はNoneを返し、通常どおりスクレイプへ進ませる。
+pub(crate) async fn precheck_url(client: &Client, link: &str) -> Option<i32> {
+    let response = match client
+        .head(link.trim())
+        .timeout(Duration::from_secs(PRECHECK_TIMEOUT_SECS))
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            warn!(link, error = %e, "記事URLの事前チェックに失敗したためスクレイプへ進みます");
+            return None;
+        }
+    };
+
+    let status = response.status();
+    (status.is_client_error() && status != StatusCode::METHOD_NOT_ALLOWED)
+        .then(|| i32::from(status.as_u16()))
+}
+
+/// スクレイピングAPIを呼び出す。レスポンスボディは`max_response_bytes`を超えた時点で打ち切る。
+#[instrument(skip_all, fields(url = %request.url))]
 async fn call_scrape_api(
     client: &Client,
     api_url: &str,
     request: &ScrapeRequest,
+    max_response_bytes: usize,
 ) -> Result<ScrapeResult> {
     let endpoint = format!("{}/fetch", api_url.trim_end_matches('/'));
     let response = client.post(endpoint).json(request).send().await?;
 
     let status = response.status();
-    let bytes = response.bytes().await?;
 
     if status.is_success() {
+        let bytes = read_body_limited(response, max_response_bytes).await?;
         let scrape_response: ScrapeResponse = serde_json::from_slice(&bytes)
             .context("スクレイピングAPIレスポンスのJSONデコードに失敗")?;
-        Ok(ScrapeResult::Success(scrape_response))
+        let status_mismatch = i32::from(status.as_u16()) != scrape_response.status_code;
+        if status_mismatch {
+            warn!(
+                http_status = status.as_u16(),
+                body_status_code = scrape_response.status_code,
+                "scraping APIのHTTPステータスとボディのstatus_codeが一致しません"
+            );
+        }
+        Ok(ScrapeResult::Success {
+            response: scrape_response,
+            raw_body: bytes,
+            status_mismatch,
+        })
     } else {
         Ok(ScrapeResult::HttpError {
             status_code: status.as_u16() as i32,
@@ -231,16 +1118,181 @@ async fn call_scrape_api(
     }
 }
 
-/// HTMLをBrotli圧縮
-pub(crate) fn compress_html(html: &str) -> Result<Vec<u8>> {
+/// デバッグ用に、scraping APIから受け取ったHTML（本文抽出前）を`<dir>/<queue_id>.html`へ書き出す。
+/// 失敗しても警告のみで、取得結果の保存には影響させない。
+async fn write_debug_html(dir: &Path, queue_id: Uuid, html: &str) {
+    let path = dir.join(format!("{}.html", queue_id));
+    let written = async {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(&path, html).await
+    }
+    .await;
+    if let Err(e) = written {
+        warn!(
+            queue_id = %queue_id,
+            path = %path.display(),
+            error = %e,
+            "デバッグ用HTMLの書き出しに失敗しました"
+        );
+    }
+}
+
+/// レスポンスボディをストリームで読み込み、累積サイズが上限を超えたらエラーにする
+pub(crate) async fn read_body_limited(response: Response, max_bytes: usize) -> Result<Vec<u8>> {
+    if let Some(length) = response.content_length() {
+        if length > max_bytes as u64 {
+            anyhow::bail!(
+                "スクレイピングAPIレスポンスが上限{}バイトを超えています({}バイト)",
+                max_bytes,
+                length
+            );
+        }
+    }
+
+    let mut body = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > max_bytes {
+            anyhow::bail!(
+                "スクレイピングAPIレスポンスが上限{}バイトを超えたため読み込みを打ち切りました",
+                max_bytes
+            );
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// readabilityでナビやサイドバーを除いた本文エリアのHTMLを抽出する。
+/// 本文らしい要素が見つからない（テキストが空の）場合はNoneを返す。
+pub(crate) fn extract_main_content(html: &str, link: &str) -> Option<String> {
+    let url = reqwest::Url::parse(link.trim()).ok()?;
+    let product = readability::extractor::extract(&mut html.as_bytes(), &url).ok()?;
+
+    if product.text.trim().is_empty() || product.content.trim().is_empty() {
+        return None;
+    }
+    Some(product.content)
+}
+
+/// HTMLの`<link rel="canonical">`のhrefを取り出し、記事linkを基準に絶対URLへ解決する。
+/// 見つからない・解釈できない場合はNoneを返す。
+pub(crate) fn extract_canonical_url(html: &str, link: &str) -> Option<String> {
+    static CANONICAL_SELECTOR: once_cell::sync::Lazy<scraper::Selector> =
+        once_cell::sync::Lazy::new(|| {
+            scraper::Selector::parse("link[rel][href]").expect("canonical用セレクタが不正")
+        });
+
+    let document = scraper::Html::parse_document(html);
+    let href = document
+        .select(&CANONICAL_SELECTOR)
+        .find(|element| {
+            element.value().attr("rel").is_some_and(|rel| {
+                rel.split_ascii_whitespace()
+                    .any(|token| token.eq_ignore_ascii_case("canonical"))
+            })
+        })?
+        .value()
+        .attr("href")?
+        .trim();
+    if href.is_empty() {
+        return None;
+    }
+
+    let canonical = match reqwest::Url::parse(link.trim()) {
+        Ok(base) => base.join(href).ok()?,
+        Err(_) => reqwest::Url::parse(href).ok()?,
+    };
+    Some(canonical.to_string())
+}
+
+/// `Last-Modified`のHTTP-dateをUTCの日時へ変換する。
+/// IMF-fixdate（`Wed, 21 Oct 2015 07:28:00 GMT`）に加え、旧形式のRFC 850・asctimeも受け付ける。解釈できなければNone。
+pub(crate) fn parse_last_modified(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc2822(value) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    ["%A, %d-%b-%y %H:%M:%S GMT", "%a %b %e %H:%M:%S %Y"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|parsed| parsed.and_utc())
+}
+
+/// SNSシェア表示用のOGメタデータ。タグが無い項目はNone
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OgMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// 記事linkを基準に絶対URLへ解決した`og:image`
+    pub image: Option<String>,
+}
+
+/// HTMLの`<meta property="og:*">`から`og:title`/`og:description`/`og:image`を取り出す。
+/// 同じプロパティが複数ある場合は先頭を使い、空の値は無いものとして扱う。
+pub(crate) fn extract_og_metadata(html: &str, link: &str) -> OgMetadata {
+    static OG_SELECTOR: once_cell::sync::Lazy<scraper::Selector> =
+        once_cell::sync::Lazy::new(|| {
+            scraper::Selector::parse("meta[content]").expect("OG用セレクタが不正")
+        });
+
+    let document = scraper::Html::parse_document(html);
+    let mut metadata = OgMetadata::default();
+    for element in document.select(&OG_SELECTOR) {
+        let element = element.value();
+        // `property`が正式だが、`name`で書くサイトもあるため両方を見る
+        let Some(property) = element.attr("property").or_else(|| element.attr("name")) else {
+            continue;
+        };
+        let slot = match property.trim().to_ascii_lowercase().as_str() {
+            "og:title" => &mut metadata.title,
+            "og:description" => &mut metadata.description,
+            "og:image" => &mut metadata.image,
+            _ => continue,
+        };
+        let content = element.attr("content").unwrap_or_default().trim();
+        if slot.is_none() && !content.is_empty() {
+            *slot = Some(content.to_string());
+        }
+    }
+
+    metadata.image = metadata
+        .image
+        .and_then(|image| match reqwest::Url::parse(link.trim()) {
+            Ok(base) => base.join(&image).ok().map(String::from),
+            Err(_) => reqwest::Url::parse(&image).ok().map(String::from),
+        });
+    metadata
+}
+
+/// HTMLを指定した方式で圧縮
+pub(crate) fn compress_html(html: &str, compression: ContentCompression) -> Result<Vec<u8>> {
+    compress_content(html.as_bytes(), compression)
+}
+
+/// HTMLの圧縮をブロッキング用のスレッドプールで行う。
+/// 圧縮はCPUバウンドなため、asyncランタイムのワーカースレッドを占有して他のタスクを止めないようにする。
+pub(crate) async fn compress_html_blocking(
+    html: String,
+    compression: ContentCompression,
+) -> Result<Vec<u8>> {
+    tokio::task::spawn_blocking(move || compress_html(&html, compression))
+        .await
+        .context("圧縮タスクの実行に失敗")?
+}
+
+/// バイト列をBrotli圧縮
+pub(crate) fn compress_bytes(data: &[u8]) -> Result<Vec<u8>> {
     let mut compressed = Vec::new();
-    let mut reader = html.as_bytes();
+    let mut reader = data;
 
     brotli::BrotliCompress(
         &mut reader,
         &mut compressed,
         &brotli::enc::BrotliEncoderParams {
-            quality: 6,
+            quality: BROTLI_QUALITY,
             ..Default::default()
         },
     )?;
@@ -248,27 +1300,146 @@ pub(crate) fn compress_html(html: &str) -> Result<Vec<u8>> {
     Ok(compressed)
 }
 
-/// 再処理対象のqueueエントリを取得（status_codeがNULLまたは200以外）
-async fn search_queue_entries_for_fetch(pool: &PgPool, limit: i64) -> Result<Vec<Queue>> {
-    let entries = sqlx::query_as::<_, Queue>(
+/// Brotli圧縮された本文を展開する
+pub(crate) fn decompress_html(data: &[u8]) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    brotli::BrotliDecompress(&mut &data[..], &mut decompressed)
+        .context("Brotli本文の展開に失敗")?;
+    Ok(decompressed)
+}
+
+/// 実行時刻を迎えたfetch_jobsのうち`filter`に一致するものをrunningにして、対応するqueueエントリを取得する。
+///
+/// queueの優先度（`priority`）が高いものを先に、同じ優先度では未試行のジョブを優先し、次にnext_run_at昇順で並べる。`FOR UPDATE SKIP LOCKED`で取得するため、
+/// 同時に動く別プロセスと同じエントリを取り合わない。異常終了でrunningのまま残ったジョブも
+/// `FETCH_JOB_STALE_SECS`経過後に再取得する。
+/// 絞り込み条件は指定された項目だけWHERE句へ足し、値はすべてバインドパラメータで渡す。
+pub(crate) async fn search_queue_entries_for_fetch(
+    pool: &PgPool,
+    limit: i64,
+    filter: &FetchContentFilter,
+) -> Result<Vec<Queue>> {
+    let mut query = QueryBuilder::<Postgres>::new(
+        r#"
+        WITH claimed AS (
+            UPDATE rss.fetch_jobs j
+            SET status = "#,
+    );
+    query.push_bind(FetchJobStatus::Running);
+    query.push(
+        r#"
+            FROM (
+                SELECT fj.queue_id
+                FROM rss.fetch_jobs AS fj
+                INNER JOIN rss.queue AS fq ON fq.id = fj.queue_id
+                WHERE ((fj.status = 'pending' AND fj.next_run_at <= NOW())
+                   OR (fj.status = "#,
+    );
+    query.push_bind(FetchJobStatus::Running);
+    query.push(" AND fj.updated_at < NOW() - make_interval(secs => ");
+    query.push_bind(FETCH_JOB_STALE_SECS as f64);
+    query.push(")))");
+    // 重複としてマージ済みの記事は取得し直さない
+    query.push(" AND fq.merged_into IS NULL");
+    push_fetch_content_filter(&mut query, filter);
+    query.push(
+        r#"
+                ORDER BY
+                    fq.priority DESC,
+                    CASE WHEN fj.attempts = 0 THEN 0 ELSE 1 END,
+                    fj.next_run_at ASC
+                LIMIT "#,
+    );
+    query.push_bind(limit);
+    query.push(
         r#"
-        SELECT id, created_at, updated_at, link, title, pub_date, description, status_code, "group"
-        FROM rss.queue
-        WHERE status_code IS NULL OR status_code <> 200
+                FOR UPDATE OF fj SKIP LOCKED
+            ) picked
+            WHERE j.queue_id = picked.queue_id
+            RETURNING j.queue_id, j.attempts, j.next_run_at
+        )
+        SELECT q.id, q.created_at, q.updated_at, q.link, q.title, q.pub_date, q.pub_date_tz_offset,
+               q.description, q.status_code, q."group", q.scrape_referer, q.scrape_accept_language
+        FROM claimed c
+        JOIN rss.queue q ON q.id = c.queue_id
         ORDER BY
-            CASE WHEN status_code IS NULL THEN 0 ELSE 1 END,
-            updated_at ASC
-        LIMIT $1
+            q.priority DESC,
+            CASE WHEN c.attempts = 0 THEN 0 ELSE 1 END,
+            c.next_run_at ASC
         "#,
-    )
-    .bind(limit)
-    .fetch_all(pool)
-    .await?;
+    );
+
+    let entries = query.build_query_as::<Queue>().fetch_all(pool).await?;
 
     Ok(entries)
 }
 
-/// queueのstatus_codeを更新
+/// `filter`の指定された項目を、queue（別名`fq`）に対するAND条件として追加する
+fn push_fetch_content_filter(query: &mut QueryBuilder<'_, Postgres>, filter: &FetchContentFilter) {
+    if !filter.groups.is_empty() {
+        query.push(r#" AND fq."group" = ANY("#);
+        query.push_bind(filter.groups.clone());
+        query.push(")");
+    }
+
+    if let Some(created_after) = filter.created_after {
+        query.push(" AND fq.created_at >= ");
+        query.push_bind(created_after);
+    }
+
+    if !filter.statuses.is_empty() {
+        let codes: Vec<i32> = filter
+            .statuses
+            .iter()
+            .filter_map(|status| match status {
+                QueueStatusFilter::Code(code) => Some(*code),
+                QueueStatusFilter::Unfetched => None,
+            })
+            .collect();
+        let unfetched = filter.statuses.contains(&QueueStatusFilter::Unfetched);
+        query.push(" AND (");
+        if unfetched {
+            query.push("fq.status_code IS NULL");
+        }
+        if !codes.is_empty() {
+            if unfetched {
+                query.push(" OR ");
+            }
+            query.push("fq.status_code = ANY(");
+            query.push_bind(codes);
+            query.push(")");
+        }
+        query.push(")");
+    }
+}
+
+/// queueのstatus_codeを手動で書き換え、更新後のエントリを返す。存在しなければNone。
+/// 本文取得ジョブも合わせて更新する（410なら以後再取得しない）。
+pub async fn set_queue_status(pool: &PgPool, id: Uuid, status_code: i32) -> Result<Option<Queue>> {
+    let mut tx = pool.begin().await?;
+    let entry = sqlx::query_as::<_, Queue>(
+        r#"
+        UPDATE rss.queue
+        SET status_code = $1, updated_at = NOW()
+        WHERE id = $2
+        RETURNING id, created_at, updated_at, link, title, pub_date, pub_date_tz_offset,
+                  description, status_code, "group", scrape_referer, scrape_accept_language
+        "#,
+    )
+    .bind(status_code)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if entry.is_some() {
+        reset_fetch_job(&mut tx, id, status_code).await?;
+    }
+    tx.commit().await?;
+
+    Ok(entry)
+}
+
+/// queueのstatus_codeを更新する（200で取得できたら`last_error`もクリアする）
 async fn update_queue_status(
     tx: &mut Transaction<'_, Postgres>,
     id: Uuid,
@@ -277,7 +1448,9 @@ async fn update_queue_status(
     sqlx::query(
         r#"
         UPDATE rss.queue
-        SET status_code = $1, updated_at = NOW()
+        SET status_code = $1,
+            last_error = CASE WHEN $1 = 200 THEN NULL ELSE last_error END,
+            updated_at = NOW()
         WHERE id = $2
         "#,
     )
@@ -289,71 +1462,153 @@ async fn update_queue_status(
     Ok(())
 }
 
-/// article_contentに保存
+/// スクレイプ・保存に失敗したエントリのエラーメッセージをqueueの`last_error`へ記録する（status_codeは変えない）
+async fn record_queue_error(pool: &PgPool, id: Uuid, message: &str) -> Result<()> {
+    sqlx::query("UPDATE rss.queue SET last_error = $2 WHERE id = $1")
+        .bind(id)
+        .bind(message)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// 圧縮前の本文のSHA-256を16進小文字で返す（本文の変更検出に使う）
+pub(crate) fn content_sha256(html: &str) -> String {
+    format!("{:x}", Sha256::digest(html.as_bytes()))
+}
+
+/// 本文と`write`のメタデータ（ハッシュ・canonical・OG・Last-Modified・生レスポンス）をarticle_contentに保存し、保存済みの本文からの変化を返す。
+///
+/// 変化は`content_sha256`の比較で判定する。ハッシュ列の追加前に保存した本文（NULL）は比較できないため更新扱いにする。
 async fn save_article_content(
     tx: &mut Transaction<'_, Postgres>,
-    queue_id: Uuid,
+    write: &PendingWrite,
     data: &[u8],
-) -> Result<()> {
+    compression: ContentCompression,
+) -> Result<ContentChange> {
+    let queue_id = write.queue_id;
+    let sha256 = write.content_sha256.as_deref();
+    let previous: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT content_sha256 FROM rss.article_content WHERE queue_id = $1 FOR UPDATE",
+    )
+    .bind(queue_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+    let change = match previous {
+        None => ContentChange::Created,
+        Some(previous) if previous.is_some() && previous.as_deref() == sha256 => {
+            ContentChange::Unchanged
+        }
+        Some(_) => ContentChange::Updated,
+    };
+
     sqlx::query(
         r#"
-        INSERT INTO rss.article_content (queue_id, data)
-        VALUES ($1, $2)
+        INSERT INTO rss.article_content (
+            queue_id, data, compression, compression_quality, content_sha256, canonical_url,
+            og_title, og_description, og_image, source_last_modified, raw_response
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11::jsonb)
         ON CONFLICT (queue_id)
         DO UPDATE SET
             data = EXCLUDED.data,
+            compression = EXCLUDED.compression,
+            compression_quality = EXCLUDED.compression_quality,
+            content_sha256 = EXCLUDED.content_sha256,
+            canonical_url = EXCLUDED.canonical_url,
+            og_title = EXCLUDED.og_title,
+            og_description = EXCLUDED.og_description,
+            og_image = EXCLUDED.og_image,
+            source_last_modified = EXCLUDED.source_last_modified,
+            raw_response = EXCLUDED.raw_response,
             updated_at = NOW()
         "#,
     )
     .bind(queue_id)
     .bind(data)
+    .bind(compression)
+    .bind(i16::from(compression.quality()))
+    .bind(sha256)
+    .bind(write.canonical_url.as_deref())
+    .bind(write.og_metadata.title.as_deref())
+    .bind(write.og_metadata.description.as_deref())
+    .bind(write.og_metadata.image.as_deref())
+    .bind(write.source_last_modified)
+    .bind(write.raw_response.as_deref())
     .execute(&mut **tx)
     .await?;
 
-    Ok(())
+    Ok(change)
 }
 
-/// 取得結果が200のときの保存処理
-async fn persist_success(
+/// 取得結果をまとめて保存する。200の本文はarticle_contentへupsertし、status_codeはすべて更新する。
+/// `writes`と同じ順で、本文を保存したエントリには本文の変化を返す。
+async fn persist_writes(
     pool: &PgPool,
-    queue_id: Uuid,
-    html: &str,
-    status_code: i32,
-) -> Result<()> {
-    let compressed = compress_html(html)?;
+    writes: &[PendingWrite],
+    delta_encoding: bool,
+) -> Result<Vec<Option<ContentChange>>> {
     let mut tx = pool.begin().await?;
+    let mut changes = Vec::with_capacity(writes.len());
 
-    save_article_content(&mut tx, queue_id, &compressed).await?;
-    update_queue_status(&mut tx, queue_id, status_code).await?;
-
-    tx.commit().await?;
-    Ok(())
-}
+    for write in writes {
+        let mut change = None;
+        if let Some((content, compression)) = &write.content {
+            archive_content_version(
+                &mut tx,
+                write.queue_id,
+                content,
+                *compression,
+                delta_encoding,
+            )
+            .await?;
+            change = Some(save_article_content(&mut tx, write, content, *compression).await?);
+            if let Some(links) = &write.links {
+                save_article_links(&mut tx, write.queue_id, links).await?;
+            }
+            if let Some(warc) = &write.warc {
+                save_article_warc(&mut tx, write.queue_id, warc).await?;
+            }
+        }
+        update_queue_status(&mut tx, write.queue_id, write.status_code).await?;
+        finish_fetch_job(&mut tx, write.queue_id, write.status_code).await?;
+        changes.push(change);
+    }
 
-/// 取得結果が非200のときの更新処理
-async fn persist_status_only(pool: &PgPool, queue_id: Uuid, status_code: i32) -> Result<()> {
-    let mut tx = pool.begin().await?;
-    update_queue_status(&mut tx, queue_id, status_code).await?;
     tx.commit().await?;
-    Ok(())
+    Ok(changes)
 }
 
 #[cfg(test)]
 mod tests {
     pub mod execute_fetch_content_tests {
         use std::io::{Cursor, Read};
+        use std::sync::Arc;
+        use std::time::Duration;
 
         use anyhow::Result;
         use brotli::Decompressor;
         use chrono::Utc;
+        use flate2::read::MultiGzDecoder;
         use serde_json::json;
+        use tokio_util::sync::CancellationToken;
         use uuid::Uuid;
-        use wiremock::matchers::{method, path};
-        use wiremock::{Mock, MockServer, ResponseTemplate};
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 
-        use crate::fetch_content::{execute_fetch_content, FetchContentEntryOutcome};
+        use crate::fetch_content::{
+            content_sha256, execute_fetch_content, execute_fetch_content_with_progress,
+            find_denied_host, ContentChange, FetchContentEntryOutcome, FetchContentOptions,
+        };
+        use crate::fetch_jobs::{release_fetch_jobs, FetchJobStatus};
         use crate::models::ArticleContent;
-        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+        use crate::rate_limit::RateLimiter;
+        use crate::test_support::{
+            clear_rss_tables, create_temp_dir, fixed_datetime, prepare_test_pool,
+            set_fetch_job_next_run_at,
+        };
+        use crate::warc::ContentArchive;
 
         /// # 検証目的
         /// ステータス200時に本文を圧縮保存し、status_codeを200へ更新できることを確認する。
@@ -395,7 +1650,9 @@ mod tests {
             .execute(&pool)
             .await?;
 
-            let summary = execute_fetch_content(&pool, 10, &server.uri()).await?;
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
             assert_eq!(summary.saved_count, 1);
             assert_eq!(summary.status_only_count, 0);
             assert_eq!(summary.error_count, 0);
@@ -470,7 +1727,9 @@ mod tests {
             .execute(&pool)
             .await?;
 
-            let summary = execute_fetch_content(&pool, 10, &server.uri()).await?;
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
             assert_eq!(summary.saved_count, 0);
             assert_eq!(summary.status_only_count, 1);
             assert_eq!(summary.error_count, 0);
@@ -530,7 +1789,9 @@ mod tests {
             .execute(&pool)
             .await?;
 
-            let summary = execute_fetch_content(&pool, 10, &server.uri()).await?;
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
             assert_eq!(summary.saved_count, 0);
             assert_eq!(summary.status_only_count, 1);
             assert_eq!(summary.error_count, 0);
@@ -555,6 +1816,14 @@ mod tests {
             .await?;
             assert!(exists.is_none());
 
+            let (job_status, last_error): (FetchJobStatus, Option<String>) =
+                sqlx::query_as("SELECT status, last_error FROM rss.fetch_jobs WHERE queue_id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(job_status, FetchJobStatus::Pending, "再試行待ちに戻る");
+            assert_eq!(last_error.as_deref(), Some("HTTPステータス503"));
+
             Ok(())
         }
 
@@ -599,7 +1868,9 @@ mod tests {
             .execute(&pool)
             .await?;
 
-            let summary = execute_fetch_content(&pool, 10, &server.uri()).await?;
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
             assert_eq!(summary.saved_count, 1);
             assert_eq!(summary.status_only_count, 0);
             assert_eq!(summary.error_count, 0);
@@ -630,156 +1901,2494 @@ mod tests {
 
             Ok(())
         }
-    }
 
-    pub mod log_fetch_content_summary {
-        use tracing_test::traced_test;
-        use uuid::Uuid;
+        /// # 検証目的
+        /// scraping APIのエラー時はメッセージがqueueの`last_error`に残り、再取得に成功したらクリアされることを確認する。
+        #[tokio::test]
+        async fn 失敗時のエラー詳細を保存し成功でクリアする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
 
-        use crate::fetch_content::{
-            log_fetch_content_summary, FetchContentEntryOutcome, FetchContentEntryReport,
-            FetchContentSummary,
-        };
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
 
-        /// # 検証目的
-        /// 保存成功・status更新のみ・エラーの各結果に対応したログが出力されることを確認する。
-        #[traced_test]
-        #[test]
-        fn 集計ログが出力される() {
-            let summary = FetchContentSummary {
-                saved_count: 1,
-                status_only_count: 1,
-                error_count: 1,
-                entries: vec![
-                    FetchContentEntryReport {
-                        queue_id: Uuid::new_v4(),
-                        title: "保存記事".to_string(),
-                        result: FetchContentEntryOutcome::Saved { status_code: 200 },
-                    },
-                    FetchContentEntryReport {
-                        queue_id: Uuid::new_v4(),
-                        title: "ステータスのみ".to_string(),
-                        result: FetchContentEntryOutcome::StatusOnly { status_code: 500 },
-                    },
-                    FetchContentEntryReport {
-                        queue_id: Uuid::new_v4(),
-                        title: "エラー".to_string(),
-                        result: FetchContentEntryOutcome::ApiError {
-                            message: "API error".to_string(),
-                        },
-                    },
-                ],
-            };
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+                .mount(&server)
+                .await;
 
-            log_fetch_content_summary(&summary);
+            let queue_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(queue_id)
+            .bind("https://example.com/last-error")
+            .bind("エラー詳細")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
 
-            assert!(logs_contain("保存完了"));
-            assert!(logs_contain("status_codeのみ記録"));
-            assert!(logs_contain("APIエラー"));
-            assert!(logs_contain("処理完了"));
-        }
-    }
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(summary.error_count, 1);
+            let last_error: Option<String> =
+                sqlx::query_scalar("SELECT last_error FROM rss.queue WHERE id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            let last_error = last_error.expect("エラー詳細が保存されていない");
+            assert!(last_error.contains("JSONデコード"), "{}", last_error);
 
-    pub mod search_queue_entries_for_fetch_tests {
-        use anyhow::Result;
-        use uuid::Uuid;
+            server.reset().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html><body>ok</body></html>",
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+            set_fetch_job_next_run_at(&pool, queue_id, fixed_datetime(2025, 10, 12, 8, 0, 0))
+                .await?;
 
-        use crate::test_support::{
-            clear_rss_tables, fixed_datetime, prepare_test_pool, set_queue_timestamp,
-        };
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(summary.saved_count, 1);
+            let last_error: Option<String> =
+                sqlx::query_scalar("SELECT last_error FROM rss.queue WHERE id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(last_error, None);
+
+            Ok(())
+        }
 
         /// # 検証目的
-        /// 未処理エントリが優先され、updated_at昇順で取得されることを確認する。
+        /// フィード設定由来のリファラとAccept-LanguageがPOSTボディに含まれ、
+        /// フィード側の指定がグローバル設定より優先されることを確認する。
         #[tokio::test]
-        async fn 未処理が優先され更新日時で並ぶ() -> Result<()> {
+        async fn リファラとaccept_languageを透過送信する() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
             let pool = prepare_test_pool().await?;
 
             sqlx::migrate!("./migrations").run(&pool).await?;
             clear_rss_tables(&pool).await?;
 
-            let first_id = Uuid::new_v4();
-            let second_id = Uuid::new_v4();
-            let third_id = Uuid::new_v4();
+            let server = MockServer::start().await;
 
-            sqlx::query(
-                r#"
-                INSERT INTO rss.queue (id, link, title, description)
-                VALUES ($1, $2, $3, $4)
-                "#,
-            )
-            .bind(first_id)
-            .bind("https://example.com/null-early")
-            .bind("NULL早期")
-            .bind("説明1")
-            .execute(&pool)
-            .await?;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(json!({
+                    "url": "https://example.com/ja",
+                    "referer": "https://example.com/",
+                    "accept_language": "ja",
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html></html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
 
-            sqlx::query(
-                r#"
-                INSERT INTO rss.queue (id, link, title, description)
-                VALUES ($1, $2, $3, $4)
-                "#,
-            )
-            .bind(second_id)
-            .bind("https://example.com/null-late")
-            .bind("NULL後期")
-            .bind("説明2")
-            .execute(&pool)
-            .await?;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(json!({
+                    "url": "https://example.com/global",
+                    "accept_language": "en",
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html></html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
 
             sqlx::query(
                 r#"
-                INSERT INTO rss.queue (id, link, title, description, status_code)
-                VALUES ($1, $2, $3, $4, $5)
+                INSERT INTO rss.queue (id, link, title, description, scrape_referer, scrape_accept_language)
+                VALUES ($1, $2, $3, $4, $5, $6), ($7, $8, $9, $10, NULL, NULL)
                 "#,
             )
-            .bind(third_id)
-            .bind("https://example.com/failed")
-            .bind("失敗済み")
-            .bind("説明3")
-            .bind(503)
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/ja")
+            .bind("フィード指定")
+            .bind("説明")
+            .bind("https://example.com/")
+            .bind("ja")
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/global")
+            .bind("グローバル指定")
+            .bind("説明")
             .execute(&pool)
             .await?;
 
-            set_queue_timestamp(&pool, first_id, fixed_datetime(2025, 10, 12, 9, 0, 0)).await?;
-            set_queue_timestamp(&pool, second_id, fixed_datetime(2025, 10, 12, 10, 0, 0)).await?;
-            set_queue_timestamp(&pool, third_id, fixed_datetime(2025, 10, 12, 8, 0, 0)).await?;
-
-            let entries = super::super::search_queue_entries_for_fetch(&pool, 10).await?;
-            assert_eq!(entries.len(), 3);
+            let options = FetchContentOptions {
+                accept_language: Some("en".to_string()),
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
             assert_eq!(
-                entries[0].id, first_id,
-                "最初はNULLで最も古いupdated_atを期待"
+                summary.saved_count, 2,
+                "想定外の結果: {:?}",
+                summary.entries
             );
-            assert_eq!(entries[1].id, second_id, "次は同じくNULLで次のupdated_at");
-            assert_eq!(entries[2].id, third_id, "最後に再試行対象のエントリが来る");
 
             Ok(())
         }
-    }
 
-    pub mod compress_html {
-        use std::io::{Cursor, Read};
+        /// # 検証目的
+        /// バッチコミットモードで、バッチ境界をまたぐ件数・端数のバッチも含めて全件が保存されることを確認する。
+        #[tokio::test]
+        async fn バッチコミットでも全件保存される() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
 
-        use brotli::Decompressor;
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
 
-        use crate::fetch_content::compress_html;
+            let server = MockServer::start().await;
 
-        /// # 検証目的
-        /// Brotli圧縮したHTMLを無損失で展開できることを確認する。
-        #[test]
-        fn 圧縮は可逆である() {
-            let html = "<html><body>テスト</body></html>";
-            let compressed = compress_html(html).expect("Brotli圧縮に失敗");
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(
+                    json!({ "url": "https://example.com/missing" }),
+                ))
+                .respond_with(ResponseTemplate::new(404))
+                .mount(&server)
+                .await;
 
-            let mut decompressor = Decompressor::new(Cursor::new(compressed), 4096);
-            let mut decompressed = String::new();
-            decompressor
-                .read_to_string(&mut decompressed)
-                .expect("Brotli展開に失敗");
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>batch</html>",
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
 
-            assert_eq!(decompressed, html);
+            for index in 0..4 {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(format!("https://example.com/batch/{}", index))
+                .bind(format!("バッチ{}", index))
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/missing")
+            .bind("存在しない")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let options = FetchContentOptions {
+                batch_size: 2,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+            assert_eq!(summary.saved_count, 4);
+            assert_eq!(summary.status_only_count, 1);
+            assert_eq!(summary.error_count, 0);
+
+            let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.article_content")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!(stored, 4);
+
+            let statuses: Vec<Option<i32>> =
+                sqlx::query_scalar("SELECT status_code FROM rss.queue ORDER BY link")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(
+                statuses,
+                vec![Some(200), Some(200), Some(200), Some(200), Some(404)]
+            );
+
+            let log: (String, i32, i32, Option<Uuid>) =
+                sqlx::query_as("SELECT command, total_processed, saved, run_id FROM rss.fetch_log")
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(
+                log,
+                ("fetch_content".to_string(), 5, 4, Some(summary.run_id))
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 除外ホスト（ワイルドカード含む）のエントリはscraping APIを呼ばずSkippedになり、status_codeも更新しないことを確認する。
+        #[tokio::test]
+        async fn 除外ホストはスキップする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(
+                    json!({ "url": "https://allowed.example/ok" }),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>ok</html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let skipped_id = Uuid::new_v4();
+            for (id, link) in [
+                (Uuid::new_v4(), "https://allowed.example/ok"),
+                (skipped_id, "https://news.paywall.example/article"),
+                (Uuid::new_v4(), "https://BLOCKED.example/heavy"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let options = FetchContentOptions {
+                deny_hosts: vec![
+                    "*.paywall.example".to_string(),
+                    "blocked.example".to_string(),
+                ],
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+
+            assert_eq!(summary.saved_count, 1);
+            assert_eq!(summary.skipped_count, 2);
+            assert_eq!(summary.error_count, 0);
+            let skipped = summary
+                .entries
+                .iter()
+                .find(|entry| entry.queue_id == skipped_id)
+                .expect("スキップしたエントリがない");
+            assert!(matches!(
+                &skipped.result,
+                FetchContentEntryOutcome::Skipped { host } if host == "news.paywall.example"
+            ));
+
+            let status: Option<i32> =
+                sqlx::query_scalar("SELECT status_code FROM rss.queue WHERE id = $1")
+                    .bind(skipped_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(status, None);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 除外ホストでスキップしたエントリは次回実行時刻が後ろへずれ、次の実行で再び選ばれて
+        /// 他のエントリをバッチから押し出さないことを確認する。
+        #[tokio::test]
+        async fn 除外ホストのエントリは次の実行で再取得しない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>ok</html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let denied_id = Uuid::new_v4();
+            let allowed_id = Uuid::new_v4();
+            for (id, link) in [
+                (denied_id, "https://blocked.example/article"),
+                (allowed_id, "https://allowed.example/ok"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+            // 除外ホストのエントリが先に選ばれる状態にする
+            set_fetch_job_next_run_at(&pool, denied_id, Utc::now() - chrono::Duration::minutes(10))
+                .await?;
+
+            let options = FetchContentOptions {
+                deny_hosts: vec!["blocked.example".to_string()],
+                ..FetchContentOptions::default()
+            };
+            let first = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(first.skipped_count, 1);
+            assert_eq!(first.entries[0].queue_id, denied_id);
+
+            let next_run_at: chrono::DateTime<Utc> =
+                sqlx::query_scalar("SELECT next_run_at FROM rss.fetch_jobs WHERE queue_id = $1")
+                    .bind(denied_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert!(next_run_at > Utc::now() + chrono::Duration::hours(1));
+
+            let second = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(second.skipped_count, 0);
+            assert_eq!(second.saved_count, 1);
+            assert_eq!(second.entries[0].queue_id, allowed_id);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 事前チェック有効時、HEADで404の記事はスクレイプせずStatusOnlyになり、HEAD非対応（405）の記事はスクレイプへ進むことを確認する。
+        #[tokio::test]
+        async fn 事前チェックで404はスクレイプしない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let missing_link = format!("{}/articles/missing", server.uri());
+            let no_head_link = format!("{}/articles/no-head", server.uri());
+
+            Mock::given(method("HEAD"))
+                .and(path("/articles/missing"))
+                .respond_with(ResponseTemplate::new(404))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("HEAD"))
+                .and(path("/articles/no-head"))
+                .respond_with(ResponseTemplate::new(405))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(json!({ "url": &no_head_link })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>ok</html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let missing_id = Uuid::new_v4();
+            for (id, link) in [
+                (missing_id, missing_link.as_str()),
+                (Uuid::new_v4(), no_head_link.as_str()),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let options = FetchContentOptions {
+                precheck_url: true,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+
+            assert_eq!(summary.saved_count, 1);
+            assert_eq!(summary.status_only_count, 1);
+            let missing = summary
+                .entries
+                .iter()
+                .find(|entry| entry.queue_id == missing_id)
+                .expect("事前チェックしたエントリがない");
+            assert!(matches!(
+                missing.result,
+                FetchContentEntryOutcome::StatusOnly { status_code: 404 }
+            ));
+
+            let status: Option<i32> =
+                sqlx::query_scalar("SELECT status_code FROM rss.queue WHERE id = $1")
+                    .bind(missing_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(status, Some(404));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// プロキシ設定時はscraping APIへのリクエストがプロキシ経由で送られることを確認する。
+        #[tokio::test]
+        async fn プロキシ経由でscraping_apiを呼ぶ() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let proxy = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>proxied</html>",
+                    "status_code": 200,
+                })))
+                .expect(1)
+                .mount(&proxy)
+                .await;
+
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/proxied")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let options = FetchContentOptions {
+                proxy_url: Some(proxy.uri()),
+                ..FetchContentOptions::default()
+            };
+            // 名前解決できないscraping APIでもプロキシが応答すれば保存できる
+            let summary =
+                execute_fetch_content(&pool, 10, "http://scraping.invalid", &options).await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 本文抽出を有効にすると抽出した本文エリアを保存し、抽出できないHTMLは生HTMLのまま保存することを確認する。
+        #[tokio::test]
+        async fn 本文抽出有効時は抽出結果を保存する() -> Result<()> {
+            use crate::fetch_content::decompress_html;
+            use crate::fetch_content::tests::extract_main_content::NOISY_HTML;
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let raw_html = "<html><body></body></html>";
+
+            for (link, html) in [
+                ("https://example.com/noisy", NOISY_HTML),
+                ("https://example.com/empty", raw_html),
+            ] {
+                Mock::given(method("POST"))
+                    .and(path("/fetch"))
+                    .and(body_partial_json(json!({ "url": link })))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "html": html,
+                        "status_code": 200,
+                    })))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+            }
+
+            let noisy_id = Uuid::new_v4();
+            let empty_id = Uuid::new_v4();
+            for (id, link) in [
+                (noisy_id, "https://example.com/noisy"),
+                (empty_id, "https://example.com/empty"),
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let options = FetchContentOptions {
+                extract_main_content: true,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+            assert_eq!(
+                summary.saved_count, 2,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            let load = |id: Uuid| {
+                let pool = pool.clone();
+                async move {
+                    let data: Vec<u8> = sqlx::query_scalar(
+                        "SELECT data FROM rss.article_content WHERE queue_id = $1",
+                    )
+                    .bind(id)
+                    .fetch_one(&pool)
+                    .await?;
+                    Ok::<_, anyhow::Error>(String::from_utf8(decompress_html(&data)?)?)
+                }
+            };
+
+            let extracted = load(noisy_id).await?;
+            assert!(extracted.contains("本文の第一段落です。"));
+            assert!(!extracted.contains("ナビ項目ニュース"));
+            assert!(!extracted.contains("サイドバーのランキング"));
+
+            assert_eq!(load(empty_id).await?, raw_html);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 要約APIが設定されていると本文テキストを要約APIへ送って要約を保存し、
+        /// 要約APIが失敗しても本文の保存は成功扱いになることを確認する。
+        #[tokio::test]
+        async fn 要約apiの結果を保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html><head><script>ignored()</script></head><body><p>長い本文です。</p></body></html>",
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/summarize"))
+                .and(body_partial_json(json!({
+                    "url": "https://example.com/summary",
+                    "title": "要約対象",
+                    "text": "長い本文です。",
+                })))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!({ "summary": " 短い要約 " })),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/broken"))
+                .respond_with(ResponseTemplate::new(500))
+                .mount(&server)
+                .await;
+
+            for (summary_path, expected) in [("/summarize", Some("短い要約")), ("/broken", None)]
+            {
+                clear_rss_tables(&pool).await?;
+                let queue_id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(queue_id)
+                .bind("https://example.com/summary")
+                .bind("要約対象")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+
+                let options = FetchContentOptions {
+                    summary_api_url: Some(format!("{}{}", server.uri(), summary_path)),
+                    ..FetchContentOptions::default()
+                };
+                let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+                assert_eq!(summary.saved_count, 1, "{:?}", summary.entries);
+                assert_eq!(summary.error_count, 0);
+
+                let stored: Option<String> = sqlx::query_scalar(
+                    "SELECT summary FROM rss.article_content WHERE queue_id = $1",
+                )
+                .bind(queue_id)
+                .fetch_one(&pool)
+                .await?;
+                assert_eq!(stored.as_deref(), expected, "path: {}", summary_path);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// CONTENT_COMPRESSIONで指定した方式で本文を圧縮し、article_contentのcompressionと圧縮品質へ記録することを確認する。
+        #[tokio::test]
+        async fn 指定した圧縮方式で保存する() -> Result<()> {
+            use crate::compression::{decompress_content, ContentCompression};
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+
+            let server = MockServer::start().await;
+            let html_body = "<html><body><p>圧縮方式のテスト</p></body></html>";
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": html_body,
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+
+            for compression in [
+                ContentCompression::Brotli,
+                ContentCompression::Gzip,
+                ContentCompression::Zstd,
+            ] {
+                clear_rss_tables(&pool).await?;
+                let queue_id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(queue_id)
+                .bind("https://example.com/compression")
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+
+                let options = FetchContentOptions {
+                    compression,
+                    ..FetchContentOptions::default()
+                };
+                let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+                assert_eq!(summary.saved_count, 1, "{:?}", summary.entries);
+
+                let (data, stored, quality): (Vec<u8>, ContentCompression, Option<i16>) =
+                    sqlx::query_as(
+                        "SELECT data, compression, compression_quality FROM rss.article_content WHERE queue_id = $1",
+                    )
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+                assert_eq!(stored, compression);
+                assert_eq!(quality, Some(i16::from(compression.quality())));
+                assert_eq!(decompress_content(&data, stored)?, html_body.as_bytes());
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 取得したHTMLの`<link rel="canonical">`を絶対URLへ解決してarticle_contentへ保存し、無い場合はNULLになることを確認する。
+        #[tokio::test]
+        async fn canonical_urlを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let cases = [
+                (
+                    "https://example.com/amp/article?utm_source=rss",
+                    r#"<html><head><link rel="canonical" href="/article"></head><body><p>本文</p></body></html>"#,
+                    Some("https://example.com/article"),
+                ),
+                (
+                    "https://example.com/plain",
+                    "<html><head></head><body><p>本文</p></body></html>",
+                    None,
+                ),
+            ];
+
+            let mut ids = Vec::new();
+            for (link, html, _) in &cases {
+                Mock::given(method("POST"))
+                    .and(path("/fetch"))
+                    .and(body_partial_json(json!({ "url": link })))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "html": html,
+                        "status_code": 200,
+                    })))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+                ids.push(id);
+            }
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(
+                summary.saved_count, 2,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            for (id, (_, _, expected)) in ids.iter().zip(cases.iter()) {
+                let canonical: Option<String> = sqlx::query_scalar(
+                    "SELECT canonical_url FROM rss.article_content WHERE queue_id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await?;
+                assert_eq!(canonical.as_deref(), *expected);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// scraping APIが返した`last_modified`をUTCの日時として`source_last_modified`へ保存し、
+        /// 返されない・解釈できない場合はNULLになることを確認する。
+        #[tokio::test]
+        async fn last_modifiedを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let cases = [
+                (
+                    "https://example.com/modified",
+                    Some("Wed, 21 Oct 2015 07:28:00 GMT"),
+                    Some(fixed_datetime(2015, 10, 21, 7, 28, 0)),
+                ),
+                ("https://example.com/broken", Some("昨日"), None),
+                ("https://example.com/missing", None, None),
+            ];
+
+            let mut ids = Vec::new();
+            for (link, last_modified, _) in &cases {
+                Mock::given(method("POST"))
+                    .and(path("/fetch"))
+                    .and(body_partial_json(json!({ "url": link })))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "html": "<html><body><p>本文</p></body></html>",
+                        "status_code": 200,
+                        "last_modified": last_modified,
+                    })))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+                ids.push(id);
+            }
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(
+                summary.saved_count, 3,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            for (id, (link, _, expected)) in ids.iter().zip(cases.iter()) {
+                let last_modified: Option<chrono::DateTime<Utc>> = sqlx::query_scalar(
+                    "SELECT source_last_modified FROM rss.article_content WHERE queue_id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await?;
+                assert_eq!(last_modified, *expected, "link: {}", link);
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 保存済み本文の`source_last_modified`を`if_modified_since`として渡し、304が返ったら
+        /// 本文を書き換えずにNotModifiedとして完了扱いにすることを確認する。
+        #[tokio::test]
+        async fn 未更新なら本文の書き込みをスキップする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(json!({
+                    "url": "https://example.com/unchanged",
+                    "if_modified_since": "Wed, 21 Oct 2015 07:28:00 GMT",
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "",
+                    "status_code": 304,
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let queue_id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(queue_id)
+            .bind("https://example.com/unchanged")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO rss.article_content (queue_id, data, source_last_modified)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(queue_id)
+            .bind(b"saved".to_vec())
+            .bind(fixed_datetime(2015, 10, 21, 7, 28, 0))
+            .execute(&pool)
+            .await?;
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert!(
+                matches!(
+                    summary.entries[0].result,
+                    FetchContentEntryOutcome::NotModified
+                ),
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+            assert_eq!(summary.saved_count, 0);
+            assert_eq!(summary.status_only_count, 1);
+
+            let data: Vec<u8> =
+                sqlx::query_scalar("SELECT data FROM rss.article_content WHERE queue_id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(data, b"saved".to_vec(), "本文は書き換えない");
+
+            let status_code: Option<i32> =
+                sqlx::query_scalar("SELECT status_code FROM rss.queue WHERE id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(status_code, Some(200));
+            let job_status: FetchJobStatus =
+                sqlx::query_scalar("SELECT status FROM rss.fetch_jobs WHERE queue_id = $1")
+                    .bind(queue_id)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(job_status, FetchJobStatus::Done);
+
+            Ok(())
+        }
+
+        /// scraping APIが呼ばれた時点でトークンをキャンセルする（着手後のキャンセルを再現する）
+        struct CancelOnScrape {
+            cancel: CancellationToken,
+        }
+
+        impl Respond for CancelOnScrape {
+            fn respond(&self, _request: &Request) -> ResponseTemplate {
+                self.cancel.cancel();
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html><body>本文</body></html>",
+                    "status_code": 200,
+                }))
+            }
+        }
+
+        /// # 検証目的
+        /// 実行中にキャンセルされた場合、着手済みのエントリは保存まで終え、残りのエントリには着手せず
+        /// ジョブをpendingへ戻すことを確認する。
+        #[tokio::test]
+        async fn キャンセル後は新しいエントリに着手しない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let cancel = CancellationToken::new();
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(CancelOnScrape {
+                    cancel: cancel.clone(),
+                })
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            for index in 0..3 {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(format!("https://example.com/cancel/{}", index))
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let summary = execute_fetch_content_with_progress(
+                &pool,
+                10,
+                &server.uri(),
+                &FetchContentOptions::default(),
+                None,
+                &cancel,
+            )
+            .await?;
+            assert_eq!(summary.saved_count, 1, "{:?}", summary.entries);
+            assert_eq!(summary.entries.len(), 1);
+            assert_eq!(summary.cancelled_count, 2);
+
+            let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.article_content")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!(stored, 1);
+            let statuses: Vec<FetchJobStatus> =
+                sqlx::query_scalar("SELECT status FROM rss.fetch_jobs ORDER BY status")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(
+                statuses,
+                vec![
+                    FetchJobStatus::Done,
+                    FetchJobStatus::Pending,
+                    FetchJobStatus::Pending
+                ]
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// QPS制限を共有したfetch-contentを並列に実行しても、すべてのエントリを取得でき、
+        /// 全体で`1 / QPS`秒ずつしか呼び出しが進まないことを確認する（間隔そのものは`rate_limit`のテストで確認する）。
+        #[tokio::test]
+        async fn 並列実行でも全体のqpsを超えない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html><body>本文</body></html>",
+                    "status_code": 200,
+                })))
+                .expect(6)
+                .mount(&server)
+                .await;
+
+            for index in 0..6 {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description)
+                    VALUES ($1, $2, 'タイトル', '説明')
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(format!("https://example.com/qps/{}", index))
+                .execute(&pool)
+                .await?;
+            }
+
+            // 20QPS = 50ms間隔
+            let options = FetchContentOptions {
+                scrape_rate_limiter: Some(Arc::new(RateLimiter::new(20.0)?)),
+                ..FetchContentOptions::default()
+            };
+            let api_url = server.uri();
+            let started = tokio::time::Instant::now();
+            let (first, second) = tokio::join!(
+                execute_fetch_content(&pool, 3, &api_url, &options),
+                execute_fetch_content(&pool, 3, &api_url, &options),
+            );
+            assert_eq!(first?.saved_count + second?.saved_count, 6);
+            // 6回目の呼び出しは5間隔分より前には送られない（下限だけを見るので遅い環境でも揺れない）
+            let elapsed = started.elapsed();
+            assert!(
+                elapsed >= Duration::from_millis(250),
+                "制限より早く終わった: {:?}",
+                elapsed
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `extract_links`有効時、本文中のリンクを最終URL基準で絶対URLへ解決してarticle_linksへ出現順に保存し、
+        /// 再取得時は前回のリンクを置き換えることを確認する。
+        #[tokio::test]
+        async fn 本文のリンクを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": r#"<html><body><a href="https://other.example.org/ref">参考</a><a href="related">関連</a></body></html>"#,
+                    "status_code": 200,
+                    "final_url": "https://example.com/articles/1",
+                })))
+                .mount(&server)
+                .await;
+
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind("https://short.example.com/x")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+            // 前回取得時のリンクは置き換えられる
+            sqlx::query("INSERT INTO rss.article_content (queue_id, data) VALUES ($1, $2)")
+                .bind(id)
+                .bind(b"old".as_slice())
+                .execute(&pool)
+                .await?;
+            sqlx::query(
+                "INSERT INTO rss.article_links (queue_id, position, href) VALUES ($1, 0, $2)",
+            )
+            .bind(id)
+            .bind("https://example.com/stale")
+            .execute(&pool)
+            .await?;
+
+            let options = FetchContentOptions {
+                extract_links: true,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 10, &server.uri(), &options).await?;
+            assert_eq!(summary.saved_count, 1, "{:?}", summary.entries);
+
+            let links: Vec<String> = sqlx::query_scalar(
+                "SELECT href FROM rss.article_links WHERE queue_id = $1 ORDER BY position",
+            )
+            .bind(id)
+            .fetch_all(&pool)
+            .await?;
+            assert_eq!(
+                links,
+                vec![
+                    "https://other.example.org/ref".to_string(),
+                    "https://example.com/articles/related".to_string(),
+                ]
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 取得したHTMLのOGメタデータをarticle_contentへ保存し、OGタグが無い記事はNULLになることを確認する。
+        #[tokio::test]
+        async fn ogメタデータを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let cases = [
+                (
+                    "https://example.com/og",
+                    r#"<html><head>
+<meta property="og:title" content="OGタイトル">
+<meta property="og:description" content="OG説明">
+<meta property="og:image" content="https://cdn.example.com/og.png">
+</head><body><p>本文</p></body></html>"#,
+                    (
+                        Some("OGタイトル"),
+                        Some("OG説明"),
+                        Some("https://cdn.example.com/og.png"),
+                    ),
+                ),
+                (
+                    "https://example.com/no-og",
+                    "<html><head></head><body><p>本文</p></body></html>",
+                    (None, None, None),
+                ),
+            ];
+
+            let mut ids = Vec::new();
+            for (link, html, _) in &cases {
+                Mock::given(method("POST"))
+                    .and(path("/fetch"))
+                    .and(body_partial_json(json!({ "url": link })))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                        "html": html,
+                        "status_code": 200,
+                    })))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+                ids.push(id);
+            }
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(
+                summary.saved_count, 2,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            for (id, (_, _, expected)) in ids.iter().zip(cases.iter()) {
+                let (title, description, image): (Option<String>, Option<String>, Option<String>) =
+                    sqlx::query_as(
+                        "SELECT og_title, og_description, og_image FROM rss.article_content WHERE queue_id = $1",
+                    )
+                    .bind(id)
+                    .fetch_one(&pool)
+                    .await?;
+                assert_eq!(
+                    (title.as_deref(), description.as_deref(), image.as_deref()),
+                    *expected
+                );
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 同じ記事を繰り返し取得したとき、初回は新規、同じ本文なら変化なし、本文が変わったときだけ更新として
+        /// レポートに記録され、`content_sha256`が圧縮前の本文のハッシュで更新されることを確認する。
+        #[tokio::test]
+        async fn 本文の変化を判定する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let first_html = "<html><body><p>初版</p></body></html>";
+            let second_html = "<html><body><p>改訂版</p></body></html>";
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": first_html,
+                    "status_code": 200,
+                })))
+                .up_to_n_times(2)
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": second_html,
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind("https://example.com/revised")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let expected = [
+                (ContentChange::Created, first_html),
+                (ContentChange::Unchanged, first_html),
+                (ContentChange::Updated, second_html),
+            ];
+            for (change, html) in expected {
+                let summary = execute_fetch_content(
+                    &pool,
+                    10,
+                    &server.uri(),
+                    &FetchContentOptions::default(),
+                )
+                .await?;
+                assert_eq!(
+                    summary.saved_count, 1,
+                    "想定外の結果: {:?}",
+                    summary.entries
+                );
+                assert_eq!(summary.entries[0].content_change, Some(change));
+
+                let sha256: Option<String> = sqlx::query_scalar(
+                    "SELECT content_sha256 FROM rss.article_content WHERE queue_id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await?;
+                assert_eq!(sha256, Some(content_sha256(html)));
+
+                // 取得済みのジョブを再び取得対象へ戻す
+                release_fetch_jobs(&pool, &[id]).await?;
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `CONTENT_ARCHIVE=warc`のとき、取得したHTMLが最終URL・ステータス付きのWARCレコードとして
+        /// `rss.article_warc`へ保存され、既定では保存されないことを確認する。
+        #[tokio::test]
+        async fn warcアーカイブを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let html = "<html><body><p>アーカイブ本文</p></body></html>";
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": html,
+                    "status_code": 200,
+                    "final_url": "https://example.com/archived?from=rss",
+                })))
+                .mount(&server)
+                .await;
+
+            for link in ["https://example.com/warc", "https://example.com/plain"] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            // 1件目はWARCあり、2件目は既定（アーカイブなし）で取得する
+            let options = FetchContentOptions {
+                archive: ContentArchive::Warc,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+            let archived_id = summary.entries[0].queue_id;
+            let summary =
+                execute_fetch_content(&pool, 1, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            let archives: Vec<(Uuid, Vec<u8>)> =
+                sqlx::query_as("SELECT queue_id, data FROM rss.article_warc")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(archives.len(), 1);
+            assert_eq!(archives[0].0, archived_id);
+
+            let mut archive = String::new();
+            MultiGzDecoder::new(archives[0].1.as_slice()).read_to_string(&mut archive)?;
+            assert!(archive.starts_with("WARC/1.1\r\nWARC-Type: request\r\n"));
+            assert!(archive.contains("WARC-Type: response\r\n"));
+            assert!(archive.contains("WARC-Target-URI: https://example.com/archived?from=rss\r\n"));
+            assert!(archive.contains("HTTP/1.1 200 OK\r\n"));
+            assert!(archive.contains(html));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `debug_dir`指定時、取得したHTMLが`<dir>/<queue_id>.html`へ書き出され、
+        /// 書き出せないディレクトリを指定しても本文の保存は成功することを確認する。
+        #[tokio::test]
+        async fn デバッグモードでhtmlを書き出す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let html = "<html><body><p>デバッグ本文</p></body></html>";
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": html,
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+
+            for link in [
+                "https://example.com/debug",
+                "https://example.com/unwritable",
+            ] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            // 存在しない下位ディレクトリも作成して書き出す
+            let temp_dir = create_temp_dir()?;
+            let debug_dir = temp_dir.path().join("scrape");
+            let options = FetchContentOptions {
+                debug_dir: Some(debug_dir.clone()),
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+            let queue_id = summary.entries[0].queue_id;
+            let written = std::fs::read_to_string(debug_dir.join(format!("{}.html", queue_id)))?;
+            assert_eq!(written, html);
+
+            // ファイルをディレクトリとして指定すると書き出しは失敗するが、保存は続ける
+            let blocker = temp_dir.path().join("not-a-dir");
+            std::fs::write(&blocker, "")?;
+            let options = FetchContentOptions {
+                debug_dir: Some(blocker),
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `raw_response`有効時、scraping APIのレスポンスJSON全体（html以外のメタを含む）が
+        /// `raw_response`へそのまま保存され、既定では保存されないことを確認する。
+        #[tokio::test]
+        async fn 生レスポンスを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let response_body = json!({
+                "html": "<html><body><p>生レスポンス</p></body></html>",
+                "status_code": 200,
+                "engine": "chromium",
+                "timings": { "total_ms": 1234 },
+            });
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response_body.clone()))
+                .mount(&server)
+                .await;
+
+            for link in ["https://example.com/raw", "https://example.com/plain"] {
+                sqlx::query(
+                    "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+                )
+                .bind(Uuid::new_v4())
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            // 1件目は生レスポンスあり、2件目は既定（保存しない）で取得する
+            let options = FetchContentOptions {
+                raw_response: true,
+                ..FetchContentOptions::default()
+            };
+            let summary = execute_fetch_content(&pool, 1, &server.uri(), &options).await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+            let raw_id = summary.entries[0].queue_id;
+            let summary =
+                execute_fetch_content(&pool, 1, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(
+                summary.saved_count, 1,
+                "想定外の結果: {:?}",
+                summary.entries
+            );
+            let plain_id = summary.entries[0].queue_id;
+
+            let raw_response: Option<String> = sqlx::query_scalar(
+                "SELECT raw_response::text FROM rss.article_content WHERE queue_id = $1",
+            )
+            .bind(raw_id)
+            .fetch_one(&pool)
+            .await?;
+            let raw_response: serde_json::Value =
+                serde_json::from_str(&raw_response.expect("raw_responseが保存されている"))?;
+            assert_eq!(raw_response, response_body);
+
+            let plain_response: Option<String> = sqlx::query_scalar(
+                "SELECT raw_response::text FROM rss.article_content WHERE queue_id = $1",
+            )
+            .bind(plain_id)
+            .fetch_one(&pool)
+            .await?;
+            assert!(plain_response.is_none());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// HTTP 200・ボディstatus_code 500の応答はstatus_codeのみ記録しつつ、レポートに不一致が付くことを確認する。
+        #[tokio::test]
+        async fn ステータス不一致をレポートに記録する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "",
+                    "status_code": 500,
+                })))
+                .mount(&server)
+                .await;
+
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(Uuid::new_v4())
+            .bind("https://example.com/mismatch")
+            .bind("タイトル")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(summary.status_only_count, 1);
+            let report = &summary.entries[0];
+            assert!(report.status_mismatch);
+            assert!(matches!(
+                report.result,
+                FetchContentEntryOutcome::StatusOnly { status_code: 500 }
+            ));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// ワイルドカードはサブドメインのみに一致し、完全一致指定は大文字小文字を区別しないことを確認する。
+        #[test]
+        fn 除外ホストの一致判定() {
+            let deny = vec!["*.example.com".to_string(), "Exact.test".to_string()];
+
+            assert_eq!(
+                find_denied_host("https://a.b.example.com/x", &deny).as_deref(),
+                Some("a.b.example.com")
+            );
+            assert_eq!(find_denied_host("https://example.com/x", &deny), None);
+            assert_eq!(find_denied_host("https://notexample.com/x", &deny), None);
+            assert_eq!(
+                find_denied_host("https://exact.test:8443/x", &deny).as_deref(),
+                Some("exact.test")
+            );
+            assert_eq!(find_denied_host("https://sub.exact.test/x", &deny), None);
+            assert_eq!(find_denied_host("not a url", &deny), None);
+        }
+    }
+
+    pub mod summarize_by_group_tests {
+        use anyhow::Result;
+        use serde_json::json;
+        use uuid::Uuid;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::fetch_content::{
+            execute_fetch_content, summarize_by_group, FetchContentEntryOutcome,
+            FetchContentEntryReport, FetchContentGroupSummary, FetchContentOptions,
+        };
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        fn report(
+            group: Option<&str>,
+            result: FetchContentEntryOutcome,
+        ) -> FetchContentEntryReport {
+            FetchContentEntryReport {
+                queue_id: Uuid::new_v4(),
+                title: "記事".to_string(),
+                group: group.map(|g| g.to_string()),
+                result,
+                status_mismatch: false,
+                content_change: None,
+            }
+        }
+
+        /// # 検証目的
+        /// 複数グループが混在する結果をグループごとに正しく集計できることを確認する。
+        #[test]
+        fn 複数グループを集計できる() {
+            let entries = vec![
+                report(
+                    Some("world"),
+                    FetchContentEntryOutcome::Saved { status_code: 200 },
+                ),
+                report(
+                    Some("tech"),
+                    FetchContentEntryOutcome::StatusOnly { status_code: 404 },
+                ),
+                report(
+                    Some("world"),
+                    FetchContentEntryOutcome::Saved { status_code: 200 },
+                ),
+                report(
+                    Some("world"),
+                    FetchContentEntryOutcome::ApiError {
+                        message: "timeout".to_string(),
+                    },
+                ),
+                report(
+                    None,
+                    FetchContentEntryOutcome::PersistError {
+                        message: "db".to_string(),
+                    },
+                ),
+            ];
+
+            let by_group = summarize_by_group(&entries);
+            assert_eq!(
+                by_group,
+                vec![
+                    FetchContentGroupSummary {
+                        group: None,
+                        saved: 0,
+                        status_only: 0,
+                        error: 1,
+                        skipped: 0,
+                    },
+                    FetchContentGroupSummary {
+                        group: Some("tech".to_string()),
+                        saved: 0,
+                        status_only: 1,
+                        error: 0,
+                        skipped: 0,
+                    },
+                    FetchContentGroupSummary {
+                        group: Some("world".to_string()),
+                        saved: 2,
+                        status_only: 0,
+                        error: 1,
+                        skipped: 0,
+                    },
+                ]
+            );
+        }
+
+        /// # 検証目的
+        /// execute_fetch_contentのサマリにqueueのgroup単位の集計が含まれることを確認する。
+        #[tokio::test]
+        async fn サマリにグループ別集計が含まれる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(
+                    json!({ "url": "https://example.com/world" }),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "<html>world</html>",
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .and(body_partial_json(
+                    json!({ "url": "https://example.com/tech" }),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "html": "",
+                    "status_code": 404,
+                })))
+                .mount(&server)
+                .await;
+
+            for (link, group) in [
+                ("https://example.com/world", "world"),
+                ("https://example.com/tech", "tech"),
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, "group")
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(link)
+                .bind("グループ記事")
+                .bind("説明")
+                .bind(group)
+                .execute(&pool)
+                .await?;
+            }
+
+            let summary =
+                execute_fetch_content(&pool, 10, &server.uri(), &FetchContentOptions::default())
+                    .await?;
+            assert_eq!(summary.by_group.len(), 2);
+
+            let tech = &summary.by_group[0];
+            assert_eq!(tech.group.as_deref(), Some("tech"));
+            assert_eq!((tech.saved, tech.status_only, tech.error), (0, 1, 0));
+
+            let world = &summary.by_group[1];
+            assert_eq!(world.group.as_deref(), Some("world"));
+            assert_eq!((world.saved, world.status_only, world.error), (1, 0, 0));
+
+            Ok(())
+        }
+    }
+
+    pub mod call_scrape_api_tests {
+        use anyhow::Result;
+        use reqwest::Client;
+        use tracing_test::traced_test;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::fetch_content::{call_scrape_api, read_body_limited, ScrapeResult};
+        use crate::models::ScrapeRequest;
+
+        /// # 検証目的
+        /// 上限を超える巨大なレスポンスボディを受け取った場合にエラーで打ち切ることを確認する。
+        #[tokio::test]
+        async fn 上限超過のレスポンスはエラーになる() -> Result<()> {
+            let server = MockServer::start().await;
+            let huge_html = "a".repeat(4096);
+
+            Mock::given(method("POST"))
+                .and(path("/fetch"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "html": huge_html,
+                    "status_code": 200,
+                })))
+                .mount(&server)
+                .await;
+
+            let request = ScrapeRequest {
+                url: "https://example.com/huge".to_string(),
+                wait_for_selector: None,
+                timeout: None,
+                referer: None,
+                accept_language: None,
+                if_modified_since: None,
+            };
+
+            let result = call_scrape_api(&Client::new(), &server.uri(), &request, 1024).await;
+            let err = result.err().expect("上限超過エラーを期待");
+            assert!(err.to_string().contains("上限"), "想定外のエラー: {}", err);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// HTTP 200でボディのstatus_codeが500のような食い違いを検出して警告ログを出し、一致時は検出しないことを確認する。
+        #[traced_test]
+        #[tokio::test]
+        async fn ステータスの不一致を検出する() -> Result<()> {
+            let server = MockServer::start().await;
+
+            for (url, status_code) in [("/mismatch", 500), ("/match", 200)] {
+                Mock::given(method("POST"))
+                    .and(path("/fetch"))
+                    .and(body_partial_json(serde_json::json!({ "url": url })))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                        "html": "<html></html>",
+                        "status_code": status_code,
+                    })))
+                    .mount(&server)
+                    .await;
+            }
+
+            let request = |url: &str| ScrapeRequest {
+                url: url.to_string(),
+                wait_for_selector: None,
+                timeout: None,
+                referer: None,
+                accept_language: None,
+                if_modified_since: None,
+            };
+            let client = Client::new();
+
+            let result = call_scrape_api(&client, &server.uri(), &request("/match"), 1024).await?;
+            assert!(matches!(
+                result,
+                ScrapeResult::Success {
+                    status_mismatch: false,
+                    ..
+                }
+            ));
+            assert!(!logs_contain("一致しません"));
+
+            let result =
+                call_scrape_api(&client, &server.uri(), &request("/mismatch"), 1024).await?;
+            match result {
+                ScrapeResult::Success {
+                    response,
+                    status_mismatch,
+                    ..
+                } => {
+                    assert!(status_mismatch);
+                    assert_eq!(response.status_code, 500);
+                }
+                ScrapeResult::HttpError { .. } => panic!("Successを期待"),
+            }
+            assert!(logs_contain("一致しません"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// Content-Lengthが無いストリームでも累積サイズで打ち切られることを確認する。
+        #[tokio::test]
+        async fn ストリームの累積サイズで打ち切る() {
+            let chunks = vec![
+                Ok::<_, std::io::Error>(vec![b'a'; 600]),
+                Ok(vec![b'b'; 600]),
+            ];
+            let body = reqwest::Body::wrap_stream(futures::stream::iter(chunks));
+            let response = reqwest::Response::from(
+                axum::http::Response::builder()
+                    .status(200)
+                    .body(body)
+                    .expect("レスポンスの構築に失敗"),
+            );
+            assert!(response.content_length().is_none());
+
+            let result = read_body_limited(response, 1000).await;
+            assert!(result.is_err(), "打ち切りエラーを期待");
+        }
+    }
+
+    pub mod log_fetch_content_summary {
+        use tracing_test::traced_test;
+        use uuid::Uuid;
+
+        use crate::fetch_content::{
+            log_fetch_content_summary, FetchContentEntryOutcome, FetchContentEntryReport,
+            FetchContentSummary,
+        };
+
+        /// # 検証目的
+        /// 保存成功・status更新のみ・エラーの各結果に対応したログが出力されることを確認する。
+        #[traced_test]
+        #[test]
+        fn 集計ログが出力される() {
+            let summary = FetchContentSummary {
+                run_id: Uuid::new_v4(),
+                saved_count: 1,
+                status_only_count: 1,
+                error_count: 1,
+                skipped_count: 0,
+                cancelled_count: 0,
+                by_group: Vec::new(),
+                entries: vec![
+                    FetchContentEntryReport {
+                        queue_id: Uuid::new_v4(),
+                        title: "保存記事".to_string(),
+                        group: None,
+                        result: FetchContentEntryOutcome::Saved { status_code: 200 },
+                        status_mismatch: false,
+                        content_change: None,
+                    },
+                    FetchContentEntryReport {
+                        queue_id: Uuid::new_v4(),
+                        title: "ステータスのみ".to_string(),
+                        group: None,
+                        result: FetchContentEntryOutcome::StatusOnly { status_code: 500 },
+                        status_mismatch: false,
+                        content_change: None,
+                    },
+                    FetchContentEntryReport {
+                        queue_id: Uuid::new_v4(),
+                        title: "エラー".to_string(),
+                        group: None,
+                        result: FetchContentEntryOutcome::ApiError {
+                            message: "API error".to_string(),
+                        },
+                        status_mismatch: false,
+                        content_change: None,
+                    },
+                ],
+                webhook: None,
+            };
+
+            log_fetch_content_summary(&summary);
+
+            assert!(logs_contain("保存完了"));
+            assert!(logs_contain("status_codeのみ記録"));
+            assert!(logs_contain("APIエラー"));
+            assert!(logs_contain("処理完了"));
+        }
+    }
+
+    pub mod search_queue_entries_for_fetch_tests {
+        use anyhow::Result;
+        use uuid::Uuid;
+
+        use crate::fetch_content::FetchContentFilter;
+
+        use crate::test_support::{
+            clear_rss_tables, fixed_datetime, prepare_test_pool, set_fetch_job_next_run_at,
+        };
+
+        /// # 検証目的
+        /// 未試行のジョブが優先され、next_run_at昇順で取得されることを確認する。
+        #[tokio::test]
+        async fn 未試行が優先され次回実行時刻で並ぶ() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let first_id = Uuid::new_v4();
+            let second_id = Uuid::new_v4();
+            let third_id = Uuid::new_v4();
+
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(first_id)
+            .bind("https://example.com/null-early")
+            .bind("NULL早期")
+            .bind("説明1")
+            .execute(&pool)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(second_id)
+            .bind("https://example.com/null-late")
+            .bind("NULL後期")
+            .bind("説明2")
+            .execute(&pool)
+            .await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO rss.queue (id, link, title, description, status_code)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(third_id)
+            .bind("https://example.com/failed")
+            .bind("失敗済み")
+            .bind("説明3")
+            .bind(503)
+            .execute(&pool)
+            .await?;
+
+            // 挿入順とは逆の実行時刻にして、並び順がnext_run_atで決まることを確かめる
+            set_fetch_job_next_run_at(&pool, first_id, fixed_datetime(2025, 10, 12, 9, 0, 0))
+                .await?;
+            set_fetch_job_next_run_at(&pool, second_id, fixed_datetime(2025, 10, 12, 8, 30, 0))
+                .await?;
+            set_fetch_job_next_run_at(&pool, third_id, fixed_datetime(2025, 10, 12, 8, 0, 0))
+                .await?;
+
+            let entries = super::super::search_queue_entries_for_fetch(
+                &pool,
+                10,
+                &FetchContentFilter::default(),
+            )
+            .await?;
+            assert_eq!(entries.len(), 3);
+            assert_eq!(
+                entries[0].id, second_id,
+                "最初は未試行で最も古いnext_run_atを期待"
+            );
+            assert_eq!(entries[1].id, first_id, "次は同じく未試行で次のnext_run_at");
+            assert_eq!(entries[2].id, third_id, "最後に再試行対象のエントリが来る");
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// queueの優先度が高いエントリは、next_run_atが遅くても先に取得されることを確認する。
+        #[tokio::test]
+        async fn 優先度の高いエントリを先に取得する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let low_id = Uuid::new_v4();
+            let high_id = Uuid::new_v4();
+            for (id, link, priority) in [
+                (low_id, "https://example.com/low", 0),
+                (high_id, "https://example.com/high", 10),
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, priority)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .bind(priority)
+                .execute(&pool)
+                .await?;
+            }
+
+            // 優先度の低いエントリの方が先に実行時刻を迎えている状態にする
+            set_fetch_job_next_run_at(&pool, low_id, fixed_datetime(2025, 10, 12, 8, 0, 0)).await?;
+            set_fetch_job_next_run_at(&pool, high_id, fixed_datetime(2025, 10, 12, 9, 0, 0))
+                .await?;
+
+            let first = super::super::search_queue_entries_for_fetch(
+                &pool,
+                1,
+                &FetchContentFilter::default(),
+            )
+            .await?;
+            let ids: Vec<Uuid> = first.iter().map(|entry| entry.id).collect();
+            assert_eq!(
+                ids,
+                vec![high_id],
+                "件数制限内では優先度の高いエントリを取る"
+            );
+
+            let rest = super::super::search_queue_entries_for_fetch(
+                &pool,
+                10,
+                &FetchContentFilter::default(),
+            )
+            .await?;
+            let ids: Vec<Uuid> = rest.iter().map(|entry| entry.id).collect();
+            assert_eq!(ids, vec![low_id]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 手動で410（諦め済み）に更新したエントリが再取得対象から外れることを確認する。
+        #[tokio::test]
+        async fn 諦め済みは再取得しない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let gone_id = Uuid::new_v4();
+            let retry_id = Uuid::new_v4();
+            for (id, link) in [
+                (gone_id, "https://example.com/gone"),
+                (retry_id, "https://example.com/retry"),
+            ] {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, status_code)
+                    VALUES ($1, $2, $3, $4, 404)
+                    "#,
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .execute(&pool)
+                .await?;
+            }
+
+            let updated = super::super::set_queue_status(&pool, gone_id, 410)
+                .await?
+                .expect("更新対象が見つからない");
+            assert_eq!(updated.status_code, Some(410));
+            assert!(super::super::set_queue_status(&pool, Uuid::new_v4(), 410)
+                .await?
+                .is_none());
+
+            let entries = super::super::search_queue_entries_for_fetch(
+                &pool,
+                10,
+                &FetchContentFilter::default(),
+            )
+            .await?;
+            let ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+            assert_eq!(ids, vec![retry_id]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// group・created_after・statusを組み合わせた絞り込みが、すべてAND条件として効くことを確認する。
+        #[tokio::test]
+        async fn 複数の絞り込み条件を組み合わせられる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let recent = fixed_datetime(2025, 10, 12, 9, 0, 0);
+            let old = fixed_datetime(2025, 10, 1, 9, 0, 0);
+            // (link, group, status_code, created_at, 対象になるか)
+            let cases = [
+                (
+                    "https://example.com/match-null",
+                    "world",
+                    None,
+                    recent,
+                    true,
+                ),
+                (
+                    "https://example.com/match-503",
+                    "world",
+                    Some(503),
+                    recent,
+                    true,
+                ),
+                (
+                    "https://example.com/other-group",
+                    "tech",
+                    None,
+                    recent,
+                    false,
+                ),
+                ("https://example.com/old", "world", None, old, false),
+                (
+                    "https://example.com/other-status",
+                    "world",
+                    Some(404),
+                    recent,
+                    false,
+                ),
+            ];
+
+            let mut expected = Vec::new();
+            for (link, group, status_code, created_at, matched) in cases {
+                let id = Uuid::new_v4();
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, status_code, "group")
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#,
+                )
+                .bind(id)
+                .bind(link)
+                .bind("タイトル")
+                .bind("説明")
+                .bind(status_code)
+                .bind(group)
+                .execute(&pool)
+                .await?;
+                crate::test_support::set_queue_timestamp(&pool, id, created_at).await?;
+                set_fetch_job_next_run_at(&pool, id, old).await?;
+                if matched {
+                    expected.push(id);
+                }
+            }
+
+            let filter = FetchContentFilter::parse(
+                Some("world"),
+                Some("2025-10-10T00:00:00Z"),
+                Some("unfetched,503"),
+            )?;
+            let entries = super::super::search_queue_entries_for_fetch(&pool, 10, &filter).await?;
+            let mut ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+            ids.sort();
+            expected.sort();
+            assert_eq!(ids, expected);
+
+            // 対象外のエントリは取得されずpendingのまま残る
+            let pending: i64 =
+                sqlx::query_scalar("SELECT COUNT(*) FROM rss.fetch_jobs WHERE status = 'pending'")
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(pending, 3);
+
+            Ok(())
+        }
+    }
+
+    pub mod fetch_content_filter {
+        use crate::fetch_content::{FetchContentFilter, QueueStatusFilter};
+        use crate::test_support::fixed_datetime;
+
+        /// # 検証目的
+        /// カンマ区切りの文字列から絞り込み条件を組み立て、不正な値はエラーになることを確認する。
+        #[test]
+        fn 文字列から絞り込み条件を組み立てる() {
+            let filter = FetchContentFilter::parse(
+                Some(" world, tech ,"),
+                Some("2025-10-12T18:00:00+09:00"),
+                Some("unfetched, 503"),
+            )
+            .expect("解析に失敗");
+            assert_eq!(filter.groups, vec!["world".to_string(), "tech".to_string()]);
+            assert_eq!(
+                filter.created_after,
+                Some(fixed_datetime(2025, 10, 12, 9, 0, 0))
+            );
+            assert_eq!(
+                filter.statuses,
+                vec![QueueStatusFilter::Unfetched, QueueStatusFilter::Code(503)]
+            );
+
+            let relative =
+                FetchContentFilter::parse(None, Some("24h"), None).expect("相対期間の解析に失敗");
+            let created_after = relative.created_after.expect("created_afterが未設定");
+            let elapsed = chrono::Utc::now() - created_after;
+            assert!(elapsed >= chrono::Duration::hours(24));
+            assert!(elapsed < chrono::Duration::hours(25));
+
+            assert_eq!(
+                FetchContentFilter::parse(None, None, None).expect("解析に失敗"),
+                FetchContentFilter::default()
+            );
+            assert!(FetchContentFilter::parse(None, Some("yesterday"), None).is_err());
+            assert!(FetchContentFilter::parse(None, None, Some("600")).is_err());
+            assert!(FetchContentFilter::parse(None, None, Some("done")).is_err());
+        }
+    }
+
+    pub mod compress_html {
+        use std::io::{Cursor, Read};
+
+        use brotli::Decompressor;
+
+        use crate::compression::{decompress_content, ContentCompression};
+        use crate::fetch_content::{compress_html, compress_html_blocking, decompress_html};
+
+        /// # 検証目的
+        /// Brotli圧縮したHTMLを無損失で展開できることを確認する。
+        #[test]
+        fn 圧縮は可逆である() {
+            let html = "<html><body>テスト</body></html>";
+            let compressed =
+                compress_html(html, ContentCompression::Brotli).expect("Brotli圧縮に失敗");
+
+            let mut decompressor = Decompressor::new(Cursor::new(compressed), 4096);
+            let mut decompressed = String::new();
+            decompressor
+                .read_to_string(&mut decompressed)
+                .expect("Brotli展開に失敗");
+
+            assert_eq!(decompressed, html);
+        }
+
+        /// # 検証目的
+        /// decompress_htmlでcompress_htmlの結果を元に戻せることを確認する。
+        #[test]
+        fn 展開ヘルパーで元に戻せる() {
+            let html = "<html><body>展開</body></html>";
+            let compressed =
+                compress_html(html, ContentCompression::Brotli).expect("Brotli圧縮に失敗");
+            let decompressed = decompress_html(&compressed).expect("Brotli展開に失敗");
+            assert_eq!(decompressed, html.as_bytes());
+        }
+
+        /// # 検証目的
+        /// ブロッキングスレッドプールで圧縮しても、同期版と同じく各方式で元のHTMLへ戻せることを確認する。
+        #[tokio::test]
+        async fn ブロッキングプールで圧縮しても可逆である() {
+            let html = "<html><body>".to_string()
+                + &"<p>ブロッキングプールでの圧縮</p>".repeat(500)
+                + "</body></html>";
+
+            for compression in [
+                ContentCompression::Brotli,
+                ContentCompression::Gzip,
+                ContentCompression::Zstd,
+            ] {
+                let compressed = compress_html_blocking(html.clone(), compression)
+                    .await
+                    .expect("圧縮に失敗");
+                assert_eq!(
+                    compressed,
+                    compress_html(&html, compression).expect("圧縮に失敗"),
+                    "{:?}",
+                    compression
+                );
+                assert_eq!(
+                    decompress_content(&compressed, compression).expect("展開に失敗"),
+                    html.as_bytes()
+                );
+            }
+        }
+    }
+
+    pub mod extract_main_content {
+        use crate::fetch_content::extract_main_content;
+
+        pub(crate) const NOISY_HTML: &str = r#"<html>
+<head><title>記事タイトル</title></head>
+<body>
+  <nav class="global-nav"><ul><li><a href="/">ホーム</a></li><li><a href="/news">ナビ項目ニュース</a></li></ul></nav>
+  <aside class="sidebar"><a href="/ranking">サイドバーのランキング</a><a href="/ad">広告リンク</a></aside>
+  <article>
+    <h1>記事タイトル</h1>
+    <p>本文の第一段落です。readabilityは段落の長さや句読点の数から本文らしさを判定するため、十分な長さの文章を用意しています。</p>
+    <p>本文の第二段落です。ナビゲーションやサイドバー、フッターのようなリンクばかりの領域は、リンク密度が高いためスコアが下がります。</p>
+    <p>本文の第三段落です。抽出結果にはこの記事エリアの段落だけが残り、周辺のノイズは取り除かれることを期待しています。</p>
+  </article>
+  <footer class="site-footer"><a href="/about">フッターの会社概要</a></footer>
+</body>
+</html>"#;
+
+        /// # 検証目的
+        /// 本文エリアが抽出され、ナビ・サイドバー・フッターのノイズが取り除かれることを確認する。
+        #[test]
+        fn 本文エリアを抽出する() {
+            let extracted = extract_main_content(NOISY_HTML, "https://example.com/article")
+                .expect("本文が抽出されない");
+
+            assert!(extracted.contains("本文の第一段落です。"));
+            assert!(extracted.contains("本文の第三段落です。"));
+            assert!(!extracted.contains("ナビ項目ニュース"));
+            assert!(!extracted.contains("サイドバーのランキング"));
+            assert!(!extracted.contains("フッターの会社概要"));
+            assert!(extracted.len() < NOISY_HTML.len());
+        }
+
+        /// # 検証目的
+        /// 本文らしい要素が無いHTMLや不正なURLではNoneを返し、呼び出し側が生HTMLへフォールバックできることを確認する。
+        #[test]
+        fn 抽出できなければnone() {
+            assert!(extract_main_content("", "https://example.com/").is_none());
+            assert!(extract_main_content(NOISY_HTML, "not a url").is_none());
+        }
+    }
+
+    pub mod extract_canonical_url {
+        use crate::fetch_content::extract_canonical_url;
+
+        /// # 検証目的
+        /// 既知のHTMLからcanonicalを取り出し、相対URLは記事linkを基準に解決されることを確認する。
+        #[test]
+        fn canonicalを抽出する() {
+            let html = r#"<html><head>
+<link rel="alternate" href="https://example.com/feed.xml">
+<link rel="Canonical" href="https://example.com/news/1">
+</head><body></body></html>"#;
+            assert_eq!(
+                extract_canonical_url(html, "https://m.example.com/news/1?ref=rss").as_deref(),
+                Some("https://example.com/news/1")
+            );
+
+            let relative = r#"<link rel="canonical" href="../posts/2">"#;
+            assert_eq!(
+                extract_canonical_url(relative, "https://example.com/amp/2").as_deref(),
+                Some("https://example.com/posts/2")
+            );
+        }
+
+        /// # 検証目的
+        /// canonicalが無い・hrefが空・解決できない場合はNoneを返すことを確認する。
+        #[test]
+        fn canonicalが無ければnone() {
+            assert!(extract_canonical_url("<html></html>", "https://example.com/").is_none());
+            assert!(extract_canonical_url(
+                r#"<link rel="canonical" href="  ">"#,
+                "https://example.com/"
+            )
+            .is_none());
+            assert!(
+                extract_canonical_url(r#"<link rel="canonical" href="/a">"#, "not a url").is_none()
+            );
+        }
+    }
+
+    pub mod parse_last_modified {
+        use crate::fetch_content::parse_last_modified;
+        use crate::test_support::fixed_datetime;
+
+        /// # 検証目的
+        /// IMF-fixdate・RFC 850・asctimeの3形式のHTTP-dateを同じUTCの日時へ変換し、解釈できない値はNoneになることを確認する。
+        #[test]
+        fn http_dateを解釈する() {
+            let expected = Some(fixed_datetime(1994, 11, 6, 8, 49, 37));
+            assert_eq!(
+                parse_last_modified("Sun, 06 Nov 1994 08:49:37 GMT"),
+                expected
+            );
+            assert_eq!(
+                parse_last_modified("Sunday, 06-Nov-94 08:49:37 GMT"),
+                expected
+            );
+            assert_eq!(parse_last_modified(" Sun Nov  6 08:49:37 1994 "), expected);
+            assert_eq!(parse_last_modified(""), None);
+            assert_eq!(parse_last_modified("2015-10-21"), None);
+        }
+    }
+
+    pub mod extract_og_metadata {
+        use crate::fetch_content::{extract_og_metadata, OgMetadata};
+
+        /// # 検証目的
+        /// 既知のHTMLからog:title/og:description/og:imageを取り出し、画像の相対URLは記事linkを基準に解決されることを確認する。
+        #[test]
+        fn ogメタデータを抽出する() {
+            let html = r#"<html><head>
+<meta property="og:type" content="article">
+<meta property="og:title" content=" 新しい交通システム ">
+<meta property="og:title" content="2つ目は使わない">
+<meta name="og:description" content="東京都で運用が始まりました">
+<meta property="OG:IMAGE" content="/images/cover.png">
+</head><body></body></html>"#;
+            assert_eq!(
+                extract_og_metadata(html, "https://example.com/news/1"),
+                OgMetadata {
+                    title: Some("新しい交通システム".to_string()),
+                    description: Some("東京都で運用が始まりました".to_string()),
+                    image: Some("https://example.com/images/cover.png".to_string()),
+                }
+            );
+        }
+
+        /// # 検証目的
+        /// OGタグが無い・値が空・画像URLを解決できない場合は該当項目がNoneになることを確認する。
+        #[test]
+        fn ogタグが無ければnone() {
+            assert_eq!(
+                extract_og_metadata(
+                    "<html><head><title>t</title></head></html>",
+                    "https://example.com/"
+                ),
+                OgMetadata::default()
+            );
+
+            let html = r#"<meta property="og:title" content="  "><meta property="og:image" content="/a.png">"#;
+            assert_eq!(
+                extract_og_metadata(html, "not a url"),
+                OgMetadata::default()
+            );
         }
     }
 }
@@ -0,0 +1,427 @@
+use anyhow::Result;
+use sqlx::{Executor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::fetch_content::GIVE_UP_STATUS_CODE;
+
+/// 再試行を諦めてfailedにするまでの試行回数
+pub(crate) const FETCH_JOB_MAX_ATTEMPTS: i32 = 8;
+/// 再試行間隔の初期値（試行ごとに倍にする）
+pub(crate) const FETCH_JOB_RETRY_BASE_SECS: i64 = 60;
+/// 再試行間隔の上限
+pub(crate) const FETCH_JOB_RETRY_MAX_SECS: i64 = 6 * 60 * 60;
+/// runningのまま更新されないジョブを、実行中に異常終了したとみなして再取得できるまでの秒数
+pub(crate) const FETCH_JOB_STALE_SECS: i64 = 60 * 60;
+/// 除外ホスト（`FETCH_CONTENT_DENY_HOSTS`）のためスキップしたジョブを、次に取得対象にするまでの秒数
+pub(crate) const FETCH_JOB_DENIED_DEFER_SECS: i64 = FETCH_JOB_RETRY_MAX_SECS;
+
+/// 本文取得ジョブの状態（rss.fetch_jobs.status）
+///
+/// pending（実行待ち）→ running（取得中）→ done（本文保存済み）/ failed（諦め済み）と遷移する。
+/// 失敗した取得は試行回数が上限に達するまで、次回実行時刻を遅らせてpendingへ戻す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum FetchJobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// 取得したstatus_codeに応じてジョブを遷移させる（200はdone、410はfailed、それ以外は再試行待ち）
+pub(crate) async fn finish_fetch_job(
+    tx: &mut Transaction<'_, Postgres>,
+    queue_id: Uuid,
+    status_code: i32,
+) -> Result<()> {
+    let status = match status_code {
+        200 => FetchJobStatus::Done,
+        GIVE_UP_STATUS_CODE => FetchJobStatus::Failed,
+        _ => {
+            let message = format!("HTTPステータス{}", status_code);
+            return schedule_fetch_job_retry(&mut **tx, queue_id, &message).await;
+        }
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE rss.fetch_jobs
+        SET status = $2, attempts = attempts + 1, last_error = NULL
+        WHERE queue_id = $1
+        "#,
+    )
+    .bind(queue_id)
+    .bind(status)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// 失敗した取得を記録し、次回実行時刻を指数的に遅らせてpendingへ戻す。試行回数が上限に達したらfailedにする。
+pub(crate) async fn schedule_fetch_job_retry<'e, E>(
+    executor: E,
+    queue_id: Uuid,
+    error: &str,
+) -> Result<()>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query(
+        r#"
+        UPDATE rss.fetch_jobs
+        SET attempts = attempts + 1,
+            status = CASE WHEN attempts + 1 >= $2 THEN 'failed' ELSE 'pending' END,
+            next_run_at = NOW() + make_interval(secs => LEAST($3 * POWER(2, attempts), $4)),
+            last_error = $5
+        WHERE queue_id = $1
+        "#,
+    )
+    .bind(queue_id)
+    .bind(FETCH_JOB_MAX_ATTEMPTS)
+    .bind(FETCH_JOB_RETRY_BASE_SECS as f64)
+    .bind(FETCH_JOB_RETRY_MAX_SECS as f64)
+    .bind(error)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// 取得しなかったジョブをpendingへ戻す（試行回数は数えず、次回の処理順では後回しになる）
+pub(crate) async fn release_fetch_jobs(pool: &PgPool, queue_ids: &[Uuid]) -> Result<()> {
+    if queue_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE rss.fetch_jobs
+        SET status = 'pending', next_run_at = NOW()
+        WHERE queue_id = ANY($1)
+        "#,
+    )
+    .bind(queue_ids)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// 除外ホストのためスキップしたジョブを、試行回数は数えずに一定時間後のpendingへ戻す
+///
+/// すぐ取得対象へ戻すと毎回の取得で先頭に選ばれ、他のエントリがバッチから押し出されるため後回しにする。
+/// 除外設定が外れていれば、次回実行時刻の後に通常どおり取得される。
+pub(crate) async fn defer_denied_fetch_jobs(pool: &PgPool, queue_ids: &[Uuid]) -> Result<()> {
+    if queue_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE rss.fetch_jobs
+        SET status = 'pending', next_run_at = NOW() + make_interval(secs => $2)
+        WHERE queue_id = ANY($1)
+        "#,
+    )
+    .bind(queue_ids)
+    .bind(FETCH_JOB_DENIED_DEFER_SECS as f64)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// status_codeの手動更新に合わせてジョブを作り直す（200はdone、410はfailed、それ以外はすぐ再取得する）
+pub(crate) async fn reset_fetch_job(
+    tx: &mut Transaction<'_, Postgres>,
+    queue_id: Uuid,
+    status_code: i32,
+) -> Result<()> {
+    let status = match status_code {
+        200 => FetchJobStatus::Done,
+        GIVE_UP_STATUS_CODE => FetchJobStatus::Failed,
+        _ => FetchJobStatus::Pending,
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE rss.fetch_jobs
+        SET status = $2, next_run_at = NOW(), last_error = NULL
+        WHERE queue_id = $1
+        "#,
+    )
+    .bind(queue_id)
+    .bind(status)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod fetch_job_transitions {
+        use anyhow::Result;
+        use chrono::{DateTime, Utc};
+        use sqlx::PgPool;
+        use uuid::Uuid;
+
+        use crate::fetch_content::{
+            search_queue_entries_for_fetch, set_queue_status, FetchContentFilter,
+        };
+        use crate::fetch_jobs::{
+            finish_fetch_job, release_fetch_jobs, schedule_fetch_job_retry, FetchJobStatus,
+            FETCH_JOB_MAX_ATTEMPTS, FETCH_JOB_RETRY_BASE_SECS, FETCH_JOB_RETRY_MAX_SECS,
+            FETCH_JOB_STALE_SECS,
+        };
+        use crate::test_support::{clear_rss_tables, prepare_test_pool};
+
+        async fn insert_queue(pool: &PgPool, link: &str) -> Result<Uuid> {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind(link)
+            .bind("タイトル")
+            .bind("説明")
+            .execute(pool)
+            .await?;
+            Ok(id)
+        }
+
+        async fn load_job(
+            pool: &PgPool,
+            queue_id: Uuid,
+        ) -> Result<(FetchJobStatus, i32, DateTime<Utc>, Option<String>)> {
+            let job = sqlx::query_as(
+                r#"
+                SELECT status, attempts, next_run_at, last_error
+                FROM rss.fetch_jobs
+                WHERE queue_id = $1
+                "#,
+            )
+            .bind(queue_id)
+            .fetch_one(pool)
+            .await?;
+            Ok(job)
+        }
+
+        /// # 検証目的
+        /// queueへの追加でpendingのジョブが作られ、取得時にrunningとなって二重に取得されないことを確認する。
+        #[tokio::test]
+        async fn 追加でpendingになり取得でrunningになる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = insert_queue(&pool, "https://example.com/job").await?;
+            let (status, attempts, _, _) = load_job(&pool, id).await?;
+            assert_eq!(status, FetchJobStatus::Pending);
+            assert_eq!(attempts, 0);
+
+            let entries =
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(load_job(&pool, id).await?.0, FetchJobStatus::Running);
+            assert!(
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default())
+                    .await?
+                    .is_empty()
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 200でdone、410でfailedとなり、以後取得対象にならないことを確認する。
+        #[tokio::test]
+        async fn 取得結果でdoneとfailedに遷移する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let done_id = insert_queue(&pool, "https://example.com/done").await?;
+            let gone_id = insert_queue(&pool, "https://example.com/gone").await?;
+            search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+
+            let mut tx = pool.begin().await?;
+            finish_fetch_job(&mut tx, done_id, 200).await?;
+            finish_fetch_job(&mut tx, gone_id, 410).await?;
+            tx.commit().await?;
+
+            let (status, attempts, _, _) = load_job(&pool, done_id).await?;
+            assert_eq!(status, FetchJobStatus::Done);
+            assert_eq!(attempts, 1);
+            assert_eq!(load_job(&pool, gone_id).await?.0, FetchJobStatus::Failed);
+            assert!(
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default())
+                    .await?
+                    .is_empty()
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 失敗時は次回実行時刻を遅らせてpendingへ戻り、試行回数が上限に達するとfailedになることを確認する。
+        #[tokio::test]
+        async fn 失敗は遅らせて再試行し上限でfailedになる() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = insert_queue(&pool, "https://example.com/retry").await?;
+            search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+
+            let mut tx = pool.begin().await?;
+            finish_fetch_job(&mut tx, id, 503).await?;
+            tx.commit().await?;
+
+            let (status, attempts, next_run_at, last_error) = load_job(&pool, id).await?;
+            assert_eq!(status, FetchJobStatus::Pending);
+            assert_eq!(attempts, 1);
+            assert!(next_run_at > Utc::now(), "次回実行時刻が未来になっていない");
+            assert_eq!(last_error.as_deref(), Some("HTTPステータス503"));
+            assert!(
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default())
+                    .await?
+                    .is_empty(),
+                "実行時刻前のジョブは取得しない"
+            );
+
+            for _ in 1..FETCH_JOB_MAX_ATTEMPTS {
+                schedule_fetch_job_retry(&pool, id, "timeout").await?;
+            }
+            let (status, attempts, _, last_error) = load_job(&pool, id).await?;
+            assert_eq!(status, FetchJobStatus::Failed);
+            assert_eq!(attempts, FETCH_JOB_MAX_ATTEMPTS);
+            assert_eq!(last_error.as_deref(), Some("timeout"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 失敗を重ねるたびに次回実行時刻までの間隔が`FETCH_JOB_RETRY_BASE_SECS × 2^(失敗前のattempts)`で倍々に延び、
+        /// 失敗の少ない記事が先に再取得対象へ戻ることを確認する。
+        #[tokio::test]
+        async fn 失敗を重ねるほど再試行間隔が空く() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let id = insert_queue(&pool, "https://example.com/backoff").await?;
+            for attempts in 0..FETCH_JOB_MAX_ATTEMPTS - 1 {
+                schedule_fetch_job_retry(&pool, id, "timeout").await?;
+                let interval_secs: f64 = sqlx::query_scalar(
+                    "SELECT EXTRACT(EPOCH FROM next_run_at - NOW())::float8 FROM rss.fetch_jobs WHERE queue_id = $1",
+                )
+                .bind(id)
+                .fetch_one(&pool)
+                .await?;
+                let expected = (FETCH_JOB_RETRY_BASE_SECS * 2_i64.pow(attempts as u32))
+                    .min(FETCH_JOB_RETRY_MAX_SECS) as f64;
+                assert!(
+                    (interval_secs - expected).abs() < 5.0,
+                    "{}回失敗後の間隔が想定外: {}秒（想定{}秒）",
+                    attempts + 1,
+                    interval_secs,
+                    expected
+                );
+            }
+
+            // 1回だけ失敗した記事は、多く失敗した記事より先に実行時刻を迎える
+            let once_id = insert_queue(&pool, "https://example.com/once").await?;
+            schedule_fetch_job_retry(&pool, once_id, "timeout").await?;
+            let (_, _, once_next_run_at, _) = load_job(&pool, once_id).await?;
+            let (_, _, backoff_next_run_at, _) = load_job(&pool, id).await?;
+            assert!(once_next_run_at < backoff_next_run_at);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// スキップしたジョブは試行回数を増やさずpendingへ戻り、
+        /// 異常終了でrunningのまま古くなったジョブは再取得されることを確認する。
+        #[tokio::test]
+        async fn スキップと放置されたrunningはpendingに戻る() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let skipped_id = insert_queue(&pool, "https://example.com/skipped").await?;
+            search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            release_fetch_jobs(&pool, &[skipped_id]).await?;
+            let (status, attempts, _, _) = load_job(&pool, skipped_id).await?;
+            assert_eq!(status, FetchJobStatus::Pending);
+            assert_eq!(attempts, 0);
+
+            search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            // updated_atはトリガーで上書きされるため、トリガーを通さず古い時刻にする
+            sqlx::query("ALTER TABLE rss.fetch_jobs DISABLE TRIGGER update_fetch_jobs_updated_at")
+                .execute(&pool)
+                .await?;
+            let result = sqlx::query(
+                "UPDATE rss.fetch_jobs SET updated_at = NOW() - make_interval(secs => $2) WHERE queue_id = $1",
+            )
+            .bind(skipped_id)
+            .bind((FETCH_JOB_STALE_SECS + 60) as f64)
+            .execute(&pool)
+            .await;
+            sqlx::query("ALTER TABLE rss.fetch_jobs ENABLE TRIGGER update_fetch_jobs_updated_at")
+                .execute(&pool)
+                .await?;
+            result?;
+
+            let entries =
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0].id, skipped_id);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// status_codeの手動更新で、410はfailed、それ以外はすぐ再取得できるpendingになることを確認する。
+        #[tokio::test]
+        async fn 手動更新でジョブを作り直す() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let gone_id = insert_queue(&pool, "https://example.com/gone").await?;
+            let retry_id = insert_queue(&pool, "https://example.com/retry").await?;
+            search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            schedule_fetch_job_retry(&pool, retry_id, "timeout").await?;
+
+            set_queue_status(&pool, gone_id, 410).await?;
+            set_queue_status(&pool, retry_id, 404).await?;
+
+            assert_eq!(load_job(&pool, gone_id).await?.0, FetchJobStatus::Failed);
+            let (status, _, next_run_at, last_error) = load_job(&pool, retry_id).await?;
+            assert_eq!(status, FetchJobStatus::Pending);
+            assert!(next_run_at <= Utc::now());
+            assert_eq!(last_error, None);
+
+            let entries =
+                search_queue_entries_for_fetch(&pool, 10, &FetchContentFilter::default()).await?;
+            let ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+            assert_eq!(ids, vec![retry_id]);
+
+            Ok(())
+        }
+    }
+}
@@ -1,42 +1,173 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::time::Duration;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use feed_rs::{model::Entry, parser};
 use futures::{stream, StreamExt};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use reqwest::Client;
+use reqwest::{header, Client, Url};
 use sqlx::PgPool;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::{NewQueue, RssFeedSource, RssLinks};
-use crate::webhook;
+use crate::article_sink::{ArticleSink, PublishedArticle};
+use crate::custom_feed::parse_custom_json_feed;
+use crate::feed_encoding::normalize_feed_encoding;
+use crate::feed_lock::FeedLocks;
+use crate::feeds::{
+    find_feed_activity, find_feed_last_build_date, guess_favicon_url, record_feed_activity,
+    record_feed_entry_count, upsert_feed_last_build_date, upsert_feed_meta, FeedActivity,
+};
+use crate::models::{FeedFormat, FeedRetryOverride, NewQueue, RssFeedSource, RssLinks};
+use crate::proxy::apply_proxy;
+use crate::webhook::{self, GroupWebhookResult, WebhookResult};
 
 static URL_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"https?://[^\s\"'<>()]+"#).expect("URL正規表現のコンパイルに失敗"));
+static RSS_ITEM_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<item[\s>].*?</item>").expect("item正規表現のコンパイルに失敗"));
+static ATOM_ENTRY_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<entry[\s>].*?</entry>").expect("entry正規表現のコンパイルに失敗")
+});
+static XML_TAG_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<(/?)([A-Za-z_][\w:.-]*)[^<>]*?(/?)>").expect("タグ正規表現のコンパイルに失敗")
+});
+static XML_OPAQUE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<!\[CDATA\[.*?\]\]>|<!--.*?-->").expect("CDATA正規表現のコンパイルに失敗")
+});
+/// item/entry内の日付要素（`pubDate`/`published`/`dc:date`/`updated`など）の生の文字列
+static DATE_ELEMENT_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"<(?:[\w-]+:)?(?:pubDate|published|date|updated|issued|modified)(?:\s[^>]*)?>([^<]*)</",
+    )
+    .expect("日付要素の正規表現のコンパイルに失敗")
+});
+static NAMESPACE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"xmlns(?::[\w.-]+)?\s*=\s*"[^"]*""#).expect("名前空間正規表現のコンパイルに失敗")
+});
 
 /// RSS取得リクエストのタイムアウト秒数（スクレイピング側と揃えている）
 const FETCH_RSS_TIMEOUT_SECS: u64 = 15;
+/// 1フィードの処理（ロック取得後の取得・解析・upsert）にかける既定の上限秒数
+pub(crate) const DEFAULT_FEED_TIMEOUT_SECS: u64 = 60;
+/// フィード取得の既定の最大リトライ回数（リトライ対象のステータスを指定したときだけ使う）
+pub(crate) const DEFAULT_RETRY_MAX: u32 = 2;
+/// フィード取得の1回目のリトライまでの既定の待ち時間（ミリ秒）
+pub(crate) const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+/// 休眠フィードを取得する既定の間隔（時間）
+pub(crate) const DEFAULT_DORMANT_INTERVAL_HOURS: u64 = 24;
+/// リトライ前に待つ時間の上限秒数（`Retry-After`が長すぎてもこれ以上は待たない）
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+/// フィード単位のタイムアウトで打ち切ったフィードに記録するエラー
+const FEED_TIMEOUT_ERROR: &str = "timeout";
 /// RSS取得時に同時実行する最大フィード数
 const MAX_CONCURRENT_FEED_REQUESTS: usize = 8;
-
-#[derive(Debug, Serialize, Deserialize)]
+/// 接続プールでアイドル接続を保持する秒数
+const FEED_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+/// 同一ホストに対して保持するアイドル接続の上限（同時取得数と揃えている）
+const FEED_POOL_MAX_IDLE_PER_HOST: usize = MAX_CONCURRENT_FEED_REQUESTS;
+/// TCP keep-aliveの送信間隔秒数
+const FEED_TCP_KEEPALIVE_SECS: u64 = 60;
+/// サマリログに表示する遅いフィードの件数
+const SLOW_FEED_LOG_COUNT: usize = 5;
+/// entry数の急減検知のために`rss.feed_cache`へ残す直近の取得回数
+const ENTRY_COUNT_HISTORY_SIZE: usize = 5;
+/// 急減を判定するのに必要な過去の取得回数（新規フィードで誤検知しないため）
+const ENTRY_COUNT_MIN_SAMPLES: usize = 3;
+/// 過去の平均entry数がこれ未満のフィードは急減を判定しない（数件の増減で誤検知しないため）
+const ENTRY_COUNT_MIN_AVERAGE: f64 = 10.0;
+/// 今回のentry数が過去の平均のこの割合を下回ったら急減とみなす
+const ENTRY_COUNT_DROP_RATIO: f64 = 0.3;
+/// queueへ保存するtitleの既定の最大文字数
+pub(crate) const DEFAULT_TITLE_MAX_CHARS: usize = 512;
+/// queueへ保存するdescriptionの既定の最大文字数
+pub(crate) const DEFAULT_DESCRIPTION_MAX_CHARS: usize = 8192;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FetchRssFeedResult {
     pub group: String,
     pub name: String,
     pub processed: usize,
+    /// processedのうち、queueに新規追加したentry数
+    #[serde(default)]
+    pub inserted: usize,
+    /// processedのうち、queueの既存entryを更新したentry数
+    #[serde(default)]
+    pub updated: usize,
+    pub malformed_entries: usize,
+    /// フィード取得（リクエスト送信からボディ読み込み完了まで）にかかった時間。失敗時は失敗までの時間
+    #[serde(default)]
+    pub elapsed_ms: u64,
+    /// フィードのlast-build-dateが前回処理時から変わっていないため、entryの取り込みを省略した
+    #[serde(default)]
+    pub unchanged: bool,
+    /// 他のインスタンスが同じフィードを取得中（advisory lockを保持中）のため、取得せずに任せた
+    #[serde(default)]
+    pub locked: bool,
+    /// 長く新規entryの無い休眠フィードで、前回の取得から間隔が空いていないため取得しなかった
+    #[serde(default)]
+    pub dormant: bool,
+    /// entry数が過去の平均から急減していた場合の内訳（フィードの破損を疑う）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_count_drop: Option<EntryCountDrop>,
     pub error: Option<String>,
 }
 
+/// フィードのentry数の急減
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryCountDrop {
+    /// 今回取得したentry数
+    pub entry_count: usize,
+    /// 直近の取得での平均entry数
+    pub average: f64,
+    /// 平均の算出に使った過去の取得回数
+    pub samples: usize,
+}
+
+/// 過去のentry数（`history`）と比べて今回の`entry_count`が急減しているか判定する。
+///
+/// 過去の取得回数が`ENTRY_COUNT_MIN_SAMPLES`未満、または平均が`ENTRY_COUNT_MIN_AVERAGE`未満のフィードは判定しない。
+pub(crate) fn detect_entry_count_drop(
+    history: &[i32],
+    entry_count: usize,
+) -> Option<EntryCountDrop> {
+    if history.len() < ENTRY_COUNT_MIN_SAMPLES {
+        return None;
+    }
+    let average = history.iter().map(|count| f64::from(*count)).sum::<f64>() / history.len() as f64;
+    if average < ENTRY_COUNT_MIN_AVERAGE || entry_count as f64 >= average * ENTRY_COUNT_DROP_RATIO {
+        return None;
+    }
+    Some(EntryCountDrop {
+        entry_count,
+        average,
+        samples: history.len(),
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FetchRssSummary {
+    /// 実行ごとに一意なID（Webhook受信側での重複排除に使う）
+    #[serde(default)]
+    pub run_id: Uuid,
     pub total_processed: usize,
     pub feeds: Vec<FetchRssFeedResult>,
+    /// Webhookの送信結果（API経由で実行し、Webhookが設定されている場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookResult>,
+    /// グループ別Webhook（`_webhook_url`）の送信結果
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_webhooks: Vec<GroupWebhookResult>,
+    /// `_webhook_url`を持つグループとそのURL（通知の振り分けに使い、レスポンスには含めない）
+    #[serde(skip)]
+    pub group_webhook_urls: BTreeMap<String, String>,
 }
 
 /// rss_links.ymlを読み込む
@@ -46,23 +177,473 @@ pub fn load_rss_links(path: &str) -> Result<Vec<RssFeedSource>> {
     Ok(links.into_sources())
 }
 
-/// RSSフィードを取得してパース
+/// 読み込んだフィード設定を検証する。
+/// `url`が空（`url`キーの書き間違いを含む）またはhttp(s)以外のフィードがあれば、該当するgroup/nameをすべて挙げたエラーを返す。
+pub fn validate_rss_links(feeds: &[RssFeedSource]) -> Result<()> {
+    let mut problems: Vec<String> = feeds
+        .iter()
+        .filter_map(|feed| {
+            validate_feed_url(&feed.url)
+                .err()
+                .map(|reason| format!("{}/{}: {}", feed.group, feed.name, reason))
+        })
+        .collect();
+    problems.extend(feeds.iter().filter_map(|feed| {
+        let statuses = feed.retry.as_ref()?.statuses.as_ref()?;
+        let invalid: Vec<String> = statuses
+            .iter()
+            .filter(|status| !(100..=599).contains(*status))
+            .map(|status| status.to_string())
+            .collect();
+        (!invalid.is_empty()).then(|| {
+            format!(
+                "{}/{}: retry.statusesにHTTPステータスではない値があります: {}",
+                feed.group,
+                feed.name,
+                invalid.join(", ")
+            )
+        })
+    }));
+    // `_webhook_url`はグループ単位の設定のため、グループごとに1度だけ検証する
+    let group_webhook_urls: BTreeMap<&str, &str> = feeds
+        .iter()
+        .filter_map(|feed| Some((feed.group.as_str(), feed.webhook_url.as_deref()?)))
+        .collect();
+    problems.extend(group_webhook_urls.into_iter().filter_map(|(group, url)| {
+        validate_feed_url(url)
+            .err()
+            .map(|reason| format!("{}/_webhook_url: {}", group, reason))
+    }));
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    // YAMLの読み込み順はHashMap由来で不定のため、並べて出力を安定させる
+    problems.sort();
+    Err(anyhow::anyhow!(
+        "rss_links.ymlに不正なフィードがあります:\n  {}",
+        problems.join("\n  ")
+    ))
+}
+
+pub(crate) fn validate_feed_url(url: &str) -> std::result::Result<(), String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("urlが空です（urlキーの書き間違いがないか確認してください）".to_string());
+    }
+
+    let parsed =
+        Url::parse(url).map_err(|e| format!("URLとして解釈できません（{}）: {}", e, url))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!("http(s)以外のスキームです（{}）: {}", scheme, url)),
+    }
+}
+
+/// フィード取得用のHTTPクライアントを構築する
+///
+/// 同一CDNから多数のフィードを取得する際に接続を使い回せるよう、アイドル接続の保持とTCP keep-aliveを有効にする。
+/// HTTP/2はTLSのALPNで合意できたホストに対して使われる（HTTP/1.1専用のホストがあるためprior knowledgeは強制しない）。
+/// `proxy_url`を指定するとフィード取得をそのプロキシ経由で行う。
+/// `dns_cache`を有効にするとhickory-dnsリゾルバを使い、同一ホストの名前解決結果をTTLの間キャッシュする。
+pub(crate) fn build_feed_client(options: &FetchRssOptions) -> Result<Client> {
+    let builder = Client::builder()
+        .timeout(Duration::from_secs(FETCH_RSS_TIMEOUT_SECS))
+        .pool_idle_timeout(Duration::from_secs(FEED_POOL_IDLE_TIMEOUT_SECS))
+        .pool_max_idle_per_host(FEED_POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(Duration::from_secs(FEED_TCP_KEEPALIVE_SECS))
+        .http2_adaptive_window(true)
+        .hickory_dns(options.dns_cache);
+    let client = apply_proxy(builder, options.proxy_url.as_deref())?.build()?;
+    Ok(client)
+}
+
+/// フィードの解析結果
+#[derive(Debug)]
+pub struct ParsedFeed {
+    pub entries: Vec<NewQueue>,
+    /// 部分破損により読み飛ばしたentry数
+    pub malformed_entries: usize,
+    /// フィードのアイコン（`icon`、無ければ`logo`）のURL
+    pub icon_url: Option<String>,
+    /// HTTPリクエスト送信からボディ読み込み完了までの時間
+    pub elapsed_ms: u64,
+    /// フィードレベルの更新日時（RSS 2.0の`<lastBuildDate>`、Atomの`<updated>`）。部分破損から復旧した場合はNone
+    pub last_build_date: Option<DateTime<Utc>>,
+}
+
+/// entryのpub_dateに`published`と`updated`のどちらを優先して使うか（`PUBDATE_PREFERENCE`）
+///
+/// 優先した側が無いentryはもう一方にフォールバックする。`custom_json`形式のフィードは`published`しか持たないため影響しない。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PubDatePreference {
+    /// 公開日時を優先する（既定）
+    #[default]
+    Published,
+    /// 更新日時を優先する
+    Updated,
+}
+
+impl PubDatePreference {
+    fn select(
+        self,
+        published: Option<DateTime<Utc>>,
+        updated: Option<DateTime<Utc>>,
+    ) -> Option<DateTime<Utc>> {
+        match self {
+            PubDatePreference::Published => published.or(updated),
+            PubDatePreference::Updated => updated.or(published),
+        }
+    }
+}
+
+impl std::str::FromStr for PubDatePreference {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "published" => Ok(PubDatePreference::Published),
+            "updated" => Ok(PubDatePreference::Updated),
+            other => Err(anyhow::anyhow!(
+                "無効なPUBDATE_PREFERENCE: {} (有効な値: published, updated)",
+                other
+            )),
+        }
+    }
+}
+
+/// フィード取得のHTTPリトライ方針
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedRetryPolicy {
+    /// リトライ対象のHTTPステータス（空ならリトライしない）
+    pub statuses: Vec<u16>,
+    /// 最大リトライ回数（初回の取得は含まない）
+    pub max_retries: u32,
+    /// 1回目のリトライまでの待ち時間（以降は倍々に延ばす）。`Retry-After`があればそちらを優先する
+    pub backoff: Duration,
+}
+
+impl Default for FeedRetryPolicy {
+    fn default() -> Self {
+        Self {
+            statuses: Vec::new(),
+            max_retries: DEFAULT_RETRY_MAX,
+            backoff: Duration::from_millis(DEFAULT_RETRY_BACKOFF_MS),
+        }
+    }
+}
+
+impl FeedRetryPolicy {
+    /// フィードごとの設定で指定された項目だけを上書きした方針を返す
+    pub(crate) fn with_override(&self, retry: Option<&FeedRetryOverride>) -> Self {
+        let Some(retry) = retry else {
+            return self.clone();
+        };
+        Self {
+            statuses: retry
+                .statuses
+                .clone()
+                .unwrap_or_else(|| self.statuses.clone()),
+            max_retries: retry.max_retries.unwrap_or(self.max_retries),
+            backoff: retry
+                .backoff_ms
+                .map(Duration::from_millis)
+                .unwrap_or(self.backoff),
+        }
+    }
+
+    fn should_retry(&self, status: u16, retries: u32) -> bool {
+        retries < self.max_retries && self.statuses.contains(&status)
+    }
+
+    /// `retries`回目（0始まり）のリトライまでの待ち時間。`Retry-After`を解釈できればそれに従う
+    fn delay(&self, retries: u32, retry_after: Option<&header::HeaderValue>) -> Duration {
+        retry_after
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after)
+            .unwrap_or_else(|| self.backoff.saturating_mul(2u32.saturating_pow(retries)))
+            .min(Duration::from_secs(MAX_RETRY_DELAY_SECS))
+    }
+}
+
+/// 長く新規entryの無いフィード（休眠フィード）の取得間隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedDormancy {
+    /// 最後に新規entryを追加してからこの期間が過ぎたフィードを休眠とみなす
+    pub after: Duration,
+    /// 休眠フィードは前回の取得からこの間隔が空くまで取得しない
+    pub interval: Duration,
+}
+
+impl FeedDormancy {
+    /// 休眠中で、前回の取得から`interval`が経っていないため今回は取得しないか
+    pub(crate) fn should_skip(&self, activity: &FeedActivity, now: DateTime<Utc>) -> bool {
+        let (Some(last_entry_new_at), Some(last_fetched_at)) =
+            (activity.last_entry_new_at, activity.last_fetched_at)
+        else {
+            return false;
+        };
+        let since = |at: DateTime<Utc>| (now - at).to_std().unwrap_or(Duration::ZERO);
+        since(last_entry_new_at) >= self.after && since(last_fetched_at) < self.interval
+    }
+}
+
+/// `Retry-After`ヘッダ（秒数またはHTTP-date）を待ち時間に変換する。過去の日時なら0、解釈できなければNone
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let retry_at = crate::fetch_content::parse_last_modified(value)?;
+    Some((retry_at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// RSSフィードを取得し、`format`に応じたパーサで解析する
+///
+/// `user_agent`を指定するとそのUser-Agentでリクエストする（未指定ならクライアントの既定）。
+/// レスポンスのステータスが`retry`のリトライ対象なら、待ち時間を置いて最大`max_retries`回まで取得し直す。
 pub async fn fetch_and_parse_feed(
     client: &Client,
     url: &str,
     group: Option<&str>,
-) -> Result<Vec<NewQueue>> {
-    let response = client.get(url).send().await?;
+    format: FeedFormat,
+    user_agent: Option<&str>,
+    retry: &FeedRetryPolicy,
+    pub_date_preference: PubDatePreference,
+) -> Result<ParsedFeed> {
+    let mut retries = 0;
+    let (started, response) = loop {
+        let started = Instant::now();
+        let mut request = client.get(url);
+        if let Some(user_agent) = user_agent {
+            request = request.header(header::USER_AGENT, user_agent);
+        }
+        let response = request.send().await?;
+
+        let status = response.status().as_u16();
+        if !retry.should_retry(status, retries) {
+            break (started, response);
+        }
+        let delay = retry.delay(retries, response.headers().get(header::RETRY_AFTER));
+        retries += 1;
+        warn!(
+            url,
+            status,
+            retry = retries,
+            delay_ms = delay.as_millis() as u64,
+            "フィードの取得に失敗したためリトライします"
+        );
+        tokio::time::sleep(delay).await;
+    };
     let content = response.bytes().await?;
-    parse_feed_content(&content, group)
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let mut parsed = parse_feed_content(&content, group, format, pub_date_preference)?;
+    parsed.elapsed_ms = elapsed_ms;
+    Ok(parsed)
+}
+
+/// `format`に応じたパーサでフィードを解析し、同一フィード内で重複したlinkのentryを取り除いて返す。
+pub(crate) fn parse_feed_content(
+    content: &[u8],
+    group: Option<&str>,
+    format: FeedFormat,
+    pub_date_preference: PubDatePreference,
+) -> Result<ParsedFeed> {
+    let mut parsed = match format {
+        FeedFormat::Feed => parse_feed_entries(content, group, pub_date_preference)?,
+        FeedFormat::CustomJson => parse_custom_json_feed(content, group)?,
+    };
+    parsed.entries = dedup_entries_by_link(parsed.entries);
+    Ok(parsed)
+}
+
+/// 同じlinkのentryは最初の出現のみ残す（フィードは通常新しい順に並ぶため、先頭側を正とする）。
+/// queueの一意制約に合わせ、正規化せずlinkの完全一致で判定する。
+fn dedup_entries_by_link(entries: Vec<NewQueue>) -> Vec<NewQueue> {
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter(|entry| seen.insert(entry.link.clone()))
+        .collect()
+}
+
+/// フィードを解析する。壊れたentryがある場合はentry単位で解析し直し、取れる分だけ返す。
+/// 1件も解析できない完全に壊れたフィードはエラーとする。
+/// XML宣言のエンコードは信用せず、BOMとバイト列から推定した実際のエンコードでUTF-8へ揃えてから解析する。
+fn parse_feed_entries(
+    content: &[u8],
+    group: Option<&str>,
+    pub_date_preference: PubDatePreference,
+) -> Result<ParsedFeed> {
+    let content = normalize_feed_encoding(content, group);
+    let text = String::from_utf8_lossy(&content);
+    let (is_atom, blocks) = split_entry_blocks(&text);
+
+    match parser::parse(content.as_ref()) {
+        Ok(feed) => {
+            let icon_url = feed
+                .icon
+                .as_ref()
+                .or(feed.logo.as_ref())
+                .map(|image| image.uri.trim().to_string())
+                .filter(|uri| !uri.is_empty());
+
+            // feed_rsは壊れたentry以降をエラーにせず切り捨てることがあるため、生のentry数と比べて欠落を検出する
+            if feed.entries.len() < blocks.len() {
+                if let Some(recovered) =
+                    recover_partial_feed(&text, is_atom, &blocks, group, pub_date_preference)
+                {
+                    if blocks.len() - recovered.malformed_entries >= feed.entries.len() {
+                        return Ok(ParsedFeed {
+                            icon_url,
+                            ..recovered
+                        });
+                    }
+                }
+            }
+
+            Ok(ParsedFeed {
+                entries: convert_entries(
+                    feed.entries,
+                    &blocks,
+                    group,
+                    feed.language.as_deref(),
+                    pub_date_preference,
+                ),
+                malformed_entries: 0,
+                icon_url,
+                elapsed_ms: 0,
+                last_build_date: feed.updated,
+            })
+        }
+        Err(err) => recover_partial_feed(&text, is_atom, &blocks, group, pub_date_preference)
+            .ok_or_else(|| err.into()),
+    }
+}
+
+/// フィード本文からitem（RSS）またはentry（Atom）の要素を切り出す。Atomならtrueを返す。
+fn split_entry_blocks(text: &str) -> (bool, Vec<&str>) {
+    let is_atom = text.contains("<feed") && ATOM_ENTRY_PATTERN.is_match(text);
+    let pattern = if is_atom {
+        &ATOM_ENTRY_PATTERN
+    } else {
+        &RSS_ITEM_PATTERN
+    };
+    let blocks = pattern.find_iter(text).map(|m| m.as_str()).collect();
+    (is_atom, blocks)
+}
+
+/// 切り出したitem/entryを個別に解析する。1件も解析できなければNoneを返す。
+fn recover_partial_feed(
+    text: &str,
+    is_atom: bool,
+    blocks: &[&str],
+    group: Option<&str>,
+    pub_date_preference: PubDatePreference,
+) -> Option<ParsedFeed> {
+    if blocks.is_empty() {
+        return None;
+    }
+
+    let mut namespaces: Vec<&str> = Vec::new();
+    for matched in NAMESPACE_PATTERN.find_iter(text) {
+        if !namespaces.contains(&matched.as_str()) {
+            namespaces.push(matched.as_str());
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut malformed_entries = 0;
+
+    for block in blocks {
+        if !is_balanced_markup(block) {
+            malformed_entries += 1;
+            continue;
+        }
+
+        let document = if is_atom {
+            let mut attributes = namespaces.join(" ");
+            if !namespaces.iter().any(|ns| ns.starts_with("xmlns=")) {
+                attributes.push_str(r#" xmlns="http://www.w3.org/2005/Atom""#);
+            }
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><feed {}><title>recovered</title>{}</feed>"#,
+                attributes, block
+            )
+        } else {
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><rss version="2.0" {}><channel><title>recovered</title>{}</channel></rss>"#,
+                namespaces.join(" "),
+                block
+            )
+        };
+
+        match parser::parse(document.as_bytes()) {
+            Ok(feed) if !feed.entries.is_empty() => {
+                entries.extend(convert_entries(
+                    feed.entries,
+                    &[block],
+                    group,
+                    None,
+                    pub_date_preference,
+                ));
+            }
+            _ => malformed_entries += 1,
+        }
+    }
+
+    if malformed_entries == blocks.len() {
+        None
+    } else {
+        Some(ParsedFeed {
+            entries,
+            malformed_entries,
+            icon_url: None,
+            elapsed_ms: 0,
+            last_build_date: None,
+        })
+    }
 }
 
-pub(crate) fn parse_feed_content(content: &[u8], group: Option<&str>) -> Result<Vec<NewQueue>> {
-    let feed = parser::parse(content)?;
+/// 開始タグと終了タグの対応が取れているかを簡易的に検査する（CDATAとコメントは除外）
+fn is_balanced_markup(block: &str) -> bool {
+    let stripped = XML_OPAQUE_PATTERN.replace_all(block, "");
+    let mut stack: Vec<&str> = Vec::new();
+
+    for captures in XML_TAG_PATTERN.captures_iter(&stripped) {
+        let is_closing = !captures[1].is_empty();
+        let is_self_closing = !captures[3].is_empty();
+        let name = captures.get(2).map(|m| m.as_str()).unwrap_or_default();
+
+        if is_closing {
+            if stack.pop() != Some(name) {
+                return false;
+            }
+        } else if !is_self_closing {
+            stack.push(name);
+        }
+    }
+
+    stack.is_empty()
+}
 
+/// feed_rsのentryをqueueへの挿入用に変換する。
+/// `raw_blocks`はentryと同じ順に並んだ生のitem/entry要素で、件数が一致する場合のみpub_dateのオフセット取得に使う。
+/// `feed_language`はフィード全体の言語で、entry自身に言語が無い場合に使う。
+/// pub_dateは`pub_date_preference`に従ってpublished/updatedのどちらかを優先する。
+fn convert_entries(
+    feed_entries: Vec<Entry>,
+    raw_blocks: &[&str],
+    group: Option<&str>,
+    feed_language: Option<&str>,
+    pub_date_preference: PubDatePreference,
+) -> Vec<NewQueue> {
     let mut entries = Vec::new();
+    let aligned = feed_entries.len() == raw_blocks.len();
 
-    for entry in feed.entries {
+    for (index, entry) in feed_entries.into_iter().enumerate() {
         let Some(link) = extract_link(&entry) else {
             continue;
         };
@@ -72,24 +653,69 @@ pub(crate) fn parse_feed_content(content: &[u8], group: Option<&str>) -> Result<
             .map(|t| t.content)
             .unwrap_or_else(|| "No title".to_string());
 
-        let pub_date = entry.published.or(entry.updated);
-
+        let pub_date = pub_date_preference.select(entry.published, entry.updated);
+        // feed_rsはUTCへ正規化するため、元のオフセットは生の日付文字列から取り直す
+        let pub_date_tz_offset = pub_date
+            .zip(aligned.then(|| raw_blocks[index]))
+            .and_then(|(pub_date, raw)| find_pub_date_offset(raw, pub_date));
+        let language = entry
+            .language
+            .as_deref()
+            .and_then(primary_language)
+            .or_else(|| feed_language.and_then(primary_language));
+
+        let content_body = entry
+            .content
+            .and_then(|c| c.body)
+            .filter(|body| !body.trim().is_empty());
+        // descriptionは従来どおりsummary優先でcontentにフォールバックし、contentはそれとは別に残す
         let description = entry
             .summary
             .map(|t| t.content)
-            .or_else(|| entry.content.and_then(|c| c.body))
+            .or_else(|| content_body.clone())
             .unwrap_or_else(String::new);
 
         entries.push(NewQueue {
             link,
             title,
             pub_date,
+            pub_date_tz_offset,
             description,
+            content_body,
+            language,
             group: group.map(|g| g.to_string()),
+            scrape_referer: None,
+            scrape_accept_language: None,
+            priority: 0,
         });
     }
 
-    Ok(entries)
+    entries
+}
+
+/// 言語タグ（`ja-JP`・`en_US`）から主言語タグを小文字で取り出す。空ならNone
+fn primary_language(tag: &str) -> Option<String> {
+    tag.trim()
+        .split(['-', '_'])
+        .next()
+        .map(str::trim)
+        .filter(|primary| !primary.is_empty())
+        .map(str::to_ascii_lowercase)
+}
+
+/// item/entry要素の日付文字列のうち、`pub_date`と同じ時刻を表すもののUTCからのオフセット（分）を返す。
+/// RFC 2822（RSS）とRFC 3339（Atom）の書式に対応し、解釈できなければNone。
+pub(crate) fn find_pub_date_offset(raw_block: &str, pub_date: DateTime<Utc>) -> Option<i32> {
+    DATE_ELEMENT_PATTERN
+        .captures_iter(raw_block)
+        .filter_map(|captures| {
+            let text = captures[1].trim();
+            DateTime::parse_from_rfc2822(text)
+                .or_else(|_| DateTime::parse_from_rfc3339(text))
+                .ok()
+        })
+        .find(|parsed| *parsed == pub_date)
+        .map(|parsed| parsed.offset().local_minus_utc() / 60)
 }
 
 pub(crate) fn extract_link(entry: &Entry) -> Option<String> {
@@ -118,7 +744,7 @@ pub(crate) fn find_first_url(text: &str) -> Option<String> {
     let matched = URL_PATTERN.find(text)?;
     let trimmed = matched
         .as_str()
-        .trim_end_matches(|c: char| matches!(c, ')' | ']' | '"' | '\'' | ',' | '.' | ';'))
+        .trim_end_matches([')', ']', '"', '\'', ',', '.', ';'])
         .to_string();
 
     if trimmed.is_empty() {
@@ -128,29 +754,60 @@ pub(crate) fn find_first_url(text: &str) -> Option<String> {
     }
 }
 
-/// queueテーブルにupsert（INSERT or UPDATE）
+/// `upsert_queue_entries`でINSERT・UPDATEになった件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueUpsertCounts {
+    pub inserted: usize,
+    pub updated: usize,
+}
+
+/// queueテーブルにupsert（INSERT or UPDATE）し、新規追加・更新の件数を返す
 pub async fn upsert_queue_entries(
     pool: &PgPool,
     entries: Vec<NewQueue>,
     group: Option<String>,
-) -> Result<usize> {
-    let mut count = 0;
+) -> Result<QueueUpsertCounts> {
+    let (counts, _) = upsert_queue_entries_with_inserted(pool, entries, group).await?;
+    Ok(counts)
+}
+
+/// `upsert_queue_entries`と同じくupsertし、件数に加えてINSERTになった記事を返す（sinkへのpublish用）
+///
+/// INSERTで作られた行は`xmax`が0になることを利用し、`RETURNING (xmax = 0)`で区別する。
+pub(crate) async fn upsert_queue_entries_with_inserted(
+    pool: &PgPool,
+    entries: Vec<NewQueue>,
+    group: Option<String>,
+) -> Result<(QueueUpsertCounts, Vec<PublishedArticle>)> {
+    let mut counts = QueueUpsertCounts::default();
+    let mut inserted_articles = Vec::new();
 
     for entry in entries {
         let group_value = group.clone().or(entry.group.clone());
         let id = Uuid::new_v4();
 
-        sqlx::query(
+        let inserted: bool = sqlx::query_scalar(
             r#"
-            INSERT INTO rss.queue (id, link, title, pub_date, description, "group")
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO rss.queue (
+                id, link, title, pub_date, description, "group",
+                scrape_referer, scrape_accept_language, pub_date_tz_offset, priority, content,
+                language
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT (link)
             DO UPDATE SET
                 title = EXCLUDED.title,
                 pub_date = EXCLUDED.pub_date,
+                pub_date_tz_offset = EXCLUDED.pub_date_tz_offset,
                 description = EXCLUDED.description,
+                content = EXCLUDED.content,
+                language = EXCLUDED.language,
                 "group" = EXCLUDED."group",
+                scrape_referer = EXCLUDED.scrape_referer,
+                scrape_accept_language = EXCLUDED.scrape_accept_language,
+                priority = EXCLUDED.priority,
                 updated_at = NOW()
+            RETURNING (xmax = 0) AS inserted
             "#,
         )
         .bind(id)
@@ -159,19 +816,128 @@ pub async fn upsert_queue_entries(
         .bind(entry.pub_date)
         .bind(&entry.description)
         .bind(&group_value)
-        .execute(pool)
+        .bind(&entry.scrape_referer)
+        .bind(&entry.scrape_accept_language)
+        .bind(entry.pub_date_tz_offset)
+        .bind(entry.priority)
+        .bind(&entry.content_body)
+        .bind(&entry.language)
+        .fetch_one(pool)
         .await?;
 
-        count += 1;
+        if inserted {
+            counts.inserted += 1;
+            inserted_articles.push(PublishedArticle {
+                id,
+                link: entry.link,
+                title: entry.title,
+                pub_date: entry.pub_date,
+                description: entry.description,
+                group: group_value,
+                language: entry.language,
+            });
+        } else {
+            counts.updated += 1;
+        }
+    }
+
+    Ok((counts, inserted_articles))
+}
+
+/// fetch-rssの挙動を調整する設定
+#[derive(Debug, Clone)]
+pub struct FetchRssOptions {
+    /// 取得に失敗したフィードを最後にまとめて1回だけ再取得する
+    pub retry_failed_feeds: bool,
+    /// フィードにアイコンが無い場合、フィードホストの/favicon.icoを推定値として保存する
+    pub guess_favicon: bool,
+    /// フィード取得に使うプロキシURL（未指定なら直接接続）
+    pub proxy_url: Option<String>,
+    /// hickory-dnsリゾルバでDNS解決結果をキャッシュする
+    pub dns_cache: bool,
+    /// フィードの取得・解析までを行い、DBへは書き込まない（processedは取り込み予定件数になる）
+    pub dry_run: bool,
+    /// queueへ保存するtitleの最大文字数（超過分は末尾を`…`にして切り詰める）
+    pub title_max_chars: usize,
+    /// queueへ保存するdescriptionの最大文字数（超過分は末尾を`…`にして切り詰める）
+    pub description_max_chars: usize,
+    /// フィード取得に使うUser-Agent（複数ならフィードごとにラウンドロビン、空ならクライアントの既定）
+    pub user_agents: Vec<String>,
+    /// 1フィードの処理の上限時間。超えたフィードは`error: "timeout"`として打ち切り、同時実行の枠を空ける
+    pub feed_timeout: Duration,
+    /// フィード取得のHTTPリトライ方針（フィードごとの`retry`で項目単位に上書きできる）
+    pub retry_policy: FeedRetryPolicy,
+    /// 休眠フィードの取得間隔を延ばす（未指定ならすべてのフィードを毎回取得する）
+    pub dormancy: Option<FeedDormancy>,
+    /// queueへ新規追加した記事をpublishする先（未指定ならqueueへの保存のみ）
+    pub sink: Option<Arc<dyn ArticleSink>>,
+    /// entryのpub_dateにpublished/updatedのどちらを優先するか
+    pub pub_date_preference: PubDatePreference,
+}
+
+impl Default for FetchRssOptions {
+    fn default() -> Self {
+        Self {
+            retry_failed_feeds: false,
+            guess_favicon: false,
+            proxy_url: None,
+            dns_cache: false,
+            dry_run: false,
+            title_max_chars: DEFAULT_TITLE_MAX_CHARS,
+            description_max_chars: DEFAULT_DESCRIPTION_MAX_CHARS,
+            user_agents: Vec::new(),
+            feed_timeout: Duration::from_secs(DEFAULT_FEED_TIMEOUT_SECS),
+            retry_policy: FeedRetryPolicy::default(),
+            dormancy: None,
+            sink: None,
+            pub_date_preference: PubDatePreference::default(),
+        }
+    }
+}
+
+/// フィードごとにUser-Agentを順番に割り当てる
+///
+/// 同一UAで大量のフィードを取得してブロックされるのを避けるため、`next`を呼ぶたびに次のUAを返す。
+/// 1つだけなら常に同じUA、空なら常にNoneを返す。
+pub(crate) struct UserAgentRotator<'a> {
+    user_agents: &'a [String],
+    next: AtomicUsize,
+}
+
+impl<'a> UserAgentRotator<'a> {
+    pub(crate) fn new(user_agents: &'a [String]) -> Self {
+        Self {
+            user_agents,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn next(&self) -> Option<&'a str> {
+        if self.user_agents.is_empty() {
+            return None;
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.user_agents.len();
+        Some(self.user_agents[index].as_str())
     }
+}
 
-    Ok(count)
+/// `max_chars`文字を超える文字列を、末尾の`…`を含めて`max_chars`文字に収まるよう文字単位で切り詰める。
+/// 切り詰めたことが分かるよう`…`を付け、保存後の長さは常に上限以内にする（`max_chars`が0なら空文字）。
+pub(crate) fn truncate_queue_text(value: String, max_chars: usize) -> String {
+    if max_chars == 0 {
+        return String::new();
+    }
+    if value.chars().nth(max_chars).is_none() {
+        return value;
+    }
+    let kept: String = value.chars().take(max_chars - 1).collect();
+    format!("{}…", kept)
 }
 
 /// fetch-rssコマンドのメイン処理
-pub async fn run(pool: PgPool, webhook_url: Option<&str>) -> Result<()> {
+pub async fn run(pool: PgPool, options: &FetchRssOptions, webhook_url: Option<&str>) -> Result<()> {
     info!("rss_links.ymlを読み込み中...");
-    let summary = execute_fetch_rss(&pool, "rss_links.yml").await?;
+    let mut summary = execute_fetch_rss(&pool, "rss_links.yml", options).await?;
 
     if summary.feeds.is_empty() {
         info!("登録されているRSSフィードがありません");
@@ -180,13 +946,51 @@ pub async fn run(pool: PgPool, webhook_url: Option<&str>) -> Result<()> {
 
     log_fetch_rss_summary(&summary);
 
-    if let Err(e) = webhook::notify_fetch_rss(webhook_url, &summary, "cli").await {
-        warn!(error = %e, "Webhook送信に失敗しました(fetch-rss)");
+    if options.dry_run {
+        info!(
+            total_processed = summary.total_processed,
+            "ドライランのためDBへは保存していません（取り込み予定件数）"
+        );
+        return Ok(());
     }
 
+    webhook::notify_fetch_rss_by_group(webhook_url, &mut summary, "cli").await;
+    log_fetch_rss_webhook_failures(&summary);
+    notify_entry_count_drops(webhook_url, &summary, "cli").await;
+
     Ok(())
 }
 
+/// entry数が急減したフィードごとにアラートのWebhookを送る（失敗は警告ログのみ）
+pub(crate) async fn notify_entry_count_drops(
+    webhook_url: Option<&str>,
+    summary: &FetchRssSummary,
+    source: &str,
+) {
+    let results = webhook::notify_entry_count_drops(webhook_url, summary, source).await;
+    for result in results.iter().filter(|result| !result.sent) {
+        warn!(error = ?result.error, "Webhook送信に失敗しました(entry数の急減アラート)");
+    }
+}
+
+/// fetch-rssのWebhook送信（グローバル・グループ別）の失敗をログに出す
+pub(crate) fn log_fetch_rss_webhook_failures(summary: &FetchRssSummary) {
+    if let Some(result) = summary.webhook.as_ref().filter(|result| !result.sent) {
+        warn!(error = ?result.error, "Webhook送信に失敗しました(fetch-rss)");
+    }
+    for group_webhook in summary
+        .group_webhooks
+        .iter()
+        .filter(|group_webhook| !group_webhook.result.sent)
+    {
+        warn!(
+            groups = ?group_webhook.groups,
+            error = ?group_webhook.result.error,
+            "グループ別Webhookの送信に失敗しました(fetch-rss)"
+        );
+    }
+}
+
 pub(crate) fn log_fetch_rss_summary(summary: &FetchRssSummary) {
     let mut grouped: BTreeMap<&str, Vec<&FetchRssFeedResult>> = BTreeMap::new();
     for feed in &summary.feeds {
@@ -205,6 +1009,12 @@ pub(crate) fn log_fetch_rss_summary(summary: &FetchRssSummary) {
                         group = %feed.group,
                         name = %feed.name,
                         processed = feed.processed,
+                        inserted = feed.inserted,
+                        updated = feed.updated,
+                        elapsed_ms = feed.elapsed_ms,
+                        unchanged = feed.unchanged,
+                        locked = feed.locked,
+                        dormant = feed.dormant,
                         "RSSを処理"
                     );
                 }
@@ -212,81 +1022,486 @@ pub(crate) fn log_fetch_rss_summary(summary: &FetchRssSummary) {
         }
     }
 
+    for feed in slowest_feeds(&summary.feeds, SLOW_FEED_LOG_COUNT) {
+        info!(
+            group = %feed.group,
+            name = %feed.name,
+            elapsed_ms = feed.elapsed_ms,
+            failed = feed.error.is_some(),
+            "取得に時間がかかったフィード"
+        );
+    }
+
     info!(total_processed = summary.total_processed, "RSS処理が完了");
 }
 
+/// 取得時間の長い順に最大`count`件のフィードを返す
+pub(crate) fn slowest_feeds(
+    feeds: &[FetchRssFeedResult],
+    count: usize,
+) -> Vec<&FetchRssFeedResult> {
+    let mut sorted: Vec<&FetchRssFeedResult> = feeds.iter().collect();
+    sorted.sort_by_key(|feed| std::cmp::Reverse(feed.elapsed_ms));
+    sorted.truncate(count);
+    sorted
+}
+
+/// フィード単位のadvisory lockを取ってから1フィード分を処理する。
+/// 他のインスタンスがロック中のフィードは取得せずに`locked`として返す。
+async fn process_feed(
+    client: &Client,
+    pool: &PgPool,
+    locks: &FeedLocks,
+    feed: RssFeedSource,
+    options: &FetchRssOptions,
+    user_agent: Option<&str>,
+) -> FetchRssFeedResult {
+    let url = feed.url.clone();
+    match locks.try_lock(&url).await {
+        Ok(true) => {}
+        Ok(false) => {
+            info!(
+                group = %feed.group,
+                name = %feed.name,
+                "他のインスタンスが取得中のためフィードをスキップします"
+            );
+            return FetchRssFeedResult {
+                locked: true,
+                ..empty_feed_result(&feed)
+            };
+        }
+        Err(e) => {
+            // 協調はベストエフォートのため、ロックを取れなくても取得は続ける
+            warn!(
+                group = %feed.group,
+                name = %feed.name,
+                error = %e,
+                "フィードのロック取得に失敗したためロックせずに取得します"
+            );
+            return fetch_and_store_feed_with_timeout(client, pool, feed, options, user_agent)
+                .await;
+        }
+    }
+
+    let result = fetch_and_store_feed_with_timeout(client, pool, feed, options, user_agent).await;
+    if let Err(e) = locks.unlock(&url).await {
+        warn!(url = %url, error = %e, "フィードのロック解放に失敗しました");
+    }
+    result
+}
+
+fn empty_feed_result(feed: &RssFeedSource) -> FetchRssFeedResult {
+    FetchRssFeedResult {
+        group: feed.group.clone(),
+        name: feed.name.clone(),
+        processed: 0,
+        inserted: 0,
+        updated: 0,
+        malformed_entries: 0,
+        elapsed_ms: 0,
+        unchanged: false,
+        locked: false,
+        dormant: false,
+        entry_count_drop: None,
+        error: None,
+    }
+}
+
+/// `fetch_and_store_feed`を`options.feed_timeout`で打ち切る。
+///
+/// クライアントのタイムアウトはリクエスト単位のため、応答後の解析やDB書き込みで止まったフィードが
+/// `buffer_unordered`の枠を占有し続けないようフィード全体の処理時間を監視する。打ち切る前にupsertした
+/// entryは残るが、last-build-dateは記録されないため次回の実行で取り込み直す。
+async fn fetch_and_store_feed_with_timeout(
+    client: &Client,
+    pool: &PgPool,
+    feed: RssFeedSource,
+    options: &FetchRssOptions,
+    user_agent: Option<&str>,
+) -> FetchRssFeedResult {
+    let timed_out = empty_feed_result(&feed);
+    match tokio::time::timeout(
+        options.feed_timeout,
+        fetch_and_store_feed(client, pool, feed, options, user_agent),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(_) => {
+            warn!(
+                group = %timed_out.group,
+                name = %timed_out.name,
+                timeout_secs = options.feed_timeout.as_secs_f64(),
+                "フィードの処理が上限時間を超えたため打ち切りました"
+            );
+            FetchRssFeedResult {
+                elapsed_ms: options.feed_timeout.as_millis() as u64,
+                error: Some(FEED_TIMEOUT_ERROR.to_string()),
+                ..timed_out
+            }
+        }
+    }
+}
+
+/// 1フィード分の取得・解析・upsertを行い、結果を返す
+async fn fetch_and_store_feed(
+    client: &Client,
+    pool: &PgPool,
+    feed: RssFeedSource,
+    options: &FetchRssOptions,
+    user_agent: Option<&str>,
+) -> FetchRssFeedResult {
+    let mut result = empty_feed_result(&feed);
+
+    if let Some(dormancy) = &options.dormancy {
+        match find_feed_activity(pool, &feed.group, &feed.name, &feed.url).await {
+            Ok(Some(activity)) if dormancy.should_skip(&activity, Utc::now()) => {
+                info!(
+                    group = %feed.group,
+                    name = %feed.name,
+                    last_entry_new_at = ?activity.last_entry_new_at,
+                    last_fetched_at = ?activity.last_fetched_at,
+                    "新規entryの無い休眠フィードのため今回は取得しません"
+                );
+                result.dormant = true;
+                return result;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(
+                    group = %feed.group,
+                    name = %feed.name,
+                    error = %e,
+                    "フィードの取得状況の取得に失敗したため休眠を判定せずに取得します"
+                );
+            }
+        }
+    }
+
+    let started = Instant::now();
+    let retry = options.retry_policy.with_override(feed.retry.as_ref());
+    let parsed = match fetch_and_parse_feed(
+        client,
+        &feed.url,
+        Some(&feed.group),
+        feed.format,
+        user_agent,
+        &retry,
+        options.pub_date_preference,
+    )
+    .await
+    {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            result.elapsed_ms = started.elapsed().as_millis() as u64;
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    result.elapsed_ms = parsed.elapsed_ms;
+
+    if parsed.malformed_entries > 0 {
+        warn!(
+            group = %feed.group,
+            name = %feed.name,
+            malformed_entries = parsed.malformed_entries,
+            "フィードの一部entryが壊れているため読み飛ばしました"
+        );
+    }
+    result.malformed_entries = parsed.malformed_entries;
+
+    if let Some(last_build_date) = parsed.last_build_date {
+        match find_feed_last_build_date(pool, &feed.group, &feed.name, &feed.url).await {
+            Ok(Some(previous)) if previous == last_build_date => {
+                info!(
+                    group = %feed.group,
+                    name = %feed.name,
+                    %last_build_date,
+                    "フィードが前回から更新されていないため取り込みを省略します"
+                );
+                result.unchanged = true;
+                if !options.dry_run {
+                    record_activity(pool, &feed, false).await;
+                }
+                return result;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                warn!(
+                    group = %feed.group,
+                    name = %feed.name,
+                    error = %e,
+                    "フィードのlast-build-dateの取得に失敗しました"
+                );
+            }
+        }
+    }
+
+    if options.dry_run {
+        result.processed = parsed.entries.len();
+        return result;
+    }
+
+    match record_feed_entry_count(
+        pool,
+        &feed.group,
+        &feed.name,
+        &feed.url,
+        i32::try_from(parsed.entries.len()).unwrap_or(i32::MAX),
+        ENTRY_COUNT_HISTORY_SIZE,
+    )
+    .await
+    {
+        Ok(history) => {
+            result.entry_count_drop = detect_entry_count_drop(&history, parsed.entries.len());
+            if let Some(drop) = &result.entry_count_drop {
+                warn!(
+                    group = %feed.group,
+                    name = %feed.name,
+                    entry_count = drop.entry_count,
+                    average = drop.average,
+                    "フィードのentry数が直近の平均から急減しています"
+                );
+            }
+        }
+        Err(e) => {
+            warn!(
+                group = %feed.group,
+                name = %feed.name,
+                error = %e,
+                "フィードのentry数の記録に失敗しました"
+            );
+        }
+    }
+
+    let icon_url = parsed.icon_url.clone().or_else(|| {
+        options
+            .guess_favicon
+            .then(|| guess_favicon_url(&feed.url))
+            .flatten()
+    });
+    if let Err(e) = upsert_feed_meta(
+        pool,
+        &feed.group,
+        &feed.name,
+        &feed.url,
+        icon_url.as_deref(),
+    )
+    .await
+    {
+        warn!(
+            group = %feed.group,
+            name = %feed.name,
+            error = %e,
+            "フィードのメタ情報の保存に失敗しました"
+        );
+    }
+
+    let entries: Vec<NewQueue> = parsed
+        .entries
+        .into_iter()
+        .map(|entry| NewQueue {
+            title: truncate_queue_text(entry.title, options.title_max_chars),
+            description: truncate_queue_text(entry.description, options.description_max_chars),
+            scrape_referer: feed.referer.clone(),
+            scrape_accept_language: feed.accept_language.clone(),
+            priority: feed.priority,
+            ..entry
+        })
+        .collect();
+
+    let processed = entries.len();
+    match upsert_queue_entries_with_inserted(pool, entries, Some(feed.group.clone())).await {
+        Ok((counts, inserted_articles)) => {
+            result.processed = processed;
+            result.inserted = counts.inserted;
+            result.updated = counts.updated;
+            record_activity(pool, &feed, counts.inserted > 0).await;
+            if let Some(sink) = &options.sink {
+                publish_to_sink(sink.as_ref(), &feed, &inserted_articles).await;
+            }
+        }
+        Err(e) => {
+            // 取り込めなかった場合は次回も処理し直すため、last-build-dateを記録しない
+            result.error = Some(e.to_string());
+            return result;
+        }
+    }
+
+    if let Some(last_build_date) = parsed.last_build_date {
+        if let Err(e) =
+            upsert_feed_last_build_date(pool, &feed.group, &feed.name, &feed.url, last_build_date)
+                .await
+        {
+            warn!(
+                group = %feed.group,
+                name = %feed.name,
+                error = %e,
+                "フィードのlast-build-dateの保存に失敗しました"
+            );
+        }
+    }
+
+    result
+}
+
+/// 新規記事をsinkへpublishする。queueへは保存済みなので、失敗しても警告ログだけで取り込みは続ける
+async fn publish_to_sink(
+    sink: &dyn ArticleSink,
+    feed: &RssFeedSource,
+    articles: &[PublishedArticle],
+) {
+    if articles.is_empty() {
+        return;
+    }
+    if let Err(e) = sink.publish(articles).await {
+        warn!(
+            group = %feed.group,
+            name = %feed.name,
+            count = articles.len(),
+            error = %e,
+            "新規記事のpublishに失敗しました"
+        );
+    }
+}
+
+/// 休眠判定のためにフィードを取得した時刻（と新規entryの有無）を記録する。失敗しても取り込みは続ける
+async fn record_activity(pool: &PgPool, feed: &RssFeedSource, has_new_entries: bool) {
+    if let Err(e) =
+        record_feed_activity(pool, &feed.group, &feed.name, &feed.url, has_new_entries).await
+    {
+        warn!(
+            group = %feed.group,
+            name = %feed.name,
+            error = %e,
+            "フィードの取得状況の記録に失敗しました"
+        );
+    }
+}
+
 /// fetch-rssのメインロジックを実行し、結果を返す
-pub async fn execute_fetch_rss(pool: &PgPool, rss_links_path: &str) -> Result<FetchRssSummary> {
+pub async fn execute_fetch_rss(
+    pool: &PgPool,
+    rss_links_path: &str,
+    options: &FetchRssOptions,
+) -> Result<FetchRssSummary> {
+    let run_id = Uuid::new_v4();
     let feeds = load_rss_links(rss_links_path)?;
+    validate_rss_links(&feeds)?;
 
     if feeds.is_empty() {
         return Ok(FetchRssSummary {
+            run_id,
             total_processed: 0,
             feeds: Vec::new(),
+            webhook: None,
+            group_webhooks: Vec::new(),
+            group_webhook_urls: BTreeMap::new(),
         });
     }
 
-    let client = Client::builder()
-        .timeout(Duration::from_secs(FETCH_RSS_TIMEOUT_SECS))
-        .build()?;
-
-    let pool = pool.clone();
-
-    let mut results = stream::iter(feeds.into_iter())
-        .map(|feed| {
-            let client = client.clone();
-            let pool = pool.clone();
-            async move {
-                let group_name = feed.group.clone();
-                let feed_name = feed.name.clone();
-
-                match fetch_and_parse_feed(&client, &feed.url, Some(&feed.group)).await {
-                    Ok(entries) => {
-                        let processed = entries.len();
-                        match upsert_queue_entries(&pool, entries, Some(feed.group.clone())).await {
-                            Ok(_) => FetchRssFeedResult {
-                                group: group_name,
-                                name: feed_name,
-                                processed,
-                                error: None,
-                            },
-                            Err(e) => FetchRssFeedResult {
-                                group: group_name,
-                                name: feed_name,
-                                processed: 0,
-                                error: Some(e.to_string()),
-                            },
-                        }
-                    }
-                    Err(e) => FetchRssFeedResult {
-                        group: group_name,
-                        name: feed_name,
-                        processed: 0,
-                        error: Some(e.to_string()),
-                    },
+    let group_webhook_urls: BTreeMap<String, String> = feeds
+        .iter()
+        .filter_map(|feed| Some((feed.group.clone(), feed.webhook_url.clone()?)))
+        .collect();
+
+    let client = build_feed_client(options)?;
+    let user_agents = UserAgentRotator::new(&options.user_agents);
+    let locks = FeedLocks::acquire(pool).await?;
+
+    let mut results =
+        process_feeds(&client, pool, &locks, feeds.clone(), options, &user_agents).await;
+
+    if options.retry_failed_feeds {
+        let failed: Vec<RssFeedSource> = feeds
+            .into_iter()
+            .filter(|feed| {
+                results.iter().any(|result| {
+                    result.error.is_some() && result.group == feed.group && result.name == feed.name
+                })
+            })
+            .collect();
+
+        if !failed.is_empty() {
+            info!(feeds = failed.len(), "取得に失敗したフィードを再取得します");
+            let retried = process_feeds(&client, pool, &locks, failed, options, &user_agents).await;
+            for retry in retried {
+                if let Some(result) = results
+                    .iter_mut()
+                    .find(|result| result.group == retry.group && result.name == retry.name)
+                {
+                    *result = retry;
                 }
             }
-        })
-        .buffer_unordered(MAX_CONCURRENT_FEED_REQUESTS)
-        .collect::<Vec<_>>()
-        .await;
+        }
+    }
+
+    if let Err(e) = locks.release().await {
+        warn!(error = %e, "フィードのロック用接続の解放に失敗しました");
+    }
 
     results.sort_by(|a, b| a.group.cmp(&b.group).then(a.name.cmp(&b.name)));
     let total_processed = results.iter().map(|feed| feed.processed).sum();
 
     Ok(FetchRssSummary {
+        run_id,
         total_processed,
         feeds: results,
+        webhook: None,
+        group_webhooks: Vec::new(),
+        group_webhook_urls,
     })
 }
 
+/// フィード群を同時実行数の上限付きで並列処理する
+///
+/// グループの優先度（`_priority`）が高いものから順に処理し、同じ優先度のフィード同士だけを並列に取得する。
+/// 低優先のフィードは高優先のフィードがすべて終わってから取得を始める。
+/// User-Agentは取得を始める順に`user_agents`から割り当てる。
+async fn process_feeds(
+    client: &Client,
+    pool: &PgPool,
+    locks: &FeedLocks,
+    feeds: Vec<RssFeedSource>,
+    options: &FetchRssOptions,
+    user_agents: &UserAgentRotator<'_>,
+) -> Vec<FetchRssFeedResult> {
+    let mut tiers: BTreeMap<std::cmp::Reverse<i32>, Vec<RssFeedSource>> = BTreeMap::new();
+    for feed in feeds {
+        tiers
+            .entry(std::cmp::Reverse(feed.priority))
+            .or_default()
+            .push(feed);
+    }
+
+    let mut results = Vec::new();
+    for (_, tier) in tiers {
+        let tier_results: Vec<FetchRssFeedResult> = stream::iter(tier)
+            .map(|feed| {
+                let client = client.clone();
+                let pool = pool.clone();
+                let user_agent = user_agents.next();
+                async move { process_feed(&client, &pool, locks, feed, options, user_agent).await }
+            })
+            .buffer_unordered(MAX_CONCURRENT_FEED_REQUESTS)
+            .collect()
+            .await;
+        results.extend(tier_results);
+    }
+    results
+}
+
 #[cfg(test)]
 mod tests {
     pub mod load_rss {
-        use crate::fetch_rss::load_rss_links;
+        use crate::fetch_rss::{
+            load_rss_links, parse_feed_content, validate_rss_links, PubDatePreference,
+        };
+        use crate::models::FeedFormat;
+        use crate::test_support::create_temp_yaml;
 
         /// # 検証目的
-        /// rss_links.ymlを読み込み、定義済みフィードが存在することを確認する。
+        /// rss_links.ymlを読み込み、定義済みフィードが存在して検証も通ることを確認する。
         #[test]
         fn rss_linksを読み込める() {
             if std::path::Path::new("rss_links.yml").exists() {
@@ -295,9 +1510,186 @@ mod tests {
 
                 let feeds = result.unwrap();
                 assert!(!feeds.is_empty(), "フィードが空");
+                validate_rss_links(&feeds).expect("rss_links.ymlの検証に失敗");
             }
         }
-    }
+
+        /// # 検証目的
+        /// 空URL・urlキーのタイポ・http(s)以外のスキームを、どのgroup/nameが不正か分かるエラーで弾くことを確認する。
+        #[test]
+        fn 不正なフィードをgroupとname付きで弾く() -> anyhow::Result<()> {
+            let yaml = create_temp_yaml(
+                r#"
+news:
+  ok: https://example.com/rss.xml
+  empty: ""
+  typo:
+    ulr: https://example.com/typo.xml
+tech:
+  ftp: ftp://example.com/feed.xml
+"#,
+            )?;
+
+            let feeds = load_rss_links(yaml.path().to_str().expect("パスの変換に失敗"))?;
+            let message = validate_rss_links(&feeds)
+                .expect_err("不正なフィードが検出されない")
+                .to_string();
+
+            assert!(message.contains("news/empty: urlが空です"), "{}", message);
+            assert!(message.contains("news/typo: urlが空です"), "{}", message);
+            assert!(
+                message.contains("tech/ftp: http(s)以外のスキームです"),
+                "{}",
+                message
+            );
+            assert!(!message.contains("news/ok"), "{}", message);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// Detailedエントリのreferer/accept_languageがフィード設定として読み込まれることを確認する。
+        #[test]
+        fn detailedエントリのスクレイピング指定を読み込める() -> anyhow::Result<()> {
+            let yaml = create_temp_yaml(
+                r#"
+news:
+  detailed:
+    url: https://example.com/rss.xml
+    referer: https://example.com/
+    accept_language: ja
+  simple: https://example.com/simple.xml
+"#,
+            )?;
+
+            let feeds = load_rss_links(yaml.path().to_str().expect("パスの変換に失敗"))?;
+            let detailed = feeds
+                .iter()
+                .find(|feed| feed.name == "detailed")
+                .expect("detailedが読み込まれていない");
+            assert_eq!(detailed.referer.as_deref(), Some("https://example.com/"));
+            assert_eq!(detailed.accept_language.as_deref(), Some("ja"));
+
+            let simple = feeds
+                .iter()
+                .find(|feed| feed.name == "simple")
+                .expect("simpleが読み込まれていない");
+            assert!(simple.referer.is_none());
+            assert!(simple.accept_language.is_none());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// Detailedエントリの`format: custom_json`が読み込まれ、未指定のフィードはRSS/Atom扱いになることを確認する。
+        #[test]
+        fn フィード形式を読み込める() -> anyhow::Result<()> {
+            let yaml = create_temp_yaml(
+                r#"
+api:
+  json:
+    url: https://example.com/articles.json
+    format: custom_json
+  xml: https://example.com/rss.xml
+"#,
+            )?;
+
+            let feeds = load_rss_links(yaml.path().to_str().expect("パスの変換に失敗"))?;
+            let format_of = |name: &str| {
+                feeds
+                    .iter()
+                    .find(|feed| feed.name == name)
+                    .map(|feed| feed.format)
+            };
+            assert_eq!(format_of("json"), Some(FeedFormat::CustomJson));
+            assert_eq!(format_of("xml"), Some(FeedFormat::Feed));
+
+            // JSON形式もRSSと同じくlinkの重複を取り除く
+            let json = br#"[{"url": "https://example.com/a"}, {"url": "https://example.com/a"}]"#;
+            let parsed = parse_feed_content(
+                json,
+                Some("api"),
+                FeedFormat::CustomJson,
+                PubDatePreference::default(),
+            )?;
+            assert_eq!(parsed.entries.len(), 1);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// グループの`_priority`がフィードへ引き継がれ、フィード名としては扱われないことを確認する。未指定のグループは0になる。
+        #[test]
+        fn グループの優先度を読み込める() -> anyhow::Result<()> {
+            let yaml = create_temp_yaml(
+                r#"
+breaking:
+  _priority: 10
+  flash: https://example.com/flash.xml
+weekly:
+  digest: https://example.com/digest.xml
+"#,
+            )?;
+
+            let feeds = load_rss_links(yaml.path().to_str().expect("パスの変換に失敗"))?;
+            assert_eq!(feeds.len(), 2);
+            let priority_of = |name: &str| {
+                feeds
+                    .iter()
+                    .find(|feed| feed.name == name)
+                    .map(|feed| feed.priority)
+            };
+            assert_eq!(priority_of("flash"), Some(10));
+            assert_eq!(priority_of("digest"), Some(0));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// グループの`_webhook_url`がフィードへ引き継がれ、http(s)以外の値は検証で弾かれることを確認する。
+        #[test]
+        fn グループのwebhook_urlを読み込める() -> anyhow::Result<()> {
+            let yaml = create_temp_yaml(
+                r#"
+world:
+  _webhook_url: https://hooks.example.com/world
+  bbc: https://example.com/bbc.xml
+tech:
+  verge: https://example.com/verge.xml
+"#,
+            )?;
+
+            let feeds = load_rss_links(yaml.path().to_str().expect("パスの変換に失敗"))?;
+            assert_eq!(feeds.len(), 2);
+            let webhook_of = |name: &str| {
+                feeds
+                    .iter()
+                    .find(|feed| feed.name == name)
+                    .and_then(|feed| feed.webhook_url.clone())
+            };
+            assert_eq!(
+                webhook_of("bbc").as_deref(),
+                Some("https://hooks.example.com/world")
+            );
+            assert_eq!(webhook_of("verge"), None);
+            validate_rss_links(&feeds)?;
+
+            let invalid = create_temp_yaml(
+                r#"
+world:
+  _webhook_url: hooks.example.com/world
+  bbc: https://example.com/bbc.xml
+"#,
+            )?;
+            let feeds = load_rss_links(invalid.path().to_str().expect("パスの変換に失敗"))?;
+            let message = validate_rss_links(&feeds)
+                .expect_err("不正なwebhook URLを検出していない")
+                .to_string();
+            assert!(message.contains("world/_webhook_url"), "{}", message);
+
+            Ok(())
+        }
+    }
 
     pub mod extract_link {
         use feed_rs::model::{Content, Entry};
@@ -308,10 +1700,13 @@ mod tests {
         /// コンテンツ内に含まれるURLを抽出し、末尾の句読点が除去されることを確認する。
         #[test]
         fn コンテンツから_urlを抽出する() {
-            let mut entry = Entry::default();
-            let mut content = Content::default();
-            content.body = Some("テキスト https://example.com/path?a=1) があります".to_string());
-            entry.content = Some(content);
+            let entry = Entry {
+                content: Some(Content {
+                    body: Some("テキスト https://example.com/path?a=1) があります".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
 
             let link = extract_link(&entry);
             assert_eq!(link.as_deref(), Some("https://example.com/path?a=1"));
@@ -328,8 +1723,10 @@ mod tests {
 
     pub mod parse_feed_content {
         use anyhow::Result;
+        use tracing_test::traced_test;
 
-        use crate::fetch_rss::parse_feed_content;
+        use crate::fetch_rss::{find_pub_date_offset, parse_feed_content, PubDatePreference};
+        use crate::models::FeedFormat;
 
         /// # 検証目的
         /// RSSドキュメントを解析し、グループや日付のフォールバックが正しく行われることを確認する。
@@ -356,7 +1753,14 @@ mod tests {
                 </rss>
             "#;
 
-            let entries = parse_feed_content(rss.as_bytes(), Some("news"))?;
+            let parsed = parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            assert_eq!(parsed.malformed_entries, 0);
+            let entries = parsed.entries;
 
             assert_eq!(entries.len(), 2);
 
@@ -374,14 +1778,386 @@ mod tests {
 
             Ok(())
         }
+
+        /// # 検証目的
+        /// entryの言語（Atomの`content`の`xml:lang`）を優先し、無ければフィード全体の言語を主言語タグ（小文字）で引き継ぐことを確認する。
+        #[test]
+        fn フィードの言語をentryへ引き継ぐ() -> Result<()> {
+            let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0">
+                  <channel>
+                    <title>Example Feed</title>
+                    <link>https://feed.example.com</link>
+                    <description>Sample</description>
+                    <language>ja-JP</language>
+                    <item>
+                      <title>Item One</title>
+                      <link>https://example.com/one</link>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+            let parsed = parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            assert_eq!(parsed.entries[0].language.as_deref(), Some("ja"));
+
+            let atom = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <feed xmlns="http://www.w3.org/2005/Atom">
+                  <title>Example Atom</title>
+                  <id>urn:example:feed</id>
+                  <updated>2025-10-13T12:00:00Z</updated>
+                  <entry>
+                    <title>English</title>
+                    <id>urn:example:en</id>
+                    <link href="https://example.com/en"/>
+                    <updated>2025-10-13T12:00:00Z</updated>
+                    <content type="text" xml:lang="en-US">Hello</content>
+                  </entry>
+                  <entry>
+                    <title>Unknown</title>
+                    <id>urn:example:unknown</id>
+                    <link href="https://example.com/unknown"/>
+                    <updated>2025-10-13T12:00:00Z</updated>
+                  </entry>
+                </feed>
+            "#;
+            let parsed = parse_feed_content(
+                atom.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            let languages: Vec<Option<&str>> = parsed
+                .entries
+                .iter()
+                .map(|entry| entry.language.as_deref())
+                .collect();
+            assert_eq!(languages, vec![Some("en"), None]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// UTF-8と宣言しつつ実際はShift_JISのフィードを、推定したエンコードで文字化けせずに解析でき、警告ログが出ることを確認する。
+        #[traced_test]
+        #[test]
+        fn 宣言と異なるエンコードのフィードを読める() -> Result<()> {
+            let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0">
+                  <channel>
+                    <title>日本語のニュース</title>
+                    <item>
+                      <title>東京都で新しい交通システムの運用が始まりました</title>
+                      <link>https://example.com/sjis</link>
+                      <description>ことしの春から、ちいきのバスとでんしゃをつなぐあたらしいしくみがはじまります。</description>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+            // 宣言どおりのUTF-8フィードでは警告しない
+            parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            assert!(!logs_contain("実際のエンコードが異なる"));
+
+            let (sjis, _, _) = encoding_rs::SHIFT_JIS.encode(rss);
+            let parsed = parse_feed_content(
+                &sjis,
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            assert_eq!(parsed.entries.len(), 1);
+            let entry = &parsed.entries[0];
+            assert_eq!(
+                entry.title,
+                "東京都で新しい交通システムの運用が始まりました"
+            );
+            assert_eq!(
+                entry.description,
+                "ことしの春から、ちいきのバスとでんしゃをつなぐあたらしいしくみがはじまります。"
+            );
+            assert!(logs_contain("実際のエンコードが異なる"));
+            assert!(logs_contain("Shift_JIS"));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// RSSのpubDateやAtomのpublishedに書かれたタイムゾーンオフセットを分単位で保持することを確認する。
+        #[test]
+        fn 配信日時のタイムゾーンオフセットを保持する() -> Result<()> {
+            let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0">
+                  <channel>
+                    <title>Example Feed</title>
+                    <link>https://feed.example.com</link>
+                    <description>Sample</description>
+                    <item>
+                      <title>JST</title>
+                      <link>https://example.com/jst</link>
+                      <pubDate>Mon, 12 Oct 2026 18:00:00 +0900</pubDate>
+                    </item>
+                    <item>
+                      <title>GMT</title>
+                      <link>https://example.com/gmt</link>
+                      <pubDate>Mon, 12 Oct 2026 09:00:00 GMT</pubDate>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+            let entries = parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?
+            .entries;
+            assert_eq!(entries[0].pub_date_tz_offset, Some(540));
+            assert_eq!(entries[0].pub_date, entries[1].pub_date);
+            assert_eq!(entries[1].pub_date_tz_offset, Some(0));
+
+            let atom = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <feed xmlns="http://www.w3.org/2005/Atom">
+                  <title>Atom Feed</title>
+                  <id>urn:example</id>
+                  <updated>2026-10-12T04:00:00-05:00</updated>
+                  <entry>
+                    <title>EST</title>
+                    <id>urn:example:1</id>
+                    <link href="https://example.com/est"/>
+                    <published>2026-10-12T04:00:00-05:00</published>
+                    <updated>2026-10-12T04:00:00-05:00</updated>
+                  </entry>
+                </feed>
+            "#;
+            let entries = parse_feed_content(
+                atom.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?
+            .entries;
+            assert_eq!(entries[0].pub_date_tz_offset, Some(-300));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 日時として解釈できない値や別の日時の値からはオフセットを推定しないことを確認する。
+        #[test]
+        fn 解釈できない日時はオフセットなしとする() {
+            let pub_date = chrono::DateTime::parse_from_rfc3339("2026-10-12T09:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc);
+            assert_eq!(
+                find_pub_date_offset("<pubDate>yesterday</pubDate>", pub_date),
+                None
+            );
+            assert_eq!(
+                find_pub_date_offset(
+                    "<pubDate>Tue, 13 Oct 2026 18:00:00 +0900</pubDate>",
+                    pub_date
+                ),
+                None
+            );
+            assert_eq!(
+                find_pub_date_offset("<dc:date>2026-10-12T18:00:00+09:00</dc:date>", pub_date),
+                Some(540)
+            );
+        }
+
+        /// # 検証目的
+        /// 既定ではpublishedを、`PubDatePreference::Updated`ではupdatedをpub_date（とそのオフセット）に使い、
+        /// 優先した側が無いentryはもう一方にフォールバックすることを確認する。
+        #[test]
+        fn 設定でupdatedを優先する() -> Result<()> {
+            let atom = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <feed xmlns="http://www.w3.org/2005/Atom">
+                  <title>Atom</title>
+                  <id>urn:example</id>
+                  <updated>2026-10-12T09:00:00+09:00</updated>
+                  <entry>
+                    <title>Both</title>
+                    <id>urn:example:1</id>
+                    <link href="https://example.com/both"/>
+                    <published>2026-10-01T00:00:00Z</published>
+                    <updated>2026-10-12T09:00:00+09:00</updated>
+                  </entry>
+                  <entry>
+                    <title>PublishedOnly</title>
+                    <id>urn:example:2</id>
+                    <link href="https://example.com/published-only"/>
+                    <published>2026-10-02T00:00:00Z</published>
+                  </entry>
+                </feed>
+            "#;
+            let utc = |value: &str| {
+                chrono::DateTime::parse_from_rfc3339(value)
+                    .unwrap()
+                    .with_timezone(&chrono::Utc)
+            };
+
+            let entries = parse_feed_content(
+                atom.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?
+            .entries;
+            assert_eq!(entries[0].pub_date, Some(utc("2026-10-01T00:00:00Z")));
+            assert_eq!(entries[0].pub_date_tz_offset, Some(0));
+
+            let entries = parse_feed_content(
+                atom.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                "updated".parse()?,
+            )?
+            .entries;
+            assert_eq!(entries[0].pub_date, Some(utc("2026-10-12T00:00:00Z")));
+            assert_eq!(entries[0].pub_date_tz_offset, Some(540));
+            assert_eq!(entries[1].pub_date, Some(utc("2026-10-02T00:00:00Z")));
+
+            assert!("modified".parse::<PubDatePreference>().is_err());
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 一部のitemだけが壊れたフィードでも、解析できたitemを取り込み壊れた件数を数えることを確認する。
+        #[test]
+        fn 部分破損フィードから取れる分を取り込む() -> Result<()> {
+            let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+                  <channel>
+                    <title>Broken Feed</title>
+                    <item>
+                      <title>Good One</title>
+                      <link>https://example.com/good-one</link>
+                      <dc:creator>writer</dc:creator>
+                    </item>
+                    <item>
+                      <title>Broken</titl>
+                      <link>https://example.com/broken</link>
+                    </item>
+                    <item>
+                      <title>Good Two</title>
+                      <link>https://example.com/good-two</link>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+
+            let parsed = parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            let links: Vec<&str> = parsed.entries.iter().map(|e| e.link.as_str()).collect();
+            assert_eq!(
+                links,
+                vec![
+                    "https://example.com/good-one",
+                    "https://example.com/good-two"
+                ]
+            );
+            assert_eq!(parsed.malformed_entries, 1);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 1件も解析できない完全に壊れたフィードは従来どおりエラーになることを確認する。
+        #[test]
+        fn 完全に壊れたフィードはエラー() {
+            let result = parse_feed_content(
+                b"<html><body>not a feed</body></html>",
+                None,
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            );
+            assert!(result.is_err());
+        }
+    }
+
+    pub mod truncate_queue_text {
+        use crate::fetch_rss::truncate_queue_text;
+
+        /// # 検証目的
+        /// 上限を超える文字列を`…`込みで上限文字数に切り詰め、マルチバイト文字の途中で切らないことを確認する。
+        #[test]
+        fn 上限文字数に切り詰める() {
+            assert_eq!(
+                truncate_queue_text("日本語のタイトル".to_string(), 5),
+                "日本語の…"
+            );
+            assert_eq!(truncate_queue_text("🦀🦀🦀".to_string(), 2), "🦀…");
+            assert_eq!(truncate_queue_text("ちょうど".to_string(), 4), "ちょうど");
+            assert_eq!(truncate_queue_text("short".to_string(), 512), "short");
+            assert_eq!(truncate_queue_text("abc".to_string(), 1), "…");
+        }
+    }
+
+    pub mod detect_entry_count_drop {
+        use crate::fetch_rss::detect_entry_count_drop;
+
+        /// # 検証目的
+        /// entry数が過去の平均の30%を下回ったときだけ急減と判定し、過去の取得回数や平均件数が少ないフィードは判定しないことを確認する。
+        #[test]
+        fn 平均から急減したときだけ検知する() {
+            let drop = detect_entry_count_drop(&[100, 100, 100], 2).expect("急減を検知する");
+            assert_eq!(drop.entry_count, 2);
+            assert_eq!(drop.average, 100.0);
+            assert_eq!(drop.samples, 3);
+
+            // 平均の30%ちょうどは急減とみなさない
+            assert!(detect_entry_count_drop(&[100, 100, 100], 30).is_none());
+            // 過去の取得回数が足りない新規フィード
+            assert!(detect_entry_count_drop(&[100, 100], 2).is_none());
+            // 普段から件数の少ないフィード
+            assert!(detect_entry_count_drop(&[5, 5, 5], 0).is_none());
+        }
+    }
+
+    pub mod user_agent_rotator {
+        use crate::fetch_rss::UserAgentRotator;
+
+        /// # 検証目的
+        /// 複数のUAは順番に繰り返し返し、1つなら固定、空ならNoneになることを確認する。
+        #[test]
+        fn ラウンドロビンでuser_agentを返す() {
+            let user_agents = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+            let rotator = UserAgentRotator::new(&user_agents);
+            let picked: Vec<Option<&str>> = (0..4).map(|_| rotator.next()).collect();
+            assert_eq!(picked, [Some("a"), Some("b"), Some("c"), Some("a")]);
+
+            let single = vec!["only".to_string()];
+            let rotator = UserAgentRotator::new(&single);
+            assert_eq!(rotator.next(), Some("only"));
+            assert_eq!(rotator.next(), Some("only"));
+
+            let rotator = UserAgentRotator::new(&[]);
+            assert_eq!(rotator.next(), None);
+        }
     }
 
     pub mod upsert_queue_entries {
         use anyhow::Result;
         use chrono::Utc;
 
-        use crate::fetch_rss::upsert_queue_entries;
-        use crate::models::NewQueue;
+        use crate::fetch_rss::{
+            parse_feed_content, upsert_queue_entries, PubDatePreference, QueueUpsertCounts,
+        };
+        use crate::models::{FeedFormat, NewQueue};
         use crate::test_support::{clear_rss_tables, prepare_test_pool};
 
         /// # 検証目的
@@ -399,20 +2175,38 @@ mod tests {
                     link: "https://example.com/item1".to_string(),
                     title: "Item1".to_string(),
                     pub_date: Some(Utc::now()),
+                    pub_date_tz_offset: None,
                     description: "本文1".to_string(),
+                    content_body: None,
+                    language: None,
                     group: None,
+                    scrape_referer: None,
+                    scrape_accept_language: None,
+                    priority: 0,
                 },
                 NewQueue {
                     link: "https://example.com/item2".to_string(),
                     title: "Item2".to_string(),
                     pub_date: None,
+                    pub_date_tz_offset: None,
                     description: "本文2".to_string(),
+                    content_body: None,
+                    language: None,
                     group: None,
+                    scrape_referer: None,
+                    scrape_accept_language: None,
+                    priority: 0,
                 },
             ];
 
-            let inserted = upsert_queue_entries(&pool, entries, Some("world".to_string())).await?;
-            assert_eq!(inserted, 2);
+            let counts = upsert_queue_entries(&pool, entries, Some("world".to_string())).await?;
+            assert_eq!(
+                counts,
+                QueueUpsertCounts {
+                    inserted: 2,
+                    updated: 0
+                }
+            );
 
             let records: Vec<(String, Option<String>)> =
                 sqlx::query_as::<_, (String, Option<String>)>(
@@ -430,7 +2224,7 @@ mod tests {
         }
 
         /// # 検証目的
-        /// 既存リンクに対するUPSERTでタイトルとグループが更新されることを確認する。
+        /// 既存リンクに対するUPSERTでタイトルとグループが更新され、更新として数えられることを確認する。
         #[tokio::test]
         async fn 重複リンクを更新できる() -> Result<()> {
             let _lock = crate::test_support::acquire_db_lock().await;
@@ -443,8 +2237,14 @@ mod tests {
                 link: "https://example.com/item".to_string(),
                 title: "Old Title".to_string(),
                 pub_date: None,
+                pub_date_tz_offset: None,
                 description: "Old Desc".to_string(),
+                content_body: None,
+                language: None,
                 group: None,
+                scrape_referer: None,
+                scrape_accept_language: None,
+                priority: 0,
             }];
 
             upsert_queue_entries(&pool, initial, Some("initial".to_string())).await?;
@@ -453,11 +2253,24 @@ mod tests {
                 link: "https://example.com/item".to_string(),
                 title: "New Title".to_string(),
                 pub_date: None,
+                pub_date_tz_offset: None,
                 description: "New Desc".to_string(),
+                content_body: None,
+                language: None,
                 group: Some("entry".to_string()),
+                scrape_referer: None,
+                scrape_accept_language: None,
+                priority: 0,
             }];
 
-            upsert_queue_entries(&pool, updated, None).await?;
+            let counts = upsert_queue_entries(&pool, updated, None).await?;
+            assert_eq!(
+                counts,
+                QueueUpsertCounts {
+                    inserted: 0,
+                    updated: 1
+                }
+            );
 
             let row: (String, String) = sqlx::query_as::<_, (String, String)>(
                 "SELECT title, description FROM rss.queue WHERE link = $1",
@@ -477,16 +2290,120 @@ mod tests {
 
             Ok(())
         }
+
+        /// # 検証目的
+        /// 配信日時のタイムゾーンオフセットがrss.queueへ保存されることを確認する。
+        #[tokio::test]
+        async fn 配信日時のオフセットを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let entries = vec![NewQueue {
+                link: "https://example.com/jst".to_string(),
+                title: "JST".to_string(),
+                pub_date: Some(Utc::now()),
+                pub_date_tz_offset: Some(540),
+                description: "本文".to_string(),
+                content_body: None,
+                language: None,
+                group: None,
+                scrape_referer: None,
+                scrape_accept_language: None,
+                priority: 0,
+            }];
+
+            upsert_queue_entries(&pool, entries, Some("news".to_string())).await?;
+
+            let offset: Option<i32> =
+                sqlx::query_scalar("SELECT pub_date_tz_offset FROM rss.queue WHERE link = $1")
+                    .bind("https://example.com/jst")
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(offset, Some(540));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// summaryとcontentの両方を持つentryでは、descriptionにsummary、`content`にcontent本文が
+        /// それぞれ保存され、contentの無いentryは`content`がNULLになることを確認する。
+        #[tokio::test]
+        async fn summaryとcontentを別々に保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+                <rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+                  <channel>
+                    <title>Example Feed</title>
+                    <link>https://feed.example.com</link>
+                    <description>Sample</description>
+                    <item>
+                      <title>Both</title>
+                      <link>https://example.com/both</link>
+                      <description>概要</description>
+                      <content:encoded><![CDATA[<p>全文</p>]]></content:encoded>
+                    </item>
+                    <item>
+                      <title>Summary only</title>
+                      <link>https://example.com/summary-only</link>
+                      <description>概要のみ</description>
+                    </item>
+                  </channel>
+                </rss>
+            "#;
+            let parsed = parse_feed_content(
+                rss.as_bytes(),
+                Some("news"),
+                FeedFormat::Feed,
+                PubDatePreference::default(),
+            )?;
+            upsert_queue_entries(&pool, parsed.entries, Some("news".to_string())).await?;
+
+            let rows: Vec<(String, String, Option<String>)> =
+                sqlx::query_as("SELECT link, description, content FROM rss.queue ORDER BY link")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(
+                rows,
+                vec![
+                    (
+                        "https://example.com/both".to_string(),
+                        "概要".to_string(),
+                        Some("<p>全文</p>".to_string()),
+                    ),
+                    (
+                        "https://example.com/summary-only".to_string(),
+                        "概要のみ".to_string(),
+                        None,
+                    ),
+                ]
+            );
+
+            Ok(())
+        }
     }
 
     pub mod execute_fetch_rss_tests {
         use anyhow::Result;
+        use std::sync::Arc;
         use std::time::{Duration, Instant};
         use wiremock::matchers::{method, path};
         use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        use crate::fetch_rss::execute_fetch_rss;
-        use crate::test_support::{clear_rss_tables, create_temp_yaml, prepare_test_pool};
+        use crate::fetch_rss::{
+            execute_fetch_rss, FeedDormancy, FetchRssOptions, DEFAULT_DESCRIPTION_MAX_CHARS,
+            DEFAULT_TITLE_MAX_CHARS, FEED_TIMEOUT_ERROR, MAX_CONCURRENT_FEED_REQUESTS,
+        };
+        use crate::test_support::{
+            clear_rss_tables, create_temp_yaml, prepare_test_pool, RecordingSink,
+        };
 
         /// # 検証目的
         /// フィード取得が失敗した場合にサマリへエラーが記録されることを確認する。
@@ -509,8 +2426,12 @@ mod tests {
             let temp_file =
                 create_temp_yaml(&format!("test:\n  failure: {url}/feed", url = server.uri()))?;
 
-            let summary =
-                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref()).await?;
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
 
             assert_eq!(summary.total_processed, 0);
             assert_eq!(summary.feeds.len(), 1);
@@ -559,8 +2480,12 @@ mod tests {
             ))?;
 
             let started = Instant::now();
-            let summary =
-                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref()).await?;
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
             let elapsed = started.elapsed();
 
             assert_eq!(summary.total_processed, 2);
@@ -573,22 +2498,1247 @@ mod tests {
             Ok(())
         }
 
-        fn simple_rss_entry(link: &str) -> String {
-            format!(
-                r#"<?xml version="1.0" encoding="UTF-8"?>
+        /// # 検証目的
+        /// queueに無いentryは`inserted`、既にあるentryは`updated`として、フィードごとに分けて数えられることを確認する。
+        #[tokio::test]
+        async fn 新規追加と更新を分けて数える() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            sqlx::query(
+                "INSERT INTO rss.queue (id, link, title, description) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(uuid::Uuid::new_v4())
+            .bind("https://example.com/existing")
+            .bind("既存")
+            .bind("説明")
+            .execute(&pool)
+            .await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/feed"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
 <rss version="2.0">
   <channel>
-    <title>Mock Feed</title>
-    <item>
-      <title>Title</title>
-      <link>{link}</link>
-      <description>desc</description>
-      <pubDate>Mon, 13 Oct 2025 12:00:00 GMT</pubDate>
-    </item>
+    <title>Counts</title>
+    <item><title>New</title><link>https://example.com/new</link></item>
+    <item><title>Existing</title><link>https://example.com/existing</link></item>
   </channel>
-</rss>
-"#
+</rss>"#,
+                ))
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!("counts:\n  feed: {}/feed\n", server.uri()))?;
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
             )
+            .await?;
+
+            let feed = &summary.feeds[0];
+            assert_eq!(feed.error, None);
+            assert_eq!(feed.processed, 2);
+            assert_eq!(feed.inserted, 1);
+            assert_eq!(feed.updated, 1);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// フィードごとのHTTP応答時間がelapsed_msに記録され、遅いフィードほど大きな値になることを確認する。
+        #[tokio::test]
+        async fn 応答時間を記録する() -> Result<()> {
+            use crate::fetch_rss::slowest_feeds;
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/slow"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/slow"))
+                        .set_delay(Duration::from_millis(300)),
+                )
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/fast"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/fast")),
+                )
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "timing:\n  slow: {url}/slow\n  fast: {url}/fast\n",
+                url = server.uri()
+            ))?;
+
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
+
+            let elapsed_of = |name: &str| {
+                summary
+                    .feeds
+                    .iter()
+                    .find(|feed| feed.name == name)
+                    .map(|feed| feed.elapsed_ms)
+                    .expect("フィードの結果が無い")
+            };
+            let slow = elapsed_of("slow");
+            let fast = elapsed_of("fast");
+            assert!((300..15_000).contains(&slow), "slow: {}ms", slow);
+            assert!(fast < slow, "fast: {}ms, slow: {}ms", fast, slow);
+
+            let slowest = slowest_feeds(&summary.feeds, 1);
+            assert_eq!(slowest.len(), 1);
+            assert_eq!(slowest[0].name, "slow");
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// --retry-failed-feeds指定時、1回目に失敗したフィードを再取得し、成功分がprocessedに計上されることを確認する。
+        #[tokio::test]
+        async fn 失敗フィードを最後に再取得する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(ResponseTemplate::new(500).set_body_string("error"))
+                .up_to_n_times(1)
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/flaky"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/flaky")),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/broken"))
+                .respond_with(ResponseTemplate::new(500).set_body_string("error"))
+                .expect(2)
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "retry:\n  flaky: {url}/flaky\n  broken: {url}/broken\n",
+                url = server.uri()
+            ))?;
+
+            let options = FetchRssOptions {
+                retry_failed_feeds: true,
+                ..FetchRssOptions::default()
+            };
+            let summary =
+                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref(), &options)
+                    .await?;
+
+            assert_eq!(summary.total_processed, 1);
+            let flaky = summary
+                .feeds
+                .iter()
+                .find(|feed| feed.name == "flaky")
+                .expect("flakyの結果がない");
+            assert_eq!(flaky.processed, 1);
+            assert!(flaky.error.is_none());
+
+            let broken = summary
+                .feeds
+                .iter()
+                .find(|feed| feed.name == "broken")
+                .expect("brokenの結果がない");
+            assert_eq!(broken.processed, 0);
+            assert!(
+                broken.error.is_some(),
+                "再取得でも失敗したエラーが残っていない"
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// sinkを指定すると、queueへ新規追加した記事だけがフィードごとにpublishされ、
+        /// 既に取り込み済みで更新になった記事は再度publishされないことを確認する。
+        #[tokio::test]
+        async fn 新規記事をsinkへpublishする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/feed"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/published")),
+                )
+                .expect(2)
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!("tech:\n  sink: {}/feed\n", server.uri()))?;
+            let sink = Arc::new(RecordingSink::default());
+            let options = FetchRssOptions {
+                sink: Some(sink.clone()),
+                ..FetchRssOptions::default()
+            };
+            let rss_links_path = temp_file.path().to_string_lossy().to_string();
+
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert_eq!(summary.feeds[0].inserted, 1);
+
+            let published = sink.published();
+            assert_eq!(published.len(), 1);
+            let article = &published[0][0];
+            assert_eq!(article.link, "https://example.com/published");
+            assert_eq!(article.title, "Title");
+            assert_eq!(article.group.as_deref(), Some("tech"));
+            let stored_id: uuid::Uuid =
+                sqlx::query_scalar("SELECT id FROM rss.queue WHERE link = $1")
+                    .bind(&article.link)
+                    .fetch_one(&pool)
+                    .await?;
+            assert_eq!(article.id, stored_id);
+
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert_eq!(summary.feeds[0].updated, 1);
+            assert_eq!(sink.published().len(), 1, "更新だけの記事がpublishされた");
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 同時実行数を超える数のフィードがハングしても、フィード単位のタイムアウトで打ち切られて枠が空き、
+        /// 正常なフィードの取り込みまで含めて全体が短時間で終わることを確認する。
+        #[tokio::test]
+        async fn ハングしたフィードをタイムアウトで打ち切る() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/hang"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/hang"))
+                        .set_delay(Duration::from_secs(10)),
+                )
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/ok"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/ok")),
+                )
+                .mount(&server)
+                .await;
+
+            // 同時実行数の上限（8）を超える数のハングフィードを並べ、枠が解放されないと正常なフィードが待たされる状況にする
+            let mut yaml = String::from("timeout:\n");
+            for index in 0..MAX_CONCURRENT_FEED_REQUESTS + 1 {
+                yaml.push_str(&format!(
+                    "  hang{}: {}/hang?n={}\n",
+                    index,
+                    server.uri(),
+                    index
+                ));
+            }
+            yaml.push_str(&format!("  ok: {}/ok\n", server.uri()));
+            let temp_file = create_temp_yaml(&yaml)?;
+
+            let options = FetchRssOptions {
+                feed_timeout: Duration::from_millis(500),
+                ..FetchRssOptions::default()
+            };
+            let started = Instant::now();
+            let summary =
+                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref(), &options)
+                    .await?;
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "ハングしたフィードに全体が止められた: {:?}",
+                started.elapsed()
+            );
+
+            assert_eq!(summary.total_processed, 1);
+            for feed in &summary.feeds {
+                if feed.name == "ok" {
+                    assert_eq!(feed.processed, 1);
+                    assert!(feed.error.is_none(), "{:?}", feed.error);
+                } else {
+                    assert_eq!(feed.processed, 0);
+                    assert_eq!(feed.error.as_deref(), Some(FEED_TIMEOUT_ERROR));
+                    assert_eq!(feed.elapsed_ms, 500);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// フィードのimage（logo）がfeed_metaのicon_urlとして保存され、
+        /// アイコンの無いフィードは--guess-favicon指定時のみ/favicon.icoが推定値として入ることを確認する。
+        #[tokio::test]
+        async fn フィードのアイコンを保存する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/with-icon"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Icon Feed</title>
+    <link>https://example.com/</link>
+    <description>desc</description>
+    <image>
+      <url>https://example.com/logo.png</url>
+      <title>Icon Feed</title>
+      <link>https://example.com/</link>
+    </image>
+    <item>
+      <title>Item</title>
+      <link>https://example.com/icon-item</link>
+      <description>desc</description>
+    </item>
+  </channel>
+</rss>"#,
+                ))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/without-icon"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/no-icon")),
+                )
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "icons:\n  with_icon: {url}/with-icon\n  without_icon: {url}/without-icon\n",
+                url = server.uri()
+            ))?;
+            let path_str = temp_file.path().to_string_lossy().to_string();
+
+            execute_fetch_rss(&pool, &path_str, &FetchRssOptions::default()).await?;
+
+            let icons: Vec<(String, Option<String>)> =
+                sqlx::query_as("SELECT name, icon_url FROM rss.feed_meta ORDER BY name")
+                    .fetch_all(&pool)
+                    .await?;
+            assert_eq!(
+                icons,
+                vec![
+                    (
+                        "with_icon".to_string(),
+                        Some("https://example.com/logo.png".to_string())
+                    ),
+                    ("without_icon".to_string(), None),
+                ]
+            );
+
+            let options = FetchRssOptions {
+                guess_favicon: true,
+                ..FetchRssOptions::default()
+            };
+            execute_fetch_rss(&pool, &path_str, &options).await?;
+
+            let guessed: Option<String> = sqlx::query_scalar(
+                "SELECT icon_url FROM rss.feed_meta WHERE name = 'without_icon'",
+            )
+            .fetch_one(&pool)
+            .await?;
+            assert_eq!(guessed, Some(format!("{}/favicon.ico", server.uri())));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 同一フィード内で同じlinkのentryが重複していても1回だけupsertされ、最初の出現が残ることを確認する。
+        #[tokio::test]
+        async fn フィード内の重複linkは1回だけ登録する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/dup"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Dup</title>
+    <item><title>最初</title><link>https://example.com/dup</link></item>
+    <item><title>別記事</title><link>https://example.com/other</link></item>
+    <item><title>重複</title><link>https://example.com/dup</link></item>
+  </channel>
+</rss>"#,
+                ))
+                .mount(&server)
+                .await;
+
+            let temp_file =
+                create_temp_yaml(&format!("dup:\n  feed: {url}/dup\n", url = server.uri()))?;
+
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
+
+            assert_eq!(summary.total_processed, 2, "重複分もupsertされている");
+
+            let title: String = sqlx::query_scalar(
+                "SELECT title FROM rss.queue WHERE link = 'https://example.com/dup'",
+            )
+            .fetch_one(&pool)
+            .await?;
+            assert_eq!(title, "最初");
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 取得ごとにentry数が`feed_cache`へ記録され、直近の平均から急減した取得だけ`entry_count_drop`が付くことを確認する。
+        #[tokio::test]
+        async fn entry数の急減を検知する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let feed_body = |count: usize| {
+                let items: String = (0..count)
+                    .map(|index| {
+                        format!(
+                            "<item><title>記事{index}</title><link>https://example.com/drop/{index}</link></item>"
+                        )
+                    })
+                    .collect();
+                format!(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"><channel><title>Drop</title>{items}</channel></rss>"#
+                )
+            };
+            let mount_feed = |body: String| {
+                Mock::given(method("GET"))
+                    .and(path("/drop"))
+                    .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            };
+
+            let temp_file =
+                create_temp_yaml(&format!("drop:\n  feed: {url}/drop\n", url = server.uri()))?;
+            let rss_links_path = temp_file.path().to_string_lossy().to_string();
+
+            mount_feed(feed_body(20)).mount(&server).await;
+            for _ in 0..3 {
+                let summary =
+                    execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+                assert!(summary.feeds[0].entry_count_drop.is_none());
+            }
+
+            server.reset().await;
+            mount_feed(feed_body(2)).mount(&server).await;
+            let summary =
+                execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+            let drop = summary.feeds[0]
+                .entry_count_drop
+                .as_ref()
+                .expect("急減を検知する");
+            assert_eq!(drop.entry_count, 2);
+            assert_eq!(drop.average, 20.0);
+            assert_eq!(drop.samples, 3);
+
+            let entry_counts: Vec<i32> = sqlx::query_scalar(
+                r#"SELECT entry_counts FROM rss.feed_cache WHERE "group" = 'drop' AND name = 'feed'"#,
+            )
+            .fetch_one(&pool)
+            .await?;
+            assert_eq!(entry_counts, vec![20, 20, 20, 2]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// lastBuildDateが前回処理時と同じフィードはentryの取り込みを省略し、変わったら再び取り込むことを確認する。
+        #[tokio::test]
+        async fn last_build_dateが変わらないフィードはスキップする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            let feed_body = |last_build_date: &str| {
+                format!(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Cache</title>
+    <lastBuildDate>{}</lastBuildDate>
+    <item><title>記事</title><link>https://example.com/cache/1</link></item>
+  </channel>
+</rss>"#,
+                    last_build_date
+                )
+            };
+            let mount_feed = |body: String| {
+                Mock::given(method("GET"))
+                    .and(path("/cache"))
+                    .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            };
+
+            mount_feed(feed_body("Mon, 12 Oct 2026 09:00:00 GMT"))
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "cache:\n  feed: {url}/cache\n",
+                url = server.uri()
+            ))?;
+            let rss_links_path = temp_file.path().to_string_lossy().to_string();
+            let queue_count = || async {
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM rss.queue")
+                    .fetch_one(&pool)
+                    .await
+            };
+
+            let summary =
+                execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+            assert_eq!(summary.total_processed, 1);
+            assert!(!summary.feeds[0].unchanged);
+
+            // 取り込み済みのentryを消しておき、スキップされれば再登録されないことで確認する
+            sqlx::query("TRUNCATE rss.queue CASCADE")
+                .execute(&pool)
+                .await?;
+
+            let summary =
+                execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+            assert_eq!(summary.total_processed, 0);
+            assert!(summary.feeds[0].unchanged);
+            assert!(summary.feeds[0].error.is_none());
+            assert_eq!(queue_count().await?, 0);
+
+            server.reset().await;
+            mount_feed(feed_body("Tue, 13 Oct 2026 09:00:00 GMT"))
+                .mount(&server)
+                .await;
+
+            let summary =
+                execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+            assert_eq!(summary.total_processed, 1);
+            assert!(!summary.feeds[0].unchanged);
+            assert_eq!(queue_count().await?, 1);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 一定期間新規entryの無い休眠フィードは前回の取得から`interval`が経つまで取得されず、
+        /// 間隔が空けば再び取得され（新規が無ければ休眠のまま）、新規entryのあるフィードは毎回取得されることを確認する。
+        #[tokio::test]
+        async fn 休眠フィードは取得間隔を延ばす() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/dormant"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"><channel><title>Dormant</title>
+<item><title>記事</title><link>https://example.com/dormant/1</link></item>
+</channel></rss>"#,
+                ))
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "dormant:\n  feed: {url}/dormant\n",
+                url = server.uri()
+            ))?;
+            let rss_links_path = temp_file.path().to_string_lossy().to_string();
+            let options = FetchRssOptions {
+                dormancy: Some(FeedDormancy {
+                    after: Duration::from_secs(30 * 86_400),
+                    interval: Duration::from_secs(24 * 3_600),
+                }),
+                ..FetchRssOptions::default()
+            };
+            let request_count = || async { server.received_requests().await.unwrap().len() };
+            let set_activity = |entry_new_days_ago: i32, fetched_hours_ago: i32| {
+                let pool = pool.clone();
+                async move {
+                    sqlx::query(
+                        r#"
+                        UPDATE rss.feed_cache
+                        SET last_entry_new_at = NOW() - make_interval(days => $1),
+                            last_fetched_at = NOW() - make_interval(hours => $2)
+                        WHERE "group" = 'dormant' AND name = 'feed'
+                        "#,
+                    )
+                    .bind(entry_new_days_ago)
+                    .bind(fetched_hours_ago)
+                    .execute(&pool)
+                    .await
+                }
+            };
+
+            // 初回は新規entryがあるため取得状況を記録するだけ
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert!(!summary.feeds[0].dormant);
+            assert_eq!(summary.feeds[0].inserted, 1);
+            assert_eq!(request_count().await, 1);
+
+            // 直近に新規entryがあれば、前回の取得直後でも取得する
+            set_activity(1, 1).await?;
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert!(!summary.feeds[0].dormant);
+            assert_eq!(request_count().await, 2);
+
+            // 60日新規entryが無く、前回の取得から1時間しか経っていなければ取得しない
+            set_activity(60, 1).await?;
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert!(summary.feeds[0].dormant);
+            assert!(summary.feeds[0].error.is_none());
+            assert_eq!(request_count().await, 2);
+
+            // 休眠判定を無効にすれば毎回取得する
+            let summary =
+                execute_fetch_rss(&pool, &rss_links_path, &FetchRssOptions::default()).await?;
+            assert!(!summary.feeds[0].dormant);
+            assert_eq!(request_count().await, 3);
+
+            // 間隔が空けば取得し、新規entryが無いので休眠のまま次の取得は再び見送る
+            set_activity(60, 48).await?;
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert!(!summary.feeds[0].dormant);
+            assert_eq!(summary.feeds[0].inserted, 0);
+            assert_eq!(request_count().await, 4);
+            let summary = execute_fetch_rss(&pool, &rss_links_path, &options).await?;
+            assert!(summary.feeds[0].dormant);
+            assert_eq!(request_count().await, 4);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// dry_run指定時はフィードを解析して取り込み予定件数を返しつつ、queue・feed_metaが一切変化しないことを確認する。
+        #[tokio::test]
+        async fn ドライランではdbを変更しない() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            Mock::given(method("GET"))
+                .and(path("/dry"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Dry</title>
+    <item><title>一件目</title><link>https://example.com/dry/1</link></item>
+    <item><title>二件目</title><link>https://example.com/dry/2</link></item>
+  </channel>
+</rss>"#,
+                ))
+                .mount(&server)
+                .await;
+
+            let temp_file =
+                create_temp_yaml(&format!("dry:\n  feed: {url}/dry\n", url = server.uri()))?;
+            let options = FetchRssOptions {
+                dry_run: true,
+                ..FetchRssOptions::default()
+            };
+
+            let summary =
+                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref(), &options)
+                    .await?;
+            assert_eq!(summary.total_processed, 2);
+            assert!(summary.feeds.iter().all(|feed| feed.error.is_none()));
+
+            let queue_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.queue")
+                .fetch_one(&pool)
+                .await?;
+            let meta_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rss.feed_meta")
+                .fetch_one(&pool)
+                .await?;
+            assert_eq!((queue_count, meta_count), (0, 0));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 長大なtitle/descriptionが上限文字数（末尾の`…`を含む）に切り詰められて保存されることを確認する。
+        #[tokio::test]
+        async fn 長すぎるテキストは上限内に切り詰める() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+
+            let long_title = "長".repeat(1000);
+            let long_description = "あいう".repeat(5000);
+            Mock::given(method("GET"))
+                .and(path("/long"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Long</title>
+    <item>
+      <title>{}</title>
+      <link>https://example.com/long</link>
+      <description>{}</description>
+    </item>
+  </channel>
+</rss>"#,
+                    long_title, long_description
+                )))
+                .mount(&server)
+                .await;
+
+            let temp_file = create_temp_yaml(&format!(
+                "long:
+  feed: {url}/long
+",
+                url = server.uri()
+            ))?;
+
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
+            assert_eq!(summary.total_processed, 1);
+
+            let (title, description): (String, String) = sqlx::query_as(
+                "SELECT title, description FROM rss.queue WHERE link = 'https://example.com/long'",
+            )
+            .fetch_one(&pool)
+            .await?;
+            assert_eq!(title.chars().count(), DEFAULT_TITLE_MAX_CHARS);
+            assert!(title.ends_with("長…"));
+            assert_eq!(description.chars().count(), DEFAULT_DESCRIPTION_MAX_CHARS);
+            assert!(description.ends_with('…'));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `_priority`の高いグループのフィードが、低いグループのフィードより先にすべて取得されることを確認する。
+        #[tokio::test]
+        async fn 優先度の高いグループから取得する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            for route in ["/low-a", "/low-b", "/mid", "/high-a", "/high-b"] {
+                Mock::given(method("GET"))
+                    .and(path(route))
+                    .respond_with(ResponseTemplate::new(200).set_body_string(simple_rss_entry(
+                        &format!("https://example.com{}", route),
+                    )))
+                    .mount(&server)
+                    .await;
+            }
+
+            let temp_file = create_temp_yaml(&format!(
+                r#"
+archive:
+  _priority: -1
+  a: {url}/low-a
+  b: {url}/low-b
+normal:
+  mid: {url}/mid
+breaking:
+  _priority: 10
+  a: {url}/high-a
+  b: {url}/high-b
+"#,
+                url = server.uri()
+            ))?;
+
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await?;
+            assert_eq!(summary.total_processed, 5);
+
+            let requested: Vec<String> = server
+                .received_requests()
+                .await
+                .expect("リクエストの記録が有効")
+                .iter()
+                .map(|request| request.url.path().to_string())
+                .collect();
+            let mut high: Vec<&str> = requested[..2].iter().map(String::as_str).collect();
+            high.sort();
+            assert_eq!(high, ["/high-a", "/high-b"]);
+            assert_eq!(requested[2], "/mid");
+            let mut low: Vec<&str> = requested[3..].iter().map(String::as_str).collect();
+            low.sort();
+            assert_eq!(low, ["/low-a", "/low-b"]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `user_agents`を複数指定すると、フィードごとにUser-Agentが順番に使われることを確認する。
+        #[tokio::test]
+        async fn フィードごとにuser_agentを切り替える() -> Result<()> {
+            use wiremock::matchers::header;
+
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            // フィードの読み込み順は不定のため、各UAが同じ回数ずつ使われることで順番の割り当てを確認する
+            for user_agent in ["agent-a/1.0", "agent-b/1.0"] {
+                Mock::given(method("GET"))
+                    .and(header("user-agent", user_agent))
+                    .respond_with(
+                        ResponseTemplate::new(200)
+                            .set_body_string(simple_rss_entry("https://example.com/ua")),
+                    )
+                    .expect(2)
+                    .mount(&server)
+                    .await;
+            }
+
+            let temp_file = create_temp_yaml(&format!(
+                "ua:\n  one: {url}/one\n  two: {url}/two\n  three: {url}/three\n  four: {url}/four\n",
+                url = server.uri()
+            ))?;
+
+            let options = FetchRssOptions {
+                user_agents: vec!["agent-a/1.0".to_string(), "agent-b/1.0".to_string()],
+                ..FetchRssOptions::default()
+            };
+            let summary =
+                execute_fetch_rss(&pool, temp_file.path().to_string_lossy().as_ref(), &options)
+                    .await?;
+            assert!(summary.feeds.iter().all(|feed| feed.error.is_none()));
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 他のインスタンス（別セッション）がadvisory lockを保持しているフィードは取得せずに`locked`として返し、
+        /// 取得したフィードのロックは実行後に解放されることを確認する。
+        #[tokio::test]
+        async fn ロック中のフィードはスキップする() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/busy"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/busy")),
+                )
+                .expect(0)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/free"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string(simple_rss_entry("https://example.com/free")),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let busy_url = format!("{}/busy", server.uri());
+            let free_url = format!("{}/free", server.uri());
+            let temp_file = create_temp_yaml(&format!(
+                "lock:\n  busy: {}\n  free: {}\n",
+                busy_url, free_url
+            ))?;
+
+            // 別インスタンスの代わりに、別の接続でbusyのロックを保持しておく
+            let mut other = pool.acquire().await?;
+            let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext($1))")
+                .bind(&busy_url)
+                .fetch_one(&mut *other)
+                .await?;
+            assert!(locked);
+
+            let summary = execute_fetch_rss(
+                &pool,
+                temp_file.path().to_string_lossy().as_ref(),
+                &FetchRssOptions::default(),
+            )
+            .await;
+            sqlx::query("SELECT pg_advisory_unlock_all()")
+                .execute(&mut *other)
+                .await?;
+            let summary = summary?;
+
+            assert_eq!(summary.total_processed, 1);
+            let busy = summary
+                .feeds
+                .iter()
+                .find(|feed| feed.name == "busy")
+                .unwrap();
+            assert!(busy.locked);
+            assert!(busy.error.is_none());
+            assert_eq!(busy.processed, 0);
+            let free = summary
+                .feeds
+                .iter()
+                .find(|feed| feed.name == "free")
+                .unwrap();
+            assert!(!free.locked);
+            assert_eq!(free.processed, 1);
+
+            // 取得し終えたフィードのロックは残っていない
+            let released: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext($1))")
+                .bind(&free_url)
+                .fetch_one(&mut *other)
+                .await?;
+            assert!(released);
+            sqlx::query("SELECT pg_advisory_unlock_all()")
+                .execute(&mut *other)
+                .await?;
+
+            Ok(())
+        }
+
+        fn simple_rss_entry(link: &str) -> String {
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Mock Feed</title>
+    <item>
+      <title>Title</title>
+      <link>{link}</link>
+      <description>desc</description>
+      <pubDate>Mon, 13 Oct 2025 12:00:00 GMT</pubDate>
+    </item>
+  </channel>
+</rss>
+"#
+            )
+        }
+    }
+
+    pub mod fetch_and_parse_feed_retry {
+        use std::time::Duration;
+
+        use anyhow::Result;
+        use reqwest::Client;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        use crate::fetch_rss::{fetch_and_parse_feed, FeedRetryPolicy, PubDatePreference};
+        use crate::models::{FeedFormat, FeedRetryOverride};
+
+        const FEED_BODY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Retry</title>
+    <item>
+      <title>Title</title>
+      <link>https://example.com/retry</link>
+    </item>
+  </channel>
+</rss>
+"#;
+
+        /// # 検証目的
+        /// リトライ対象のステータス（503）は`Retry-After`に従って取得し直し、対象外のステータス（500）はリトライしないことを確認する。
+        /// 既定の待ち時間は長くしておき、`Retry-After: 0`が優先されることも確かめる。
+        #[tokio::test]
+        async fn 指定したステータスだけリトライする() -> Result<()> {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/unavailable"))
+                .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "0"))
+                .up_to_n_times(2)
+                .expect(2)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/unavailable"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(FEED_BODY))
+                .expect(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/internal-error"))
+                .respond_with(ResponseTemplate::new(500))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let global = FeedRetryPolicy {
+                statuses: vec![429, 502, 503, 500],
+                max_retries: 0,
+                backoff: Duration::from_secs(30),
+            };
+            // フィードごとの設定で500を対象から外し、回数だけ増やす
+            let policy = global.with_override(Some(&FeedRetryOverride {
+                statuses: Some(vec![429, 502, 503]),
+                max_retries: Some(2),
+                backoff_ms: None,
+            }));
+            assert_eq!(policy.backoff, Duration::from_secs(30));
+
+            let client = Client::new();
+            let parsed = fetch_and_parse_feed(
+                &client,
+                &format!("{}/unavailable", server.uri()),
+                Some("retry"),
+                FeedFormat::default(),
+                None,
+                &policy,
+                PubDatePreference::default(),
+            )
+            .await?;
+            assert_eq!(parsed.entries.len(), 1);
+
+            let failed = fetch_and_parse_feed(
+                &client,
+                &format!("{}/internal-error", server.uri()),
+                Some("retry"),
+                FeedFormat::default(),
+                None,
+                &policy,
+                PubDatePreference::default(),
+            )
+            .await;
+            assert!(failed.is_err());
+
+            server.verify().await;
+
+            Ok(())
+        }
+    }
+
+    pub mod build_feed_client {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        use anyhow::Result;
+        use reqwest::Client;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        use crate::fetch_rss::{
+            build_feed_client, fetch_and_parse_feed, FeedRetryPolicy, FetchRssOptions,
+            PubDatePreference,
+        };
+        use crate::models::FeedFormat;
+
+        const FEED_BODY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>Keep Alive</title>
+    <item>
+      <title>Title</title>
+      <link>https://example.com/keep-alive</link>
+    </item>
+  </channel>
+</rss>
+"#;
+
+        /// 受け付けたTCP接続数を数えるkeep-alive対応の最小HTTPサーバを起動する
+        async fn spawn_counting_server() -> Result<(String, Arc<AtomicUsize>)> {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            let addr = listener.local_addr()?;
+            let connections = Arc::new(AtomicUsize::new(0));
+            let counter = connections.clone();
+
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut buffer = Vec::new();
+                        let mut chunk = [0u8; 1024];
+                        loop {
+                            let read = match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(read) => read,
+                            };
+                            buffer.extend_from_slice(&chunk[..read]);
+                            while let Some(end) =
+                                buffer.windows(4).position(|window| window == b"\r\n\r\n")
+                            {
+                                buffer.drain(..end + 4);
+                                let response = format!(
+                                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\n\r\n{}",
+                                    FEED_BODY.len(),
+                                    FEED_BODY
+                                );
+                                if socket.write_all(response.as_bytes()).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    });
+                }
+            });
+
+            Ok((format!("http://{}", addr), connections))
+        }
+
+        /// # 検証目的
+        /// 同一ホストの複数フィードを取得する際に接続が再利用され、プール無効時より接続数が少ないことを確認する。
+        #[tokio::test]
+        async fn 同一ホストへの接続を再利用する() -> Result<()> {
+            let (base_url, pooled_connections) = spawn_counting_server().await?;
+            let client = build_feed_client(&FetchRssOptions::default())?;
+            for index in 0..5 {
+                let url = format!("{}/feed{}", base_url, index);
+                let parsed = fetch_and_parse_feed(
+                    &client,
+                    &url,
+                    Some("pool"),
+                    FeedFormat::default(),
+                    None,
+                    &FeedRetryPolicy::default(),
+                    PubDatePreference::default(),
+                )
+                .await?;
+                assert_eq!(parsed.entries.len(), 1);
+            }
+
+            let (base_url, unpooled_connections) = spawn_counting_server().await?;
+            let unpooled_client = Client::builder().pool_max_idle_per_host(0).build()?;
+            for index in 0..5 {
+                let url = format!("{}/feed{}", base_url, index);
+                fetch_and_parse_feed(
+                    &unpooled_client,
+                    &url,
+                    Some("pool"),
+                    FeedFormat::default(),
+                    None,
+                    &FeedRetryPolicy::default(),
+                    PubDatePreference::default(),
+                )
+                .await?;
+            }
+
+            assert_eq!(pooled_connections.load(Ordering::SeqCst), 1);
+            assert_eq!(unpooled_connections.load(Ordering::SeqCst), 5);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// DNSキャッシュ有効時もクライアントを構築でき、ホスト名指定のフィードを取得できることを確認する。
+        #[tokio::test]
+        async fn dnsキャッシュ有効でもフィードを取得できる() -> Result<()> {
+            let (base_url, connections) = spawn_counting_server().await?;
+            let base_url = base_url.replace("127.0.0.1", "localhost");
+            let options = FetchRssOptions {
+                dns_cache: true,
+                ..FetchRssOptions::default()
+            };
+            let client = build_feed_client(&options)?;
+
+            for index in 0..3 {
+                let url = format!("{}/feed{}", base_url, index);
+                let parsed = fetch_and_parse_feed(
+                    &client,
+                    &url,
+                    Some("dns"),
+                    FeedFormat::default(),
+                    None,
+                    &FeedRetryPolicy::default(),
+                    PubDatePreference::default(),
+                )
+                .await?;
+                assert_eq!(parsed.entries.len(), 1);
+            }
+            assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+            Ok(())
         }
     }
 }
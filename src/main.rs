@@ -1,10 +1,33 @@
 mod api;
+mod article_export;
+mod article_filter_dsl;
+mod article_links;
+mod article_sink;
 mod articles;
+mod check_links;
+mod compression;
 mod config;
+mod content_versions;
+mod custom_feed;
 mod db;
+mod delta;
+mod events;
+mod feed_encoding;
+mod feed_lock;
+mod feeds;
 mod fetch_content;
+mod fetch_jobs;
 mod fetch_rss;
 mod models;
+mod opml;
+mod proxy;
+mod rate_limit;
+mod report;
+mod response_cache;
+mod secrets;
+mod summarize;
+mod telemetry;
+mod warc;
 mod webhook;
 
 #[cfg(test)]
@@ -13,22 +36,102 @@ mod test_support;
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::net::IpAddr;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
-fn init_tracing() {
+/// ログ出力を初期化する。`LOG_FORMAT=json`ならJSON形式で出力し、
+/// `OTEL_EXPORTER_OTLP_ENDPOINT`が設定されていればOTLPへspanを送るレイヤも追加する。
+fn init_tracing() -> telemetry::TracingGuard {
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
     use tracing_subscriber::EnvFilter;
 
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
+    // 不正な値はConfig::from_env_with_overridesでエラーにするため、ここではテキスト形式で続行する
+    let log_format: telemetry::LogFormat = std::env::var("LOG_FORMAT")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default();
+    let text_layer = (log_format == telemetry::LogFormat::Text)
+        .then(|| tracing_subscriber::fmt::layer().with_target(false));
+    let json_layer = (log_format == telemetry::LogFormat::Json)
+        .then(|| telemetry::json_fmt_layer(std::io::stdout));
+
+    let endpoint = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+    let (provider, otel_error) = match telemetry::build_tracer_provider(endpoint.as_deref()) {
+        Ok(provider) => (provider, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let otel_layer = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(telemetry::SERVICE_NAME))
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(text_layer)
+        .with(json_layer)
+        .with(otel_layer)
         .try_init();
+
+    if let Some(e) = otel_error {
+        warn!(error = %e, "OpenTelemetryの初期化に失敗したためOTLP送信を無効化します");
+    }
+
+    telemetry::TracingGuard { provider }
+}
+
+/// SIGINT（Ctrl-C）またはSIGTERMを受けたらキャンセルされるトークンを返す
+fn cancel_on_shutdown_signal() -> CancellationToken {
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    tokio::spawn(async move {
+        wait_for_shutdown_signal().await;
+        info!("終了シグナルを受信しました。着手済みの処理を終えてから停止します");
+        cancel.cancel();
+    });
+    token
+}
+
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!(error = %e, "Ctrl-Cの待ち受けに失敗しました");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!(error = %e, "SIGTERMの待ち受けに失敗しました");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
 
 #[derive(Parser)]
 #[command(name = "datadoggo-v3-rss")]
 #[command(about = "RSSフィードから記事を収集してDBに保存する", long_about = None)]
 struct Cli {
+    #[command(flatten)]
+    overrides: config::ConfigOverrides,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -36,13 +139,92 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// RSSフィードから新規記事をqueueに登録
-    FetchRss,
+    FetchRss {
+        /// 取得に失敗したフィードを最後にまとめて1回だけ再取得する
+        #[arg(long)]
+        retry_failed_feeds: bool,
+
+        /// フィードにアイコンが無い場合、フィードホストの/favicon.icoを推定値として保存する
+        #[arg(long)]
+        guess_favicon: bool,
 
-    /// queue内のstatus_code=NULLな記事に対してAPI実行
+        /// フィードの取得・解析のみ行い、DBへ保存せずに取り込み予定件数を表示する
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 実行時刻を迎えたqueueの記事に対してAPI実行（--group等で対象を絞り込める）
     FetchContent {
         /// 処理する最大件数（デフォルト: 100）
         #[arg(short, long, default_value = "100")]
         limit: i64,
+
+        /// 取得対象のグループ（カンマ区切り）
+        #[arg(long)]
+        group: Option<String>,
+
+        /// この日時（RFC3339、または24hのような相対期間）以降にqueueへ追加されたものに限る
+        #[arg(long)]
+        created_after: Option<String>,
+
+        /// 取得対象のstatus_code（カンマ区切り、unfetchedは未取得）
+        #[arg(long)]
+        status: Option<String>,
+    },
+
+    /// queueのlinkにHEADリクエストを送り、リンク切れ（404/410）を記録
+    CheckLinks {
+        /// 確認する最大件数（デフォルト: 100）
+        #[arg(short, long, default_value = "100")]
+        limit: i64,
+
+        /// 同時に確認するリンク数（デフォルト: 8）
+        #[arg(long, default_value_t = check_links::DEFAULT_CHECK_LINKS_CONCURRENCY)]
+        concurrency: usize,
+
+        /// リクエストを送り出す最小間隔ミリ秒（デフォルト: 200）
+        #[arg(long, default_value_t = check_links::DEFAULT_CHECK_LINKS_INTERVAL_MS)]
+        interval_ms: u64,
+    },
+
+    /// OPMLファイルのフィードをrss_links.ymlへ取り込む（outlineの階層は`/`区切りのグループになる）
+    ImportOpml {
+        /// 取り込むOPMLファイル
+        file: String,
+
+        /// 取り込んだフィードを入れるグループ（OPMLのフォルダはこの配下のグループになる）
+        #[arg(long, default_value = opml::DEFAULT_OPML_GROUP)]
+        group: String,
+
+        /// 追記先のrss_links.yml（無ければ新規作成）
+        #[arg(long, default_value = "rss_links.yml")]
+        output: String,
+    },
+
+    /// 指定日（UTC）にqueueへ取り込まれた記事をグループ別・言語別に集計して出力
+    Report {
+        /// 集計する日付（YYYY-MM-DD、デフォルト: 前日）
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+
+        /// 出力形式（text / json、デフォルト: text）
+        #[arg(long, value_enum, default_value_t = report::ReportFormat::Text)]
+        format: report::ReportFormat,
+
+        /// レポートをWEBHOOK_URLへも送信する
+        #[arg(long)]
+        webhook: bool,
+    },
+
+    /// DBマイグレーションを適用（--downでロールバック）
+    Migrate {
+        /// 直近のマイグレーションをロールバックする
+        #[arg(long)]
+        down: bool,
+
+        /// ロールバックする件数（--down指定時のみ有効、デフォルト: 1）
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
     },
 
     /// APIサーバを起動
@@ -59,31 +241,102 @@ enum Commands {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    init_tracing();
+    let _tracing_guard = init_tracing();
 
     let cli = Cli::parse();
 
-    // 設定を読み込む
-    let config = config::Config::from_env()?;
+    // 設定を読み込む（CLIフラグ > 環境変数 > デフォルト）
+    // secret://の解決でAWS Secrets Managerの応答を待つため、ランタイムのワーカーの外で行う
+    let overrides = cli.overrides.clone();
+    let config =
+        tokio::task::spawn_blocking(move || config::Config::from_env_with_overrides(&overrides))
+            .await??;
+
+    // rss_links.ymlを書き換えるだけのコマンドはDBへ接続しない
+    if let Commands::ImportOpml {
+        file,
+        group,
+        output,
+    } = &cli.command
+    {
+        info!("=== import-opml コマンドを実行 ===");
+        return opml::run(file, group, output);
+    }
 
     // データベース接続プールを作成
     let pool = db::create_pool(&config.database_url).await?;
 
     match cli.command {
-        Commands::FetchRss => {
+        Commands::FetchRss {
+            retry_failed_feeds,
+            guess_favicon,
+            dry_run,
+        } => {
             info!("=== fetch-rss コマンドを実行 ===");
-            fetch_rss::run(pool, config.webhook_url.as_deref()).await?;
+            let options = fetch_rss::FetchRssOptions {
+                retry_failed_feeds,
+                guess_favicon,
+                dry_run,
+                ..config.fetch_rss_options()
+            };
+            fetch_rss::run(pool, &options, config.webhook_url.as_deref()).await?;
         }
-        Commands::FetchContent { limit } => {
+        Commands::FetchContent {
+            limit,
+            group,
+            created_after,
+            status,
+        } => {
             info!("=== fetch-content コマンドを実行 ===");
+            let options = fetch_content::FetchContentOptions {
+                filter: fetch_content::FetchContentFilter::parse(
+                    group.as_deref(),
+                    created_after.as_deref(),
+                    status.as_deref(),
+                )?,
+                ..config.fetch_content_options()
+            };
             fetch_content::run(
                 pool,
                 limit,
                 &config.scraping_api_url,
+                &options,
                 config.webhook_url.as_deref(),
+                &cancel_on_shutdown_signal(),
             )
             .await?;
         }
+        Commands::CheckLinks {
+            limit,
+            concurrency,
+            interval_ms,
+        } => {
+            info!("=== check-links コマンドを実行 ===");
+            let options = check_links::CheckLinksOptions {
+                concurrency,
+                min_interval: std::time::Duration::from_millis(interval_ms),
+                proxy_url: config.proxy_url.clone(),
+            };
+            check_links::run(pool, limit, &options).await?;
+        }
+        Commands::Report {
+            date,
+            format,
+            webhook,
+        } => {
+            info!("=== report コマンドを実行 ===");
+            report::run(pool, date, format, webhook, config.webhook_url.as_deref()).await?;
+        }
+        Commands::ImportOpml { .. } => unreachable!("DB接続前に処理済み"),
+        Commands::Migrate { down, steps } => {
+            if down {
+                info!("=== migrate --down コマンドを実行 ===");
+                db::rollback_migrations(&pool, steps).await?;
+            } else {
+                info!("=== migrate コマンドを実行 ===");
+                db::run_migrations(&pool).await?;
+            }
+        }
         Commands::Serve { host, port } => {
             info!("=== APIサーバを起動 ===");
             let state = api::ApiState::new(
@@ -91,7 +344,18 @@ async fn main() -> Result<()> {
                 config.scraping_api_url.clone(),
                 "rss_links.yml".to_string(),
                 config.webhook_url.clone(),
-            );
+            )
+            .with_fetch_rss_options(config.fetch_rss_options())
+            .with_fetch_content_options(config.fetch_content_options())
+            .with_json_case(config.api_json_case)
+            .with_default_sort(config.api_default_sort)
+            .with_max_body_bytes(config.api_max_body_bytes)
+            .with_article_cache_ttl(config.api_cache_ttl_secs)
+            .with_token_ttl(config.api_token_ttl_secs)
+            .with_token_secret(config.api_token_secret.as_deref())
+            .with_admin_token(config.api_admin_token.clone())
+            .with_access_log(config.log_format == telemetry::LogFormat::Json)
+            .with_shutdown(cancel_on_shutdown_signal());
             api::serve(state, host, port).await?;
         }
     }
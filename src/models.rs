@@ -12,9 +12,13 @@ pub struct Queue {
     pub link: String,
     pub title: String,
     pub pub_date: Option<DateTime<Utc>>,
+    /// フィードに書かれていたpub_dateのUTCからのオフセット（分）
+    pub pub_date_tz_offset: Option<i32>,
     pub description: String,
     pub status_code: Option<i32>,
     pub group: Option<String>,
+    pub scrape_referer: Option<String>,
+    pub scrape_accept_language: Option<String>,
 }
 
 /// article_contentテーブルのモデル
@@ -33,15 +37,40 @@ pub struct NewQueue {
     pub link: String,
     pub title: String,
     pub pub_date: Option<DateTime<Utc>>,
+    /// フィードに書かれていたpub_dateのUTCからのオフセット（分）。元の文字列から取れなければNone
+    pub pub_date_tz_offset: Option<i32>,
     pub description: String,
+    /// entryのcontent本文（RSSの`content:encoded`・Atomの`content`）。descriptionとは別に保存する
+    pub content_body: Option<String>,
+    /// entryの言語（Atomの`content`の`xml:lang`、無ければフィードの`language`/`xml:lang`）の主言語タグ（`ja`・`en`など小文字）。entryにもフィードにも無ければNone
+    pub language: Option<String>,
     pub group: Option<String>,
+    /// scraping APIへ渡すリファラ（フィード設定由来）
+    pub scrape_referer: Option<String>,
+    /// scraping APIへ渡すAccept-Language（フィード設定由来）
+    pub scrape_accept_language: Option<String>,
+    /// fetch-contentの取得優先度（所属グループの`_priority`由来、大きいほど先に取得する）
+    pub priority: i32,
 }
 
 /// RSSリンク設定（rss_links.ymlから読み込む）
 #[derive(Debug, Clone, Deserialize)]
 pub struct RssLinks {
     #[serde(flatten)]
-    groups: std::collections::HashMap<String, std::collections::HashMap<String, RssLinkEntry>>,
+    groups: std::collections::HashMap<String, RssGroup>,
+}
+
+/// rss_links.ymlの1グループ分の設定
+#[derive(Debug, Clone, Deserialize)]
+struct RssGroup {
+    /// 取得の優先度（大きいほど先に処理、既定0）。フィード名と衝突しないよう`_`始まりの予約キーにする
+    #[serde(rename = "_priority", default)]
+    priority: i32,
+    /// このグループのfetch-rss結果の通知先（未指定ならグローバルの`WEBHOOK_URL`）
+    #[serde(rename = "_webhook_url", default)]
+    webhook_url: Option<String>,
+    #[serde(flatten)]
+    entries: std::collections::HashMap<String, RssLinkEntry>,
 }
 
 impl RssLinks {
@@ -49,17 +78,38 @@ impl RssLinks {
     pub fn into_sources(self) -> Vec<RssFeedSource> {
         let mut feeds = Vec::new();
 
-        for (group, entries) in self.groups {
+        for (
+            group,
+            RssGroup {
+                priority,
+                webhook_url,
+                entries,
+            },
+        ) in self.groups
+        {
             for (name, entry) in entries {
-                let url = match entry {
-                    RssLinkEntry::Url(url) => url,
-                    RssLinkEntry::Detailed { url, .. } => url,
+                let (url, referer, accept_language, format, retry) = match entry {
+                    RssLinkEntry::Url(url) => (url, None, None, FeedFormat::default(), None),
+                    RssLinkEntry::Detailed {
+                        url,
+                        referer,
+                        accept_language,
+                        format,
+                        retry,
+                        ..
+                    } => (url, referer, accept_language, format, retry),
                 };
 
                 feeds.push(RssFeedSource {
                     group: group.clone(),
                     name,
                     url,
+                    referer,
+                    accept_language,
+                    priority,
+                    webhook_url: webhook_url.clone(),
+                    format,
+                    retry,
                 });
             }
         }
@@ -73,6 +123,41 @@ pub struct RssFeedSource {
     pub group: String,
     pub name: String,
     pub url: String,
+    pub referer: Option<String>,
+    pub accept_language: Option<String>,
+    /// 所属グループの優先度（大きいほど先に取得する）
+    pub priority: i32,
+    /// 所属グループのWebhook URL（`_webhook_url`）
+    pub webhook_url: Option<String>,
+    /// フィードの形式（Detailedの`format`、既定はRSS/Atom）
+    pub format: FeedFormat,
+    /// フィード取得のリトライ設定の上書き（Detailedの`retry`）
+    pub retry: Option<FeedRetryOverride>,
+}
+
+/// フィードごとのHTTPリトライ設定（Detailedの`retry`）。指定した項目だけグローバル設定を上書きする
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct FeedRetryOverride {
+    /// リトライ対象のHTTPステータス（空ならリトライしない）
+    #[serde(default)]
+    pub statuses: Option<Vec<u16>>,
+    /// 最大リトライ回数（初回の取得は含まない）
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+    /// 1回目のリトライまでの待ち時間（ミリ秒、以降は倍々に延ばす）
+    #[serde(default)]
+    pub backoff_ms: Option<u64>,
+}
+
+/// フィードの形式。`feed_rs`で解析できない独自形式は組み込みのカスタムパーサへ回す
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    /// RSS/Atom（`feed_rs`で解析する）
+    #[default]
+    Feed,
+    /// `[{"url", "title", "published"}]`形式のJSON配列
+    CustomJson,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -80,11 +165,21 @@ pub struct RssFeedSource {
 enum RssLinkEntry {
     Url(String),
     Detailed {
+        /// キーの書き間違いで欠けた場合も空文字として読み込み、`validate_rss_links`で検出する
+        #[serde(default)]
         url: String,
         #[serde(default)]
         _wait_for_selector: Option<String>,
         #[serde(default)]
         _timeout: Option<u64>,
+        #[serde(default)]
+        referer: Option<String>,
+        #[serde(default)]
+        accept_language: Option<String>,
+        #[serde(default)]
+        format: FeedFormat,
+        #[serde(default)]
+        retry: Option<FeedRetryOverride>,
     },
 }
 
@@ -96,6 +191,13 @@ pub struct ScrapeRequest {
     pub wait_for_selector: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+    /// 前回保存した本文の`source_last_modified`（HTTP-date）。条件付き取得に対応したscraping APIは未更新なら304を返す
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub if_modified_since: Option<String>,
 }
 
 /// スクレイピングAPIレスポンス
@@ -103,4 +205,10 @@ pub struct ScrapeRequest {
 pub struct ScrapeResponse {
     pub html: String,
     pub status_code: i32,
+    /// リダイレクト後の最終URL（返さないscraping APIもあるため任意）
+    #[serde(default)]
+    pub final_url: Option<String>,
+    /// 元ページのレスポンスヘッダ`Last-Modified`（HTTP-date、返さないscraping APIもあるため任意）
+    #[serde(default)]
+    pub last_modified: Option<String>,
 }
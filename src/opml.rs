@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+
+use anyhow::{Context, Result};
+use serde_yaml::{Mapping, Value};
+use tracing::{info, warn};
+
+use crate::fetch_rss::validate_feed_url;
+use crate::models::RssLinks;
+
+/// `--group`を省略したときにOPMLのフィードを入れるグループ
+pub(crate) const DEFAULT_OPML_GROUP: &str = "imported";
+
+/// OPMLから読み取ったフィード1件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OpmlFeed {
+    pub group: String,
+    pub name: String,
+    pub url: String,
+}
+
+/// import-opmlの取り込み結果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct OpmlImportSummary {
+    /// rss_links.ymlへ追加したフィード数
+    pub imported: usize,
+    /// 同じURLが登録済みのため追加しなかったフィード数
+    pub duplicated: usize,
+    /// URLが不正なため追加しなかったフィード数
+    pub invalid: usize,
+}
+
+/// import-opmlコマンドのメイン処理。OPMLのフィードを`rss_links_path`のrss_links.ymlへ追記する
+pub fn run(opml_path: &str, group: &str, rss_links_path: &str) -> Result<()> {
+    let group = group.trim();
+    if group.is_empty() {
+        anyhow::bail!("--groupに空文字は指定できません");
+    }
+
+    let content = fs::read_to_string(opml_path)
+        .with_context(|| format!("OPMLファイルを読み込めません: {}", opml_path))?;
+    let feeds = parse_opml(&content, group)?;
+    info!("OPMLから{}件のフィードを読み込みました", feeds.len());
+
+    let existing = match fs::read_to_string(rss_links_path) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let (yaml, summary) = merge_into_rss_links(existing.as_deref(), &feeds)?;
+    if summary.imported > 0 {
+        fs::write(rss_links_path, yaml)?;
+    }
+
+    info!(
+        imported = summary.imported,
+        duplicated = summary.duplicated,
+        invalid = summary.invalid,
+        "{}へ取り込みました",
+        rss_links_path
+    );
+    Ok(())
+}
+
+/// OPMLを解析し、`xmlUrl`を持つoutlineをフィードとして返す。
+///
+/// `xmlUrl`を持たないoutlineはフォルダとみなし、`text`（無ければ`title`）を`/`区切りで`group`の下へ積んだ
+/// 階層グループ（`imported/tech/rust`）にその配下のフィードを入れる。フィード名は`text`→`title`→URLの順で採る。
+pub(crate) fn parse_opml(content: &str, group: &str) -> Result<Vec<OpmlFeed>> {
+    let document = roxmltree::Document::parse(content).context("OPMLとして解析できません")?;
+    let body = document
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("body"))
+        .context("OPMLに<body>がありません")?;
+
+    let mut feeds = Vec::new();
+    collect_outline_feeds(body, group, &mut feeds);
+    Ok(feeds)
+}
+
+fn collect_outline_feeds(parent: roxmltree::Node, group: &str, feeds: &mut Vec<OpmlFeed>) {
+    for outline in parent
+        .children()
+        .filter(|node| node.has_tag_name("outline"))
+    {
+        let label = ["text", "title"]
+            .iter()
+            .filter_map(|name| outline.attribute(*name))
+            .map(str::trim)
+            .find(|label| !label.is_empty());
+
+        match outline
+            .attribute("xmlUrl")
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+        {
+            Some(url) => feeds.push(OpmlFeed {
+                group: group.to_string(),
+                name: label.unwrap_or(url).to_string(),
+                url: url.to_string(),
+            }),
+            None => {
+                // フォルダ名に`/`が含まれていても階層が増えないよう置き換える
+                let child_group = match label {
+                    Some(label) => format!("{}/{}", group, label.replace('/', "-")),
+                    None => group.to_string(),
+                };
+                collect_outline_feeds(outline, &child_group, feeds);
+            }
+        }
+    }
+}
+
+/// rss_links.ymlの内容へフィードを追記したYAMLと取り込み結果を返す（`existing`がNoneなら新規作成）。
+///
+/// 同じURLが登録済みのフィードとURLが不正なフィードは追加しない。同じグループに同名のフィードがある場合は
+/// `_2`などの連番を付ける。既存のグループ・フィードの並びは保つが、YAMLのコメントは残らない。
+pub(crate) fn merge_into_rss_links(
+    existing: Option<&str>,
+    feeds: &[OpmlFeed],
+) -> Result<(String, OpmlImportSummary)> {
+    let existing = existing.filter(|content| !content.trim().is_empty());
+    let (mut groups, mut urls) = match existing {
+        Some(content) => {
+            let groups: Mapping =
+                serde_yaml::from_str(content).context("rss_links.ymlを解析できません")?;
+            let links: RssLinks = serde_yaml::from_str(content)?;
+            let urls: HashSet<String> = links
+                .into_sources()
+                .into_iter()
+                .map(|feed| feed.url.trim().to_string())
+                .collect();
+            (groups, urls)
+        }
+        None => (Mapping::new(), HashSet::new()),
+    };
+
+    let mut summary = OpmlImportSummary::default();
+    for feed in feeds {
+        if let Err(reason) = validate_feed_url(&feed.url) {
+            warn!(
+                group = %feed.group,
+                name = %feed.name,
+                reason = %reason,
+                "不正なURLのフィードを読み飛ばします"
+            );
+            summary.invalid += 1;
+            continue;
+        }
+        if !urls.insert(feed.url.clone()) {
+            summary.duplicated += 1;
+            continue;
+        }
+
+        let entries = groups
+            .entry(Value::String(feed.group.clone()))
+            .or_insert_with(|| Value::Mapping(Mapping::new()))
+            .as_mapping_mut()
+            .with_context(|| {
+                format!(
+                    "rss_links.ymlのグループ{}がマップではありません",
+                    feed.group
+                )
+            })?;
+        let name = (1..)
+            .map(|index| match index {
+                1 => feed.name.clone(),
+                _ => format!("{}_{}", feed.name, index),
+            })
+            .find(|name| !entries.contains_key(name.as_str()))
+            .expect("連番は尽きない");
+        entries.insert(Value::String(name), Value::String(feed.url.clone()));
+        summary.imported += 1;
+    }
+
+    // 既存ファイルに合わせ、グループの間を空行で区切る
+    let mut sections = Vec::with_capacity(groups.len());
+    for (group, entries) in groups {
+        let mut section = Mapping::new();
+        section.insert(group, entries);
+        sections.push(serde_yaml::to_string(&section)?);
+    }
+
+    Ok((sections.join("\n"), summary))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod parse_opml {
+        use crate::opml::{parse_opml, OpmlFeed};
+
+        /// # 検証目的
+        /// outlineの階層が`/`区切りのグループへ対応付けられ、フィード名が`text`→`title`→URLの順で採られることを確認する。
+        #[test]
+        fn ネストしたoutlineをグループへ対応付ける() -> anyhow::Result<()> {
+            let opml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head><title>購読リスト</title></head>
+  <body>
+    <outline text="トップ" type="rss" xmlUrl="https://example.com/top.xml"/>
+    <outline text="Tech">
+      <outline title="Rust Blog" xmlUrl="https://blog.rust-lang.org/feed.xml"/>
+      <outline text="News/JP">
+        <outline xmlUrl="https://example.jp/rss"/>
+      </outline>
+    </outline>
+  </body>
+</opml>"#;
+
+            let feed = |group: &str, name: &str, url: &str| OpmlFeed {
+                group: group.to_string(),
+                name: name.to_string(),
+                url: url.to_string(),
+            };
+            assert_eq!(
+                parse_opml(opml, "imported")?,
+                vec![
+                    feed("imported", "トップ", "https://example.com/top.xml"),
+                    feed(
+                        "imported/Tech",
+                        "Rust Blog",
+                        "https://blog.rust-lang.org/feed.xml"
+                    ),
+                    feed(
+                        "imported/Tech/News-JP",
+                        "https://example.jp/rss",
+                        "https://example.jp/rss"
+                    ),
+                ]
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// XMLとして壊れたファイルや`<body>`の無いファイルはエラーになることを確認する。
+        #[test]
+        fn 不正なopmlはエラーになる() {
+            assert!(parse_opml("<opml><body>", "imported").is_err());
+            assert!(parse_opml("<opml><head/></opml>", "imported").is_err());
+        }
+    }
+
+    pub mod merge_into_rss_links {
+        use crate::fetch_rss::load_rss_links;
+        use crate::opml::{merge_into_rss_links, parse_opml, OpmlImportSummary};
+        use crate::test_support::create_temp_yaml;
+
+        /// # 検証目的
+        /// OPMLのフィードがrss_links.ymlへ追記されて読み込めること、登録済みのURLと不正なURLは追加されず、
+        /// 同名のフィードには連番が付くことを確認する。
+        #[test]
+        fn opmlのフィードをrss_linksへ登録する() -> anyhow::Result<()> {
+            let existing = "bbc:\n  top: https://feeds.bbci.co.uk/news/rss.xml\n";
+            let opml = r#"<opml version="1.0"><body>
+  <outline text="BBC" xmlUrl="https://feeds.bbci.co.uk/news/rss.xml"/>
+  <outline text="Tech">
+    <outline text="blog" xmlUrl="https://example.com/a.xml"/>
+    <outline text="blog" xmlUrl="https://example.com/b.xml"/>
+    <outline text="壊れたURL" xmlUrl="feed://example.com/c.xml"/>
+  </outline>
+</body></opml>"#;
+
+            let feeds = parse_opml(opml, "imported")?;
+            let (yaml, summary) = merge_into_rss_links(Some(existing), &feeds)?;
+            assert_eq!(
+                summary,
+                OpmlImportSummary {
+                    imported: 2,
+                    duplicated: 1,
+                    invalid: 1,
+                }
+            );
+
+            let file = create_temp_yaml(&yaml)?;
+            let mut sources: Vec<(String, String, String)> =
+                load_rss_links(file.path().to_str().unwrap())?
+                    .into_iter()
+                    .map(|feed| (feed.group, feed.name, feed.url))
+                    .collect();
+            sources.sort();
+            let expected = [
+                ("bbc", "top", "https://feeds.bbci.co.uk/news/rss.xml"),
+                ("imported/Tech", "blog", "https://example.com/a.xml"),
+                ("imported/Tech", "blog_2", "https://example.com/b.xml"),
+            ];
+            assert_eq!(
+                sources,
+                expected
+                    .iter()
+                    .map(|(group, name, url)| (
+                        group.to_string(),
+                        name.to_string(),
+                        url.to_string()
+                    ))
+                    .collect::<Vec<_>>()
+            );
+            // 既存のグループが先頭に残り、グループ間は空行で区切られる
+            assert!(yaml.starts_with("bbc:\n"), "{}", yaml);
+            assert!(yaml.contains("\n\nimported/Tech:\n"), "{}", yaml);
+
+            Ok(())
+        }
+    }
+}
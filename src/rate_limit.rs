@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// 呼び出しを一定間隔以下に抑えるトークンバケット（容量1）
+///
+/// 呼び出しごとに`interval`ずつ後ろの送出時刻を割り当てるため、バーストは許さない。
+/// 同じインスタンス（`Arc`）を共有した並列タスク同士で上限を共有する。
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// 1秒あたり`qps`回まで。`qps`は0より大きい有限値（1未満なら数秒に1回）
+    pub fn new(qps: f64) -> Result<Self> {
+        if !qps.is_finite() || qps <= 0.0 {
+            anyhow::bail!("QPSは0より大きい数値で指定してください: {}", qps);
+        }
+        // 極端に小さいQPSでは間隔がDurationに収まらない
+        let interval = Duration::try_from_secs_f64(1.0 / qps)
+            .map_err(|_| anyhow::anyhow!("QPSは0より大きい数値で指定してください: {}", qps))?;
+        Ok(Self::from_interval(interval))
+    }
+
+    /// 送出の最小間隔を直接指定する（0なら待たない）
+    pub fn from_interval(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// 前の呼び出しから`interval`以上空くまで待つ（並列タスク間で送出時刻を順番に割り当てる）
+    pub async fn acquire(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        let slot = {
+            let mut next = self.next_slot.lock().await;
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod rate_limiter {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use futures::future::join_all;
+        use tokio::time::Instant;
+
+        use crate::rate_limit::RateLimiter;
+
+        /// # 検証目的
+        /// 並列に待機しても送出時刻が最小間隔ずつずらされることを、停止した時計の上で確認する。
+        #[tokio::test(start_paused = true)]
+        async fn 並列でも最小間隔を空ける() {
+            let limiter = RateLimiter::from_interval(Duration::from_millis(50));
+            let started = Instant::now();
+
+            let mut finished = join_all((0..4).map(|_| async {
+                limiter.acquire().await;
+                started.elapsed()
+            }))
+            .await;
+            finished.sort();
+
+            for (index, elapsed) in finished.iter().enumerate() {
+                let expected = Duration::from_millis(50) * index as u32;
+                // 時計は止まっているので、タイマーの丸め（1ms）以上ずれることはない
+                assert!(
+                    *elapsed >= expected && *elapsed < expected + Duration::from_millis(1),
+                    "{}件目の送出時刻がずれている: {:?}",
+                    index + 1,
+                    elapsed
+                );
+            }
+        }
+
+        /// # 検証目的
+        /// QPSを共有したタスクを並列に動かしても、時計を進めた分のトークンしか払い出されないことを確認する。
+        #[tokio::test(start_paused = true)]
+        async fn 並列実行でも全体のqpsを超えない() {
+            // 20QPS = 50ms間隔
+            let limiter = Arc::new(RateLimiter::new(20.0).unwrap());
+            let acquired = Arc::new(AtomicUsize::new(0));
+            let tasks: Vec<_> = (0..6)
+                .map(|_| {
+                    let limiter = limiter.clone();
+                    let acquired = acquired.clone();
+                    tokio::spawn(async move {
+                        limiter.acquire().await;
+                        acquired.fetch_add(1, Ordering::SeqCst);
+                    })
+                })
+                .collect();
+
+            // 最初の1件は待たずに通る
+            settle().await;
+            assert_eq!(acquired.load(Ordering::SeqCst), 1);
+
+            // 間隔に満たない間は次のトークンが出ない
+            tokio::time::advance(Duration::from_millis(49)).await;
+            settle().await;
+            assert_eq!(acquired.load(Ordering::SeqCst), 1);
+
+            // 間隔ごとに1件ずつ払い出される
+            for expected in 2..=6 {
+                let step = if expected == 2 { 1 } else { 50 };
+                tokio::time::advance(Duration::from_millis(step)).await;
+                settle().await;
+                assert_eq!(acquired.load(Ordering::SeqCst), expected);
+            }
+
+            for task in tasks {
+                task.await.unwrap();
+            }
+        }
+
+        /// 起こされたタスクが走り終わるまで他のタスクへ譲る（時計は進めない）
+        async fn settle() {
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+        }
+
+        /// # 検証目的
+        /// 0以下や非有限のQPS、間隔がDurationに収まらないほど小さいQPSをpanicせずに拒否することを確認する。
+        #[test]
+        fn 不正なqpsを拒否する() {
+            for qps in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-20, f64::MIN_POSITIVE] {
+                assert!(RateLimiter::new(qps).is_err(), "{}", qps);
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{bail, Result};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::webhook;
+
+/// 日次レポートの出力形式（`--format`。値の検証はclapの引数解析で行う）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// 人が読むテキスト形式（既定）
+    #[default]
+    Text,
+    /// `DailyReport`をそのままシリアライズしたJSON
+    Json,
+}
+
+/// グループ・言語ごとの件数（groupやlanguageが無い記事は`key`がNone）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportCount {
+    pub key: Option<String>,
+    pub count: i64,
+}
+
+/// その日（UTC）にqueueへ取り込まれた記事の集計
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub total: i64,
+    /// 件数の多い順（同数はグループ名順、グループ無しは最後）
+    pub by_group: Vec<ReportCount>,
+    /// 件数の多い順（同数は言語名順、言語不明は最後）
+    pub by_language: Vec<ReportCount>,
+}
+
+/// `date`（UTCの0時から24時間）にqueueへ追加された記事をグループ別・言語別に集計する
+pub async fn build_daily_report(pool: &PgPool, date: NaiveDate) -> Result<DailyReport> {
+    let start = date.and_hms_opt(0, 0, 0).expect("0時は常に有効").and_utc();
+    let end = start + Duration::days(1);
+
+    let by_group = count_by(pool, r#""group""#, start, end).await?;
+    let by_language = count_by(pool, "language", start, end).await?;
+    let total = by_group.iter().map(|count| count.count).sum();
+
+    Ok(DailyReport {
+        date,
+        total,
+        by_group,
+        by_language,
+    })
+}
+
+/// queueの`column`ごとに期間内の件数を数える（`column`は呼び出し側で固定した列名のみ渡す）
+async fn count_by(
+    pool: &PgPool,
+    column: &str,
+    start: chrono::DateTime<Utc>,
+    end: chrono::DateTime<Utc>,
+) -> Result<Vec<ReportCount>> {
+    let query = format!(
+        r#"
+        SELECT {column} AS key, COUNT(*)::BIGINT AS count
+        FROM rss.queue
+        WHERE created_at >= $1 AND created_at < $2
+        GROUP BY {column}
+        ORDER BY count DESC, key ASC NULLS LAST
+        "#,
+    );
+    let rows = sqlx::query_as::<_, (Option<String>, i64)>(&query)
+        .bind(start)
+        .bind(end)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(key, count)| ReportCount { key, count })
+        .collect())
+}
+
+/// レポートをテキスト形式に整形する
+pub fn render_text(report: &DailyReport) -> String {
+    let mut lines = vec![
+        format!("日次レポート {} (UTC)", report.date),
+        format!("取り込み件数: {}", report.total),
+        String::new(),
+        "グループ別:".to_string(),
+    ];
+    lines.extend(render_counts(&report.by_group, "(グループなし)"));
+    lines.push(String::new());
+    lines.push("言語別:".to_string());
+    lines.extend(render_counts(&report.by_language, "(不明)"));
+    lines.join("\n")
+}
+
+fn render_counts<'a>(
+    counts: &'a [ReportCount],
+    missing_label: &'a str,
+) -> impl Iterator<Item = String> + 'a {
+    counts.iter().map(move |count| {
+        format!(
+            "  {}: {}",
+            count.key.as_deref().unwrap_or(missing_label),
+            count.count
+        )
+    })
+}
+
+/// reportコマンドのエントリポイント。`date`未指定なら前日（UTC）を集計して標準出力へ書き出し、
+/// `send_webhook`ならWEBHOOK_URLへも送る
+pub async fn run(
+    pool: PgPool,
+    date: Option<NaiveDate>,
+    format: ReportFormat,
+    send_webhook: bool,
+    webhook_url: Option<&str>,
+) -> Result<()> {
+    let webhook_url = match (send_webhook, webhook_url) {
+        (false, _) => None,
+        (true, Some(url)) => Some(url),
+        (true, None) => bail!("--webhookを指定する場合はWEBHOOK_URLを設定してください"),
+    };
+    let date = date.unwrap_or_else(|| Utc::now().date_naive() - Duration::days(1));
+    let report = build_daily_report(&pool, date).await?;
+
+    match format {
+        ReportFormat::Text => println!("{}", render_text(&report)),
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if let Some(url) = webhook_url {
+        let result = webhook::notify_daily_report(url, &report, "cli").await;
+        if !result.sent {
+            warn!(error = ?result.error, "Webhook送信に失敗しました(report)");
+        }
+    }
+
+    info!(date = %report.date, total = report.total, "日次レポートを出力しました");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod build_daily_report {
+        use anyhow::Result;
+        use uuid::Uuid;
+
+        use crate::report::{build_daily_report, render_text, ReportCount};
+        use crate::test_support::{clear_rss_tables, fixed_datetime, prepare_test_pool};
+
+        fn count(key: Option<&str>, count: i64) -> ReportCount {
+            ReportCount {
+                key: key.map(str::to_string),
+                count,
+            }
+        }
+
+        /// # 検証目的
+        /// 指定日（UTC）に追加された記事だけがグループ別・言語別に件数の多い順で集計され、
+        /// groupや言語の無い記事は最後にまとめられることを確認する。
+        #[tokio::test]
+        async fn 指定日の記事をグループ別と言語別に集計する() -> Result<()> {
+            let _lock = crate::test_support::acquire_db_lock().await;
+            let pool = prepare_test_pool().await?;
+
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            clear_rss_tables(&pool).await?;
+
+            let day = fixed_datetime(2025, 10, 13, 0, 0, 0);
+            let rows = [
+                (Some("tech"), Some("ja"), day),
+                (
+                    Some("tech"),
+                    Some("en"),
+                    fixed_datetime(2025, 10, 13, 12, 0, 0),
+                ),
+                (
+                    Some("tech"),
+                    Some("ja"),
+                    fixed_datetime(2025, 10, 13, 23, 59, 59),
+                ),
+                (
+                    Some("world"),
+                    Some("ja"),
+                    fixed_datetime(2025, 10, 13, 8, 0, 0),
+                ),
+                (None, None, fixed_datetime(2025, 10, 13, 9, 0, 0)),
+                // 前日・翌日の記事は含めない
+                (
+                    Some("tech"),
+                    Some("ja"),
+                    fixed_datetime(2025, 10, 12, 23, 59, 59),
+                ),
+                (
+                    Some("world"),
+                    Some("en"),
+                    fixed_datetime(2025, 10, 14, 0, 0, 0),
+                ),
+            ];
+            for (index, (group, language, created_at)) in rows.into_iter().enumerate() {
+                sqlx::query(
+                    r#"
+                    INSERT INTO rss.queue (id, link, title, description, "group", language, created_at)
+                    VALUES ($1, $2, 'タイトル', '説明', $3, $4, $5)
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(format!("https://example.com/{}", index))
+                .bind(group)
+                .bind(language)
+                .bind(created_at)
+                .execute(&pool)
+                .await?;
+            }
+
+            let report = build_daily_report(&pool, day.date_naive()).await?;
+            assert_eq!(report.total, 5);
+            assert_eq!(
+                report.by_group,
+                vec![
+                    count(Some("tech"), 3),
+                    count(Some("world"), 1),
+                    count(None, 1)
+                ]
+            );
+            assert_eq!(
+                report.by_language,
+                vec![count(Some("ja"), 3), count(Some("en"), 1), count(None, 1)]
+            );
+
+            let text = render_text(&report);
+            assert!(text.contains("取り込み件数: 5"));
+            assert!(text.contains("  tech: 3"));
+            assert!(text.contains("  (不明): 1"));
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::hash::Hash;
+use std::time::Duration;
+
+use axum::body::{to_bytes, Bytes};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use moka::future::Cache;
+
+/// キャッシュに保持するレスポンスボディの合計上限（バイト）。超えると使われていないものから追い出す
+pub(crate) const RESPONSE_CACHE_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// キャッシュしたレスポンス（200のみ保持する）
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response {
+        let mut response = (StatusCode::OK, self.body).into_response();
+        if let Some(content_type) = self.content_type {
+            response
+                .headers_mut()
+                .insert(header::CONTENT_TYPE, content_type);
+        }
+        response
+    }
+}
+
+/// 同じクエリの繰り返しでDBを叩かないよう、レスポンスをサーバ内メモリに`ttl`の間キャッシュする。
+///
+/// `moka`のキャッシュでボディのバイト数を重みとして`RESPONSE_CACHE_MAX_BYTES`まで保持する。
+/// 記事の内容が変わる処理（API経由のfetch-content/fetch-rss）の後は`clear`で全件破棄する。
+#[derive(Clone)]
+pub struct ResponseCache<K> {
+    cache: Cache<K, CachedResponse>,
+}
+
+impl<K> ResponseCache<K>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+{
+    pub fn new(ttl: Duration) -> Self {
+        let cache = Cache::builder()
+            .max_capacity(RESPONSE_CACHE_MAX_BYTES)
+            .weigher(|_key, value: &CachedResponse| {
+                u32::try_from(value.body.len()).unwrap_or(u32::MAX)
+            })
+            .time_to_live(ttl)
+            .build();
+        Self { cache }
+    }
+
+    /// キャッシュ済みのレスポンスを返す
+    pub async fn get(&self, key: &K) -> Option<Response> {
+        self.cache.get(key).await.map(IntoResponse::into_response)
+    }
+
+    /// 200のレスポンスをキャッシュに入れて返す。それ以外のステータスはキャッシュせずそのまま返す
+    pub async fn insert(&self, key: K, response: Response) -> Result<Response, axum::Error> {
+        if response.status() != StatusCode::OK {
+            return Ok(response);
+        }
+
+        let content_type = response.headers().get(header::CONTENT_TYPE).cloned();
+        let body = to_bytes(response.into_body(), usize::MAX).await?;
+        let cached = CachedResponse { content_type, body };
+        self.cache.insert(key, cached.clone()).await;
+        Ok(cached.into_response())
+    }
+
+    /// キャッシュを全件破棄する
+    pub fn clear(&self) {
+        self.cache.invalidate_all();
+    }
+}
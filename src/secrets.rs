@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use aws_sdk_secretsmanager::error::DisplayErrorContext;
+
+/// シークレット参照を表す値のプレフィックス（`secret://<key>`）
+const SECRET_PREFIX: &str = "secret://";
+
+/// `secret://<key>`の`<key>`から実値を取り出す
+pub trait SecretResolver {
+    fn resolve(&self, key: &str) -> Result<String>;
+}
+
+/// AWS Secrets Managerからシークレットを取得する
+///
+/// AWS SDKの既定の設定（`AWS_PROFILE`・`AWS_REGION`やインスタンスロールなど）で認証する。
+/// `<key>`にはシークレット名またはARNを指定し、`SecretString`をそのまま値として使う。
+///
+/// SDKの呼び出しを現在のtokioランタイムで待つため、非同期タスクからは直接呼ばず
+/// `spawn_blocking`の中で呼ぶこと（ランタイムのワーカーを塞がないようにする）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AwsSecretsManager;
+
+impl SecretResolver for AwsSecretsManager {
+    fn resolve(&self, key: &str) -> Result<String> {
+        let runtime = tokio::runtime::Handle::try_current()
+            .context("AWS Secrets Managerの取得にはtokioランタイムが必要です")?;
+        runtime.block_on(fetch_secret_string(key))
+    }
+}
+
+async fn fetch_secret_string(key: &str) -> Result<String> {
+    let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let client = aws_sdk_secretsmanager::Client::new(&config);
+    let output = client
+        .get_secret_value()
+        .secret_id(key)
+        .send()
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "AWS Secrets Managerからシークレットを取得できません: {} ({})",
+                key,
+                DisplayErrorContext(e)
+            )
+        })?;
+
+    output.secret_string().map(str::to_string).ok_or_else(|| {
+        anyhow::anyhow!(
+            "シークレットにSecretStringがありません（バイナリのシークレットには対応していません）: {}",
+            key
+        )
+    })
+}
+
+/// 設定値が`secret://<key>`形式ならシークレットの実値に置き換える
+///
+/// プレフィックスが無い値は平文としてそのまま返す。
+pub fn resolve_secret(value: &str, resolver: &dyn SecretResolver) -> Result<String> {
+    let Some(key) = value.strip_prefix(SECRET_PREFIX) else {
+        return Ok(value.to_string());
+    };
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(anyhow::anyhow!(
+            "シークレットのキーが空です: {}（secret://<key>の形式で指定してください）",
+            value
+        ));
+    }
+
+    resolver
+        .resolve(key)
+        .with_context(|| format!("シークレットを解決できません: {}", key))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod resolve_secret {
+        use crate::secrets::resolve_secret;
+        use crate::test_support::StaticSecrets;
+
+        /// # 検証目的
+        /// `secret://<key>`形式の値は解決器から取得した実値に置き換わり、それ以外の値は平文のまま返ることを確認する。
+        #[test]
+        fn secret形式の値だけを展開する() {
+            let resolver = StaticSecrets::new(&[("prod/database-url", "postgres://secret/db")]);
+
+            assert_eq!(
+                resolve_secret("secret://prod/database-url", &resolver).unwrap(),
+                "postgres://secret/db"
+            );
+            assert_eq!(
+                resolve_secret("postgres://plain/db", &resolver).unwrap(),
+                "postgres://plain/db"
+            );
+        }
+
+        /// # 検証目的
+        /// 存在しないキーや空のキーはエラーになり、平文として扱われないことを確認する。
+        #[test]
+        fn 解決できないキーはエラー() {
+            let resolver = StaticSecrets::default();
+
+            assert!(resolve_secret("secret://missing", &resolver).is_err());
+            assert!(resolve_secret("secret://", &resolver).is_err());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// 要約API呼び出しのタイムアウト秒数（LLMの応答待ちを見込んで長めにとる）
+pub(crate) const SUMMARY_TIMEOUT_SECS: u64 = 60;
+/// 要約APIへ送る本文テキストの最大文字数（超過分は切り捨てる）
+pub(crate) const SUMMARY_MAX_INPUT_CHARS: usize = 20_000;
+
+static SUMMARY_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(SUMMARY_TIMEOUT_SECS))
+        .build()
+        .expect("要約API用Clientの初期化に失敗")
+});
+
+/// 要約APIへのリクエスト
+#[derive(Debug, Serialize)]
+struct SummaryRequest<'a> {
+    url: &'a str,
+    title: &'a str,
+    text: &'a str,
+}
+
+/// 要約APIのレスポンス
+#[derive(Debug, Deserialize)]
+struct SummaryResponse {
+    summary: String,
+}
+
+/// HTMLから要約対象のテキストを取り出す。script/style等を除いた本文の文字列を空白で区切って連結し、
+/// `SUMMARY_MAX_INPUT_CHARS`文字までに切り詰める。
+pub(crate) fn html_to_text(html: &str) -> String {
+    static BODY_SELECTOR: Lazy<scraper::Selector> =
+        Lazy::new(|| scraper::Selector::parse("body").expect("body用セレクタが不正"));
+    const SKIPPED_ELEMENTS: [&str; 4] = ["script", "style", "noscript", "template"];
+
+    let document = scraper::Html::parse_document(html);
+    let root = document
+        .select(&BODY_SELECTOR)
+        .next()
+        .unwrap_or_else(|| document.root_element());
+
+    let mut text = String::new();
+    for node in root.descendants() {
+        let Some(fragment) = node.value().as_text() else {
+            continue;
+        };
+        let skipped = node.ancestors().any(|ancestor| {
+            ancestor
+                .value()
+                .as_element()
+                .is_some_and(|element| SKIPPED_ELEMENTS.contains(&element.name()))
+        });
+        if skipped {
+            continue;
+        }
+        for word in fragment.split_whitespace() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(word);
+        }
+    }
+
+    match text.char_indices().nth(SUMMARY_MAX_INPUT_CHARS) {
+        Some((byte_index, _)) => text[..byte_index].to_string(),
+        None => text,
+    }
+}
+
+/// 要約APIへ本文テキストをPOSTし、返ってきた要約を返す
+pub(crate) async fn request_summary(
+    api_url: &str,
+    link: &str,
+    title: &str,
+    text: &str,
+) -> Result<String> {
+    let response = SUMMARY_CLIENT
+        .post(api_url)
+        .json(&SummaryRequest {
+            url: link,
+            title,
+            text,
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+    let body: SummaryResponse = response
+        .json()
+        .await
+        .context("要約APIレスポンスのJSONデコードに失敗")?;
+
+    let summary = body.summary.trim();
+    if summary.is_empty() {
+        anyhow::bail!("要約APIが空の要約を返しました");
+    }
+    Ok(summary.to_string())
+}
+
+/// 保存済みの本文に要約を記録する
+pub(crate) async fn save_article_summary(
+    pool: &PgPool,
+    queue_id: Uuid,
+    summary: &str,
+) -> Result<()> {
+    sqlx::query("UPDATE rss.article_content SET summary = $2 WHERE queue_id = $1")
+        .bind(queue_id)
+        .bind(summary)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod html_to_text {
+        use crate::summarize::{html_to_text, SUMMARY_MAX_INPUT_CHARS};
+
+        /// # 検証目的
+        /// scriptやstyleを除いた本文テキストを空白区切りで取り出し、上限文字数で切り詰めることを確認する。
+        #[test]
+        fn 本文テキストを取り出す() {
+            let html = r#"<html><head><title>T</title><style>p { color: red; }</style></head>
+                <body><h1>見出し</h1>
+                <script>var x = 1;</script>
+                <p>本文の
+                   段落です。</p></body></html>"#;
+            assert_eq!(html_to_text(html), "見出し 本文の 段落です。");
+
+            let long = format!("<p>{}</p>", "あ".repeat(SUMMARY_MAX_INPUT_CHARS + 10));
+            assert_eq!(html_to_text(&long).chars().count(), SUMMARY_MAX_INPUT_CHARS);
+        }
+    }
+}
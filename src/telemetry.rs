@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use tracing::{warn, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+pub(crate) const SERVICE_NAME: &str = "datadoggo-v3-rss";
+
+/// ログの出力形式（`LOG_FORMAT`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// 人が読むテキスト形式（既定）
+    #[default]
+    Text,
+    /// 1行1イベントのJSON。APIサーバのアクセスログも有効になる
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(anyhow::anyhow!(
+                "無効なログ形式: {} (有効な値: text, json)",
+                other
+            )),
+        }
+    }
+}
+
+/// イベントのフィールドをトップレベルに展開したJSON形式のログレイヤを構築する
+pub(crate) fn json_fmt_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_writer(writer)
+}
+
+/// トレーシングの後始末を行うガード。Drop時にOTLPへ未送信のspanをフラッシュする。
+pub struct TracingGuard {
+    pub(crate) provider: Option<TracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                warn!(error = %e, "OpenTelemetryのシャットダウンに失敗しました");
+            }
+        }
+    }
+}
+
+/// エンドポイントが指定されている場合のみOTLP(HTTP)へ送信するトレーサプロバイダを構築する
+pub(crate) fn build_tracer_provider(endpoint: Option<&str>) -> Result<Option<TracerProvider>> {
+    let Some(endpoint) = endpoint else {
+        return Ok(None);
+    };
+
+    // OTEL_EXPORTER_OTLP_ENDPOINTはベースURLのため、トレース用のパスを付与する
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()
+        .context("OTLPエクスポータの構築に失敗")?;
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            SERVICE_NAME,
+        )]))
+        .build();
+
+    Ok(Some(provider))
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod build_tracer_provider {
+        use anyhow::Result;
+        use opentelemetry::trace::{Tracer, TracerProvider as _};
+
+        use crate::telemetry::build_tracer_provider;
+
+        /// # 検証目的
+        /// エンドポイント設定時にトレーサプロバイダが初期化され、spanを生成できることを確認する。
+        #[tokio::test(flavor = "multi_thread")]
+        async fn エンドポイント設定時にトレーサを初期化する() -> Result<()> {
+            let provider = build_tracer_provider(Some("http://127.0.0.1:4318/"))?
+                .expect("トレーサプロバイダが初期化されていない");
+
+            let tracer = provider.tracer("test");
+            tracer.in_span("fetch_content", |_| {});
+
+            let _ = provider.shutdown();
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// エンドポイント未設定ではOTLP送信を有効化しないことを確認する。
+        #[tokio::test]
+        async fn エンドポイント未設定なら無効() -> Result<()> {
+            assert!(build_tracer_provider(None)?.is_none());
+            Ok(())
+        }
+    }
+}
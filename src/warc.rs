@@ -0,0 +1,363 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use reqwest::StatusCode;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+const WARC_VERSION: &str = "WARC/1.1";
+
+/// fetch-contentで取得したHTMLのアーカイブ方式（`CONTENT_ARCHIVE`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContentArchive {
+    /// アーカイブしない（既定）
+    #[default]
+    None,
+    /// request/responseのWARCレコードを`rss.article_warc`へ保存する
+    Warc,
+}
+
+impl std::str::FromStr for ContentArchive {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(ContentArchive::None),
+            "warc" => Ok(ContentArchive::Warc),
+            other => Err(anyhow::anyhow!(
+                "無効なアーカイブ方式: {} (有効な値: none, warc)",
+                other
+            )),
+        }
+    }
+}
+
+/// WARCレコードにする1回分の取得結果
+pub(crate) struct WarcCapture {
+    /// リダイレクト後の最終URL（scraping APIが返さなければ記事link）
+    pub target_uri: String,
+    pub fetched_at: DateTime<Utc>,
+    /// scraping APIが返した記事のステータス
+    pub status_code: i32,
+    pub html: String,
+    /// scraping APIへ渡したリファラ・Accept-Language（requestレコードのヘッダにする）
+    pub referer: Option<String>,
+    pub accept_language: Option<String>,
+}
+
+/// 取得結果をrequest/responseのWARCレコードにし、レコードごとのgzipメンバーを連結した`.warc.gz`形式で返す。
+///
+/// WARCヘッダには最終URL（`WARC-Target-URI`）と取得時刻（`WARC-Date`）、responseのHTTPステータス行に
+/// 記事のステータスを記録する。scraping API経由の取得で元のHTTPヘッダは得られないため、
+/// HTTPメッセージ部分は取得内容から組み立て直したものになる。
+pub(crate) fn build_warc_gz(capture: &WarcCapture) -> Result<Vec<u8>> {
+    let date = capture.fetched_at.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let response_id = format!("<urn:uuid:{}>", Uuid::new_v4());
+    let request_id = format!("<urn:uuid:{}>", Uuid::new_v4());
+
+    let request = warc_record(
+        "request",
+        &request_id,
+        &date,
+        capture,
+        Some(&response_id),
+        "application/http;msgtype=request",
+        &http_request_block(capture),
+    );
+    let response = warc_record(
+        "response",
+        &response_id,
+        &date,
+        capture,
+        None,
+        "application/http;msgtype=response",
+        &http_response_block(capture),
+    );
+
+    let mut archive = Vec::new();
+    for record in [request, response] {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&record)?;
+        archive.extend(encoder.finish()?);
+    }
+    Ok(archive)
+}
+
+/// `build_warc_gz`をブロッキング用のスレッドで行う（gzip圧縮でasyncのワーカーを塞がないため）
+pub(crate) async fn build_warc_gz_blocking(capture: WarcCapture) -> Result<Vec<u8>> {
+    tokio::task::spawn_blocking(move || build_warc_gz(&capture))
+        .await
+        .context("WARC生成タスクの実行に失敗")?
+}
+
+fn warc_record(
+    record_type: &str,
+    record_id: &str,
+    date: &str,
+    capture: &WarcCapture,
+    concurrent_to: Option<&str>,
+    content_type: &str,
+    block: &[u8],
+) -> Vec<u8> {
+    let mut header = format!(
+        "{}\r\nWARC-Type: {}\r\nWARC-Record-ID: {}\r\nWARC-Date: {}\r\nWARC-Target-URI: {}\r\n",
+        WARC_VERSION,
+        record_type,
+        record_id,
+        date,
+        header_value(&capture.target_uri)
+    );
+    if let Some(concurrent_to) = concurrent_to {
+        header.push_str(&format!("WARC-Concurrent-To: {}\r\n", concurrent_to));
+    }
+    header.push_str(&format!(
+        "Content-Type: {}\r\nContent-Length: {}\r\n\r\n",
+        content_type,
+        block.len()
+    ));
+
+    let mut record = header.into_bytes();
+    record.extend_from_slice(block);
+    record.extend_from_slice(b"\r\n\r\n");
+    record
+}
+
+fn http_request_block(capture: &WarcCapture) -> Vec<u8> {
+    let (target, host) = match reqwest::Url::parse(&capture.target_uri) {
+        Ok(url) => {
+            let mut target = url.path().to_string();
+            if let Some(query) = url.query() {
+                target.push('?');
+                target.push_str(query);
+            }
+            let host = url.host_str().map(|host| match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            });
+            (target, host)
+        }
+        Err(_) => (header_value(&capture.target_uri), None),
+    };
+
+    let mut block = format!("GET {} HTTP/1.1\r\n", target);
+    if let Some(host) = host {
+        block.push_str(&format!("Host: {}\r\n", host));
+    }
+    if let Some(referer) = &capture.referer {
+        block.push_str(&format!("Referer: {}\r\n", header_value(referer)));
+    }
+    if let Some(accept_language) = &capture.accept_language {
+        block.push_str(&format!(
+            "Accept-Language: {}\r\n",
+            header_value(accept_language)
+        ));
+    }
+    block.push_str("\r\n");
+    block.into_bytes()
+}
+
+fn http_response_block(capture: &WarcCapture) -> Vec<u8> {
+    let reason = u16::try_from(capture.status_code)
+        .ok()
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let mut block = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\r\n",
+        capture.status_code,
+        reason,
+        capture.html.len()
+    )
+    .into_bytes();
+    block.extend_from_slice(capture.html.as_bytes());
+    block
+}
+
+/// ヘッダ値に改行が混ざってレコードが壊れないよう空白へ置き換える
+fn header_value(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// 記事のWARCアーカイブを置き換える（前回取得時のアーカイブは残さない）
+pub(crate) async fn save_article_warc(
+    tx: &mut Transaction<'_, Postgres>,
+    queue_id: Uuid,
+    data: &[u8],
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO rss.article_warc (queue_id, data)
+        VALUES ($1, $2)
+        ON CONFLICT (queue_id)
+        DO UPDATE SET data = EXCLUDED.data, updated_at = NOW()
+        "#,
+    )
+    .bind(queue_id)
+    .bind(data)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    pub mod build_warc_gz {
+        use std::io::Read;
+
+        use chrono::{TimeZone, Utc};
+        use flate2::read::MultiGzDecoder;
+
+        use crate::warc::{build_warc_gz, WarcCapture};
+
+        /// 読み取ったWARCレコード（ヘッダは出現順）
+        #[derive(Debug)]
+        struct ParsedWarcRecord {
+            headers: Vec<(String, String)>,
+            block: Vec<u8>,
+        }
+
+        impl ParsedWarcRecord {
+            fn header(&self, name: &str) -> Option<&str> {
+                self.headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value.as_str())
+            }
+        }
+
+        /// `.warc.gz`を展開し、バージョン行・Content-Length・レコード末尾の空行を検証しながらレコードへ分解する
+        fn parse_warc_gz(data: &[u8]) -> Vec<ParsedWarcRecord> {
+            let mut archive = Vec::new();
+            MultiGzDecoder::new(data)
+                .read_to_end(&mut archive)
+                .expect("gzipとして展開できる");
+
+            let mut records = Vec::new();
+            let mut rest = archive.as_slice();
+            while !rest.is_empty() {
+                let header_end = rest
+                    .windows(4)
+                    .position(|window| window == b"\r\n\r\n")
+                    .expect("ヘッダの終端がある");
+                let header = std::str::from_utf8(&rest[..header_end]).expect("ヘッダはUTF-8");
+                let mut lines = header.split("\r\n");
+                assert_eq!(lines.next(), Some("WARC/1.1"));
+                let headers: Vec<(String, String)> = lines
+                    .map(|line| {
+                        let (key, value) = line.split_once(": ").expect("`名前: 値`の形式");
+                        (key.to_string(), value.to_string())
+                    })
+                    .collect();
+
+                let record = ParsedWarcRecord {
+                    headers,
+                    block: Vec::new(),
+                };
+                let length: usize = record
+                    .header("Content-Length")
+                    .expect("Content-Lengthがある")
+                    .parse()
+                    .expect("Content-Lengthは数値");
+                let block_start = header_end + 4;
+                let block = rest[block_start..block_start + length].to_vec();
+                assert_eq!(
+                    &rest[block_start + length..block_start + length + 4],
+                    b"\r\n\r\n"
+                );
+                rest = &rest[block_start + length + 4..];
+                records.push(ParsedWarcRecord { block, ..record });
+            }
+            records
+        }
+
+        /// # 検証目的
+        /// 取得結果からrequest/responseの2レコードが生成され、WARCとして読み取れる形式（バージョン行・必須ヘッダ・
+        /// Content-Length・末尾の空行）で最終URL・取得時刻・ステータスが記録されることを確認する。
+        #[test]
+        fn 有効なwarcレコードを生成する() -> anyhow::Result<()> {
+            let html = "<html><body><p>本文</p></body></html>";
+            let capture = WarcCapture {
+                target_uri: "https://example.com:8443/news/1?page=2".to_string(),
+                fetched_at: Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap(),
+                status_code: 200,
+                html: html.to_string(),
+                referer: Some("https://example.com/".to_string()),
+                accept_language: Some("ja".to_string()),
+            };
+
+            let records = parse_warc_gz(&build_warc_gz(&capture)?);
+            assert_eq!(records.len(), 2);
+            let (request, response) = (&records[0], &records[1]);
+
+            for record in &records {
+                assert_eq!(
+                    record.header("WARC-Target-URI"),
+                    Some("https://example.com:8443/news/1?page=2")
+                );
+                assert_eq!(record.header("WARC-Date"), Some("2026-10-16T09:30:00Z"));
+                let record_id = record.header("WARC-Record-ID").unwrap();
+                assert!(
+                    record_id.starts_with("<urn:uuid:") && record_id.ends_with('>'),
+                    "{}",
+                    record_id
+                );
+            }
+
+            assert_eq!(request.header("WARC-Type"), Some("request"));
+            assert_eq!(
+                request.header("Content-Type"),
+                Some("application/http;msgtype=request")
+            );
+            assert_eq!(
+                request.header("WARC-Concurrent-To"),
+                response.header("WARC-Record-ID")
+            );
+            assert_eq!(
+                String::from_utf8(request.block.clone())?,
+                "GET /news/1?page=2 HTTP/1.1\r\nHost: example.com:8443\r\nReferer: https://example.com/\r\nAccept-Language: ja\r\n\r\n"
+            );
+
+            assert_eq!(response.header("WARC-Type"), Some("response"));
+            assert_eq!(
+                response.header("Content-Type"),
+                Some("application/http;msgtype=response")
+            );
+            let block = String::from_utf8(response.block.clone())?;
+            let (head, body) = block.split_once("\r\n\r\n").unwrap();
+            assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{}", head);
+            assert!(head.contains(&format!("Content-Length: {}", html.len())));
+            assert_eq!(body, html);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 非200のステータスもHTTPステータス行へそのまま記録し、ヘッダ値に混ざった改行でレコードが壊れないことを確認する。
+        #[test]
+        fn ステータスと改行を安全に記録する() -> anyhow::Result<()> {
+            let capture = WarcCapture {
+                target_uri: "https://example.com/gone".to_string(),
+                fetched_at: Utc::now(),
+                status_code: 410,
+                html: String::new(),
+                referer: Some("https://example.com/\r\nX-Injected: 1".to_string()),
+                accept_language: None,
+            };
+
+            let records = parse_warc_gz(&build_warc_gz(&capture)?);
+            let request = String::from_utf8(records[0].block.clone())?;
+            assert!(!request.contains("\r\nX-Injected"), "{}", request);
+            let response = String::from_utf8(records[1].block.clone())?;
+            assert!(
+                response.starts_with("HTTP/1.1 410 Gone\r\n"),
+                "{}",
+                response
+            );
+
+            Ok(())
+        }
+    }
+}
@@ -1,16 +1,24 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Duration;
 
-use anyhow::Result;
 use once_cell::sync::Lazy;
 use reqwest::Client;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use tokio::time::Instant;
+use tracing::warn;
+use uuid::Uuid;
 
-use crate::fetch_content::FetchContentSummary;
-use crate::fetch_rss::FetchRssSummary;
+use crate::fetch_content::{
+    ContentChange, FetchContentEntryOutcome, FetchContentEntryReport, FetchContentSummary,
+};
+use crate::fetch_rss::{FetchRssFeedResult, FetchRssSummary};
+use crate::report::DailyReport;
 
 /// Webhook POSTのタイムアウト秒数
 pub(crate) const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+/// fetch-contentの通知に含めるエントリ数の既定上限
+pub(crate) const DEFAULT_WEBHOOK_MAX_ENTRIES: usize = 50;
 
 static WEBHOOK_CLIENT: Lazy<Client> = Lazy::new(|| {
     Client::builder()
@@ -19,53 +27,374 @@ static WEBHOOK_CLIENT: Lazy<Client> = Lazy::new(|| {
         .expect("Webhook用Clientの初期化に失敗")
 });
 
-/// Webhookへ通知を送る。URLが未設定の場合は何もしない。
+/// Webhook送信の結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookResult {
+    /// 2xxの応答を受け取れたか
+    pub sent: bool,
+    /// 応答のHTTPステータス（接続失敗・タイムアウト時はNone）
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// グループ別Webhook（rss_links.ymlの`_webhook_url`）の送信結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupWebhookResult {
+    /// このWebhookへ結果を送ったグループ
+    pub groups: Vec<String>,
+    #[serde(flatten)]
+    pub result: WebhookResult,
+}
+
+/// Webhookへ通知を送り、送信結果を返す。URLが未設定の場合は何もせずNoneを返す。
 pub async fn notify_fetch_rss(
     webhook_url: Option<&str>,
     summary: &FetchRssSummary,
     source: &str,
-) -> Result<()> {
-    if let Some(url) = webhook_url {
+) -> Option<WebhookResult> {
+    let url = webhook_url?;
+    let payload = json!({
+        "event": "fetch_rss",
+        "run_id": summary.run_id,
+        "source": source,
+        "summary": summary,
+    });
+    Some(send(url, &payload).await)
+}
+
+/// fetch-rssの結果をグループの通知先ごとに振り分けて送り、`summary.webhook`と`summary.group_webhooks`へ結果を記録する。
+///
+/// `_webhook_url`を持つグループはそのURLへ、持たないグループはグローバルの`webhook_url`へ、
+/// 該当グループのフィードだけに絞ったサマリを送る（同じURLのグループは1回にまとめる）。
+/// どのグループも`_webhook_url`を持たない場合は従来どおり全体のサマリをグローバルへ送る。
+pub async fn notify_fetch_rss_by_group(
+    webhook_url: Option<&str>,
+    summary: &mut FetchRssSummary,
+    source: &str,
+) {
+    let mut routes: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut fallback_groups: BTreeSet<String> = BTreeSet::new();
+    for feed in &summary.feeds {
+        match summary.group_webhook_urls.get(&feed.group) {
+            Some(url) => {
+                routes
+                    .entry(url.clone())
+                    .or_default()
+                    .insert(feed.group.clone());
+            }
+            None => {
+                fallback_groups.insert(feed.group.clone());
+            }
+        }
+    }
+
+    summary.webhook = if routes.is_empty() {
+        notify_fetch_rss(webhook_url, summary, source).await
+    } else if fallback_groups.is_empty() {
+        None
+    } else {
+        let fallback = summary_for_groups(summary, &fallback_groups);
+        notify_fetch_rss(webhook_url, &fallback, source).await
+    };
+
+    let mut group_webhooks = Vec::with_capacity(routes.len());
+    for (url, groups) in routes {
+        let group_summary = summary_for_groups(summary, &groups);
+        if let Some(result) = notify_fetch_rss(Some(&url), &group_summary, source).await {
+            group_webhooks.push(GroupWebhookResult {
+                groups: groups.into_iter().collect(),
+                result,
+            });
+        }
+    }
+    summary.group_webhooks = group_webhooks;
+}
+
+/// entry数が急減したフィードごとに`feed_entry_drop`を通知し、送信結果を返す。
+///
+/// 通知先はfetch-rssのサマリと同じく、`_webhook_url`を持つグループはそのURL、持たないグループは`webhook_url`。
+/// 通知先が無いフィードは送らない。
+pub async fn notify_entry_count_drops(
+    webhook_url: Option<&str>,
+    summary: &FetchRssSummary,
+    source: &str,
+) -> Vec<WebhookResult> {
+    let mut results = Vec::new();
+    for feed in &summary.feeds {
+        let Some(drop) = &feed.entry_count_drop else {
+            continue;
+        };
+        let Some(url) = summary
+            .group_webhook_urls
+            .get(&feed.group)
+            .map(String::as_str)
+            .or(webhook_url)
+        else {
+            continue;
+        };
+
         let payload = json!({
-            "event": "fetch_rss",
+            "event": "feed_entry_drop",
+            "run_id": summary.run_id,
             "source": source,
-            "summary": summary,
+            "group": feed.group,
+            "name": feed.name,
+            "entry_count": drop.entry_count,
+            "average": drop.average,
+            "samples": drop.samples,
         });
-        send(url, &payload).await?;
+        results.push(send(url, &payload).await);
+    }
+    results
+}
+
+/// 指定したグループのフィードだけに絞ったサマリを作る
+fn summary_for_groups(summary: &FetchRssSummary, groups: &BTreeSet<String>) -> FetchRssSummary {
+    let feeds: Vec<FetchRssFeedResult> = summary
+        .feeds
+        .iter()
+        .filter(|feed| groups.contains(&feed.group))
+        .cloned()
+        .collect();
+
+    FetchRssSummary {
+        run_id: summary.run_id,
+        total_processed: feeds.iter().map(|feed| feed.processed).sum(),
+        feeds,
+        webhook: None,
+        group_webhooks: Vec::new(),
+        group_webhook_urls: BTreeMap::new(),
     }
-    Ok(())
 }
 
-/// Webhookへfetch-contentの結果を通知する。
+/// Webhookへfetch-contentの結果を通知し、送信結果を返す。
+///
+/// `entries`が`max_entries`を超える場合はペイロードを縮めるため上限件数だけを含める
+/// （`fetch_content_payload`参照）。
 pub async fn notify_fetch_content(
     webhook_url: Option<&str>,
     summary: &FetchContentSummary,
     source: &str,
-) -> Result<()> {
-    if let Some(url) = webhook_url {
-        let payload = json!({
+    max_entries: usize,
+) -> Option<WebhookResult> {
+    let url = webhook_url?;
+    let payload = fetch_content_payload(summary, source, max_entries);
+    Some(send(url, &payload).await)
+}
+
+/// fetch-contentの通知ペイロードを組み立てる。
+///
+/// `entries`が`max_entries`を超える場合はエラーエントリを優先して上限件数まで残し（元の順序は保つ）、
+/// 残りは`truncated: true`と`omitted_entries`（省略件数）で示す。件数の集計値（`saved_count`など）は全件分のまま。
+pub(crate) fn fetch_content_payload(
+    summary: &FetchContentSummary,
+    source: &str,
+    max_entries: usize,
+) -> serde_json::Value {
+    if summary.entries.len() <= max_entries {
+        return json!({
             "event": "fetch_content",
+            "run_id": summary.run_id,
             "source": source,
             "summary": summary,
         });
-        send(url, &payload).await?;
     }
-    Ok(())
+
+    let entries = select_webhook_entries(&summary.entries, max_entries);
+    let omitted_entries = summary.entries.len() - entries.len();
+    let mut summary_value = json!(summary);
+    summary_value["entries"] = json!(entries);
+
+    json!({
+        "event": "fetch_content",
+        "run_id": summary.run_id,
+        "source": source,
+        "summary": summary_value,
+        "truncated": true,
+        "omitted_entries": omitted_entries,
+    })
+}
+
+/// 本文を初めて保存した記事・本文が変わった記事ごとに`article_created`/`article_updated`を通知し、送信結果を返す。
+///
+/// 本文が変わらなかった記事・本文を保存しなかった記事は通知しない。URLが未設定の場合は何もしない。
+pub async fn notify_article_changes(
+    webhook_url: Option<&str>,
+    summary: &FetchContentSummary,
+    source: &str,
+) -> Vec<WebhookResult> {
+    let Some(url) = webhook_url else {
+        return Vec::new();
+    };
+
+    let mut results = Vec::new();
+    for entry in &summary.entries {
+        if let Some(payload) = article_change_payload(entry, summary, source) {
+            results.push(send(url, &payload).await);
+        }
+    }
+    results
+}
+
+/// 記事単位の変更通知のペイロードを組み立てる（通知しないエントリはNone）
+fn article_change_payload(
+    entry: &FetchContentEntryReport,
+    summary: &FetchContentSummary,
+    source: &str,
+) -> Option<serde_json::Value> {
+    let event = match entry.content_change? {
+        ContentChange::Created => "article_created",
+        ContentChange::Updated => "article_updated",
+        ContentChange::Unchanged => return None,
+    };
+
+    Some(json!({
+        "event": event,
+        "run_id": summary.run_id,
+        "source": source,
+        "queue_id": entry.queue_id,
+        "title": entry.title,
+        "group": entry.group,
+    }))
+}
+
+/// 日次レポートを`daily_report`イベントとして送信する
+pub async fn notify_daily_report(url: &str, report: &DailyReport, source: &str) -> WebhookResult {
+    let payload = json!({
+        "event": "daily_report",
+        "source": source,
+        "report": report,
+    });
+    send(url, &payload).await
+}
+
+/// fetch-contentの成功エントリをまとめて通知するバッチ通知の設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookBatchOptions {
+    pub url: String,
+    /// 1回の`articles_batch`に含める最大件数（溜まった時点で送る）
+    pub max_entries: usize,
+    /// 前回の送信からこの時間が経っていれば件数に満たなくても送る（未指定なら件数のみで判定）
+    pub interval: Option<Duration>,
+}
+
+/// 保存に成功したエントリをバッファし、`articles_batch`としてまとめて通知する。
+///
+/// 送るかどうかは`push`・`flush_if_due`を呼んだ時点で判定する（タイマーでは送らない）ため、
+/// 実行中は各エントリの処理後に`flush_if_due`を呼び、終了時に`flush`で残りを送る。
+pub(crate) struct ArticleBatchNotifier {
+    options: WebhookBatchOptions,
+    run_id: Uuid,
+    buffer: Vec<FetchContentEntryReport>,
+    last_flush: Instant,
+    sent_batches: usize,
+}
+
+impl ArticleBatchNotifier {
+    pub(crate) fn new(options: WebhookBatchOptions, run_id: Uuid) -> Self {
+        Self {
+            options,
+            run_id,
+            buffer: Vec::new(),
+            last_flush: Instant::now(),
+            sent_batches: 0,
+        }
+    }
+
+    /// 保存に成功したエントリだけをバッファし、件数が上限に達したら送る
+    pub(crate) async fn push(&mut self, entry: &FetchContentEntryReport) {
+        if !matches!(entry.result, FetchContentEntryOutcome::Saved { .. }) {
+            return;
+        }
+        self.buffer.push(entry.clone());
+        if self.buffer.len() >= self.options.max_entries.max(1) {
+            self.flush().await;
+        }
+    }
+
+    /// 前回の送信から`interval`が経っていればバッファを送る
+    pub(crate) async fn flush_if_due(&mut self) {
+        let due = self
+            .options
+            .interval
+            .is_some_and(|interval| self.last_flush.elapsed() >= interval);
+        if due {
+            self.flush().await;
+        }
+    }
+
+    /// バッファに残ったエントリを送る（空なら送らない）。送信失敗は警告ログのみ
+    pub(crate) async fn flush(&mut self) {
+        self.last_flush = Instant::now();
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let entries = std::mem::take(&mut self.buffer);
+        let payload = json!({
+            "event": "articles_batch",
+            "run_id": self.run_id,
+            "batch_index": self.sent_batches,
+            "entries": entries,
+        });
+        self.sent_batches += 1;
+
+        let result = send(&self.options.url, &payload).await;
+        if !result.sent {
+            warn!(
+                error = ?result.error,
+                entries = entries.len(),
+                "Webhook送信に失敗しました(articles_batch)"
+            );
+        }
+    }
+}
+
+/// エラーエントリを優先して`max_entries`件まで選ぶ（戻り値は元の順序）
+fn select_webhook_entries(
+    entries: &[FetchContentEntryReport],
+    max_entries: usize,
+) -> Vec<&FetchContentEntryReport> {
+    let is_error = |entry: &FetchContentEntryReport| {
+        matches!(
+            entry.result,
+            FetchContentEntryOutcome::ApiError { .. }
+                | FetchContentEntryOutcome::PersistError { .. }
+        )
+    };
+
+    let mut selected: Vec<usize> = (0..entries.len())
+        .filter(|&index| is_error(&entries[index]))
+        .chain((0..entries.len()).filter(|&index| !is_error(&entries[index])))
+        .take(max_entries)
+        .collect();
+    selected.sort_unstable();
+
+    selected.into_iter().map(|index| &entries[index]).collect()
 }
 
-async fn send<T: Serialize>(url: &str, payload: &T) -> Result<()> {
-    WEBHOOK_CLIENT
-        .post(url)
-        .json(payload)
-        .send()
-        .await?
-        .error_for_status()?;
-    Ok(())
+async fn send<T: Serialize>(url: &str, payload: &T) -> WebhookResult {
+    match WEBHOOK_CLIENT.post(url).json(payload).send().await {
+        Ok(response) => {
+            let status = response.status();
+            WebhookResult {
+                sent: status.is_success(),
+                status: Some(status.as_u16()),
+                error: (!status.is_success()).then(|| format!("HTTPステータス{}", status)),
+            }
+        }
+        Err(e) => WebhookResult {
+            sent: false,
+            status: e.status().map(|status| status.as_u16()),
+            error: Some(e.to_string()),
+        },
+    }
 }
 
 #[cfg(test)]
 mod tests {
     pub mod webhook_notify {
+        use std::collections::BTreeMap;
         use std::time::Duration;
 
         use anyhow::Result;
@@ -73,26 +402,42 @@ mod tests {
         use wiremock::matchers::{body_json, method, path};
         use wiremock::{Mock, MockServer, ResponseTemplate};
 
-        use crate::fetch_content::FetchContentSummary;
-        use crate::fetch_rss::{FetchRssFeedResult, FetchRssSummary};
-        use crate::webhook::{notify_fetch_content, notify_fetch_rss, WEBHOOK_TIMEOUT_SECS};
+        use uuid::Uuid;
+
+        use crate::fetch_content::{
+            ContentChange, FetchContentEntryOutcome, FetchContentEntryReport, FetchContentSummary,
+        };
+        use crate::fetch_rss::{EntryCountDrop, FetchRssFeedResult, FetchRssSummary};
+        use crate::webhook::{
+            fetch_content_payload, notify_article_changes, notify_entry_count_drops,
+            notify_fetch_content, notify_fetch_rss, notify_fetch_rss_by_group,
+            ArticleBatchNotifier, WebhookBatchOptions, DEFAULT_WEBHOOK_MAX_ENTRIES,
+            WEBHOOK_TIMEOUT_SECS,
+        };
 
         /// # 検証目的
-        /// fetch-rssのサマリがWebhookへPOSTされることを確認する。
+        /// fetch-rssのサマリが実行ごとのrun_id付きでWebhookへPOSTされることを確認する。
         #[tokio::test]
         async fn fetch_rssの通知を送信できる() -> Result<()> {
             let server = MockServer::start().await;
+            let run_id = Uuid::new_v4();
 
             let expected = json!({
                 "event": "fetch_rss",
+                "run_id": run_id,
                 "source": "test",
                 "summary": {
+                    "run_id": run_id,
                     "total_processed": 1,
                     "feeds": [
                         {
                             "group": "test",
                             "name": "feed",
                             "processed": 1,
+                            "malformed_entries": 0,
+                            "elapsed_ms": 120,
+                            "unchanged": false,
+                            "locked": false,
                             "error": null
                         }
                     ]
@@ -107,40 +452,228 @@ mod tests {
                 .await;
 
             let summary = FetchRssSummary {
+                run_id,
                 total_processed: 1,
                 feeds: vec![FetchRssFeedResult {
                     group: "test".to_string(),
                     name: "feed".to_string(),
                     processed: 1,
+                    inserted: 1,
+                    updated: 0,
+                    malformed_entries: 0,
+                    elapsed_ms: 120,
+                    unchanged: false,
+                    locked: false,
+                    dormant: false,
+                    entry_count_drop: None,
                     error: None,
                 }],
+                webhook: None,
+                group_webhooks: Vec::new(),
+                group_webhook_urls: BTreeMap::new(),
+            };
+
+            let result =
+                notify_fetch_rss(Some(&format!("{}/hook", server.uri())), &summary, "test")
+                    .await
+                    .expect("URL設定時は送信結果を返す");
+            assert!(result.sent, "送信に失敗: {:?}", result);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// `_webhook_url`を持つグループの結果はそのWebhookへ、持たないグループの結果はグローバルのWebhookへ
+        /// 振り分けて送られることを確認する。
+        #[tokio::test]
+        async fn グループ別のwebhookへ振り分けて送信する() -> Result<()> {
+            let server = MockServer::start().await;
+            // 振り分け先が分かれても同じ実行のrun_idを送る
+            let run_id = Uuid::new_v4();
+
+            let feed = |group: &str, processed: usize| FetchRssFeedResult {
+                group: group.to_string(),
+                name: "feed".to_string(),
+                processed,
+                inserted: processed,
+                updated: 0,
+                malformed_entries: 0,
+                elapsed_ms: 10,
+                unchanged: false,
+                locked: false,
+                dormant: false,
+                entry_count_drop: None,
+                error: None,
+            };
+            let expected_for = |feeds: Vec<FetchRssFeedResult>| {
+                json!({
+                    "event": "fetch_rss",
+                    "run_id": run_id,
+                    "source": "test",
+                    "summary": {
+                        "run_id": run_id,
+                        "total_processed": feeds.iter().map(|feed| feed.processed).sum::<usize>(),
+                        "feeds": feeds,
+                    }
+                })
+            };
+
+            for (hook_path, feeds) in [
+                ("/world", vec![feed("world", 1)]),
+                ("/tech", vec![feed("science", 3), feed("tech", 2)]),
+                ("/global", vec![feed("sports", 4)]),
+            ] {
+                Mock::given(method("POST"))
+                    .and(path(hook_path))
+                    .and(body_json(expected_for(feeds)))
+                    .respond_with(ResponseTemplate::new(200))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+            }
+
+            let mut summary = FetchRssSummary {
+                run_id,
+                total_processed: 10,
+                feeds: vec![
+                    feed("science", 3),
+                    feed("sports", 4),
+                    feed("tech", 2),
+                    feed("world", 1),
+                ],
+                webhook: None,
+                group_webhooks: Vec::new(),
+                group_webhook_urls: BTreeMap::from([
+                    ("world".to_string(), format!("{}/world", server.uri())),
+                    ("tech".to_string(), format!("{}/tech", server.uri())),
+                    ("science".to_string(), format!("{}/tech", server.uri())),
+                ]),
             };
 
-            notify_fetch_rss(Some(&format!("{}/hook", server.uri())), &summary, "test").await?;
+            notify_fetch_rss_by_group(
+                Some(&format!("{}/global", server.uri())),
+                &mut summary,
+                "test",
+            )
+            .await;
 
+            assert!(summary.webhook.as_ref().is_some_and(|result| result.sent));
+            let sent_groups: Vec<(Vec<String>, bool)> = summary
+                .group_webhooks
+                .iter()
+                .map(|group_webhook| (group_webhook.groups.clone(), group_webhook.result.sent))
+                .collect();
+            assert_eq!(
+                sent_groups,
+                vec![
+                    (vec!["science".to_string(), "tech".to_string()], true),
+                    (vec!["world".to_string()], true),
+                ]
+            );
+            // 各Mockのexpect(1)はserverのdrop時に検証される
             Ok(())
         }
 
         /// # 検証目的
-        /// fetch-contentのサマリがWebhookへPOSTされることを確認する。
+        /// entry数が急減したフィードだけ`feed_entry_drop`が送られ、`_webhook_url`を持つグループはそのURLへ送られることを確認する。
+        #[tokio::test]
+        async fn entry数の急減をアラートする() -> Result<()> {
+            let server = MockServer::start().await;
+            let run_id = Uuid::new_v4();
+
+            Mock::given(method("POST"))
+                .and(path("/group-hook"))
+                .and(body_json(json!({
+                    "event": "feed_entry_drop",
+                    "run_id": run_id,
+                    "source": "test",
+                    "group": "world",
+                    "name": "broken",
+                    "entry_count": 2,
+                    "average": 100.0,
+                    "samples": 3,
+                })))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let feed = |name: &str, entry_count_drop: Option<EntryCountDrop>| FetchRssFeedResult {
+                group: "world".to_string(),
+                name: name.to_string(),
+                processed: 2,
+                inserted: 0,
+                updated: 2,
+                malformed_entries: 0,
+                elapsed_ms: 10,
+                unchanged: false,
+                locked: false,
+                dormant: false,
+                entry_count_drop,
+                error: None,
+            };
+            let summary = FetchRssSummary {
+                run_id,
+                total_processed: 4,
+                feeds: vec![
+                    feed(
+                        "broken",
+                        Some(EntryCountDrop {
+                            entry_count: 2,
+                            average: 100.0,
+                            samples: 3,
+                        }),
+                    ),
+                    feed("healthy", None),
+                ],
+                webhook: None,
+                group_webhooks: Vec::new(),
+                group_webhook_urls: BTreeMap::from([(
+                    "world".to_string(),
+                    format!("{}/group-hook", server.uri()),
+                )]),
+            };
+
+            let results =
+                notify_entry_count_drops(Some(&format!("{}/hook", server.uri())), &summary, "test")
+                    .await;
+            assert_eq!(results.len(), 1);
+            assert!(results[0].sent, "送信に失敗: {:?}", results[0]);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// fetch-contentのサマリが実行ごとのrun_id付きでWebhookへPOSTされることを確認する。
         #[tokio::test]
         async fn fetch_contentの通知を送信できる() -> Result<()> {
             let server = MockServer::start().await;
+            let run_id = Uuid::new_v4();
 
             let summary = FetchContentSummary {
+                run_id,
                 saved_count: 1,
                 status_only_count: 0,
                 error_count: 0,
+                skipped_count: 0,
+                cancelled_count: 0,
+                by_group: Vec::new(),
                 entries: Vec::new(),
+                webhook: None,
             };
 
             let expected = json!({
                 "event": "fetch_content",
+                "run_id": run_id,
                 "source": "test",
                 "summary": {
+                    "run_id": run_id,
                     "saved_count": 1,
                     "status_only_count": 0,
                     "error_count": 0,
+                    "skipped_count": 0,
+                    "cancelled_count": 0,
+                    "by_group": [],
                     "entries": []
                 },
             });
@@ -152,7 +685,157 @@ mod tests {
                 .mount(&server)
                 .await;
 
-            notify_fetch_content(Some(&format!("{}/hook", server.uri())), &summary, "test").await?;
+            let result = notify_fetch_content(
+                Some(&format!("{}/hook", server.uri())),
+                &summary,
+                "test",
+                DEFAULT_WEBHOOK_MAX_ENTRIES,
+            )
+            .await
+            .expect("URL設定時は送信結果を返す");
+            assert!(result.sent, "送信に失敗: {:?}", result);
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// entriesが上限を超える場合はエラーエントリを優先して上限件数に絞り、truncatedと省略件数を付けてペイロードが縮むことを確認する。
+        #[test]
+        fn 大量エントリは要約してペイロードを縮める() {
+            let entries: Vec<FetchContentEntryReport> = (0..300)
+                .map(|index| FetchContentEntryReport {
+                    queue_id: Uuid::new_v4(),
+                    title: format!("記事{}", index),
+                    group: Some("news".to_string()),
+                    result: if index % 100 == 99 {
+                        FetchContentEntryOutcome::ApiError {
+                            message: format!("timeout {}", index),
+                        }
+                    } else {
+                        FetchContentEntryOutcome::Saved { status_code: 200 }
+                    },
+                    status_mismatch: false,
+                    content_change: None,
+                })
+                .collect();
+            let summary = FetchContentSummary {
+                run_id: Uuid::new_v4(),
+                saved_count: 297,
+                status_only_count: 0,
+                error_count: 3,
+                skipped_count: 0,
+                cancelled_count: 0,
+                by_group: Vec::new(),
+                entries,
+                webhook: None,
+            };
+
+            let full = fetch_content_payload(&summary, "test", summary.entries.len());
+            assert!(full.get("truncated").is_none());
+
+            let trimmed = fetch_content_payload(&summary, "test", 10);
+            assert_eq!(trimmed["truncated"], json!(true));
+            assert_eq!(trimmed["omitted_entries"], json!(290));
+            assert_eq!(trimmed["summary"]["saved_count"], json!(297));
+
+            let titles: Vec<&str> = trimmed["summary"]["entries"]
+                .as_array()
+                .expect("entriesが配列")
+                .iter()
+                .map(|entry| entry["title"].as_str().unwrap())
+                .collect();
+            assert_eq!(titles.len(), 10);
+            // エラーの3件は後方にあっても残り、元の順序を保つ
+            assert_eq!(
+                &titles[..7],
+                ["記事0", "記事1", "記事2", "記事3", "記事4", "記事5", "記事6"]
+            );
+            assert_eq!(&titles[7..], ["記事99", "記事199", "記事299"]);
+
+            let full_len = serde_json::to_vec(&full).unwrap().len();
+            let trimmed_len = serde_json::to_vec(&trimmed).unwrap().len();
+            assert!(
+                trimmed_len * 10 < full_len,
+                "縮小前{}バイト、縮小後{}バイト",
+                full_len,
+                trimmed_len
+            );
+        }
+
+        /// # 検証目的
+        /// 本文を新規保存した記事は`article_created`、本文が変わった記事は`article_updated`として1件ずつ通知され、
+        /// 本文が変わらなかった記事・本文を保存しなかった記事は通知されないことを確認する。
+        #[tokio::test]
+        async fn 本文の変化があった記事だけ通知する() -> Result<()> {
+            let server = MockServer::start().await;
+            let run_id = Uuid::new_v4();
+
+            let entry =
+                |title: &str, result: FetchContentEntryOutcome, change| FetchContentEntryReport {
+                    queue_id: Uuid::new_v4(),
+                    title: title.to_string(),
+                    group: Some("news".to_string()),
+                    result,
+                    status_mismatch: false,
+                    content_change: change,
+                };
+            let saved = || FetchContentEntryOutcome::Saved { status_code: 200 };
+            let entries = vec![
+                entry("新規", saved(), Some(ContentChange::Created)),
+                entry("更新", saved(), Some(ContentChange::Updated)),
+                entry("変更なし", saved(), Some(ContentChange::Unchanged)),
+                entry(
+                    "ステータスのみ",
+                    FetchContentEntryOutcome::StatusOnly { status_code: 500 },
+                    None,
+                ),
+            ];
+
+            for (event, entry) in [
+                ("article_created", &entries[0]),
+                ("article_updated", &entries[1]),
+            ] {
+                Mock::given(method("POST"))
+                    .and(path("/hook"))
+                    .and(body_json(json!({
+                        "event": event,
+                        "run_id": run_id,
+                        "source": "test",
+                        "queue_id": entry.queue_id,
+                        "title": entry.title,
+                        "group": "news",
+                    })))
+                    .respond_with(ResponseTemplate::new(200))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+            }
+
+            let summary = FetchContentSummary {
+                run_id,
+                saved_count: 3,
+                status_only_count: 1,
+                error_count: 0,
+                skipped_count: 0,
+                cancelled_count: 0,
+                by_group: Vec::new(),
+                entries,
+                webhook: None,
+            };
+
+            let results =
+                notify_article_changes(Some(&format!("{}/hook", server.uri())), &summary, "test")
+                    .await;
+            assert_eq!(results.len(), 2);
+            assert!(results.iter().all(|result| result.sent), "{:?}", results);
+            assert_eq!(
+                server.received_requests().await.unwrap_or_default().len(),
+                2
+            );
+
+            assert!(notify_article_changes(None, &summary, "test")
+                .await
+                .is_empty());
 
             Ok(())
         }
@@ -173,16 +856,24 @@ mod tests {
                 .await;
 
             let summary = FetchRssSummary {
+                run_id: Uuid::new_v4(),
                 total_processed: 0,
                 feeds: Vec::new(),
+                webhook: None,
+                group_webhooks: Vec::new(),
+                group_webhook_urls: BTreeMap::new(),
             };
 
             let before = tokio::time::Instant::now();
             let result =
-                notify_fetch_rss(Some(&format!("{}/slow", server.uri())), &summary, "test").await;
+                notify_fetch_rss(Some(&format!("{}/slow", server.uri())), &summary, "test")
+                    .await
+                    .expect("URL設定時は送信結果を返す");
             let elapsed = before.elapsed();
 
-            assert!(result.is_err(), "タイムアウトエラーを期待");
+            assert!(!result.sent, "タイムアウトエラーを期待");
+            assert!(result.status.is_none());
+            assert!(result.error.is_some());
             assert!(
                 elapsed < Duration::from_secs(WEBHOOK_TIMEOUT_SECS + 3),
                 "タイムアウトまでに想定以上の時間がかかっています: {:?}",
@@ -191,5 +882,119 @@ mod tests {
 
             Ok(())
         }
+
+        fn saved_entry(title: &str) -> FetchContentEntryReport {
+            FetchContentEntryReport {
+                queue_id: Uuid::new_v4(),
+                title: title.to_string(),
+                group: Some("news".to_string()),
+                result: FetchContentEntryOutcome::Saved { status_code: 200 },
+                status_mismatch: false,
+                content_change: Some(ContentChange::Created),
+            }
+        }
+
+        /// 受信した`articles_batch`ごとのエントリのtitle一覧
+        async fn received_batches(server: &MockServer) -> Vec<Vec<String>> {
+            server
+                .received_requests()
+                .await
+                .unwrap_or_default()
+                .iter()
+                .map(|request| {
+                    let body: serde_json::Value =
+                        serde_json::from_slice(&request.body).expect("JSONのボディ");
+                    assert_eq!(body["event"], json!("articles_batch"));
+                    body["entries"]
+                        .as_array()
+                        .expect("entriesは配列")
+                        .iter()
+                        .map(|entry| entry["title"].as_str().unwrap_or_default().to_string())
+                        .collect()
+                })
+                .collect()
+        }
+
+        /// # 検証目的
+        /// 成功エントリだけがバッファされ、上限件数ごとに`articles_batch`が送られ、終了時のflushで残りが送られることを確認する。
+        #[tokio::test]
+        async fn 件数ごとにまとめて通知し残りをflushする() -> Result<()> {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/hook"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+
+            let mut notifier = ArticleBatchNotifier::new(
+                WebhookBatchOptions {
+                    url: format!("{}/hook", server.uri()),
+                    max_entries: 2,
+                    interval: None,
+                },
+                Uuid::new_v4(),
+            );
+
+            notifier.push(&saved_entry("a")).await;
+            notifier
+                .push(&FetchContentEntryReport {
+                    result: FetchContentEntryOutcome::ApiError {
+                        message: "timeout".to_string(),
+                    },
+                    ..saved_entry("error")
+                })
+                .await;
+            notifier.push(&saved_entry("b")).await;
+            notifier.push(&saved_entry("c")).await;
+            notifier.flush_if_due().await;
+            assert_eq!(received_batches(&server).await.len(), 1);
+
+            notifier.push(&saved_entry("d")).await;
+            notifier.push(&saved_entry("e")).await;
+            notifier.flush().await;
+            // バッファが空ならflushしても送らない
+            notifier.flush().await;
+
+            assert_eq!(
+                received_batches(&server).await,
+                vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]
+            );
+
+            Ok(())
+        }
+
+        /// # 検証目的
+        /// 件数が上限に満たなくても、前回の送信からintervalが経っていれば`flush_if_due`で送られることを確認する。
+        #[tokio::test]
+        async fn 一定時間ごとにまとめて通知する() -> Result<()> {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/hook"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(&server)
+                .await;
+
+            let mut notifier = ArticleBatchNotifier::new(
+                WebhookBatchOptions {
+                    url: format!("{}/hook", server.uri()),
+                    max_entries: 100,
+                    interval: Some(Duration::from_millis(200)),
+                },
+                Uuid::new_v4(),
+            );
+
+            notifier.push(&saved_entry("a")).await;
+            notifier.flush_if_due().await;
+            assert!(received_batches(&server).await.is_empty());
+
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            notifier.push(&saved_entry("b")).await;
+            notifier.flush_if_due().await;
+            notifier.flush().await;
+
+            assert_eq!(received_batches(&server).await, vec![vec!["a", "b"]]);
+
+            Ok(())
+        }
     }
 }